@@ -0,0 +1,6 @@
+//! Git merge driver / textconv for conlang project files. See `conlang_maker_lib::merge`.
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    std::process::exit(conlang_maker_lib::merge::run_cli(&args));
+}
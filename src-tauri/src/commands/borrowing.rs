@@ -0,0 +1,636 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use tauri::command;
+
+use crate::commands::grammar::load_grammar;
+use crate::commands::language_merge::unique_id;
+use crate::commands::lexicon::{read_all_words, save_words, WordSaveRequest};
+use crate::commands::oplog::create_snapshot;
+use crate::commands::phoneme_features::{features_of, nearest_phoneme, PHONEME_FEATURES};
+use crate::commands::phonology::load_phonology;
+use crate::commands::sca_engine::{build_macros, IpaGenerator};
+use crate::commands::workspace::load_workspace;
+use crate::models::{Etymology, PhonologyConfig, WordEvolution};
+
+// ── 借词与音系适配 ───────────────────────────────────────
+//
+// 从工作区任意语言（不限亲属关系）借入词条，按借入语言的音系改造词形：
+//   1. 取源词的音位形式（已有 IPA 优先，否则按源语言转写表生成）并按音素切分
+//   2. 借入语言音位库中没有的音素，替换为音位库中特征最接近的音素；特征表未收录的音素删去
+//   3. 按借入语言的音节结构做最小代价修补：插入增音或删除音段，使整词可切分为合法音节
+//   4. 用借入语言默认转写表把音位形式反推回拼写
+// 每一步都记入适配日志，随结果返回。
+// 义项的 pos_id 按词性名称映射到借入语言语法中的同名词性；借入语言没有对应词性时清空，同样记入结果。
+
+/// 适配过程中的一步
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AdaptationStep {
+    pub kind: String, // "substitution" | "deletion" | "epenthesis" | "blacklist"
+    /// 涉及的音素（增音时为空）
+    pub from: String,
+    /// 结果音素（删除时为空；blacklist 时为命中的模式）
+    pub to: String,
+    /// 该步发生时在音位序列中的位置（blacklist 时为 0）
+    pub position: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BorrowedWord {
+    pub source_entry_id: String,
+    /// 借入语言中的新词条 ID
+    pub entry_id: String,
+    pub source_form: String,
+    pub source_phonemes: String,
+    pub adapted_phonemes: String,
+    pub adapted_form: String,
+    pub steps: Vec<AdaptationStep>,
+    /// pos_id 被映射或清空的义项
+    #[serde(default)]
+    pub pos_changes: Vec<BorrowPosChange>,
+}
+
+/// 借入时改动的义项词性
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BorrowPosChange {
+    pub sense_id: String,
+    /// 来源语言中的 pos_id
+    pub source_pos_id: String,
+    /// 借入语言中的 pos_id；借入语言没有对应词性时为空
+    pub pos_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BorrowOptions {
+    /// 是否按借入语言音系适配；关闭时原样复制词形
+    #[serde(default = "default_true")]
+    pub adapt: bool,
+    /// 音节修补时优先 "epenthesis"（默认）还是 "deletion"
+    #[serde(default)]
+    pub repair: Option<String>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BorrowReport {
+    pub dry_run: bool,
+    pub language_id: String,
+    pub source_language_id: String,
+    pub words: Vec<BorrowedWord>,
+    /// 源语言中找不到、或已从同一词条借入过的 entry_id
+    pub skipped: Vec<String>,
+    /// 执行前创建的快照，预览或无改动时为空
+    #[serde(default)]
+    pub log_id: Option<String>,
+}
+
+/// 按音素表最长优先切分；表中没有的字符单独成段
+fn tokenize(form: &str, phonemes: &[&str]) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut rest = form;
+    while let Some(ch) = rest.chars().next() {
+        if ch.is_whitespace() {
+            rest = &rest[ch.len_utf8()..];
+            continue;
+        }
+        let len = phonemes
+            .iter()
+            .find(|p| !p.is_empty() && rest.starts_with(**p))
+            .map_or(ch.len_utf8(), |p| p.len());
+        tokens.push(rest[..len].to_string());
+        rest = &rest[len..];
+    }
+    tokens
+}
+
+fn sorted_by_length<'a>(phonemes: impl Iterator<Item = &'a str>) -> Vec<&'a str> {
+    let mut list: Vec<&str> = phonemes.collect();
+    list.sort_by_key(|p| std::cmp::Reverse(p.chars().count()));
+    list.dedup();
+    list
+}
+
+// ── 音节模板 ────────────────────────────────────────────
+
+/// 音节模板中的一个槽位，如 "(C)V(C)" 中的三个
+struct Slot {
+    phonemes: Vec<String>,
+    optional: bool,
+}
+
+/// 解析音节结构：每个字符是一个宏（V、C 或自定义宏），括号内或后跟 `?` 的槽位可省略；
+/// 不是宏的字符按字面音素处理
+fn parse_template(structure: &str, phonology: &PhonologyConfig) -> Vec<Slot> {
+    let macros = build_macros(phonology);
+    let mut slots: Vec<Slot> = Vec::new();
+    let mut depth = 0usize;
+    for ch in structure.chars() {
+        match ch {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            '?' => {
+                if let Some(last) = slots.last_mut() {
+                    last.optional = true;
+                }
+            }
+            c if c.is_whitespace() || c == '.' => {}
+            c => {
+                let key = c.to_string();
+                let phonemes = match macros.get(&key) {
+                    Some(values) if !values.is_empty() => values.clone(),
+                    _ => vec![key],
+                };
+                slots.push(Slot {
+                    phonemes,
+                    optional: depth > 0,
+                });
+            }
+        }
+    }
+    slots
+}
+
+/// 增音用的音素：元音槽优先央元音和高元音，辅音槽优先喉塞音与 h
+fn epenthetic(slot: &Slot, vowels: &HashSet<&str>) -> String {
+    let is_vowel_slot = slot.phonemes.iter().all(|p| vowels.contains(p.as_str()));
+    let preferred: &[&str] = if is_vowel_slot {
+        &["ə", "ɨ", "i", "u", "e", "a"]
+    } else {
+        &["ʔ", "h", "j", "w", "t", "n"]
+    };
+    preferred
+        .iter()
+        .find(|p| slot.phonemes.iter().any(|s| s == *p))
+        .map(|p| p.to_string())
+        .or_else(|| slot.phonemes.first().cloned())
+        .unwrap_or_default()
+}
+
+#[derive(Clone, Copy)]
+enum Op {
+    Start,
+    Match,
+    Delete,
+    Insert(usize),
+    Skip,
+    Wrap,
+}
+
+/// 以最小代价（插入/删除）把音素序列修补为一个或多个合法音节。
+/// 状态为 (已处理的音素数, 当前音节中的槽位, 当前音节是否非空)，在同一位置上的
+/// 插入、省略、换音节是 ε 转移，用反复松弛求最短路。
+fn repair_syllables(
+    tokens: &[String],
+    slots: &[Slot],
+    vowels: &HashSet<&str>,
+    prefer_deletion: bool,
+    steps: &mut Vec<AdaptationStep>,
+) -> Vec<String> {
+    let m = slots.len();
+    if m == 0 || tokens.is_empty() {
+        return tokens.to_vec();
+    }
+    let (insert_cost, delete_cost) = if prefer_deletion { (3u32, 2u32) } else { (2u32, 3u32) };
+    let state = |k: usize, ne: bool| k * 2 + usize::from(ne);
+    let width = (m + 1) * 2;
+    let n = tokens.len();
+    let mut cost = vec![vec![u32::MAX; width]; n + 1];
+    let mut back: Vec<Vec<(Op, usize)>> = vec![vec![(Op::Start, 0); width]; n + 1];
+    cost[0][state(0, false)] = 0;
+
+    for i in 0..=n {
+        // 同一位置上的 ε 转移，状态数很小，松弛到不再变化即可
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for k in 0..=m {
+                for ne in [false, true] {
+                    let from = state(k, ne);
+                    let base = cost[i][from];
+                    if base == u32::MAX {
+                        continue;
+                    }
+                    let mut relax = |to: usize, c: u32, op: Op| {
+                        if c < cost[i][to] {
+                            cost[i][to] = c;
+                            back[i][to] = (op, from);
+                            changed = true;
+                        }
+                    };
+                    match slots.get(k) {
+                        Some(slot) => {
+                            if slot.optional {
+                                relax(state(k + 1, ne), base, Op::Skip);
+                            }
+                            relax(state(k + 1, true), base + insert_cost, Op::Insert(k));
+                        }
+                        None if ne => relax(state(0, false), base, Op::Wrap),
+                        None => {}
+                    }
+                }
+            }
+        }
+        if i == n {
+            break;
+        }
+        for k in 0..=m {
+            for ne in [false, true] {
+                let from = state(k, ne);
+                let base = cost[i][from];
+                if base == u32::MAX {
+                    continue;
+                }
+                if slots.get(k).is_some_and(|slot| slot.phonemes.contains(&tokens[i])) {
+                    let to = state(k + 1, true);
+                    if base < cost[i + 1][to] {
+                        cost[i + 1][to] = base;
+                        back[i + 1][to] = (Op::Match, from);
+                    }
+                }
+                if base + delete_cost < cost[i + 1][from] {
+                    cost[i + 1][from] = base + delete_cost;
+                    back[i + 1][from] = (Op::Delete, from);
+                }
+            }
+        }
+    }
+
+    // 回溯，得到倒序的操作序列
+    let mut ops: Vec<Op> = Vec::new();
+    let (mut i, mut s) = (n, state(m, true));
+    if cost[n][s] == u32::MAX {
+        return tokens.to_vec();
+    }
+    loop {
+        let (op, prev) = back[i][s];
+        match op {
+            Op::Start => break,
+            Op::Match | Op::Delete => i -= 1,
+            _ => {}
+        }
+        ops.push(op);
+        s = prev;
+    }
+
+    let mut out: Vec<String> = Vec::new();
+    let mut i = 0;
+    for op in ops.into_iter().rev() {
+        match op {
+            Op::Match => {
+                out.push(tokens[i].clone());
+                i += 1;
+            }
+            Op::Delete => {
+                steps.push(AdaptationStep {
+                    kind: "deletion".to_string(),
+                    from: tokens[i].clone(),
+                    to: String::new(),
+                    position: out.len(),
+                });
+                i += 1;
+            }
+            Op::Insert(k) => {
+                let phoneme = epenthetic(&slots[k], vowels);
+                steps.push(AdaptationStep {
+                    kind: "epenthesis".to_string(),
+                    from: String::new(),
+                    to: phoneme.clone(),
+                    position: out.len(),
+                });
+                out.push(phoneme);
+            }
+            Op::Start | Op::Skip | Op::Wrap => {}
+        }
+    }
+    out
+}
+
+// ── 拼写反推 ────────────────────────────────────────────
+
+/// 借入语言默认转写表的逆映射：音位 → 拼写，只取无语境的规则
+struct Respeller {
+    rules: Vec<(String, String)>,
+}
+
+impl Respeller {
+    fn new(phonology: &PhonologyConfig) -> Option<Self> {
+        let map = phonology
+            .romanization_maps
+            .iter()
+            .find(|m| m.is_default)
+            .or_else(|| phonology.romanization_maps.first())?;
+        let mut rules: Vec<(String, String)> = Vec::new();
+        for rule in &map.rules {
+            let free = |c: &str| c.is_empty() || c == "_";
+            if rule.output_phoneme.is_empty()
+                || !free(&rule.context_before)
+                || !free(&rule.context_after)
+                || rules.iter().any(|(p, _)| *p == rule.output_phoneme)
+            {
+                continue;
+            }
+            rules.push((rule.output_phoneme.clone(), rule.input.clone()));
+        }
+        rules.sort_by_key(|(p, _)| std::cmp::Reverse(p.chars().count()));
+        Some(Self { rules })
+    }
+
+    fn respell(&self, phonemes: &str) -> String {
+        let mut out = String::new();
+        let mut rest = phonemes;
+        while let Some(ch) = rest.chars().next() {
+            match self.rules.iter().find(|(p, _)| rest.starts_with(p.as_str())) {
+                Some((p, spelling)) => {
+                    out.push_str(spelling);
+                    rest = &rest[p.len()..];
+                }
+                None => {
+                    out.push(ch);
+                    rest = &rest[ch.len_utf8()..];
+                }
+            }
+        }
+        out
+    }
+}
+
+/// 按借入语言的音系改造一个音位形式，返回适配后的音位序列
+fn adapt_phonemes(
+    source_phonemes: &str,
+    source_inventory: &[&str],
+    recipient: &PhonologyConfig,
+    slots: &[Slot],
+    blacklist: &[Regex],
+    prefer_deletion: bool,
+    steps: &mut Vec<AdaptationStep>,
+) -> String {
+    let inventory: Vec<&str> = recipient
+        .phoneme_inventory
+        .consonants
+        .iter()
+        .chain(recipient.phoneme_inventory.vowels.iter())
+        .map(String::as_str)
+        .collect();
+    let vowels: HashSet<&str> = recipient.phoneme_inventory.vowels.iter().map(String::as_str).collect();
+    let known = sorted_by_length(
+        source_inventory
+            .iter()
+            .copied()
+            .chain(inventory.iter().copied())
+            .chain(PHONEME_FEATURES.iter().map(|(p, _)| *p)),
+    );
+
+    let mut tokens: Vec<String> = Vec::new();
+    for token in tokenize(source_phonemes, &known) {
+        if inventory.is_empty() || inventory.contains(&token.as_str()) {
+            tokens.push(token);
+            continue;
+        }
+        let replacement = features_of(&token).and_then(|features| nearest_phoneme(features, inventory.iter().copied()));
+        match replacement {
+            Some(phoneme) => {
+                steps.push(AdaptationStep {
+                    kind: "substitution".to_string(),
+                    from: token,
+                    to: phoneme.to_string(),
+                    position: tokens.len(),
+                });
+                tokens.push(phoneme.to_string());
+            }
+            None => steps.push(AdaptationStep {
+                kind: "deletion".to_string(),
+                from: token,
+                to: String::new(),
+                position: tokens.len(),
+            }),
+        }
+    }
+
+    let tokens = repair_syllables(&tokens, slots, &vowels, prefer_deletion, steps);
+    let adapted = tokens.concat();
+    // 音位配列黑名单只做提示，不自动修补
+    for pattern in blacklist {
+        if pattern.is_match(&adapted) {
+            steps.push(AdaptationStep {
+                kind: "blacklist".to_string(),
+                from: String::new(),
+                to: pattern.as_str().to_string(),
+                position: 0,
+            });
+        }
+    }
+    adapted
+}
+
+#[command]
+pub fn borrow_words(
+    project_path: String,
+    conlang_file_path: String,
+    language_id: String,
+    source_language_id: String,
+    mut entry_ids: Vec<String>,
+    options: Option<BorrowOptions>,
+) -> Result<BorrowReport, String> {
+    let mut requested = HashSet::new();
+    entry_ids.retain(|id| requested.insert(id.clone()));
+    let options = options.unwrap_or(BorrowOptions {
+        adapt: true,
+        ..Default::default()
+    });
+    if language_id == source_language_id {
+        return Err("Cannot borrow words from the same language".to_string());
+    }
+    let project = Path::new(&project_path);
+    let ws = load_workspace(conlang_file_path)?;
+    let find = |id: &str| {
+        ws.languages
+            .iter()
+            .find(|l| l.language_id == id)
+            .ok_or_else(|| format!("Language '{}' not found in workspace", id))
+    };
+    let recipient = find(&language_id)?;
+    let source = find(&source_language_id)?;
+
+    let recipient_phonology = load_phonology(project_path.clone(), recipient.path.clone())?;
+    let source_phonology = load_phonology(project_path.clone(), source.path.clone())?;
+    let source_ipa = IpaGenerator::new(&source_phonology);
+    let source_inventory: Vec<&str> = source_phonology
+        .phoneme_inventory
+        .consonants
+        .iter()
+        .chain(source_phonology.phoneme_inventory.vowels.iter())
+        .map(String::as_str)
+        .collect();
+    let slots = parse_template(&recipient_phonology.phonotactics.syllable_structure, &recipient_phonology);
+    let blacklist: Vec<Regex> = recipient_phonology
+        .phonotactics
+        .blacklist_patterns
+        .iter()
+        .filter_map(|p| Regex::new(p).ok())
+        .collect();
+    let respeller = Respeller::new(&recipient_phonology);
+    let prefer_deletion = options.repair.as_deref() == Some("deletion");
+
+    // 来源 pos_id → 借入语言中同名词性的 pos_id（名称不区分大小写）
+    let recipient_grammar = load_grammar(project_path.clone(), recipient.path.clone())?;
+    let source_grammar = load_grammar(project_path.clone(), source.path.clone())?;
+    let recipient_pos: HashSet<&str> = recipient_grammar
+        .parts_of_speech
+        .iter()
+        .map(|p| p.pos_id.as_str())
+        .collect();
+    let map_pos = |pos_id: &str| -> String {
+        if recipient_pos.contains(pos_id) {
+            return pos_id.to_string();
+        }
+        source_grammar
+            .parts_of_speech
+            .iter()
+            .find(|p| p.pos_id == pos_id)
+            .and_then(|source_pos| {
+                recipient_grammar
+                    .parts_of_speech
+                    .iter()
+                    .find(|p| p.name.trim().to_lowercase() == source_pos.name.trim().to_lowercase())
+            })
+            .map(|p| p.pos_id.clone())
+            .unwrap_or_default()
+    };
+
+    let source_words = read_all_words(&project.join(&source.path).join("lexicon"))?;
+    let recipient_words = read_all_words(&project.join(&recipient.path).join("lexicon"))?;
+    let mut taken: HashSet<String> = recipient_words.iter().map(|w| w.entry_id.clone()).collect();
+
+    let mut report = BorrowReport {
+        dry_run: options.dry_run,
+        language_id: language_id.clone(),
+        source_language_id: source_language_id.clone(),
+        words: Vec::new(),
+        skipped: Vec::new(),
+        log_id: None,
+    };
+    let mut writes: Vec<WordSaveRequest> = Vec::new();
+
+    for entry_id in &entry_ids {
+        let already = recipient_words.iter().any(|w| {
+            w.etymology.origin_type == "borrowed"
+                && w.etymology.source_language_id.as_deref() == Some(source_language_id.as_str())
+                && w.etymology.parent_entry_id.as_deref() == Some(entry_id.as_str())
+        });
+        let Some(source_word) = source_words.iter().find(|w| &w.entry_id == entry_id).filter(|_| !already) else {
+            report.skipped.push(entry_id.clone());
+            continue;
+        };
+
+        let source_phonemes = if !source_word.phonetic_ipa.trim().is_empty() {
+            source_word.phonetic_ipa.trim().to_string()
+        } else {
+            source_ipa
+                .as_ref()
+                .map(|g| g.generate(&source_word.con_word_romanized))
+                .unwrap_or_else(|| source_word.con_word_romanized.clone())
+        };
+        let mut steps = Vec::new();
+        let (adapted_phonemes, adapted_form) = if options.adapt {
+            let phonemes = adapt_phonemes(
+                &source_phonemes,
+                &source_inventory,
+                &recipient_phonology,
+                &slots,
+                &blacklist,
+                prefer_deletion,
+                &mut steps,
+            );
+            let form = respeller
+                .as_ref()
+                .map_or_else(|| phonemes.clone(), |r| r.respell(&phonemes));
+            (phonemes, form)
+        } else {
+            (source_phonemes.clone(), source_word.con_word_romanized.clone())
+        };
+
+        let mut word = source_word.clone();
+        word.entry_id = unique_id(&format!("{}_borrowed", source_word.entry_id), &language_id, &mut taken);
+        word.language_id = language_id.clone();
+        word.con_word_romanized = adapted_form.clone();
+        if options.adapt {
+            word.phonetic_ipa = adapted_phonemes.clone();
+            word.phonetic_override = false;
+        }
+        word.etymology = Etymology {
+            origin_type: "borrowed".to_string(),
+            parent_entry_id: Some(source_word.entry_id.clone()),
+            source_language_id: Some(source_language_id.clone()),
+            ..Default::default()
+        };
+        word.evolution = WordEvolution::default();
+        let mut pos_changes = Vec::new();
+        for sense in &mut word.senses {
+            if sense.pos_id.is_empty() {
+                continue;
+            }
+            let pos_id = map_pos(&sense.pos_id);
+            if pos_id != sense.pos_id {
+                pos_changes.push(BorrowPosChange {
+                    sense_id: sense.sense_id.clone(),
+                    source_pos_id: std::mem::replace(&mut sense.pos_id, pos_id.clone()),
+                    pos_id,
+                });
+            }
+        }
+
+        report.words.push(BorrowedWord {
+            source_entry_id: source_word.entry_id.clone(),
+            entry_id: word.entry_id.clone(),
+            source_form: source_word.con_word_romanized.clone(),
+            source_phonemes,
+            adapted_phonemes,
+            adapted_form,
+            steps,
+            pos_changes,
+        });
+        writes.push(WordSaveRequest {
+            word,
+            old_romanized: None,
+            expected_hash: Some(String::new()),
+        });
+    }
+
+    if report.dry_run || writes.is_empty() {
+        return Ok(report);
+    }
+
+    let log = create_snapshot(
+        project_path.clone(),
+        recipient.path.clone(),
+        "borrowing".to_string(),
+        source_language_id.clone(),
+        language_id.clone(),
+        format!("Borrowed {} words from {}", writes.len(), source_language_id),
+        Some(vec!["lexicon".to_string()]),
+    )?;
+    report.log_id = log.entries.last().map(|e| e.log_id.clone());
+    let log_id = report.log_id.clone().unwrap_or_default();
+
+    let results = save_words(project_path, recipient.path.clone(), writes, None)
+        .map_err(|e| format!("Borrowing failed; roll back to snapshot '{}' to undo: {}", log_id, e))?;
+    let failed: Vec<String> = results
+        .iter()
+        .filter(|r| r.status == "error")
+        .map(|r| format!("{}: {}", r.entry_id, r.error.clone().unwrap_or_default()))
+        .collect();
+    if !failed.is_empty() {
+        return Err(format!(
+            "Some borrowed words were not saved; roll back to snapshot '{}' to undo: {}",
+            log_id,
+            failed.join("; ")
+        ));
+    }
+    Ok(report)
+}
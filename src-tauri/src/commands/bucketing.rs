@@ -0,0 +1,265 @@
+use crate::commands::journal::{begin, capture_before};
+use crate::commands::lexicon::{atomic_write, quarantine_lines, QuarantineRecord};
+use crate::commands::project_lock::check_write_access;
+use crate::commands::watcher::note_own_removal;
+use crate::models::BucketingConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+// ── 词典分桶策略 ─────────────────────────────────────────
+//
+// 策略保存在 lexicon/bucketing.json，与分桶文件放在一起，
+// 这样快照 / 回滚复制 lexicon 目录时策略与分桶始终一致。
+// 文件不存在时使用旧的 ASCII 首字母分桶（lexicon_a … lexicon_z + lexicon_others）。
+// 未知的 strategy / normalization 一律报错，不会静默退回 ASCII 分桶。
+//
+// size 策略的切分点只在迁移时计算：之后保存词条只按已有切分点落桶，
+// 不会重新平衡，分桶会随新增词条逐渐变大；需要重新切分时再执行一次迁移。
+
+fn bucketing_file(lexicon_dir: &Path) -> PathBuf {
+    lexicon_dir.join("bucketing.json")
+}
+
+pub fn load_bucketing(lexicon_dir: &Path) -> Result<BucketingConfig, String> {
+    let path = bucketing_file(lexicon_dir);
+    if !path.exists() {
+        return Ok(BucketingConfig::default());
+    }
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let config: BucketingConfig = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    validate_bucketing(&config)?;
+    Ok(config)
+}
+
+const STRATEGIES: [&str; 4] = ["ascii", "grapheme", "hash", "size"];
+const NORMALIZATIONS: [&str; 2] = ["nfc", "nfd"];
+
+/// 拒绝未知的分桶策略与折叠方式
+pub fn validate_bucketing(config: &BucketingConfig) -> Result<(), String> {
+    if !STRATEGIES.contains(&config.strategy.as_str()) {
+        return Err(format!(
+            "Unknown bucketing strategy '{}' (expected one of: {})",
+            config.strategy,
+            STRATEGIES.join(", ")
+        ));
+    }
+    if !NORMALIZATIONS.contains(&config.normalization.as_str()) {
+        return Err(format!(
+            "Unknown bucketing normalization '{}' (expected one of: {})",
+            config.normalization,
+            NORMALIZATIONS.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+/// FNV-1a：跨平台、跨版本稳定，不能用 std 的随机化哈希
+fn fnv1a(text: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in text.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+fn fold(config: &BucketingConfig, word: &str) -> String {
+    if config.normalization == "nfd" {
+        // NFD 分解后去掉组合附加符号：é → e、ё → е
+        word.nfd().filter(|c| !is_combining_mark(*c)).collect::<String>().to_lowercase()
+    } else {
+        word.nfc().collect::<String>().to_lowercase()
+    }
+}
+
+fn bucket_key(config: &BucketingConfig, word: &str) -> String {
+    match config.strategy.as_str() {
+        "grapheme" => match fold(config, word).graphemes(true).next() {
+            Some(g) if g.chars().all(char::is_alphanumeric) => g.to_string(),
+            _ => "others".to_string(),
+        },
+        "hash" => {
+            let shards = config.shard_count.max(1) as u64;
+            format!("h{:03}", fnv1a(&fold(config, word)) % shards)
+        }
+        "size" => {
+            let idx = config.split_points.partition_point(|p| p.as_str() <= word);
+            format!("r{:03}", idx)
+        }
+        // "ascii"：其他取值已被 validate_bucketing 拒绝
+        _ => {
+            let first_char = word
+                .chars()
+                .next()
+                .map(|c| c.to_lowercase().next().unwrap_or('_'))
+                .unwrap_or('_');
+            if first_char.is_ascii_alphabetic() {
+                first_char.to_string()
+            } else {
+                "others".to_string()
+            }
+        }
+    }
+}
+
+/// 根据语言的分桶策略计算词条所在的分桶文件
+pub fn bucket_path(lexicon_dir: &Path, config: &BucketingConfig, word: &str) -> PathBuf {
+    lexicon_dir.join(format!("lexicon_{}.ndjson", bucket_key(config, word)))
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BucketMigrationReport {
+    pub dry_run: bool,
+    pub strategy: String,
+    pub words_scanned: usize,
+    pub words_moved: usize,
+    /// 分桶文件名 → 词条数
+    pub buckets_before: BTreeMap<String, usize>,
+    pub buckets_after: BTreeMap<String, usize>,
+    /// 无法解析或缺少 con_word_romanized 的行，迁移时移入隔离文件
+    pub unparseable_lines: usize,
+}
+
+/// 切换语言的分桶策略，并把现有词条重新分配到新的分桶中。
+/// 词条按原始行移动，内容不做任何改写；默认 dry-run。
+/// 新旧分桶的写入和删除都经过 atomic_write / 撤销日志，文件监听不会把迁移当成外部修改。
+#[command]
+pub fn migrate_lexicon_bucketing(
+    project_path: String,
+    language_path: String,
+    config: BucketingConfig,
+    dry_run: Option<bool>,
+) -> Result<BucketMigrationReport, String> {
+    let lexicon_dir = Path::new(&project_path)
+        .join(&language_path)
+        .join("lexicon");
+    let is_dry_run = dry_run.unwrap_or(true);
+    validate_bucketing(&config)?;
+    if !is_dry_run {
+        check_write_access(&lexicon_dir)?;
+    }
+
+    let mut config = config;
+    let mut lines: Vec<(String, String, String)> = Vec::new(); // (romanized, 原始行, 原分桶)
+    let mut unparseable: Vec<(String, String)> = Vec::new();
+    let mut buckets_before: BTreeMap<String, usize> = BTreeMap::new();
+    let mut old_files: Vec<PathBuf> = Vec::new();
+
+    if lexicon_dir.exists() {
+        for entry in fs::read_dir(&lexicon_dir).map_err(|e| e.to_string())? {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            if path.extension().is_none_or(|e| e != "ndjson") {
+                continue;
+            }
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+            for line in content.lines() {
+                let trimmed = line.trim();
+                if trimmed.is_empty() {
+                    continue;
+                }
+                let romanized = serde_json::from_str::<serde_json::Value>(trimmed)
+                    .ok()
+                    .and_then(|v| v.get("con_word_romanized")?.as_str().map(str::to_string));
+                match romanized {
+                    Some(r) => {
+                        *buckets_before.entry(name.clone()).or_insert(0) += 1;
+                        lines.push((r, trimmed.to_string(), name.clone()));
+                    }
+                    None => unparseable.push((name.clone(), trimmed.to_string())),
+                }
+            }
+            old_files.push(path);
+        }
+    }
+
+    lines.sort_by(|a, b| a.0.cmp(&b.0));
+
+    // 定长切分：根据排序后的词条计算切分点，每个分桶最多 max_bucket_size 条
+    if config.strategy == "size" {
+        let size = config.max_bucket_size.max(1);
+        config.split_points = lines
+            .chunks(size)
+            .skip(1)
+            .filter_map(|chunk| chunk.first().map(|l| l.0.clone()))
+            .collect();
+        config.split_points.dedup();
+    }
+
+    let mut new_buckets: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut words_moved = 0;
+    for (romanized, raw, old_name) in &lines {
+        let target = bucket_path(&lexicon_dir, &config, romanized);
+        let target_name = target
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if &target_name != old_name {
+            words_moved += 1;
+        }
+        new_buckets.entry(target_name).or_default().push(raw.clone());
+    }
+
+    let report = BucketMigrationReport {
+        dry_run: is_dry_run,
+        strategy: config.strategy.clone(),
+        words_scanned: lines.len(),
+        words_moved,
+        buckets_before,
+        buckets_after: new_buckets.iter().map(|(k, v)| (k.clone(), v.len())).collect(),
+        unparseable_lines: unparseable.len(),
+    };
+    if is_dry_run {
+        return Ok(report);
+    }
+
+    let _journal = begin(Path::new(&project_path), "migrate_lexicon_bucketing", None);
+    if !unparseable.is_empty() {
+        let records: Vec<QuarantineRecord> = unparseable
+            .into_iter()
+            .map(|(bucket, line)| QuarantineRecord {
+                bucket,
+                line,
+                error: "missing con_word_romanized during bucket migration".to_string(),
+            })
+            .collect();
+        quarantine_lines(&lexicon_dir, &records)?;
+    }
+
+    // 先写入新分桶，再删除不再使用的旧分桶，最后写入策略。
+    // 中途失败时词条最多重复出现在新旧两个分桶里，不会丢失；整个迁移可以通过撤销日志回退
+    for (name, bucket_lines) in &new_buckets {
+        atomic_write(&lexicon_dir.join(name), &(bucket_lines.join("\n") + "\n"))?;
+    }
+    for path in &old_files {
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        if new_buckets.contains_key(&name) {
+            continue;
+        }
+        capture_before(path);
+        note_own_removal(path);
+        fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    atomic_write(&bucketing_file(&lexicon_dir), &content)?;
+
+    Ok(report)
+}
+
+/// 读取语言当前的分桶策略
+#[command]
+pub fn load_bucketing_config(project_path: String, language_path: String) -> Result<BucketingConfig, String> {
+    let lexicon_dir = Path::new(&project_path)
+        .join(&language_path)
+        .join("lexicon");
+    load_bucketing(&lexicon_dir)
+}
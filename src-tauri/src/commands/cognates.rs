@@ -0,0 +1,299 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+use tauri::command;
+
+use crate::commands::lexicon::read_all_words;
+use crate::commands::workspace::load_workspace;
+use crate::models::{WordEntry, WorkspaceConfig};
+
+// ── 同源词集与反映形式表 ─────────────────────────────────
+//
+// 沿 etymology.parent_entry_id / source_language_id 把整个工作区的词条连成树：
+// 没写 source_language_id 的继承词默认指向本语言的父语言。
+// 从选定语言的词条出发，先上溯到工作区中能找到的最早来源，再向下收集所有后代，
+// 得到以该词为根的同源词集。借用关系同样会被追溯，借词以 origin_type
+// "borrowed" 出现在借出词所在的词集中。
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CognateReflex {
+    pub language_id: String,
+    pub entry_id: String,
+    /// 直接来源词条，根词为空
+    pub parent_entry_id: Option<String>,
+    pub origin_type: String,
+    /// 距根词的代数
+    pub depth: usize,
+    pub con_word_romanized: String,
+    pub phonetic_ipa: String,
+    pub glosses: Vec<String>,
+    pub is_deprecated: bool,
+    pub deprecated_since_language_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CognateSet {
+    pub root_language_id: String,
+    pub root_entry_id: String,
+    /// 按工作区语言顺序排列，根词在最前
+    pub reflexes: Vec<CognateReflex>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CognateLanguage {
+    pub language_id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CognateTable {
+    /// 至少出现在一个同源词集中的语言，按工作区顺序
+    pub languages: Vec<CognateLanguage>,
+    pub sets: Vec<CognateSet>,
+}
+
+type EntryKey = (String, String); // (language_id, entry_id)
+
+/// 词条的直接来源；没有来源链接，或借词缺少来源语言时为 None
+fn source_of(word: &WordEntry, parent_language: Option<&str>) -> Option<EntryKey> {
+    let link = word.etymology.parent_entry_id.clone().filter(|l| !l.is_empty())?;
+    let language = match word.etymology.source_language_id.as_deref() {
+        Some(source) if !source.is_empty() => source.to_string(),
+        _ if word.etymology.origin_type == "borrowed" => return None,
+        _ => parent_language?.to_string(),
+    };
+    Some((language, link))
+}
+
+fn reflex_of(word: &WordEntry, language_id: &str, depth: usize, is_root: bool) -> CognateReflex {
+    CognateReflex {
+        language_id: language_id.to_string(),
+        entry_id: word.entry_id.clone(),
+        parent_entry_id: if is_root { None } else { word.etymology.parent_entry_id.clone() },
+        origin_type: word.etymology.origin_type.clone(),
+        depth,
+        con_word_romanized: word.con_word_romanized.clone(),
+        phonetic_ipa: word.phonetic_ipa.clone(),
+        glosses: word
+            .senses
+            .iter()
+            .map(|s| s.gloss.clone())
+            .filter(|g| !g.is_empty())
+            .collect(),
+        is_deprecated: word.evolution.is_deprecated,
+        deprecated_since_language_id: word.evolution.deprecated_since_language_id.clone(),
+    }
+}
+
+fn build_table(
+    project: &Path,
+    ws: &WorkspaceConfig,
+    language_id: &str,
+    entry_ids: Option<Vec<String>>,
+) -> Result<CognateTable, String> {
+    if !ws.languages.iter().any(|l| l.language_id == language_id) {
+        return Err(format!("Language '{}' not found in workspace", language_id));
+    }
+
+    let mut words: HashMap<EntryKey, WordEntry> = HashMap::new();
+    let mut sources: HashMap<EntryKey, EntryKey> = HashMap::new();
+    let mut children: HashMap<EntryKey, Vec<EntryKey>> = HashMap::new();
+    let mut start: Vec<EntryKey> = Vec::new();
+    for lang in &ws.languages {
+        let lexicon = read_all_words(&project.join(&lang.path).join("lexicon"))?;
+        for word in lexicon {
+            let key = (lang.language_id.clone(), word.entry_id.clone());
+            if let Some(source) = source_of(&word, lang.parent_id.as_deref()) {
+                children.entry(source.clone()).or_default().push(key.clone());
+                sources.insert(key.clone(), source);
+            }
+            if lang.language_id == language_id
+                && entry_ids.as_ref().is_none_or(|ids| ids.contains(&word.entry_id))
+            {
+                start.push(key.clone());
+            }
+            words.insert(key, word);
+        }
+    }
+    let order: HashMap<&str, usize> = ws
+        .languages
+        .iter()
+        .enumerate()
+        .map(|(i, l)| (l.language_id.as_str(), i))
+        .collect();
+
+    let mut sets: Vec<CognateSet> = Vec::new();
+    let mut seen_roots: HashSet<EntryKey> = HashSet::new();
+    for key in start {
+        // 来源词条不存在（如已删除）时就以当前词为根；path 防止链接成环
+        let mut root = key;
+        let mut path: HashSet<EntryKey> = HashSet::from([root.clone()]);
+        while let Some(source) = sources.get(&root) {
+            if !words.contains_key(source) || !path.insert(source.clone()) {
+                break;
+            }
+            root = source.clone();
+        }
+        if !seen_roots.insert(root.clone()) {
+            continue;
+        }
+
+        let mut reflexes = vec![reflex_of(&words[&root], &root.0, 0, true)];
+        let mut visited: HashSet<EntryKey> = HashSet::from([root.clone()]);
+        let mut queue: VecDeque<(EntryKey, usize)> = VecDeque::from([(root.clone(), 0)]);
+        while let Some((current, depth)) = queue.pop_front() {
+            for child in children.get(&current).into_iter().flatten() {
+                if !visited.insert(child.clone()) {
+                    continue;
+                }
+                reflexes.push(reflex_of(&words[child], &child.0, depth + 1, false));
+                queue.push_back((child.clone(), depth + 1));
+            }
+        }
+        reflexes.sort_by_key(|r| (r.depth > 0, order.get(r.language_id.as_str()).copied(), r.depth));
+        sets.push(CognateSet {
+            root_language_id: root.0,
+            root_entry_id: root.1,
+            reflexes,
+        });
+    }
+    sets.sort_by(|a, b| {
+        let gloss = |s: &CognateSet| s.reflexes[0].glosses.first().cloned().unwrap_or_default();
+        gloss(a)
+            .cmp(&gloss(b))
+            .then_with(|| a.reflexes[0].con_word_romanized.cmp(&b.reflexes[0].con_word_romanized))
+    });
+
+    let used: HashSet<&str> = sets
+        .iter()
+        .flat_map(|s| s.reflexes.iter().map(|r| r.language_id.as_str()))
+        .collect();
+    let languages = ws
+        .languages
+        .iter()
+        .filter(|l| used.contains(l.language_id.as_str()))
+        .map(|l| CognateLanguage {
+            language_id: l.language_id.clone(),
+            name: l.name.clone(),
+        })
+        .collect();
+    Ok(CognateTable { languages, sets })
+}
+
+// ── 导出 ────────────────────────────────────────────────
+
+fn csv_field(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+/// 每个反映形式一行，便于在表格软件中筛选；带 BOM 以便 Excel 正确识别 UTF-8
+fn render_csv(table: &CognateTable) -> String {
+    let names: HashMap<&str, &str> = table
+        .languages
+        .iter()
+        .map(|l| (l.language_id.as_str(), l.name.as_str()))
+        .collect();
+    let mut lines = vec![[
+        "set", "language_id", "language", "entry_id", "parent_entry_id", "origin_type", "depth",
+        "con_word_romanized", "phonetic_ipa", "glosses", "deprecated",
+    ]
+    .map(csv_field)
+    .join(",")];
+    for set in &table.sets {
+        let set_id = format!("{}:{}", set.root_language_id, set.root_entry_id);
+        for r in &set.reflexes {
+            let depth = r.depth.to_string();
+            let glosses = r.glosses.join("; ");
+            let deprecated = if r.is_deprecated {
+                r.deprecated_since_language_id.clone().unwrap_or_else(|| "true".to_string())
+            } else {
+                String::new()
+            };
+            let row = [
+                set_id.as_str(),
+                r.language_id.as_str(),
+                names.get(r.language_id.as_str()).copied().unwrap_or_default(),
+                r.entry_id.as_str(),
+                r.parent_entry_id.as_deref().unwrap_or_default(),
+                r.origin_type.as_str(),
+                depth.as_str(),
+                r.con_word_romanized.as_str(),
+                r.phonetic_ipa.as_str(),
+                glosses.as_str(),
+                deprecated.as_str(),
+            ];
+            lines.push(row.map(csv_field).join(","));
+        }
+    }
+    format!("\u{feff}{}\n", lines.join("\n"))
+}
+
+fn md_escape(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+/// 每个同源词集一行、每种语言一列；借词标注来源语言，已废弃的词加删除线
+fn render_markdown(table: &CognateTable) -> String {
+    let mut out = String::new();
+    let header: Vec<String> = std::iter::once("Gloss".to_string())
+        .chain(table.languages.iter().map(|l| md_escape(&l.name)))
+        .collect();
+    out.push_str(&format!("| {} |\n", header.join(" | ")));
+    out.push_str(&format!("|{}\n", "---|".repeat(header.len())));
+    for set in &table.sets {
+        let mut cells = vec![md_escape(&set.reflexes[0].glosses.join("; "))];
+        for lang in &table.languages {
+            let forms: Vec<String> = set
+                .reflexes
+                .iter()
+                .filter(|r| r.language_id == lang.language_id)
+                .map(|r| {
+                    let mut cell = md_escape(&r.con_word_romanized);
+                    if !r.phonetic_ipa.is_empty() {
+                        cell.push_str(&format!(" /{}/", md_escape(&r.phonetic_ipa)));
+                    }
+                    if r.is_deprecated {
+                        cell = format!("~~{}~~", cell);
+                    }
+                    if r.origin_type == "borrowed" {
+                        cell.push_str(" (loan)");
+                    }
+                    cell
+                })
+                .collect();
+            cells.push(if forms.is_empty() { "—".to_string() } else { forms.join("<br>") });
+        }
+        out.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    out
+}
+
+/// 以 language_id 中的词条（entry_ids 为空时取全部）为起点构建同源词集
+#[command]
+pub fn build_cognate_sets(
+    project_path: String,
+    conlang_file_path: String,
+    language_id: String,
+    entry_ids: Option<Vec<String>>,
+) -> Result<CognateTable, String> {
+    let ws = load_workspace(conlang_file_path)?;
+    build_table(Path::new(&project_path), &ws, &language_id, entry_ids)
+}
+
+/// 把同源词集表渲染为 "csv" 或 "markdown" 文本，由前端写入用户选择的文件
+#[command]
+pub fn export_cognate_sets(
+    project_path: String,
+    conlang_file_path: String,
+    language_id: String,
+    entry_ids: Option<Vec<String>>,
+    format: String,
+) -> Result<String, String> {
+    let ws = load_workspace(conlang_file_path)?;
+    let table = build_table(Path::new(&project_path), &ws, &language_id, entry_ids)?;
+    match format.as_str() {
+        "csv" => Ok(render_csv(&table)),
+        "markdown" | "md" => Ok(render_markdown(&table)),
+        other => Err(format!("Unsupported export format '{}'", other)),
+    }
+}
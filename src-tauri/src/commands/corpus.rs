@@ -1,5 +1,10 @@
+use crate::commands::journal::{begin, capture_before};
 use crate::commands::lexicon::atomic_write;
-use crate::models::{CorpusIndexEntry, CorpusText, CorpusTextMeta};
+use crate::commands::project_lock::check_write_access;
+use crate::commands::watcher::{check_expected_hash, content_hash, note_own_removal};
+use crate::commands::schema::{ensure_writable, parse_versioned, SchemaKind};
+use crate::commands::timestamp::{normalize_timestamp, stamp_timestamps};
+use crate::models::{CorpusIndexEntry, CorpusText, ExtraFields, CURRENT_SCHEMA_VERSION};
 use std::fs;
 use std::path::Path;
 use tauri::command;
@@ -21,11 +26,20 @@ pub fn load_corpus_index(
     }
 
     let content = fs::read_to_string(&index_path).map_err(|e| e.to_string())?;
-    let entries: Vec<CorpusIndexEntry> =
+    let mut entries: Vec<CorpusIndexEntry> =
         serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    // 索引没有 schema 版本，旧格式的时间戳在读取时统一，下次保存时写回
+    for entry in &mut entries {
+        entry.metadata.created_at = normalize_timestamp(&entry.metadata.created_at);
+        entry.metadata.updated_at = normalize_timestamp(&entry.metadata.updated_at);
+    }
     Ok(entries)
 }
 
+fn corpus_times(text: &mut CorpusText) -> (&mut String, &mut String) {
+    (&mut text.metadata.created_at, &mut text.metadata.updated_at)
+}
+
 /// Load a single corpus text by ID
 #[command]
 pub fn load_corpus_text(
@@ -41,21 +55,34 @@ pub fn load_corpus_text(
     }
 
     let content = fs::read_to_string(&file_path).map_err(|e| e.to_string())?;
-    serde_json::from_str(&content).map_err(|e| e.to_string())
+    parse_versioned(SchemaKind::Corpus, &content)
 }
 
-/// Save a corpus text (create or update) and update the index
+/// Save a corpus text (create or update) and update the index.
+/// Returns the content hash of the written file for the next `expected_hash`.
 #[command]
 pub fn save_corpus_text(
     project_path: String,
     language_path: String,
     text: CorpusText,
-) -> Result<(), String> {
+    expected_hash: Option<String>,
+    transaction_id: Option<String>,
+) -> Result<String, String> {
+    let _journal = begin(Path::new(&project_path), "save_corpus_text", transaction_id);
     let corpus_dir = Path::new(&project_path).join(&language_path).join("corpus");
     fs::create_dir_all(&corpus_dir).map_err(|e| e.to_string())?;
 
     // Save the individual corpus file
     let file_path = corpus_dir.join(format!("{}.json", text.corpus_id));
+    check_expected_hash(&file_path, expected_hash.as_deref())?;
+    ensure_writable(&file_path, text.schema_version)?;
+    let mut text = text;
+    text.schema_version = CURRENT_SCHEMA_VERSION;
+    let existing: Option<CorpusText> = match fs::read_to_string(&file_path) {
+        Ok(content) => parse_versioned(SchemaKind::Corpus, &content).ok(),
+        Err(_) => None,
+    };
+    stamp_timestamps(&mut text, existing.as_ref(), corpus_times);
     let content = serde_json::to_string_pretty(&text).map_err(|e| e.to_string())?;
     atomic_write(&file_path, &content)?;
 
@@ -69,19 +96,17 @@ pub fn save_corpus_text(
     };
 
     // Upsert index entry
-    let new_entry = CorpusIndexEntry {
+    let mut new_entry = CorpusIndexEntry {
         corpus_id: text.corpus_id.clone(),
         title: text.title.clone(),
         description: text.description.clone(),
-        metadata: CorpusTextMeta {
-            tags: text.metadata.tags.clone(),
-            created_at: text.metadata.created_at.clone(),
-            updated_at: text.metadata.updated_at.clone(),
-            auto_gloss_report: text.metadata.auto_gloss_report.clone(),
-        },
+        metadata: text.metadata.clone(),
+        extra: ExtraFields::new(),
     };
 
     if let Some(pos) = index.iter().position(|e| e.corpus_id == text.corpus_id) {
+        // 保留索引条目上未识别的字段
+        new_entry.extra = std::mem::take(&mut index[pos].extra);
         index[pos] = new_entry;
     } else {
         index.push(new_entry);
@@ -93,7 +118,7 @@ pub fn save_corpus_text(
     let idx_content = serde_json::to_string_pretty(&index).map_err(|e| e.to_string())?;
     atomic_write(&index_path, &idx_content)?;
 
-    Ok(())
+    Ok(content_hash(content.as_bytes()))
 }
 
 /// Delete a corpus text and update the index
@@ -102,12 +127,19 @@ pub fn delete_corpus_text(
     project_path: String,
     language_path: String,
     corpus_id: String,
+    expected_hash: Option<String>,
+    transaction_id: Option<String>,
 ) -> Result<(), String> {
+    let _journal = begin(Path::new(&project_path), "delete_corpus_text", transaction_id);
     let corpus_dir = Path::new(&project_path).join(&language_path).join("corpus");
 
     // Delete the individual file
     let file_path = corpus_dir.join(format!("{}.json", corpus_id));
+    check_write_access(&file_path)?;
+    check_expected_hash(&file_path, expected_hash.as_deref())?;
     if file_path.exists() {
+        capture_before(&file_path);
+        note_own_removal(&file_path);
         fs::remove_file(&file_path).map_err(|e| e.to_string())?;
     }
 
@@ -4,7 +4,9 @@ use std::collections::HashMap;
 use tauri::command;
 use serde::{Deserialize, Serialize};
 use crate::models::{WorkspaceConfig, WordEntry, CorpusText, CorpusIndexEntry};
-use crate::commands::lexicon::atomic_write;
+use crate::commands::bucketing::{bucket_path, load_bucketing};
+use crate::commands::lexicon::{atomic_write, parse_word_line};
+use crate::commands::search_index::invalidate_index;
 
 /// Bundle format for exporting/importing entire workspace
 #[derive(Serialize, Deserialize)]
@@ -79,7 +81,7 @@ pub fn export_workspace_bundle(project_path: String, conlang_file_path: String)
                     for line in content.lines() {
                         let line = line.trim();
                         if !line.is_empty() {
-                            if let Ok(word) = serde_json::from_str::<WordEntry>(line) {
+                            if let Ok(word) = parse_word_line(line) {
                                 words.push(word);
                             }
                         }
@@ -165,24 +167,19 @@ pub fn import_workspace_bundle(project_path: String, conlang_file_path: String,
         let lexicon_dir = lang_dir.join("lexicon");
         fs::create_dir_all(&lexicon_dir).map_err(|e| e.to_string())?;
 
-        // Group words by bucket
-        let mut buckets: HashMap<String, Vec<String>> = HashMap::new();
+        // Group words by bucket (using the language's bucketing strategy)
+        let bucketing = load_bucketing(&lexicon_dir)?;
+        let mut buckets: HashMap<std::path::PathBuf, Vec<String>> = HashMap::new();
         for word in &lang_bundle.words {
-            let first_char = word.con_word_romanized.chars().next()
-                .map(|c| c.to_lowercase().next().unwrap_or('_'))
-                .unwrap_or('_');
-            let bucket_name = if first_char.is_ascii_alphabetic() {
-                format!("lexicon_{}.ndjson", first_char)
-            } else {
-                "lexicon_others.ndjson".to_string()
-            };
+            let bucket = bucket_path(&lexicon_dir, &bucketing, &word.con_word_romanized);
             let json_line = serde_json::to_string(word).map_err(|e| e.to_string())?;
-            buckets.entry(bucket_name).or_default().push(json_line);
+            buckets.entry(bucket).or_default().push(json_line);
         }
-        for (filename, lines) in &buckets {
+        for (bucket, lines) in &buckets {
             let content = lines.join("\n") + "\n";
-            atomic_write(&lexicon_dir.join(filename), &content)?;
+            atomic_write(bucket, &content)?;
         }
+        invalidate_index(&lexicon_dir)?;
 
         // Corpus
         if !lang_bundle.corpus_index.is_empty() {
@@ -1,9 +1,18 @@
+use std::ffi::OsString;
 use std::fs;
 use std::path::Path;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use tauri::command;
-use crate::models::{WorkspaceConfig, LanguageEntry, WordEntry};
-use crate::commands::lexicon::atomic_write;
+use crate::models::{
+    WorkspaceConfig, LanguageEntry, ExtraFields, PhonologyConfig, SCAConfig, SCARuleSet, WordEntry,
+    CURRENT_SCHEMA_VERSION,
+};
+use crate::commands::bucketing::{bucket_path, load_bucketing};
+use crate::commands::lexicon::{atomic_write, parse_word_line, write_bucket, ParsedBucket};
+use crate::commands::sca_engine::{build_macros, fired_rules, IpaGenerator, SoundChanger};
+use crate::commands::schema::{newer_schema_error, parse_versioned, SchemaKind};
+use crate::commands::project_lock::check_write_access;
+use crate::commands::timestamp::now_rfc3339;
 
 /// Copy a directory recursively
 pub fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), String> {
@@ -21,6 +30,172 @@ pub fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), String> {
     Ok(())
 }
 
+/// 分叉时对继承词条执行的祖语音变：直接给出规则集，或指定一门语言复制其 sca_rules.json
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
+pub struct ForkSoundChanges {
+    #[serde(default)]
+    pub rule_sets: Option<Vec<SCARuleSet>>,
+    #[serde(default)]
+    pub template_language_id: Option<String>,
+}
+
+/// 分叉音变造成的一次音位合并：from 变成了 into（into 为空表示与零合并，即脱落）
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct SoundChangeMerger {
+    pub rule_id: String,
+    pub from: String,
+    pub into: String,
+    /// 发生该合并的词条数
+    pub words: usize,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct HomophoneMember {
+    pub entry_id: String,
+    /// 音变前（父语言中）的词形
+    pub parent_form: String,
+    pub gloss: String,
+}
+
+/// 音变后新出现的同音词：父语言中词形不同，子语言中变得相同
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct HomophoneGroup {
+    pub form: String,
+    pub members: Vec<HomophoneMember>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct ForkSoundChangeReport {
+    pub language_id: String,
+    pub rule_sets: usize,
+    /// 语境无法编译、被跳过的规则
+    pub skipped_rules: Vec<String>,
+    pub words: usize,
+    pub changed: usize,
+    pub ipa_regenerated: usize,
+    pub mergers: Vec<SoundChangeMerger>,
+    pub homophones: Vec<HomophoneGroup>,
+}
+
+/// 分叉时对继承词条逐个执行音变，并累计合并与同音词统计
+struct ForkEvolution<'a> {
+    changer: &'a SoundChanger,
+    ipa: Option<IpaGenerator>,
+    /// 父语言音位库；音段变成其中已有的音位才算合并
+    inventory: HashSet<String>,
+    words: usize,
+    changed: usize,
+    ipa_regenerated: usize,
+    mergers: Vec<SoundChangeMerger>,
+    forms: BTreeMap<String, Vec<HomophoneMember>>,
+}
+
+impl<'a> ForkEvolution<'a> {
+    fn new(changer: &'a SoundChanger, ipa: Option<IpaGenerator>, phonology: Option<&PhonologyConfig>) -> Self {
+        let inventory = phonology
+            .map(|p| {
+                p.phoneme_inventory
+                    .consonants
+                    .iter()
+                    .chain(p.phoneme_inventory.vowels.iter())
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            changer,
+            ipa,
+            inventory,
+            words: 0,
+            changed: 0,
+            ipa_regenerated: 0,
+            mergers: Vec::new(),
+            forms: BTreeMap::new(),
+        }
+    }
+
+    /// 改写词形、记录生效的规则并重新生成 IPA；返回词形是否改变
+    fn apply(&mut self, word: &mut WordEntry) -> bool {
+        self.words += 1;
+        let parent_form = word.con_word_romanized.clone();
+        let (result, steps) = self.changer.apply(&parent_form);
+        let result = result.trim().to_string();
+
+        let mut merged: BTreeSet<(&str, &str, &str)> = BTreeSet::new();
+        for step in &steps {
+            for (from, into) in &step.segments {
+                if into.is_empty() || self.inventory.contains(into) {
+                    merged.insert((&step.rule_id, from, into));
+                }
+            }
+        }
+        for (rule_id, from, into) in merged {
+            match self
+                .mergers
+                .iter_mut()
+                .find(|m| m.rule_id == rule_id && m.from == from && m.into == into)
+            {
+                Some(merger) => merger.words += 1,
+                None => self.mergers.push(SoundChangeMerger {
+                    rule_id: rule_id.to_string(),
+                    from: from.to_string(),
+                    into: into.to_string(),
+                    words: 1,
+                }),
+            }
+        }
+        word.etymology.applied_sound_changes = fired_rules(&steps);
+
+        let changed = result != parent_form;
+        if changed {
+            self.changed += 1;
+            word.con_word_romanized = result;
+            // 手动指定的 IPA 保留；没有转写表时清空，避免留下父语言的读音
+            if !word.phonetic_override {
+                word.phonetic_ipa = match &self.ipa {
+                    Some(generator) => {
+                        self.ipa_regenerated += 1;
+                        generator.generate(&word.con_word_romanized)
+                    }
+                    None => String::new(),
+                };
+            }
+        }
+
+        self.forms
+            .entry(word.con_word_romanized.clone())
+            .or_default()
+            .push(HomophoneMember {
+                entry_id: word.entry_id.clone(),
+                parent_form,
+                gloss: word.senses.first().map(|s| s.gloss.clone()).unwrap_or_default(),
+            });
+        changed
+    }
+
+    fn into_report(self, language_id: &str, rule_sets: usize, skipped_rules: Vec<String>) -> ForkSoundChangeReport {
+        let homophones = self
+            .forms
+            .into_iter()
+            .filter(|(_, members)| {
+                let parent_forms: HashSet<&str> = members.iter().map(|m| m.parent_form.as_str()).collect();
+                parent_forms.len() > 1
+            })
+            .map(|(form, members)| HomophoneGroup { form, members })
+            .collect();
+        ForkSoundChangeReport {
+            language_id: language_id.to_string(),
+            rule_sets,
+            skipped_rules,
+            words: self.words,
+            changed: self.changed,
+            ipa_regenerated: self.ipa_regenerated,
+            mergers: self.mergers,
+            homophones,
+        }
+    }
+}
+
 #[command]
 pub fn fork_language(
     project_path: String,
@@ -29,8 +204,10 @@ pub fn fork_language(
     new_name: String,
     new_id: String,
     new_path: String,
-) -> Result<(), String> {
+    sound_changes: Option<ForkSoundChanges>,
+) -> Result<Option<ForkSoundChangeReport>, String> {
     let project = Path::new(&project_path);
+    check_write_access(project)?;
     let ws_path = Path::new(&conlang_file_path);
 
     // 1. Load workspace config to find parent path
@@ -54,6 +231,87 @@ pub fn fork_language(
         return Err(format!("Directory '{}' already exists", new_path));
     }
 
+    // 分叉时执行的祖语音变：直接传入，或从模板语言的 sca_rules.json 复制
+    let sound_changes = sound_changes.unwrap_or_default();
+    let rule_sets = match (sound_changes.rule_sets, sound_changes.template_language_id) {
+        (Some(_), Some(_)) => {
+            return Err("Pass either rule_sets or template_language_id, not both".to_string())
+        }
+        (Some(sets), None) => sets,
+        (None, Some(template_id)) => {
+            let template = ws_config
+                .languages
+                .iter()
+                .find(|l| l.language_id == template_id)
+                .ok_or_else(|| format!("Template language '{}' not found in workspace", template_id))?;
+            let sca_path = project.join(&template.path).join("sca_rules.json");
+            if sca_path.exists() {
+                let content = fs::read_to_string(&sca_path).map_err(|e| e.to_string())?;
+                let config: SCAConfig = parse_versioned(SchemaKind::Sca, &content)?;
+                if config.schema_version > CURRENT_SCHEMA_VERSION {
+                    return Err(newer_schema_error("sca_rules.json", config.schema_version));
+                }
+                config.rule_sets
+            } else {
+                Vec::new()
+            }
+        }
+        (None, None) => Vec::new(),
+    };
+    let phonology: Option<PhonologyConfig> = fs::read_to_string(parent_dir.join("phonology.json"))
+        .ok()
+        .and_then(|content| parse_versioned(SchemaKind::Phonology, &content).ok());
+    let macros = phonology.as_ref().map(build_macros).unwrap_or_default();
+    let (changer, skipped_rules) = SoundChanger::new(&rule_sets, &macros);
+    let ipa = phonology.as_ref().and_then(IpaGenerator::new);
+    let mut evolution = (!rule_sets.is_empty()).then(|| ForkEvolution::new(&changer, ipa, phonology.as_ref()));
+
+    // 读取并演化父语言的词条。在创建新目录之前完成，词条 schema 过新等错误不会留下半成品目录。
+    // 音变改了词形的词条可能落入别的分桶，先按目标分桶文件名归集，创建目录后统一写入
+    let lexicon_src = parent_dir.join("lexicon");
+    let mut buckets: Option<BTreeMap<OsString, Vec<WordEntry>>> = None;
+    if lexicon_src.exists() {
+        let bucketing = load_bucketing(&lexicon_src)?;
+        let grouped = buckets.get_or_insert_with(BTreeMap::new);
+        for entry in fs::read_dir(&lexicon_src).map_err(|e| e.to_string())? {
+            let entry = entry.map_err(|e| e.to_string())?;
+            let src_file = entry.path();
+            if src_file.extension().map_or(false, |e| e == "ndjson") {
+                let content = fs::read_to_string(&src_file).map_err(|e| e.to_string())?;
+                for line in content.lines() {
+                    let line = line.trim();
+                    if line.is_empty() {
+                        continue;
+                    }
+                    match parse_word_line(line) {
+                        Ok(mut word) => {
+                            if word.schema_version > CURRENT_SCHEMA_VERSION {
+                                return Err(newer_schema_error(&word.entry_id, word.schema_version));
+                            }
+                            // Keep inherited entry_id stable across parent/child for deterministic sync.
+                            let original_entry_id = word.entry_id.clone();
+                            word.language_id = new_id.clone();
+                            word.etymology.origin_type = "evolved".to_string();
+                            word.etymology.parent_entry_id = Some(original_entry_id);
+                            word.etymology.source_language_id = Some(parent_id.clone());
+                            let mut file_name = entry.file_name();
+                            if let Some(evolution) = evolution.as_mut() {
+                                if evolution.apply(&mut word) {
+                                    let target = bucket_path(&lexicon_src, &bucketing, &word.con_word_romanized);
+                                    file_name = target.file_name().map(OsString::from).unwrap_or(file_name);
+                                }
+                            }
+                            grouped.entry(file_name).or_default().push(word);
+                        }
+                        Err(e) => {
+                            eprintln!("Fork: skipping malformed word entry: {}", e);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     // 2. Copy phonology.json
     let phono_src = parent_dir.join("phonology.json");
     fs::create_dir_all(&new_dir).map_err(|e| e.to_string())?;
@@ -89,56 +347,38 @@ pub fn fork_language(
         }
     }
 
-    // 4. Create empty sca_rules.json for the new language
-    let sca_content = format!(
-        "{{\"language_id\":\"{}\",\"rule_sets\":[]}}",
-        new_id
-    );
-    let sca_pretty: serde_json::Value = serde_json::from_str(&sca_content).map_err(|e| e.to_string())?;
+    // 4. Create sca_rules.json for the new language, seeded with the fork-time rule sets.
+    // The inherited lexicon is evolved below, so the sets are marked as already applied to it
+    let applied_at = now_rfc3339();
+    let sca_config = SCAConfig {
+        schema_version: CURRENT_SCHEMA_VERSION,
+        language_id: new_id.clone(),
+        rule_sets: rule_sets
+            .iter()
+            .map(|rs| SCARuleSet {
+                lexicon_applied_at: Some(applied_at.clone()),
+                ..rs.clone()
+            })
+            .collect(),
+        extra: ExtraFields::new(),
+    };
     atomic_write(
         &new_dir.join("sca_rules.json"),
-        &serde_json::to_string_pretty(&sca_pretty).map_err(|e| e.to_string())?,
+        &serde_json::to_string_pretty(&sca_config).map_err(|e| e.to_string())?,
     )?;
 
-    // 5. Copy lexicon/ and update word entries
-    let lexicon_src = parent_dir.join("lexicon");
-    let lexicon_dst = new_dir.join("lexicon");
-    if lexicon_src.exists() {
+    // 5. Write the inherited lexicon
+    if let Some(buckets) = buckets {
+        let lexicon_dst = new_dir.join("lexicon");
         fs::create_dir_all(&lexicon_dst).map_err(|e| e.to_string())?;
-        for entry in fs::read_dir(&lexicon_src).map_err(|e| e.to_string())? {
-            let entry = entry.map_err(|e| e.to_string())?;
-            let src_file = entry.path();
-            if src_file.extension().map_or(false, |e| e == "ndjson") {
-                let content = fs::read_to_string(&src_file).map_err(|e| e.to_string())?;
-                let mut updated_lines: Vec<String> = Vec::new();
-                for line in content.lines() {
-                    let line = line.trim();
-                    if line.is_empty() {
-                        continue;
-                    }
-                    match serde_json::from_str::<WordEntry>(line) {
-                        Ok(mut word) => {
-                            // Keep inherited entry_id stable across parent/child for deterministic sync.
-                            let original_entry_id = word.entry_id.clone();
-                            word.language_id = new_id.clone();
-                            word.etymology.origin_type = "evolved".to_string();
-                            word.etymology.parent_entry_id = Some(original_entry_id);
-                            word.etymology.source_language_id = Some(parent_id.clone());
-                            if let Ok(json) = serde_json::to_string(&word) {
-                                updated_lines.push(json);
-                            }
-                        }
-                        Err(e) => {
-                            eprintln!("Fork: skipping malformed word entry: {}", e);
-                        }
-                    }
-                }
-                if !updated_lines.is_empty() {
-                    let new_content = updated_lines.join("\n") + "\n";
-                    let dst_file = lexicon_dst.join(entry.file_name());
-                    atomic_write(&dst_file, &new_content)?;
-                }
-            }
+        // 分桶文件名沿用父语言，分桶策略也必须一起复制
+        let bucketing_src = lexicon_src.join("bucketing.json");
+        if bucketing_src.exists() {
+            fs::copy(&bucketing_src, lexicon_dst.join("bucketing.json")).map_err(|e| e.to_string())?;
+        }
+        // write_bucket 按 con_word_romanized 排序，与保存词条时的分桶顺序一致
+        for (file_name, words) in buckets {
+            write_bucket(&lexicon_dst, &lexicon_dst.join(file_name), ParsedBucket { words, malformed: Vec::new() })?;
         }
     }
 
@@ -150,17 +390,19 @@ pub fn fork_language(
     }
 
     // 7. Update workspace config
+    let report = evolution.map(|e| e.into_report(&new_id, rule_sets.len(), skipped_rules));
     let mut updated_ws = ws_config;
     updated_ws.languages.push(LanguageEntry {
         language_id: new_id,
         name: new_name,
         path: new_path,
         parent_id: Some(parent_id),
+        extra: ExtraFields::new(),
     });
     let ws_content = serde_json::to_string_pretty(&updated_ws).map_err(|e| e.to_string())?;
     atomic_write(ws_path, &ws_content)?;
 
-    Ok(())
+    Ok(report)
 }
 
 /// Delete a language and its data directory
@@ -178,6 +420,7 @@ pub fn delete_language(
     language_id: String,
 ) -> Result<(), String> {
     let project = Path::new(&project_path);
+    check_write_access(project)?;
     let ws_path = Path::new(&conlang_file_path);
 
     // 1. Load workspace config
@@ -233,7 +476,7 @@ pub struct MigrationStats {
     pub files_changed: usize,
 }
 
-fn load_language_entry_ids(project: &Path, language_path: &str) -> Result<HashSet<String>, String> {
+pub fn load_language_entry_ids(project: &Path, language_path: &str) -> Result<HashSet<String>, String> {
     let lexicon_dir = project.join(language_path).join("lexicon");
     let mut ids = HashSet::new();
     if !lexicon_dir.exists() {
@@ -249,7 +492,7 @@ fn load_language_entry_ids(project: &Path, language_path: &str) -> Result<HashSe
                 if trimmed.is_empty() {
                     continue;
                 }
-                if let Ok(word) = serde_json::from_str::<WordEntry>(trimmed) {
+                if let Ok(word) = parse_word_line(trimmed) {
                     ids.insert(word.entry_id);
                 }
             }
@@ -319,7 +562,7 @@ pub fn migrate_inherited_lexicon_links(
                     continue;
                 }
 
-                match serde_json::from_str::<WordEntry>(trimmed) {
+                match parse_word_line(trimmed) {
                     Ok(mut word) => {
                         let mut line_changed = false;
                         stats.words_scanned += 1;
@@ -351,7 +594,8 @@ pub fn migrate_inherited_lexicon_links(
                             line_changed = true;
                         }
 
-                        if line_changed {
+                        // 更新版本写入的词条只统计、不改写
+                        if line_changed && word.schema_version <= CURRENT_SCHEMA_VERSION {
                             file_changed = true;
                             if let Ok(serialized) = serde_json::to_string(&word) {
                                 new_lines.push(serialized);
@@ -0,0 +1,708 @@
+use crate::commands::journal::{begin, capture_before};
+use crate::commands::lexicon::{
+    atomic_write, delete_words_in_dir, parse_bucket, parse_word_line, save_words_in_dir, WordDeleteRequest,
+    WordSaveRequest,
+};
+use crate::commands::project_lock::check_write_access;
+use crate::commands::search_index::invalidate_index;
+use crate::commands::timestamp::format_unix_millis;
+use crate::commands::watcher::{note_own_removal, word_hash};
+use crate::commands::workspace::load_workspace;
+use crate::merge::{conflicts_of, resolve_conflict, to_pretty_json, MergeConflict};
+use crate::models::{LanguageEntry, WordEntry, WorkspaceConfig};
+use git2::{
+    Commit, Delta, DiffOptions, IndexAddOption, ObjectType, Repository, Signature, Sort, StatusOptions, Tree,
+    TreeWalkMode, TreeWalkResult,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::Path;
+use tauri::command;
+
+// ── 本地 Git 集成 ────────────────────────────────────────
+//
+// 在项目目录（.conlang 所在目录）中维护一个本地仓库，只做本地操作，不涉及任何远程。
+// - git_init：初始化仓库并写入（或补全）.gitignore，把现有文件作为第一次提交
+// - git_checkpoint：暂存全部改动并提交，提交信息根据改动内容生成（例如 "Edit word amar in Proto"）
+// - git_log：提交历史，可按语言目录过滤
+// - git_revert_language：把一个语言目录恢复为某次提交时的内容，并作为新的提交记录下来
+// - list_merge_conflicts / resolve_merge_conflict：列出 conlang-merge 留下的 `_merge_conflicts` 记录，选定一方后写回
+//
+// 快照、撤销日志、反查索引等点开头的目录是派生数据，不纳入版本控制。
+// 词典分桶与 grammar.json / sca_rules.json 通过 .gitattributes 交给配套的 conlang-merge 做语义合并和可读 diff。
+
+const GITIGNORE: &str = "# Conlang Maker: 快照、撤销日志、索引等派生数据不纳入版本控制\n.*/\n*.tmp\n*.conlang.lock\n";
+
+const GITATTRIBUTES: &str = "# Conlang Maker: 词典分桶与配置文件按词条/规则合并，diff 时展开为可读格式\n*.ndjson merge=conlang diff=conlang\ngrammar.json merge=conlang diff=conlang\nsca_rules.json merge=conlang diff=conlang\n";
+
+/// 生成的提交信息正文最多列出的改动条数
+const MAX_MESSAGE_LINES: usize = 20;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GitCommitInfo {
+    pub commit_id: String,
+    pub short_id: String,
+    pub summary: String,
+    pub message: String,
+    pub author: String,
+    /// RFC 3339
+    pub time: String,
+    /// 本次提交改动的文件（相对项目目录；按语言过滤时只含该语言的文件）
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GitRepoStatus {
+    pub initialized: bool,
+    pub branch: Option<String>,
+    pub head: Option<GitCommitInfo>,
+    /// 尚未提交的改动（相对项目目录）
+    pub changed_files: Vec<String>,
+}
+
+/// 一个对象上未解决的合并冲突：词条，或语言的某个配置文件
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MergeConflictGroup {
+    /// 相对项目目录的路径
+    pub file: String,
+    pub language_id: String,
+    /// 冲突在词条上时为该词条的 entry_id；配置文件为 None
+    pub entry_id: Option<String>,
+    /// 显示用：词条拼写，配置文件为文件名
+    pub label: String,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GitRevertResult {
+    /// 被改写或重新创建的文件
+    pub restored: Vec<String>,
+    /// 在目标提交中不存在、因此被删除的文件
+    pub removed: Vec<String>,
+    /// 记录这次恢复的提交；语言目录本来就与目标提交一致时为 None
+    pub commit: Option<GitCommitInfo>,
+}
+
+fn open_repo(project_path: &str) -> Result<Repository, String> {
+    Repository::open(project_path)
+        .map_err(|_| format!("{} is not a git repository; initialize it first", project_path))
+}
+
+fn signature(repo: &Repository) -> Result<Signature<'static>, String> {
+    // 用户没有配置 user.name / user.email 时使用应用自己的身份
+    repo.signature()
+        .or_else(|_| Signature::now("Conlang Maker", "conlang-maker@localhost"))
+        .map_err(|e| e.to_string())
+}
+
+fn head_commit(repo: &Repository) -> Option<Commit<'_>> {
+    repo.head().ok().and_then(|h| h.peel_to_commit().ok())
+}
+
+/// 提交相对第一个父提交改动的文件；pathspec 为空时不过滤
+fn changed_files(repo: &Repository, commit: &Commit, pathspec: &[String]) -> Result<Vec<String>, String> {
+    let tree = commit.tree().map_err(|e| e.to_string())?;
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree().map_err(|e| e.to_string())?),
+        Err(_) => None,
+    };
+    let mut opts = DiffOptions::new();
+    for spec in pathspec {
+        opts.pathspec(spec);
+    }
+    let diff = repo
+        .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut opts))
+        .map_err(|e| e.to_string())?;
+    let mut files = BTreeSet::new();
+    for delta in diff.deltas() {
+        let path = delta.new_file().path().or_else(|| delta.old_file().path());
+        if let Some(path) = path {
+            files.insert(path.to_string_lossy().replace('\\', "/"));
+        }
+    }
+    Ok(files.into_iter().collect())
+}
+
+fn commit_info(commit: &Commit, files: Vec<String>) -> GitCommitInfo {
+    let commit_id = commit.id().to_string();
+    GitCommitInfo {
+        short_id: commit_id[..7.min(commit_id.len())].to_string(),
+        commit_id,
+        summary: commit.summary().unwrap_or_default().to_string(),
+        message: commit.message().unwrap_or_default().to_string(),
+        author: commit.author().name().unwrap_or_default().to_string(),
+        time: format_unix_millis(commit.time().seconds() * 1000),
+        files,
+    }
+}
+
+/// 路径所属的语言（取目录最长匹配的那个，嵌套的子语言优先）
+fn language_of<'a>(config: &'a WorkspaceConfig, path: &str) -> Option<&'a LanguageEntry> {
+    config
+        .languages
+        .iter()
+        .filter(|l| !l.path.is_empty() && path.starts_with(&format!("{}/", l.path.trim_end_matches('/'))))
+        .max_by_key(|l| l.path.len())
+}
+
+/// 属于语言目录本身、而不属于嵌套子语言目录或点开头目录的路径
+fn in_language(config: &WorkspaceConfig, language_path: &str, path: &str) -> bool {
+    let lang = language_path.trim_end_matches('/');
+    let Some(rest) = path.strip_prefix(&format!("{}/", lang)) else {
+        return false;
+    };
+    if rest.split('/').rev().skip(1).any(|dir| dir.starts_with('.')) {
+        return false;
+    }
+    language_of(config, path).is_some_and(|l| l.path.trim_end_matches('/') == lang)
+}
+
+/// entry_id → (拼写, 词条哈希)
+type WordDigests = BTreeMap<String, (String, String)>;
+
+fn read_words(content: &[u8]) -> WordDigests {
+    String::from_utf8_lossy(content)
+        .lines()
+        .filter_map(|line| parse_word_line(line.trim()).ok())
+        .map(|w| (w.entry_id.clone(), (w.con_word_romanized.clone(), word_hash(&w))))
+        .collect()
+}
+
+/// 词典分桶在 HEAD 与工作目录中的词条，累积到 old / new 中
+fn collect_bucket_words(
+    repo: &Repository,
+    head_tree: Option<&Tree>,
+    path: &str,
+    old: &mut WordDigests,
+    new: &mut WordDigests,
+) {
+    if let Some(blob) = head_tree
+        .and_then(|t| t.get_path(Path::new(path)).ok())
+        .and_then(|e| repo.find_blob(e.id()).ok())
+    {
+        old.extend(read_words(blob.content()));
+    }
+    if let Some(bytes) = repo.workdir().and_then(|dir| fs::read(dir.join(path)).ok()) {
+        new.extend(read_words(&bytes));
+    }
+}
+
+/// 一种语言的词条改动 → 提交信息中的若干行。改了拼写的词条会换分桶，按 entry_id 合并后算作一次编辑
+fn describe_words(
+    lang_name: &str,
+    old: &WordDigests,
+    new: &WordDigests,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (id, (word, hash)) in new {
+        match old.get(id) {
+            None => lines.push(format!("Add word {} in {}", word, lang_name)),
+            Some((_, old_hash)) if old_hash != hash => lines.push(format!("Edit word {} in {}", word, lang_name)),
+            _ => {}
+        }
+    }
+    for (id, (word, _)) in old {
+        if !new.contains_key(id) {
+            lines.push(format!("Delete word {} in {}", word, lang_name));
+        }
+    }
+    lines
+}
+
+/// 词典以外文件的改动 → 提交信息中的一行，例如 "Edit grammar in Proto"；不值得单独提及的文件返回 None
+fn describe_file(lang: Option<&LanguageEntry>, path: &str, status: Delta) -> Option<String> {
+    let lang_name = lang.map(|l| l.name.as_str()).unwrap_or("project");
+    let rel = lang
+        .and_then(|l| path.strip_prefix(&format!("{}/", l.path.trim_end_matches('/'))))
+        .unwrap_or(path);
+    let verb = match status {
+        Delta::Added | Delta::Untracked => "Add",
+        Delta::Deleted => "Delete",
+        _ => "Edit",
+    };
+    let what = match rel {
+        "phonology.json" => "phonology".to_string(),
+        "grammar.json" => "grammar".to_string(),
+        "sca_rules.json" => "sound changes".to_string(),
+        "corpus/corpus_index.json" | "lexicon/bucketing.json" => return None,
+        _ if rel.starts_with("corpus/") => format!(
+            "corpus text {}",
+            rel.trim_start_matches("corpus/").trim_end_matches(".json")
+        ),
+        _ if lang.is_none() && path.ends_with(".conlang") => return Some("Edit workspace".to_string()),
+        _ => rel.to_string(),
+    };
+    Some(format!("{} {} in {}", verb, what, lang_name))
+}
+
+/// 根据暂存区相对 HEAD 的改动生成提交信息：只有一项改动时直接作为标题，否则汇总后逐条列出
+fn generate_message(repo: &Repository, config: &WorkspaceConfig) -> Result<String, String> {
+    let index = repo.index().map_err(|e| e.to_string())?;
+    let head_tree = match head_commit(repo) {
+        Some(c) => Some(c.tree().map_err(|e| e.to_string())?),
+        None => None,
+    };
+    let diff = repo
+        .diff_tree_to_index(head_tree.as_ref(), Some(&index), None)
+        .map_err(|e| e.to_string())?;
+
+    let mut lines = Vec::new();
+    let mut languages = BTreeSet::new();
+    // 语言名 → (HEAD 中的词条, 工作目录中的词条)，只含有改动的分桶
+    let mut words: BTreeMap<String, (WordDigests, WordDigests)> = BTreeMap::new();
+    for delta in diff.deltas() {
+        let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) else {
+            continue;
+        };
+        let path = path.to_string_lossy().replace('\\', "/");
+        let lang = language_of(config, &path);
+        if let Some(lang) = lang {
+            languages.insert(lang.name.clone());
+            let rel = &path[lang.path.trim_end_matches('/').len() + 1..];
+            if rel.starts_with("lexicon/lexicon_") && rel.ends_with(".ndjson") {
+                let (old, new) = words.entry(lang.name.clone()).or_default();
+                collect_bucket_words(repo, head_tree.as_ref(), &path, old, new);
+                continue;
+            }
+        }
+        lines.extend(describe_file(lang, &path, delta.status()));
+    }
+    for (lang_name, (old, new)) in &words {
+        lines.extend(describe_words(lang_name, old, new));
+    }
+
+    Ok(match lines.len() {
+        0 => "Checkpoint".to_string(),
+        1 => lines.remove(0),
+        n => {
+            let scope = if languages.is_empty() {
+                "project".to_string()
+            } else {
+                languages.into_iter().collect::<Vec<_>>().join(", ")
+            };
+            let mut message = format!("Update {} items in {}\n", n, scope);
+            for line in lines.iter().take(MAX_MESSAGE_LINES) {
+                message.push_str(&format!("\n- {}", line));
+            }
+            if n > MAX_MESSAGE_LINES {
+                message.push_str(&format!("\n- … and {} more", n - MAX_MESSAGE_LINES));
+            }
+            message
+        }
+    })
+}
+
+/// 暂存全部改动（包括删除）并提交；与 HEAD 没有差别时返回 None
+fn commit_all(repo: &Repository, message: Option<String>, config: &WorkspaceConfig) -> Result<Option<GitCommitInfo>, String> {
+    let mut index = repo.index().map_err(|e| e.to_string())?;
+    index
+        .add_all(["*"].iter(), IndexAddOption::DEFAULT, None)
+        .map_err(|e| e.to_string())?;
+    index.update_all(["*"].iter(), None).map_err(|e| e.to_string())?;
+    index.write().map_err(|e| e.to_string())?;
+    let tree_id = index.write_tree().map_err(|e| e.to_string())?;
+
+    let parent = head_commit(repo);
+    if parent.as_ref().is_some_and(|p| p.tree_id() == tree_id) {
+        return Ok(None);
+    }
+    let message = match message.map(|m| m.trim().to_string()).filter(|m| !m.is_empty()) {
+        Some(m) => m,
+        None => generate_message(repo, config)?,
+    };
+    let tree = repo.find_tree(tree_id).map_err(|e| e.to_string())?;
+    let sig = signature(repo)?;
+    let parents: Vec<&Commit> = parent.iter().collect();
+    let oid = repo
+        .commit(Some("HEAD"), &sig, &sig, &message, &tree, &parents)
+        .map_err(|e| e.to_string())?;
+    let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+    let files = changed_files(repo, &commit, &[])?;
+    Ok(Some(commit_info(&commit, files)))
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+#[command]
+pub fn git_status(project_path: String) -> Result<GitRepoStatus, String> {
+    let Ok(repo) = Repository::open(&project_path) else {
+        return Ok(GitRepoStatus {
+            initialized: false,
+            branch: None,
+            head: None,
+            changed_files: Vec::new(),
+        });
+    };
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true).recurse_untracked_dirs(true);
+    let statuses = repo.statuses(Some(&mut opts)).map_err(|e| e.to_string())?;
+    let pending: Vec<String> = statuses
+        .iter()
+        .filter_map(|s| s.path().map(|p| p.to_string()))
+        .collect();
+    let branch = repo
+        .head()
+        .ok()
+        .and_then(|h| h.shorthand().map(|s| s.to_string()));
+    let head = match head_commit(&repo) {
+        Some(c) => Some(commit_info(&c, changed_files(&repo, &c, &[])?)),
+        None => None,
+    };
+    Ok(GitRepoStatus {
+        initialized: true,
+        branch,
+        head,
+        changed_files: pending,
+    })
+}
+
+/// 确保文件包含模板中的全部规则：文件不存在时写入整个模板，
+/// 已存在时只在末尾追加缺少的规则行（连同模板的注释行），不改动用户已有的内容
+fn ensure_rules(path: &Path, template: &str) -> Result<(), String> {
+    if !path.exists() {
+        return atomic_write(path, template);
+    }
+    let existing = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let present: HashSet<&str> = existing.lines().map(str::trim).collect();
+    let missing: Vec<&str> = template
+        .lines()
+        .filter(|l| !l.starts_with('#') && !present.contains(l.trim()))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    let mut content = existing;
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.extend(template.lines().filter(|l| l.starts_with('#')).map(|l| format!("{}\n", l)));
+    content.extend(missing.iter().map(|l| format!("{}\n", l)));
+    atomic_write(path, &content)
+}
+
+/// 写入 .gitattributes，并在仓库配置中登记与应用放在一起的 conlang-merge。
+/// 找不到 conlang-merge 时只写属性文件：未定义的驱动会让 git 退回普通的文本合并
+fn install_merge_driver(repo: &Repository, root: &Path) -> Result<(), String> {
+    ensure_rules(&root.join(".gitattributes"), GITATTRIBUTES)?;
+    let exe = std::env::current_exe()
+        .map_err(|e| e.to_string())?
+        .with_file_name(format!("conlang-merge{}", std::env::consts::EXE_SUFFIX));
+    if !exe.exists() {
+        return Ok(());
+    }
+    let exe = exe.to_string_lossy().replace('\\', "/");
+    let mut config = repo.config().map_err(|e| e.to_string())?;
+    let entries = [
+        ("merge.conlang.name", "Conlang Maker entry-level merge".to_string()),
+        ("merge.conlang.driver", format!("\"{}\" merge %O %A %B %P", exe)),
+        ("diff.conlang.textconv", format!("\"{}\" textconv", exe)),
+    ];
+    for (name, value) in entries {
+        config.set_str(name, &value).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// 在项目目录中初始化仓库（已存在时保持不变），补全 .gitignore 中缺少的规则并提交现有文件
+#[command]
+pub fn git_init(project_path: String, conlang_file_path: String) -> Result<GitRepoStatus, String> {
+    let root = Path::new(&project_path);
+    check_write_access(root)?;
+    let repo = match Repository::open(root) {
+        Ok(repo) => repo,
+        Err(_) => Repository::init(root).map_err(|e| e.to_string())?,
+    };
+    ensure_rules(&root.join(".gitignore"), GITIGNORE)?;
+    install_merge_driver(&repo, root)?;
+    if head_commit(&repo).is_none() {
+        let config = load_workspace(conlang_file_path)?;
+        commit_all(&repo, Some("Initialize conlang project history".to_string()), &config)?;
+    }
+    git_status(project_path)
+}
+
+/// 提交当前所有改动。message 为空时根据改动内容生成；没有改动时返回 None
+#[command]
+pub fn git_checkpoint(
+    project_path: String,
+    conlang_file_path: String,
+    message: Option<String>,
+) -> Result<Option<GitCommitInfo>, String> {
+    check_write_access(Path::new(&project_path))?;
+    let repo = open_repo(&project_path)?;
+    let config = load_workspace(conlang_file_path)?;
+    commit_all(&repo, message, &config)
+}
+
+/// 从 HEAD 开始的提交历史（新的在前）。指定 language_path 时只列出改动了该语言目录
+/// （不含嵌套子语言目录）的提交，files 也只含该语言的文件
+#[command]
+pub fn git_log(
+    project_path: String,
+    conlang_file_path: String,
+    language_path: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<GitCommitInfo>, String> {
+    let repo = open_repo(&project_path)?;
+    if head_commit(&repo).is_none() {
+        return Ok(Vec::new());
+    }
+    let config = load_workspace(conlang_file_path)?;
+    let pathspec: Vec<String> = language_path
+        .iter()
+        .map(|p| format!("{}/", p.trim_end_matches('/')))
+        .collect();
+
+    let mut walk = repo.revwalk().map_err(|e| e.to_string())?;
+    walk.push_head().map_err(|e| e.to_string())?;
+    walk.set_sorting(Sort::TIME).map_err(|e| e.to_string())?;
+
+    let limit = limit.unwrap_or(200);
+    let mut commits = Vec::new();
+    for oid in walk {
+        if commits.len() >= limit {
+            break;
+        }
+        let commit = repo
+            .find_commit(oid.map_err(|e| e.to_string())?)
+            .map_err(|e| e.to_string())?;
+        let mut files = changed_files(&repo, &commit, &pathspec)?;
+        if let Some(lang) = &language_path {
+            files.retain(|f| in_language(&config, lang, f));
+            if files.is_empty() {
+                continue;
+            }
+        }
+        commits.push(commit_info(&commit, files));
+    }
+    Ok(commits)
+}
+
+/// 把语言目录（不含嵌套子语言目录和点开头的目录）恢复为 commit_id 时的内容，然后提交。
+/// 改写经过撤销日志，可以用 undo 撤回
+#[command]
+pub fn git_revert_language(
+    project_path: String,
+    conlang_file_path: String,
+    language_path: String,
+    commit_id: String,
+) -> Result<GitRevertResult, String> {
+    let root = Path::new(&project_path);
+    check_write_access(root)?;
+    let repo = open_repo(&project_path)?;
+    let config = load_workspace(conlang_file_path)?;
+    let lang = language_path.trim_end_matches('/').to_string();
+    let target = repo
+        .revparse_single(&commit_id)
+        .and_then(|o| o.peel_to_commit())
+        .map_err(|_| format!("Commit {} not found", commit_id))?;
+    let target_tree = target.tree().map_err(|e| e.to_string())?;
+
+    // 目标提交中该语言的文件；写入任何文件之前先全部解码，某个 blob 不是 UTF-8 时工作目录保持不变
+    let mut wanted: BTreeMap<String, String> = BTreeMap::new();
+    if let Ok(entry) = target_tree.get_path(Path::new(&lang)) {
+        let subtree = repo.find_tree(entry.id()).map_err(|e| e.to_string())?;
+        let mut blobs = Vec::new();
+        subtree
+            .walk(TreeWalkMode::PreOrder, |dir, entry| {
+                if entry.kind() == Some(ObjectType::Blob) {
+                    if let Some(name) = entry.name() {
+                        blobs.push((format!("{}/{}{}", lang, dir, name), entry.id()));
+                    }
+                }
+                TreeWalkResult::Ok
+            })
+            .map_err(|e| e.to_string())?;
+        for (path, id) in blobs {
+            if in_language(&config, &lang, &path) {
+                let blob = repo.find_blob(id).map_err(|e| e.to_string())?;
+                let content = String::from_utf8(blob.content().to_vec())
+                    .map_err(|_| format!("{} is not valid UTF-8 in the target commit; nothing was restored", path))?;
+                wanted.insert(path, content);
+            }
+        }
+    }
+
+    // 工作目录中该语言当前的文件
+    let mut current = BTreeSet::new();
+    collect_files(root, &root.join(&lang), &mut current)?;
+    current.retain(|p| in_language(&config, &lang, p) && !repo.is_path_ignored(Path::new(p)).unwrap_or(false));
+
+    let journal = begin(root, "git_revert_language", None);
+    let mut result = GitRevertResult {
+        restored: Vec::new(),
+        removed: Vec::new(),
+        commit: None,
+    };
+    for (path, content) in &wanted {
+        let full = root.join(path);
+        if fs::read(&full).ok().as_deref() == Some(content.as_bytes()) {
+            continue;
+        }
+        atomic_write(&full, content)?;
+        result.restored.push(path.clone());
+    }
+    for path in current.iter().filter(|p| !wanted.contains_key(*p)) {
+        let full = root.join(path);
+        capture_before(&full);
+        note_own_removal(&full);
+        fs::remove_file(&full).map_err(|e| e.to_string())?;
+        result.removed.push(path.clone());
+    }
+    drop(journal);
+
+    if result.restored.is_empty() && result.removed.is_empty() {
+        return Ok(result);
+    }
+    // 反查索引不在版本控制中，让它在下次查询时按恢复后的词典重建
+    let lexicon_dir = root.join(&lang).join("lexicon");
+    if lexicon_dir.exists() {
+        invalidate_index(&lexicon_dir)?;
+    }
+
+    let lang_name = config
+        .languages
+        .iter()
+        .find(|l| l.path.trim_end_matches('/') == lang)
+        .map(|l| l.name.clone())
+        .unwrap_or_else(|| lang.clone());
+    let info = commit_info(&target, Vec::new());
+    let message = format!("Revert {} to {}: {}", lang_name, info.short_id, info.summary);
+    result.commit = commit_all(&repo, Some(message), &config)?;
+    Ok(result)
+}
+
+/// 可能带有合并冲突记录的配置文件（与 .gitattributes 中交给 conlang-merge 的文件一致）
+const MERGED_CONFIG_FILES: [&str; 3] = ["phonology.json", "grammar.json", "sca_rules.json"];
+
+/// 列出项目中所有未解决的合并冲突
+#[command]
+pub fn list_merge_conflicts(project_path: String, conlang_file_path: String) -> Result<Vec<MergeConflictGroup>, String> {
+    let root = Path::new(&project_path);
+    let config = load_workspace(conlang_file_path)?;
+    let rel = |path: &Path| {
+        path.strip_prefix(root)
+            .map(|p| p.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default()
+    };
+    let mut groups = Vec::new();
+    for language in &config.languages {
+        let lang_dir = root.join(&language.path);
+        for name in MERGED_CONFIG_FILES {
+            let path = lang_dir.join(name);
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let Ok(value) = serde_json::from_str::<serde_json::Value>(&content) else {
+                continue;
+            };
+            let conflicts = conflicts_of(&value);
+            if !conflicts.is_empty() {
+                groups.push(MergeConflictGroup {
+                    file: rel(&path),
+                    language_id: language.language_id.clone(),
+                    entry_id: None,
+                    label: name.to_string(),
+                    conflicts,
+                });
+            }
+        }
+
+        let mut buckets = BTreeSet::new();
+        collect_files(&lang_dir, &lang_dir.join("lexicon"), &mut buckets)?;
+        for bucket in buckets.iter().filter(|b| b.ends_with(".ndjson") && !b.contains("/.")) {
+            let path = lang_dir.join(bucket);
+            for word in parse_bucket(&path)?.words {
+                let value = serde_json::to_value(&word).map_err(|e| e.to_string())?;
+                let conflicts = conflicts_of(&value);
+                if !conflicts.is_empty() {
+                    groups.push(MergeConflictGroup {
+                        file: rel(&path),
+                        language_id: language.language_id.clone(),
+                        entry_id: Some(word.entry_id.clone()),
+                        label: word.con_word_romanized.clone(),
+                        conflicts,
+                    });
+                }
+            }
+        }
+    }
+    Ok(groups)
+}
+
+/// 用冲突记录中的一方（"ours" | "theirs" | "base"）解决一个字段的冲突，并删除这条记录。
+/// 词条通过 save_words / delete_words 的同一路径写回（会进入撤销日志与修订历史），配置文件整体改写
+#[command]
+pub fn resolve_merge_conflict(
+    project_path: String,
+    file: String,
+    entry_id: Option<String>,
+    field: String,
+    choice: String,
+) -> Result<(), String> {
+    let root = Path::new(&project_path);
+    let path = root.join(&file);
+    check_write_access(&path)?;
+    let _journal = begin(root, "resolve_merge_conflict", None);
+
+    let Some(entry_id) = entry_id else {
+        let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        let mut value: serde_json::Value = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+        if !resolve_conflict(&mut value, &field, &choice)? {
+            return Err(format!("Resolving '{}' with '{}' would remove {} entirely", field, choice, file));
+        }
+        return atomic_write(&path, &to_pretty_json(&file, value)?);
+    };
+
+    let lexicon_dir = path.parent().ok_or_else(|| format!("Invalid bucket path '{}'", file))?;
+    let word = parse_bucket(&path)?
+        .words
+        .into_iter()
+        .find(|w| w.entry_id == entry_id)
+        .ok_or_else(|| format!("Entry {} not found in {}", entry_id, file))?;
+    let old_romanized = word.con_word_romanized.clone();
+    let mut value = serde_json::to_value(&word).map_err(|e| e.to_string())?;
+    let results = if resolve_conflict(&mut value, &field, &choice)? {
+        let mut resolved: WordEntry = serde_json::from_value(value).map_err(|e| e.to_string())?;
+        // 选中的版本可能来自另一方的整条词条，entry_id 保持不变
+        resolved.entry_id = entry_id;
+        save_words_in_dir(
+            lexicon_dir,
+            vec![WordSaveRequest {
+                word: resolved,
+                old_romanized: Some(old_romanized),
+                expected_hash: None,
+            }],
+        )
+    } else {
+        delete_words_in_dir(
+            lexicon_dir,
+            vec![WordDeleteRequest {
+                entry_id,
+                con_word_romanized: old_romanized,
+                expected_hash: None,
+            }],
+        )
+    };
+    match results.into_iter().find_map(|r| r.error) {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// 递归列出 dir 下的文件（相对 root，以 / 分隔）
+fn collect_files(root: &Path, dir: &Path, out: &mut BTreeSet<String>) -> Result<(), String> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.is_dir() {
+            collect_files(root, &path, out)?;
+        } else if let Ok(rel) = path.strip_prefix(root) {
+            out.insert(rel.to_string_lossy().replace('\\', "/"));
+        }
+    }
+    Ok(())
+}
@@ -1,8 +1,11 @@
 use std::fs;
 use std::path::Path;
 use tauri::command;
-use crate::models::GrammarConfig;
+use crate::models::{GrammarConfig, CURRENT_SCHEMA_VERSION};
+use crate::commands::journal::begin;
 use crate::commands::lexicon::atomic_write;
+use crate::commands::watcher::{check_expected_hash, content_hash};
+use crate::commands::schema::{ensure_writable, parse_versioned, SchemaKind};
 
 #[command]
 pub fn load_grammar(project_path: String, language_path: String) -> Result<GrammarConfig, String> {
@@ -11,14 +14,26 @@ pub fn load_grammar(project_path: String, language_path: String) -> Result<Gramm
         return Ok(GrammarConfig::default());
     }
     let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
-    serde_json::from_str(&content).map_err(|e| e.to_string())
+    parse_versioned(SchemaKind::Grammar, &content)
 }
 
 #[command]
-pub fn save_grammar(project_path: String, language_path: String, config: GrammarConfig) -> Result<(), String> {
+pub fn save_grammar(
+    project_path: String,
+    language_path: String,
+    config: GrammarConfig,
+    expected_hash: Option<String>,
+    transaction_id: Option<String>,
+) -> Result<String, String> {
+    let _journal = begin(Path::new(&project_path), "save_grammar", transaction_id);
     let dir = Path::new(&project_path).join(&language_path);
     fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
     let path = dir.join("grammar.json");
+    check_expected_hash(&path, expected_hash.as_deref())?;
+    ensure_writable(&path, config.schema_version)?;
+    let mut config = config;
+    config.schema_version = CURRENT_SCHEMA_VERSION;
     let content = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
-    atomic_write(&path, &content)
+    atomic_write(&path, &content)?;
+    Ok(content_hash(content.as_bytes()))
 }
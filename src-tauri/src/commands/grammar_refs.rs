@@ -0,0 +1,593 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use tauri::command;
+
+use crate::commands::grammar::load_grammar;
+use crate::commands::journal::begin;
+use crate::commands::lexicon::{atomic_write, read_all_words, save_words_in_dir, WordSaveRequest};
+use crate::commands::oplog::{create_snapshot, rollback_to_snapshot};
+use crate::commands::project_lock::check_write_access;
+use crate::commands::schema::ensure_writable;
+use crate::commands::watcher::{check_expected_hash, content_hash};
+use crate::models::{GrammarConfig, WordEntry, CURRENT_SCHEMA_VERSION};
+
+// ── 语法 ID 的引用级联 ───────────────────────────────────
+//
+// 词性、屈折维度、维度取值、屈折规则在 grammar.json 与词典中都以字符串 ID 互相引用：
+//   词性     Sense.pos_id、InflectionRule.pos_id、DerivationRule.source_pos_id / target_pos_id、
+//            InflectionDimension.applies_to_pos、ConjugationClass.applies_to_pos、EmbeddedParadigm.pos_id
+//   维度     InflectionRule.dimension_values / fused_dimensions 的键、AffixSlot.dimension_id、
+//            IrregularOverride.dimension_values 的键、EmbeddedParadigm.dimension_ids
+//   维度取值 InflectionRule.dimension_values / fused_dimensions、IrregularOverride.dimension_values 的值
+//   屈折规则 ConjugationClass.rule_ids
+//
+// 重命名时改写全部引用；删除时按引用的性质处理：
+//   unlink  只去掉引用（置空或从列表中移除），所在对象保留
+//   remove  所在对象失去意义，一并删除（例如某词性的屈折规则、某取值对应的不规则形式）
+// 先用 preview_grammar_ref_change 查看影响范围，apply_grammar_ref_change 在一次操作中改写
+// grammar.json 与词典，执行前自动创建 oplog 快照，可整体回滚。
+
+/// 一次重命名或删除
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GrammarRefChange {
+    pub kind: String,   // "pos" | "dimension" | "value" | "rule"
+    pub action: String, // "rename" | "delete"
+    pub id: String,
+    /// kind 为 "value" 时取值所属的维度
+    #[serde(default)]
+    pub dim_id: Option<String>,
+    /// action 为 "rename" 时的新 ID
+    #[serde(default)]
+    pub new_id: Option<String>,
+}
+
+/// 受影响的一处引用
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GrammarReference {
+    pub file: String, // "grammar" | "lexicon"
+    /// 引用所在位置，例如 "inflection_rules[r1].pos_id"、"senses[s1].pos_id"
+    pub location: String,
+    /// file 为 "lexicon" 时的词条
+    #[serde(default)]
+    pub entry_id: Option<String>,
+    /// 便于识别的名称：词性名、规则标签、词条拼写等
+    pub label: String,
+    pub effect: String, // "rename" | "unlink" | "remove"
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GrammarRefReport {
+    pub change: GrammarRefChange,
+    pub references: Vec<GrammarReference>,
+    /// 需要改写的词条数
+    pub lexicon_entries: usize,
+    /// 执行后创建的快照，可用 rollback_to_snapshot 撤回；预览时为 None
+    #[serde(default)]
+    pub log_id: Option<String>,
+    /// 执行后 grammar.json 的新哈希
+    #[serde(default)]
+    pub grammar_hash: Option<String>,
+}
+
+// ── 改写计划 ─────────────────────────────────────────────
+
+struct Plan<'a> {
+    change: &'a GrammarRefChange,
+    references: Vec<GrammarReference>,
+}
+
+impl Plan<'_> {
+    fn renaming(&self) -> Option<&str> {
+        match self.change.action.as_str() {
+            "rename" => self.change.new_id.as_deref().map(str::trim),
+            _ => None,
+        }
+    }
+
+    /// 引用本身被删除时的处理：重命名时为 rename，删除时为 fallback
+    fn effect(&self, fallback: &str) -> &'static str {
+        match (self.renaming(), fallback) {
+            (Some(_), _) => "rename",
+            (None, "remove") => "remove",
+            _ => "unlink",
+        }
+    }
+
+    fn grammar(&mut self, location: String, label: &str, effect: &str) {
+        self.references.push(GrammarReference {
+            file: "grammar".to_string(),
+            location,
+            entry_id: None,
+            label: label.to_string(),
+            effect: effect.to_string(),
+        });
+    }
+
+    fn word(&mut self, word: &WordEntry, location: String, effect: &str) {
+        self.references.push(GrammarReference {
+            file: "lexicon".to_string(),
+            location,
+            entry_id: Some(word.entry_id.clone()),
+            label: word.con_word_romanized.clone(),
+            effect: effect.to_string(),
+        });
+    }
+}
+
+fn rule_label(tag: &str, rule_id: &str) -> String {
+    if tag.is_empty() {
+        rule_id.to_string()
+    } else {
+        tag.to_string()
+    }
+}
+
+fn validate(grammar: &GrammarConfig, change: &GrammarRefChange) -> Result<(), String> {
+    let exists = |id: &str| -> Result<bool, String> {
+        Ok(match change.kind.as_str() {
+            "pos" => grammar.parts_of_speech.iter().any(|p| p.pos_id == id),
+            "dimension" => grammar.inflection_dimensions.iter().any(|d| d.dim_id == id),
+            "value" => {
+                let dim_id = change.dim_id.as_deref().unwrap_or_default();
+                let dim = grammar
+                    .inflection_dimensions
+                    .iter()
+                    .find(|d| d.dim_id == dim_id)
+                    .ok_or_else(|| format!("Dimension '{}' not found", dim_id))?;
+                dim.values.iter().any(|v| v.val_id == id)
+            }
+            // 变位类的 rule_ids 不区分屈折与派生规则，两者的 ID 不能重复
+            "rule" => {
+                grammar.inflection_rules.iter().any(|r| r.rule_id == id)
+                    || grammar.derivation_rules.iter().any(|r| r.rule_id == id)
+            }
+            other => return Err(format!("Unknown grammar item kind: '{}'", other)),
+        })
+    };
+
+    if change.kind == "rule" && !grammar.inflection_rules.iter().any(|r| r.rule_id == change.id) {
+        return Err(format!("Inflection rule '{}' not found", change.id));
+    }
+    if !exists(&change.id)? {
+        return Err(format!("'{}' not found", change.id));
+    }
+    match change.action.as_str() {
+        "delete" => Ok(()),
+        "rename" => {
+            let new_id = change.new_id.as_deref().map(str::trim).unwrap_or_default();
+            if new_id.is_empty() {
+                return Err("New ID cannot be empty".to_string());
+            }
+            if new_id == change.id {
+                return Err("New ID is the same as the current one".to_string());
+            }
+            if exists(new_id)? {
+                return Err(format!("'{}' is already in use", new_id));
+            }
+            Ok(())
+        }
+        other => Err(format!("Unknown action: '{}'", other)),
+    }
+}
+
+/// 在 grammar 与 words 上执行改动，返回全部引用以及被改写词条在 words 中的下标
+fn plan_change(
+    grammar: &mut GrammarConfig,
+    words: &mut [WordEntry],
+    change: &GrammarRefChange,
+) -> Result<(Vec<GrammarReference>, Vec<usize>), String> {
+    validate(grammar, change)?;
+    let mut plan = Plan {
+        change,
+        references: Vec::new(),
+    };
+    // 只有词性出现在词典中
+    let changed_words = match change.kind.as_str() {
+        "pos" => plan_pos(&mut plan, grammar, words),
+        "dimension" => {
+            plan_dimension(&mut plan, grammar);
+            Vec::new()
+        }
+        "value" => {
+            plan_value(&mut plan, grammar);
+            Vec::new()
+        }
+        _ => {
+            plan_rule(&mut plan, grammar);
+            Vec::new()
+        }
+    };
+    Ok((plan.references, changed_words))
+}
+
+fn plan_pos(plan: &mut Plan, grammar: &mut GrammarConfig, words: &mut [WordEntry]) -> Vec<usize> {
+    let id = plan.change.id.clone();
+    let new_id = plan.renaming().map(str::to_string);
+
+    if let Some(pos) = grammar.parts_of_speech.iter_mut().find(|p| p.pos_id == id) {
+        let label = if pos.name.is_empty() { id.clone() } else { pos.name.clone() };
+        plan.grammar(format!("parts_of_speech[{}]", id), &label, plan.effect("remove"));
+        match &new_id {
+            Some(new_id) => pos.pos_id = new_id.clone(),
+            None => grammar.parts_of_speech.retain(|p| p.pos_id != id),
+        }
+    }
+
+    // 只适用于该词性的屈折 / 派生规则随之删除
+    let mut removed_rules: HashSet<String> = HashSet::new();
+    for rule in &mut grammar.inflection_rules {
+        if rule.pos_id != id {
+            continue;
+        }
+        let location = format!("inflection_rules[{}].pos_id", rule.rule_id);
+        plan.grammar(location, &rule_label(&rule.tag, &rule.rule_id), plan.effect("remove"));
+        match &new_id {
+            Some(new_id) => rule.pos_id = new_id.clone(),
+            None => {
+                removed_rules.insert(rule.rule_id.clone());
+            }
+        }
+    }
+    grammar.inflection_rules.retain(|r| !removed_rules.contains(&r.rule_id));
+
+    let mut removed_derivations: HashSet<String> = HashSet::new();
+    for rule in &mut grammar.derivation_rules {
+        let label = rule_label(&rule.name, &rule.rule_id);
+        for (field, value) in [
+            ("source_pos_id", &mut rule.source_pos_id),
+            ("target_pos_id", &mut rule.target_pos_id),
+        ] {
+            if *value != id {
+                continue;
+            }
+            plan.grammar(format!("derivation_rules[{}].{}", rule.rule_id, field), &label, plan.effect("remove"));
+            match &new_id {
+                Some(new_id) => *value = new_id.clone(),
+                None => {
+                    removed_derivations.insert(rule.rule_id.clone());
+                }
+            }
+        }
+    }
+    grammar.derivation_rules.retain(|r| !removed_derivations.contains(&r.rule_id));
+    removed_rules.extend(removed_derivations);
+
+    for dim in &mut grammar.inflection_dimensions {
+        if dim.applies_to_pos.contains(&id) {
+            plan.grammar(format!("inflection_dimensions[{}].applies_to_pos", dim.dim_id), &dim.name, plan.effect("unlink"));
+            rename_or_drop(&mut dim.applies_to_pos, &id, new_id.as_deref());
+        }
+    }
+
+    for class in &mut grammar.conjugation_classes {
+        if class.applies_to_pos == id {
+            plan.grammar(format!("conjugation_classes[{}].applies_to_pos", class.class_id), &class.name, plan.effect("unlink"));
+            class.applies_to_pos = new_id.clone().unwrap_or_default();
+        }
+    }
+
+    for chapter in &mut grammar.grammar_manual {
+        for paradigm in &mut chapter.embedded_paradigms {
+            if paradigm.pos_id == id {
+                let location = format!("grammar_manual[{}].embedded_paradigms[{}].pos_id", chapter.chapter_id, paradigm.paradigm_id);
+                plan.grammar(location, &chapter.title, plan.effect("unlink"));
+                paradigm.pos_id = new_id.clone().unwrap_or_default();
+            }
+        }
+    }
+
+    unlink_rules(plan, grammar, &removed_rules);
+
+    // 词典：义项的词性置空，义项本身保留
+    let mut changed = Vec::new();
+    for (i, word) in words.iter_mut().enumerate() {
+        let mut touched = false;
+        for sense_index in 0..word.senses.len() {
+            if word.senses[sense_index].pos_id != id {
+                continue;
+            }
+            let location = format!("senses[{}].pos_id", word.senses[sense_index].sense_id);
+            plan.word(word, location, plan.effect("unlink"));
+            word.senses[sense_index].pos_id = new_id.clone().unwrap_or_default();
+            touched = true;
+        }
+        if touched {
+            changed.push(i);
+        }
+    }
+    changed
+}
+
+fn plan_dimension(plan: &mut Plan, grammar: &mut GrammarConfig) {
+    let id = plan.change.id.clone();
+    let new_id = plan.renaming().map(str::to_string);
+
+    if let Some(dim) = grammar.inflection_dimensions.iter_mut().find(|d| d.dim_id == id) {
+        plan.grammar(format!("inflection_dimensions[{}]", id), &dim.name.clone(), plan.effect("remove"));
+        match &new_id {
+            Some(new_id) => dim.dim_id = new_id.clone(),
+            None => grammar.inflection_dimensions.retain(|d| d.dim_id != id),
+        }
+    }
+
+    // 规则只去掉该维度的键；规则仍按其余维度生效
+    for rule in &mut grammar.inflection_rules {
+        let label = rule_label(&rule.tag, &rule.rule_id);
+        if let Some(value) = rule.dimension_values.remove(&id) {
+            plan.grammar(format!("inflection_rules[{}].dimension_values", rule.rule_id), &label, plan.effect("unlink"));
+            if let Some(new_id) = &new_id {
+                rule.dimension_values.insert(new_id.clone(), value);
+            }
+        }
+        if let Some(fused) = &mut rule.fused_dimensions {
+            if fused.iter().any(|combo| combo.contains_key(&id)) {
+                plan.grammar(format!("inflection_rules[{}].fused_dimensions", rule.rule_id), &label, plan.effect("unlink"));
+                for combo in fused.iter_mut() {
+                    if let Some(value) = combo.remove(&id) {
+                        if let Some(new_id) = &new_id {
+                            combo.insert(new_id.clone(), value);
+                        }
+                    }
+                }
+                fused.retain(|combo| !combo.is_empty());
+            }
+        }
+    }
+
+    for slot in &mut grammar.affix_slots {
+        if slot.dimension_id == id {
+            plan.grammar(format!("affix_slots[{}].dimension_id", slot.slot_id), &slot.label, plan.effect("unlink"));
+            slot.dimension_id = new_id.clone().unwrap_or_default();
+        }
+    }
+
+    // 不规则形式只剩空的维度组合时没有意义，整条删除
+    let mut index = 0;
+    grammar.irregular_overrides.retain_mut(|o| {
+        index += 1;
+        let Some(value) = o.dimension_values.remove(&id) else {
+            return true;
+        };
+        let keep = new_id.is_some() || !o.dimension_values.is_empty();
+        let location = format!("irregular_overrides[{}].dimension_values", index - 1);
+        plan.grammar(location, &o.surface_form, if keep { plan.effect("unlink") } else { "remove" });
+        if let Some(new_id) = &new_id {
+            o.dimension_values.insert(new_id.clone(), value);
+        }
+        keep
+    });
+
+    for chapter in &mut grammar.grammar_manual {
+        for paradigm in &mut chapter.embedded_paradigms {
+            if paradigm.dimension_ids.contains(&id) {
+                let location = format!("grammar_manual[{}].embedded_paradigms[{}].dimension_ids", chapter.chapter_id, paradigm.paradigm_id);
+                plan.grammar(location, &chapter.title, plan.effect("unlink"));
+                rename_or_drop(&mut paradigm.dimension_ids, &id, new_id.as_deref());
+            }
+        }
+    }
+}
+
+fn plan_value(plan: &mut Plan, grammar: &mut GrammarConfig) {
+    let id = plan.change.id.clone();
+    let dim_id = plan.change.dim_id.clone().unwrap_or_default();
+    let new_id = plan.renaming().map(str::to_string);
+
+    if let Some(dim) = grammar.inflection_dimensions.iter_mut().find(|d| d.dim_id == dim_id) {
+        if let Some(value) = dim.values.iter_mut().find(|v| v.val_id == id) {
+            plan.grammar(format!("inflection_dimensions[{}].values[{}]", dim_id, id), &value.name.clone(), plan.effect("remove"));
+            match &new_id {
+                Some(new_id) => value.val_id = new_id.clone(),
+                None => dim.values.retain(|v| v.val_id != id),
+            }
+        }
+    }
+
+    let is_target = |values: &std::collections::HashMap<String, String>| values.get(&dim_id) == Some(&id);
+
+    // 生成该取值的屈折规则随之删除
+    let mut removed_rules: HashSet<String> = HashSet::new();
+    for rule in &mut grammar.inflection_rules {
+        let label = rule_label(&rule.tag, &rule.rule_id);
+        if is_target(&rule.dimension_values) {
+            plan.grammar(format!("inflection_rules[{}].dimension_values", rule.rule_id), &label, plan.effect("remove"));
+            match &new_id {
+                Some(new_id) => {
+                    rule.dimension_values.insert(dim_id.clone(), new_id.clone());
+                }
+                None => {
+                    removed_rules.insert(rule.rule_id.clone());
+                    continue;
+                }
+            }
+        }
+        if let Some(fused) = &mut rule.fused_dimensions {
+            if fused.iter().any(is_target) {
+                plan.grammar(format!("inflection_rules[{}].fused_dimensions", rule.rule_id), &label, plan.effect("unlink"));
+                match &new_id {
+                    Some(new_id) => {
+                        for combo in fused.iter_mut().filter(|c| is_target(c)) {
+                            combo.insert(dim_id.clone(), new_id.clone());
+                        }
+                    }
+                    None => fused.retain(|c| !is_target(c)),
+                }
+            }
+        }
+    }
+    grammar.inflection_rules.retain(|r| !removed_rules.contains(&r.rule_id));
+
+    let mut index = 0;
+    grammar.irregular_overrides.retain_mut(|o| {
+        index += 1;
+        if !is_target(&o.dimension_values) {
+            return true;
+        }
+        let location = format!("irregular_overrides[{}].dimension_values", index - 1);
+        plan.grammar(location, &o.surface_form, plan.effect("remove"));
+        match &new_id {
+            Some(new_id) => {
+                o.dimension_values.insert(dim_id.clone(), new_id.clone());
+                true
+            }
+            None => false,
+        }
+    });
+
+    unlink_rules(plan, grammar, &removed_rules);
+}
+
+fn plan_rule(plan: &mut Plan, grammar: &mut GrammarConfig) {
+    let id = plan.change.id.clone();
+    let new_id = plan.renaming().map(str::to_string);
+
+    if let Some(rule) = grammar.inflection_rules.iter_mut().find(|r| r.rule_id == id) {
+        plan.grammar(format!("inflection_rules[{}]", id), &rule_label(&rule.tag, &rule.rule_id), plan.effect("remove"));
+        match &new_id {
+            Some(new_id) => rule.rule_id = new_id.clone(),
+            None => grammar.inflection_rules.retain(|r| r.rule_id != id),
+        }
+    }
+
+    match &new_id {
+        Some(new_id) => {
+            for class in &mut grammar.conjugation_classes {
+                if class.rule_ids.contains(&id) {
+                    plan.grammar(format!("conjugation_classes[{}].rule_ids", class.class_id), &class.name, "rename");
+                    rename_or_drop(&mut class.rule_ids, &id, Some(new_id));
+                }
+            }
+        }
+        None => unlink_rules(plan, grammar, &HashSet::from([id])),
+    }
+}
+
+/// 已删除的规则从变位类中移除
+fn unlink_rules(plan: &mut Plan, grammar: &mut GrammarConfig, removed: &HashSet<String>) {
+    if removed.is_empty() {
+        return;
+    }
+    for class in &mut grammar.conjugation_classes {
+        if class.rule_ids.iter().any(|r| removed.contains(r)) {
+            plan.grammar(format!("conjugation_classes[{}].rule_ids", class.class_id), &class.name, "unlink");
+            class.rule_ids.retain(|r| !removed.contains(r));
+        }
+    }
+}
+
+fn rename_or_drop(ids: &mut Vec<String>, id: &str, new_id: Option<&str>) {
+    match new_id {
+        Some(new_id) => {
+            for item in ids.iter_mut().filter(|item| *item == id) {
+                *item = new_id.to_string();
+            }
+        }
+        None => ids.retain(|item| item != id),
+    }
+}
+
+fn describe(change: &GrammarRefChange) -> String {
+    let what = match change.kind.as_str() {
+        "pos" => "part of speech",
+        "dimension" => "dimension",
+        "value" => "dimension value",
+        _ => "inflection rule",
+    };
+    match change.new_id.as_deref().filter(|_| change.action == "rename") {
+        Some(new_id) => format!("Rename {} {} → {}", what, change.id, new_id),
+        None => format!("Delete {} {}", what, change.id),
+    }
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+/// 列出一次重命名 / 删除会改动的全部引用，不写入任何文件
+#[command]
+pub fn preview_grammar_ref_change(
+    project_path: String,
+    language_path: String,
+    change: GrammarRefChange,
+) -> Result<GrammarRefReport, String> {
+    let lexicon_dir = Path::new(&project_path).join(&language_path).join("lexicon");
+    let mut grammar = load_grammar(project_path, language_path)?;
+    let mut words = read_all_words(&lexicon_dir)?;
+    let (references, changed) = plan_change(&mut grammar, &mut words, &change)?;
+    Ok(GrammarRefReport {
+        change,
+        references,
+        lexicon_entries: changed.len(),
+        log_id: None,
+        grammar_hash: None,
+    })
+}
+
+/// 执行重命名 / 删除并改写全部引用。先为 grammar.json（以及需要改写时的词典）创建快照，
+/// 词典写入失败时回滚到该快照，grammar.json 最后写入
+#[command]
+pub fn apply_grammar_ref_change(
+    project_path: String,
+    language_path: String,
+    change: GrammarRefChange,
+    expected_hash: Option<String>,
+    transaction_id: Option<String>,
+) -> Result<GrammarRefReport, String> {
+    let _journal = begin(Path::new(&project_path), "apply_grammar_ref_change", transaction_id);
+    let lang_dir = Path::new(&project_path).join(&language_path);
+    check_write_access(&lang_dir)?;
+    let grammar_path = lang_dir.join("grammar.json");
+    let lexicon_dir = lang_dir.join("lexicon");
+    check_expected_hash(&grammar_path, expected_hash.as_deref())?;
+
+    let mut grammar = load_grammar(project_path.clone(), language_path.clone())?;
+    ensure_writable(&grammar_path, grammar.schema_version)?;
+    let mut words = read_all_words(&lexicon_dir)?;
+    let originals = words.clone();
+    let (references, changed) = plan_change(&mut grammar, &mut words, &change)?;
+
+    let mut components = vec!["grammar.json".to_string()];
+    if !changed.is_empty() {
+        components.push("lexicon".to_string());
+    }
+    let language_id = grammar.language_id.clone();
+    let log = create_snapshot(
+        project_path.clone(),
+        language_path.clone(),
+        "grammar_refactor".to_string(),
+        language_id.clone(),
+        language_id,
+        describe(&change),
+        Some(components),
+    )?;
+    let log_id = log.entries.last().map(|e| e.log_id.clone()).unwrap_or_default();
+
+    let requests: Vec<WordSaveRequest> = changed
+        .iter()
+        .map(|&i| WordSaveRequest {
+            word: words[i].clone(),
+            old_romanized: Some(originals[i].con_word_romanized.clone()),
+            expected_hash: None,
+        })
+        .collect();
+    if let Some(error) = save_words_in_dir(&lexicon_dir, requests)
+        .into_iter()
+        .find_map(|r| r.error.map(|e| format!("{}: {}", r.entry_id, e)))
+    {
+        if let Err(e) = rollback_to_snapshot(project_path, language_path, log_id.clone()) {
+            return Err(format!("{}; rollback to '{}' failed: {}", error, log_id, e));
+        }
+        return Err(error);
+    }
+
+    grammar.schema_version = CURRENT_SCHEMA_VERSION;
+    let content = serde_json::to_string_pretty(&grammar).map_err(|e| e.to_string())?;
+    atomic_write(&grammar_path, &content)?;
+
+    Ok(GrammarRefReport {
+        change,
+        references,
+        lexicon_entries: changed.len(),
+        log_id: Some(log_id),
+        grammar_hash: Some(content_hash(content.as_bytes())),
+    })
+}
@@ -0,0 +1,401 @@
+use crate::commands::journal::begin;
+use crate::commands::lexicon::{
+    atomic_write, read_all_words, save_words_in_dir, BatchEntryResult, WordSaveRequest,
+};
+use crate::commands::project_lock::check_write_access;
+use crate::commands::schema::{migrate_value, SchemaKind};
+use crate::commands::timestamp::now_rfc3339;
+use crate::commands::watcher::{content_hash, word_hash};
+use crate::models::{ExtraFields, WordEntry};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+// ── 词条修订历史 ─────────────────────────────────────────
+//
+// 每次 save_word / delete_word 改动词条时，把改动前的版本追加到 <语言目录>/.history/<entry_id>.ndjson，
+// 一行一个修订，按时间先后排列。历史放在 lexicon 目录之外，oplog 快照与回滚不会影响它。
+// 每个词条最多保留 max_revisions 个修订（与 OperationLog.max_snapshots 相同的方式配置），超出时丢弃最旧的。
+
+/// diff / restore 中代表词典里当前版本的修订 ID
+pub const CURRENT_REVISION: &str = "current";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HistoryConfig {
+    #[serde(default = "default_max_revisions")]
+    pub max_revisions: u32,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+fn default_max_revisions() -> u32 {
+    20
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            max_revisions: default_max_revisions(),
+            extra: ExtraFields::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WordRevision {
+    pub revision_id: String,
+    pub entry_id: String,
+    /// 该版本被替换（或删除）的时间
+    pub recorded_at: String,
+    pub reason: String, // "save" | "delete"
+    /// 该版本词条的哈希（与 load_word_hashes 相同的算法）
+    pub content_hash: String,
+    pub word: WordEntry,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WordHistory {
+    pub entry_id: String,
+    pub max_revisions: u32,
+    /// 从旧到新
+    pub revisions: Vec<WordRevision>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FieldChange {
+    /// 字段路径，例如 "phonetic_ipa"、"senses[s1].gloss"、"etymology.origin_type"
+    pub field: String,
+    pub change: String, // "added" | "removed" | "modified"
+    #[serde(default)]
+    pub before: Option<Value>,
+    #[serde(default)]
+    pub after: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WordRevisionDiff {
+    pub entry_id: String,
+    pub from_revision: String,
+    pub to_revision: String,
+    pub changes: Vec<FieldChange>,
+}
+
+fn history_dir(lang_dir: &Path) -> PathBuf {
+    lang_dir.join(".history")
+}
+
+fn config_file(history_dir: &Path) -> PathBuf {
+    history_dir.join("history.json")
+}
+
+/// entry_id 通常只含字母数字与下划线，可直接作文件名；否则用其哈希
+fn history_file(history_dir: &Path, entry_id: &str) -> PathBuf {
+    let safe = !entry_id.is_empty()
+        && entry_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    let name = if safe {
+        entry_id.to_string()
+    } else {
+        format!("h_{}", &content_hash(entry_id.as_bytes())[..32])
+    };
+    history_dir.join(format!("{}.ndjson", name))
+}
+
+fn load_config(history_dir: &Path) -> Result<HistoryConfig, String> {
+    let file = config_file(history_dir);
+    if !file.exists() {
+        return Ok(HistoryConfig::default());
+    }
+    let content = fs::read_to_string(&file).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+fn read_revisions(path: &Path) -> Result<Vec<WordRevision>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut revisions = Vec::new();
+    for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let Ok(mut value) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        // 历史中的旧版本词条同样按 schema 迁移后再读取
+        if let Some(word) = value.get_mut("word") {
+            migrate_value(SchemaKind::Lexicon, word);
+        }
+        if let Ok(revision) = serde_json::from_value::<WordRevision>(value) {
+            revisions.push(revision);
+        }
+    }
+    Ok(revisions)
+}
+
+fn write_revisions(path: &Path, revisions: &[WordRevision]) -> Result<(), String> {
+    check_write_access(path)?;
+    if revisions.is_empty() {
+        if path.exists() {
+            fs::remove_file(path).map_err(|e| e.to_string())?;
+        }
+        return Ok(());
+    }
+    let mut content = String::new();
+    for revision in revisions {
+        content.push_str(&serde_json::to_string(revision).map_err(|e| e.to_string())?);
+        content.push('\n');
+    }
+    atomic_write(path, &content)
+}
+
+fn generate_revision_id(entry_id: &str, index: usize) -> String {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let seed = format!("{}:{}:{}", entry_id, nanos, index);
+    format!("rev_{}", &content_hash(seed.as_bytes())[..16])
+}
+
+/// 把词条改动前的版本追加到各自的历史文件，并按 max_revisions 裁剪
+pub fn record_revisions(lexicon_dir: &Path, previous: &[WordEntry], reason: &str) -> Result<(), String> {
+    if previous.is_empty() {
+        return Ok(());
+    }
+    let lang_dir = lexicon_dir.parent().unwrap_or(lexicon_dir);
+    let dir = history_dir(lang_dir);
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let max = load_config(&dir)?.max_revisions as usize;
+    let recorded_at = now_rfc3339();
+
+    for (i, word) in previous.iter().enumerate() {
+        let path = history_file(&dir, &word.entry_id);
+        let mut revisions = read_revisions(&path)?;
+        let hash = word_hash(word);
+        // 连续两次记录同一内容（例如保存失败后重试）时不重复追加
+        if revisions.last().is_some_and(|r| r.content_hash == hash) {
+            continue;
+        }
+        revisions.push(WordRevision {
+            revision_id: generate_revision_id(&word.entry_id, i),
+            entry_id: word.entry_id.clone(),
+            recorded_at: recorded_at.clone(),
+            reason: reason.to_string(),
+            content_hash: hash,
+            word: word.clone(),
+            extra: ExtraFields::new(),
+        });
+        if revisions.len() > max {
+            revisions.drain(..revisions.len() - max);
+        }
+        write_revisions(&path, &revisions)?;
+    }
+    Ok(())
+}
+
+// ── 字段级比较 ───────────────────────────────────────────
+
+/// 数组元素按哪个键配对：义项按 sense_id，其余按下标
+fn element_key(value: &Value, index: usize) -> String {
+    match value.get("sense_id").and_then(Value::as_str) {
+        Some(id) if !id.is_empty() => id.to_string(),
+        _ => index.to_string(),
+    }
+}
+
+fn diff_values(path: &str, before: Option<&Value>, after: Option<&Value>, changes: &mut Vec<FieldChange>) {
+    let child = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    };
+    match (before, after) {
+        (Some(Value::Object(a)), Some(Value::Object(b))) => {
+            let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+            for key in keys {
+                diff_values(&child(key), a.get(key), b.get(key), changes);
+            }
+        }
+        (Some(Value::Array(a)), Some(Value::Array(b)))
+            if a.iter().chain(b.iter()).all(Value::is_object) =>
+        {
+            let keyed = |items: &Vec<Value>| -> Vec<(String, Value)> {
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| (element_key(v, i), v.clone()))
+                    .collect()
+            };
+            let (a, b) = (keyed(a), keyed(b));
+            let mut keys: Vec<&String> = a.iter().map(|(k, _)| k).collect();
+            for (k, _) in &b {
+                if !keys.contains(&k) {
+                    keys.push(k);
+                }
+            }
+            for key in keys {
+                let find = |items: &[(String, Value)]| items.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+                let (old, new) = (find(&a), find(&b));
+                diff_values(&format!("{}[{}]", path, key), old.as_ref(), new.as_ref(), changes);
+            }
+        }
+        (Some(a), Some(b)) if a == b => {}
+        (None, None) => {}
+        (before, after) => changes.push(FieldChange {
+            // 整个词条新增或删除时路径为空
+            field: if path.is_empty() { "entry".to_string() } else { path.to_string() },
+            change: match (before, after) {
+                (None, _) => "added",
+                (_, None) => "removed",
+                _ => "modified",
+            }
+            .to_string(),
+            before: before.cloned(),
+            after: after.cloned(),
+        }),
+    }
+}
+
+/// 比较两个版本的词条，返回逐字段的变化（忽略 schema_version 与 metadata 中的时间戳）
+pub fn diff_words(before: Option<&WordEntry>, after: Option<&WordEntry>) -> Vec<FieldChange> {
+    let strip = |word: Option<&WordEntry>| {
+        word.and_then(|w| serde_json::to_value(w).ok()).map(|mut v| {
+            if let Some(obj) = v.as_object_mut() {
+                obj.remove("schema_version");
+                if let Some(Value::Object(meta)) = obj.get_mut("metadata") {
+                    meta.remove("created_at");
+                    meta.remove("updated_at");
+                }
+            }
+            v
+        })
+    };
+    let mut changes = Vec::new();
+    diff_values("", strip(before).as_ref(), strip(after).as_ref(), &mut changes);
+    changes
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+fn current_word(lexicon_dir: &Path, entry_id: &str) -> Result<Option<WordEntry>, String> {
+    Ok(read_all_words(lexicon_dir)?
+        .into_iter()
+        .find(|w| w.entry_id == entry_id))
+}
+
+fn revision_word(lang_dir: &Path, entry_id: &str, revision_id: &str) -> Result<Option<WordEntry>, String> {
+    if revision_id == CURRENT_REVISION {
+        return current_word(&lang_dir.join("lexicon"), entry_id);
+    }
+    let path = history_file(&history_dir(lang_dir), entry_id);
+    read_revisions(&path)?
+        .into_iter()
+        .find(|r| r.revision_id == revision_id)
+        .map(|r| Some(r.word))
+        .ok_or_else(|| format!("Revision {} not found for entry {}", revision_id, entry_id))
+}
+
+/// 列出词条的修订历史（从旧到新，不含当前版本）
+#[command]
+pub fn list_word_revisions(
+    project_path: String,
+    language_path: String,
+    entry_id: String,
+) -> Result<WordHistory, String> {
+    let dir = history_dir(&Path::new(&project_path).join(&language_path));
+    Ok(WordHistory {
+        max_revisions: load_config(&dir)?.max_revisions,
+        revisions: read_revisions(&history_file(&dir, &entry_id))?,
+        entry_id,
+    })
+}
+
+/// 逐字段比较两个修订；修订 ID 传 "current" 表示词典中的当前版本（已删除的词条视为不存在）
+#[command]
+pub fn diff_word_revisions(
+    project_path: String,
+    language_path: String,
+    entry_id: String,
+    from_revision: String,
+    to_revision: String,
+) -> Result<WordRevisionDiff, String> {
+    let lang_dir = Path::new(&project_path).join(&language_path);
+    let before = revision_word(&lang_dir, &entry_id, &from_revision)?;
+    let after = revision_word(&lang_dir, &entry_id, &to_revision)?;
+    Ok(WordRevisionDiff {
+        changes: diff_words(before.as_ref(), after.as_ref()),
+        entry_id,
+        from_revision,
+        to_revision,
+    })
+}
+
+/// 把词条恢复为某个修订。恢复本身也是一次保存：当前版本会先记入历史，因此可以撤销
+#[command]
+pub fn restore_word_revision(
+    project_path: String,
+    language_path: String,
+    entry_id: String,
+    revision_id: String,
+    expected_hash: Option<String>,
+) -> Result<BatchEntryResult, String> {
+    let _journal = begin(Path::new(&project_path), "restore_word_revision", None);
+    let lang_dir = Path::new(&project_path).join(&language_path);
+    let lexicon_dir = lang_dir.join("lexicon");
+    let path = history_file(&history_dir(&lang_dir), &entry_id);
+    let revision = read_revisions(&path)?
+        .into_iter()
+        .find(|r| r.revision_id == revision_id)
+        .ok_or_else(|| format!("Revision {} not found for entry {}", revision_id, entry_id))?;
+
+    // 拼写不同时，当前版本位于另一个分桶，需要一并移除
+    let current = current_word(&lexicon_dir, &entry_id)?;
+    let request = WordSaveRequest {
+        word: revision.word,
+        old_romanized: current.map(|w| w.con_word_romanized),
+        expected_hash,
+    };
+    let result = save_words_in_dir(&lexicon_dir, vec![request])
+        .into_iter()
+        .next()
+        .ok_or_else(|| "Restore produced no result".to_string())?;
+    match &result.error {
+        Some(e) => Err(e.clone()),
+        None => Ok(result),
+    }
+}
+
+/// 修改每个词条保留的修订数量，立即裁剪所有历史文件
+#[command]
+pub fn set_max_revisions(
+    project_path: String,
+    language_path: String,
+    max_revisions: u32,
+) -> Result<HistoryConfig, String> {
+    let dir = history_dir(&Path::new(&project_path).join(&language_path));
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let mut config = load_config(&dir)?;
+    config.max_revisions = max_revisions;
+    let content = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
+    atomic_write(&config_file(&dir), &content)?;
+
+    let limit = max_revisions as usize;
+    for entry in fs::read_dir(&dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().is_some_and(|e| e == "ndjson") {
+            let mut revisions = read_revisions(&path)?;
+            if revisions.len() > limit {
+                revisions.drain(..revisions.len() - limit);
+                write_revisions(&path, &revisions)?;
+            }
+        }
+    }
+    Ok(config)
+}
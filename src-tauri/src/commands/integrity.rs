@@ -0,0 +1,315 @@
+use crate::commands::bucketing::{bucket_path, load_bucketing};
+use crate::commands::fork::load_language_entry_ids;
+use crate::commands::lexicon::{parse_bucket, write_bucket, ParsedBucket};
+use crate::commands::project_lock::check_write_access;
+use crate::commands::schema::{parse_versioned, SchemaKind};
+use crate::commands::search_index::invalidate_index;
+use crate::models::{GrammarConfig, WordEntry, WorkspaceConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LexiconIssue {
+    pub kind: String, // "duplicate_entry_id" | "wrong_bucket" | "language_id_mismatch" | "dangling_parent_entry" | "dangling_source_language" | "unknown_pos" | "stale_tmp_file"
+    #[serde(default)]
+    pub entry_id: Option<String>,
+    /// 分桶文件名或 .tmp 文件相对语言目录的路径
+    #[serde(default)]
+    pub file: Option<String>,
+    pub detail: String,
+    /// 该问题可由 repair 模式自动修复
+    pub repairable: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LexiconCheckReport {
+    pub dry_run: bool,
+    pub language_id: String,
+    pub buckets_scanned: usize,
+    pub words_scanned: usize,
+    pub issues: Vec<LexiconIssue>,
+    /// 实际修复的问题数（dry-run 时为 0）
+    pub repaired: usize,
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn issue(kind: &str, entry_id: Option<&str>, file: Option<String>, detail: String, repairable: bool) -> LexiconIssue {
+    LexiconIssue {
+        kind: kind.to_string(),
+        entry_id: entry_id.map(str::to_string),
+        file,
+        detail,
+        repairable,
+    }
+}
+
+/// 递归查找 atomic_write 中断后残留的 .tmp 文件
+fn find_tmp_files(dir: &Path, out: &mut Vec<PathBuf>) -> Result<(), String> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.is_dir() {
+            find_tmp_files(&path, out)?;
+        } else if path.extension().is_some_and(|e| e == "tmp") {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// 词典完整性检查（fsck）。默认 dry-run，只返回报告；`dry_run = false` 时修复：
+/// 重复 entry_id（保留位于正确分桶、updated_at 最新的一份）、错误分桶、language_id 不一致和残留 .tmp 文件。
+/// 悬空的词源引用与未定义的词性只报告，不自动修改。
+#[command]
+pub fn check_lexicon(
+    project_path: String,
+    conlang_file_path: String,
+    language_id: String,
+    dry_run: Option<bool>,
+) -> Result<LexiconCheckReport, String> {
+    let project = Path::new(&project_path);
+    let ws_path = Path::new(&conlang_file_path);
+    let is_dry_run = dry_run.unwrap_or(true);
+
+    let ws_config: WorkspaceConfig = if ws_path.exists() {
+        let content = fs::read_to_string(ws_path).map_err(|e| e.to_string())?;
+        serde_json::from_str(&content).map_err(|e| e.to_string())?
+    } else {
+        return Err("Workspace config not found".to_string());
+    };
+    let language = ws_config
+        .languages
+        .iter()
+        .find(|l| l.language_id == language_id)
+        .ok_or_else(|| format!("Language '{}' not found in workspace", language_id))?;
+
+    let lang_dir = project.join(&language.path);
+    let lexicon_dir = lang_dir.join("lexicon");
+    let bucketing = load_bucketing(&lexicon_dir)?;
+    let mut issues: Vec<LexiconIssue> = Vec::new();
+
+    // 1. 读取所有分桶
+    let mut buckets: BTreeMap<PathBuf, ParsedBucket> = BTreeMap::new();
+    if lexicon_dir.exists() {
+        for entry in fs::read_dir(&lexicon_dir).map_err(|e| e.to_string())? {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            if path.extension().is_some_and(|e| e == "ndjson") {
+                let parsed = parse_bucket(&path)?;
+                buckets.insert(path, parsed);
+            }
+        }
+    }
+    let words_scanned = buckets.values().map(|b| b.words.len()).sum();
+
+    // 2. 重复 entry_id 与错误分桶：每个 entry_id 选出一份保留
+    let mut occurrences: HashMap<String, Vec<(PathBuf, usize)>> = HashMap::new();
+    for (path, parsed) in &buckets {
+        for (i, word) in parsed.words.iter().enumerate() {
+            occurrences
+                .entry(word.entry_id.clone())
+                .or_default()
+                .push((path.clone(), i));
+        }
+    }
+
+    let mut keep: Vec<(PathBuf, WordEntry)> = Vec::new(); // (原分桶, 保留的词条)
+    let mut dirty: HashSet<PathBuf> = HashSet::new();
+    let mut entry_ids: Vec<&String> = occurrences.keys().collect();
+    entry_ids.sort();
+    for entry_id in entry_ids {
+        let copies = &occurrences[entry_id];
+        let word_at = |(path, i): &(PathBuf, usize)| &buckets[path].words[*i];
+        let winner = copies
+            .iter()
+            .max_by(|a, b| {
+                let (wa, wb) = (word_at(a), word_at(b));
+                let a_ok = bucket_path(&lexicon_dir, &bucketing, &wa.con_word_romanized) == a.0;
+                let b_ok = bucket_path(&lexicon_dir, &bucketing, &wb.con_word_romanized) == b.0;
+                a_ok.cmp(&b_ok)
+                    .then_with(|| wa.metadata.updated_at.cmp(&wb.metadata.updated_at))
+            })
+            .ok_or_else(|| format!("No copies found for entry '{}'", entry_id))?;
+
+        if copies.len() > 1 {
+            let files: Vec<String> = copies.iter().map(|(p, _)| file_name(p)).collect();
+            issues.push(issue(
+                "duplicate_entry_id",
+                Some(entry_id),
+                Some(file_name(&winner.0)),
+                format!("entry_id appears {} times: {}", copies.len(), files.join(", ")),
+                true,
+            ));
+            dirty.extend(copies.iter().map(|(p, _)| p.clone()));
+        }
+
+        let word = word_at(winner);
+        let expected = bucket_path(&lexicon_dir, &bucketing, &word.con_word_romanized);
+        if expected != winner.0 {
+            issues.push(issue(
+                "wrong_bucket",
+                Some(entry_id),
+                Some(file_name(&winner.0)),
+                format!(
+                    "'{}' belongs in {}",
+                    word.con_word_romanized,
+                    file_name(&expected)
+                ),
+                true,
+            ));
+            dirty.insert(winner.0.clone());
+            dirty.insert(expected);
+        }
+
+        let mut word = word.clone();
+        if word.language_id != language_id {
+            issues.push(issue(
+                "language_id_mismatch",
+                Some(entry_id),
+                Some(file_name(&winner.0)),
+                format!("language_id is '{}', expected '{}'", word.language_id, language_id),
+                true,
+            ));
+            word.language_id = language_id.clone();
+            dirty.insert(winner.0.clone());
+        }
+        keep.push((winner.0.clone(), word));
+    }
+
+    // 3. 悬空的词源引用
+    let known_languages: HashMap<&str, &str> = ws_config
+        .languages
+        .iter()
+        .map(|l| (l.language_id.as_str(), l.path.as_str()))
+        .collect();
+    let own_ids: HashSet<String> = keep.iter().map(|(_, w)| w.entry_id.clone()).collect();
+    let mut foreign_ids: HashMap<String, HashSet<String>> = HashMap::new();
+    for (_, word) in &keep {
+        let ety = &word.etymology;
+        if let Some(source) = &ety.source_language_id {
+            if !known_languages.contains_key(source.as_str()) {
+                issues.push(issue(
+                    "dangling_source_language",
+                    Some(&word.entry_id),
+                    None,
+                    format!("source_language_id '{}' is not in the workspace", source),
+                    false,
+                ));
+                continue;
+            }
+        }
+        let Some(parent_entry_id) = &ety.parent_entry_id else {
+            continue;
+        };
+        let lookup_language = ety
+            .source_language_id
+            .as_deref()
+            .or(language.parent_id.as_deref())
+            .unwrap_or(language_id.as_str());
+        let found = if lookup_language == language_id {
+            own_ids.contains(parent_entry_id)
+        } else {
+            let Some(path) = known_languages.get(lookup_language) else {
+                continue;
+            };
+            if !foreign_ids.contains_key(lookup_language) {
+                foreign_ids.insert(
+                    lookup_language.to_string(),
+                    load_language_entry_ids(project, path)?,
+                );
+            }
+            foreign_ids
+                .get(lookup_language)
+                .is_some_and(|ids| ids.contains(parent_entry_id))
+        };
+        if !found {
+            issues.push(issue(
+                "dangling_parent_entry",
+                Some(&word.entry_id),
+                None,
+                format!(
+                    "parent_entry_id '{}' not found in language '{}'",
+                    parent_entry_id, lookup_language
+                ),
+                false,
+            ));
+        }
+    }
+
+    // 4. 未在 grammar.json 中定义的词性
+    let grammar_path = lang_dir.join("grammar.json");
+    if grammar_path.exists() {
+        let content = fs::read_to_string(&grammar_path).map_err(|e| e.to_string())?;
+        let grammar: GrammarConfig = parse_versioned(SchemaKind::Grammar, &content)?;
+        let pos_ids: HashSet<&str> = grammar.parts_of_speech.iter().map(|p| p.pos_id.as_str()).collect();
+        for (_, word) in &keep {
+            for sense in &word.senses {
+                if !sense.pos_id.is_empty() && !pos_ids.contains(sense.pos_id.as_str()) {
+                    issues.push(issue(
+                        "unknown_pos",
+                        Some(&word.entry_id),
+                        None,
+                        format!("sense '{}' uses undefined pos_id '{}'", sense.sense_id, sense.pos_id),
+                        false,
+                    ));
+                }
+            }
+        }
+    }
+
+    // 5. 残留的 .tmp 文件
+    let mut tmp_files = Vec::new();
+    find_tmp_files(&lang_dir, &mut tmp_files)?;
+    for path in &tmp_files {
+        let rel = path
+            .strip_prefix(&lang_dir)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|_| file_name(path));
+        issues.push(issue("stale_tmp_file", None, Some(rel), "left behind by an interrupted write".to_string(), true));
+    }
+
+    let mut report = LexiconCheckReport {
+        dry_run: is_dry_run,
+        language_id: language_id.clone(),
+        buckets_scanned: buckets.len(),
+        words_scanned,
+        issues,
+        repaired: 0,
+    };
+    if is_dry_run {
+        return Ok(report);
+    }
+    check_write_access(&lang_dir)?;
+
+    // 修复：把保留的词条重新分配到正确分桶，只改写受影响的分桶
+    if !dirty.is_empty() {
+        let mut regrouped: BTreeMap<PathBuf, ParsedBucket> = BTreeMap::new();
+        for (_, word) in keep {
+            let target = bucket_path(&lexicon_dir, &bucketing, &word.con_word_romanized);
+            regrouped.entry(target).or_default().words.push(word);
+        }
+        for path in &dirty {
+            let mut parsed = regrouped.remove(path).unwrap_or_default();
+            if let Some(original) = buckets.remove(path) {
+                parsed.malformed = original.malformed;
+            }
+            write_bucket(&lexicon_dir, path, parsed)?;
+        }
+        invalidate_index(&lexicon_dir)?;
+    }
+    for path in &tmp_files {
+        fs::remove_file(path).map_err(|e| e.to_string())?;
+    }
+    report.repaired = report.issues.iter().filter(|i| i.repairable).count();
+
+    Ok(report)
+}
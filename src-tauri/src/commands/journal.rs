@@ -0,0 +1,500 @@
+use crate::commands::lexicon::atomic_write;
+use crate::commands::project_lock::check_write_access;
+use crate::commands::search_index::invalidate_index;
+use crate::commands::snapshot_store::{prune_blobs, put_blob, read_blob, write_bytes};
+use crate::commands::timestamp::now_rfc3339;
+use crate::commands::watcher::{content_hash, file_hash, note_own_removal};
+use crate::models::ExtraFields;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tauri::command;
+
+// ── 项目级撤销/重做日志 ─────────────────────────────────
+//
+// <project>/.journal/journal.json   撤销栈与重做栈
+// <project>/.journal/blobs/...      文件内容，与快照相同的内容寻址存储
+//
+// 每条记录对应一次 save_* / delete_* 命令改动的文件：相对项目目录的路径 + 改动前/后内容的 blob 哈希
+// （None 表示文件不存在）。撤销 = 把文件恢复为改动前的内容，重做 = 恢复为改动后的内容。
+// 相同 transaction_id 的连续记录作为一组一起撤销/重做；命令未指定时每次调用自成一组。
+//
+// 捕获方式：命令开始时调用 begin() 取得 JournalScope；atomic_write 与删除文件前调用 capture_before()，
+// 记录文件在本次命令中第一次被改动之前的内容；JournalScope 析构时读取改动后的内容并写入日志。
+// 点开头的目录（.oplog、.history、.index、.journal 等）是派生数据，不记录。
+
+const DEFAULT_MAX_ENTRIES: usize = 100;
+
+fn default_max_entries() -> usize {
+    DEFAULT_MAX_ENTRIES
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct JournalFileChange {
+    /// 相对项目目录的路径（以 / 分隔）
+    pub path: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JournalEntry {
+    pub journal_id: String,
+    pub transaction_id: String,
+    /// 产生这条记录的命令，例如 "save_grammar"
+    pub command: String,
+    pub timestamp: String,
+    pub files: Vec<JournalFileChange>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Journal {
+    #[serde(default = "default_max_entries")]
+    pub max_entries: usize,
+    /// 从旧到新；末尾是下一次撤销的对象
+    #[serde(default)]
+    pub undo: Vec<JournalEntry>,
+    /// 从旧到新；末尾是下一次重做的对象。新的改动会清空重做栈
+    #[serde(default)]
+    pub redo: Vec<JournalEntry>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Default for Journal {
+    fn default() -> Self {
+        Self {
+            max_entries: DEFAULT_MAX_ENTRIES,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            extra: ExtraFields::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JournalStatus {
+    pub max_entries: usize,
+    pub undo_count: usize,
+    pub redo_count: usize,
+    /// 下一次撤销/重做所属组中最后一条记录的命令
+    pub next_undo: Option<String>,
+    pub next_redo: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JournalApplyResult {
+    pub transaction_id: String,
+    pub commands: Vec<String>,
+    /// 被恢复的文件（相对项目目录）
+    pub files: Vec<String>,
+    /// 写入中途失败时的错误：files 只包含已恢复的文件，其余改动仍留在原来的栈中
+    pub error: Option<String>,
+    pub status: JournalStatus,
+}
+
+fn journal_dir(project_root: &Path) -> PathBuf {
+    project_root.join(".journal")
+}
+
+fn journal_file(project_root: &Path) -> PathBuf {
+    journal_dir(project_root).join("journal.json")
+}
+
+fn read_journal(project_root: &Path) -> Result<Journal, String> {
+    let path = journal_file(project_root);
+    if !path.exists() {
+        return Ok(Journal::default());
+    }
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+/// 从栈底按整组（同一 transaction_id 的连续记录）裁剪，直到不超过 max 条，
+/// 避免一组只剩下后半部分、撤销时只恢复一半。栈顶的一组即使单独超出上限也保留
+fn trim_groups(stack: &mut Vec<JournalEntry>, max: usize) {
+    while stack.len() > max {
+        let tx = &stack[0].transaction_id;
+        let end = stack
+            .iter()
+            .position(|e| &e.transaction_id != tx)
+            .unwrap_or(stack.len());
+        if end == stack.len() {
+            break;
+        }
+        stack.drain(..end);
+    }
+}
+
+/// 写入日志，同时裁剪超出上限的旧记录并清理不再引用的 blob
+fn write_journal(project_root: &Path, journal: &mut Journal) -> Result<(), String> {
+    let max = journal.max_entries.max(1);
+    trim_groups(&mut journal.undo, max);
+    trim_groups(&mut journal.redo, max);
+    let dir = journal_dir(project_root);
+    let content = serde_json::to_string_pretty(journal).map_err(|e| e.to_string())?;
+    write_bytes(&journal_file(project_root), content.as_bytes())?;
+
+    let referenced: HashSet<String> = journal
+        .undo
+        .iter()
+        .chain(journal.redo.iter())
+        .flat_map(|e| e.files.iter())
+        .flat_map(|f| f.before.iter().chain(f.after.iter()).cloned())
+        .collect();
+    prune_blobs(&dir, &referenced)?;
+    Ok(())
+}
+
+fn status_of(journal: &Journal) -> JournalStatus {
+    JournalStatus {
+        max_entries: journal.max_entries,
+        undo_count: journal.undo.len(),
+        redo_count: journal.redo.len(),
+        next_undo: journal.undo.last().map(|e| e.command.clone()),
+        next_redo: journal.redo.last().map(|e| e.command.clone()),
+    }
+}
+
+fn generate_id(seed: &str) -> String {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    content_hash(format!("{}:{}", seed, nanos).as_bytes())[..16].to_string()
+}
+
+// ── 写入捕获 ─────────────────────────────────────────────
+
+struct ActiveScope {
+    project_root: PathBuf,
+    command: String,
+    transaction_id: String,
+    /// 文件在本次命令第一次改动之前的内容（None = 不存在）
+    before: BTreeMap<PathBuf, Option<Vec<u8>>>,
+}
+
+thread_local! {
+    static ACTIVE: RefCell<Option<ActiveScope>> = const { RefCell::new(None) };
+}
+
+/// 命令期间持有；析构时把捕获到的改动写入日志
+pub struct JournalScope {
+    owner: bool,
+}
+
+/// 开始记录一次命令的改动。嵌套调用时沿用外层的记录
+pub fn begin(project_root: &Path, command: &str, transaction_id: Option<String>) -> JournalScope {
+    ACTIVE.with(|active| {
+        let mut active = active.borrow_mut();
+        if active.is_some() {
+            return JournalScope { owner: false };
+        }
+        *active = Some(ActiveScope {
+            project_root: project_root.to_path_buf(),
+            command: command.to_string(),
+            transaction_id: transaction_id
+                .filter(|t| !t.is_empty())
+                .unwrap_or_else(|| format!("tx_{}", generate_id(command))),
+            before: BTreeMap::new(),
+        });
+        JournalScope { owner: true }
+    })
+}
+
+/// 相对项目目录的路径；不在项目内或位于点开头的目录中时返回 None
+fn tracked_path(project_root: &Path, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(project_root).ok()?;
+    let mut parts = Vec::new();
+    let components: Vec<Component> = rel.components().collect();
+    for (i, part) in components.iter().enumerate() {
+        let Component::Normal(name) = part else {
+            return None;
+        };
+        let name = name.to_string_lossy();
+        if i + 1 < components.len() && name.starts_with('.') {
+            return None;
+        }
+        parts.push(name.to_string());
+    }
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join("/"))
+    }
+}
+
+/// 在改写或删除文件之前调用。没有进行中的记录、或该文件已捕获过时什么也不做
+pub fn capture_before(path: &Path) {
+    ACTIVE.with(|active| {
+        let mut active = active.borrow_mut();
+        let Some(scope) = active.as_mut() else {
+            return;
+        };
+        if tracked_path(&scope.project_root, path).is_none() || scope.before.contains_key(path) {
+            return;
+        }
+        scope.before.insert(path.to_path_buf(), fs::read(path).ok());
+    });
+}
+
+fn finish(scope: ActiveScope) -> Result<(), String> {
+    let dir = journal_dir(&scope.project_root);
+    let mut files = Vec::new();
+    for (path, before) in scope.before {
+        let after = fs::read(&path).ok();
+        if before == after {
+            continue;
+        }
+        let Some(rel) = tracked_path(&scope.project_root, &path) else {
+            continue;
+        };
+        files.push(JournalFileChange {
+            path: rel,
+            before: before.map(|b| put_blob(&dir, &b)).transpose()?,
+            after: after.map(|b| put_blob(&dir, &b)).transpose()?,
+        });
+    }
+    if files.is_empty() {
+        return Ok(());
+    }
+
+    let mut journal = read_journal(&scope.project_root)?;
+    journal.undo.push(JournalEntry {
+        journal_id: format!("j_{}", generate_id(&scope.command)),
+        transaction_id: scope.transaction_id,
+        command: scope.command,
+        timestamp: now_rfc3339(),
+        files,
+        extra: ExtraFields::new(),
+    });
+    journal.redo.clear();
+    write_journal(&scope.project_root, &mut journal)
+}
+
+impl Drop for JournalScope {
+    fn drop(&mut self) {
+        if !self.owner {
+            return;
+        }
+        let Some(scope) = ACTIVE.with(|active| active.borrow_mut().take()) else {
+            return;
+        };
+        // 命令本身已经完成，日志写入失败只影响撤销，不影响命令结果
+        if let Err(e) = finish(scope) {
+            eprintln!("写入撤销日志失败: {}", e);
+        }
+    }
+}
+
+// ── 撤销 / 重做 ──────────────────────────────────────────
+
+/// 从栈顶取出同一 transaction_id 的连续记录（按原顺序）
+fn pop_group(stack: &mut Vec<JournalEntry>) -> Vec<JournalEntry> {
+    let Some(tx) = stack.last().map(|e| e.transaction_id.clone()) else {
+        return Vec::new();
+    };
+    let start = stack
+        .iter()
+        .rposition(|e| e.transaction_id != tx)
+        .map_or(0, |i| i + 1);
+    stack.split_off(start)
+}
+
+/// 一组记录应用到第几步：steps 按应用顺序排列，前 applied 个已写入磁盘
+struct GroupProgress {
+    /// (记录下标, 文件下标)
+    steps: Vec<(usize, usize)>,
+    applied: usize,
+    error: Option<String>,
+}
+
+/// 把一组记录涉及的文件恢复为改动前（undo）或改动后（redo）的内容。
+/// 任何文件在此之后被其他途径修改过、没有写权限或缺少 blob 时整体拒绝，不改动任何文件；
+/// 写入中途失败时停在失败的那一步，由调用方按 GroupProgress 记录已完成的部分
+fn apply_group(project_root: &Path, group: &[JournalEntry], undo: bool) -> Result<GroupProgress, String> {
+    // 按应用顺序展开：撤销时从最新的改动往回走
+    let mut steps: Vec<(usize, usize)> = group
+        .iter()
+        .enumerate()
+        .flat_map(|(i, e)| (0..e.files.len()).map(move |j| (i, j)))
+        .collect();
+    if undo {
+        steps.reverse();
+    }
+    let change = |(i, j): (usize, usize)| &group[i].files[j];
+
+    // 每个文件在应用前应当处于的状态：该文件第一个步骤的起点
+    let mut expected: BTreeMap<&str, Option<&String>> = BTreeMap::new();
+    for step in steps.iter().map(|s| change(*s)) {
+        let (from, _) = if undo { (&step.after, &step.before) } else { (&step.before, &step.after) };
+        expected.entry(step.path.as_str()).or_insert(from.as_ref());
+    }
+    for (rel, hash) in &expected {
+        let current = file_hash(&project_root.join(rel))?;
+        if current.as_ref() != *hash {
+            return Err(format!(
+                "Conflict: {} was changed after this edit; undo/redo would overwrite newer work",
+                rel
+            ));
+        }
+        // 删除与写入一样需要写权限
+        check_write_access(&project_root.join(rel))?;
+    }
+
+    // 先读出全部目标内容，缺少 blob 时在改动任何文件之前失败
+    let dir = journal_dir(project_root);
+    let mut contents: Vec<Option<String>> = Vec::with_capacity(steps.len());
+    for step in steps.iter().map(|s| change(*s)) {
+        let target = if undo { &step.before } else { &step.after };
+        contents.push(match target {
+            Some(hash) => Some(String::from_utf8(read_blob(&dir, hash)?).map_err(|e| e.to_string())?),
+            None => None,
+        });
+    }
+
+    let mut progress = GroupProgress {
+        steps,
+        applied: 0,
+        error: None,
+    };
+    let mut lexicon_dirs = BTreeSet::new();
+    for (step, content) in progress.steps.iter().zip(&contents) {
+        let path = project_root.join(&change(*step).path);
+        let result = match content {
+            Some(content) => atomic_write(&path, content),
+            None if path.exists() => {
+                note_own_removal(&path);
+                fs::remove_file(&path).map_err(|e| e.to_string())
+            }
+            None => Ok(()),
+        };
+        if let Err(e) = result {
+            progress.error = Some(e);
+            break;
+        }
+        progress.applied += 1;
+        if let Some(parent) = path.parent().filter(|p| p.ends_with("lexicon")) {
+            lexicon_dirs.insert(parent.to_path_buf());
+        }
+    }
+    // 反查索引按词条增量维护，整文件恢复后让它在下次查询时重建
+    for lexicon_dir in lexicon_dirs {
+        if let Err(e) = invalidate_index(&lexicon_dir) {
+            eprintln!("重建反查索引失败: {}", e);
+        }
+    }
+    Ok(progress)
+}
+
+/// 按文件拆分一组记录：返回（已应用的部分, 未应用的部分），两者都保持原顺序并丢弃空记录
+fn split_group(group: Vec<JournalEntry>, progress: &GroupProgress) -> (Vec<JournalEntry>, Vec<JournalEntry>) {
+    let applied: HashSet<(usize, usize)> = progress.steps[..progress.applied].iter().copied().collect();
+    let mut done = Vec::new();
+    let mut remaining = Vec::new();
+    for (i, entry) in group.into_iter().enumerate() {
+        let (applied_files, rest): (Vec<_>, Vec<_>) = entry
+            .files
+            .iter()
+            .cloned()
+            .enumerate()
+            .partition(|(j, _)| applied.contains(&(i, *j)));
+        for (files, target) in [(applied_files, &mut done), (rest, &mut remaining)] {
+            if !files.is_empty() {
+                target.push(JournalEntry {
+                    files: files.into_iter().map(|(_, f)| f).collect(),
+                    ..entry.clone()
+                });
+            }
+        }
+    }
+    (done, remaining)
+}
+
+fn undo_or_redo(project_path: &str, undo: bool) -> Result<Option<JournalApplyResult>, String> {
+    let root = Path::new(project_path);
+    let mut journal = read_journal(root)?;
+    let group = if undo {
+        pop_group(&mut journal.undo)
+    } else {
+        pop_group(&mut journal.redo)
+    };
+    let Some(first) = group.first() else {
+        return Ok(None);
+    };
+    let transaction_id = first.transaction_id.clone();
+    let commands = group.iter().map(|e| e.command.clone()).collect();
+    let progress = apply_group(root, &group, undo)?;
+
+    // 中途失败时只把已恢复的文件移到另一个栈，其余改动留在原栈中，日志始终与磁盘一致
+    let (done, remaining) = split_group(group, &progress);
+    let files: BTreeSet<String> = done.iter().flat_map(|e| e.files.iter().map(|f| f.path.clone())).collect();
+    if undo {
+        journal.undo.extend(remaining);
+        journal.redo.extend(done);
+    } else {
+        journal.redo.extend(remaining);
+        journal.undo.extend(done);
+    }
+    write_journal(root, &mut journal)?;
+    Ok(Some(JournalApplyResult {
+        transaction_id,
+        commands,
+        files: files.into_iter().collect(),
+        error: progress.error.map(|e| {
+            format!(
+                "{} ({} of {} file changes applied; the rest can be retried)",
+                e,
+                progress.applied,
+                progress.steps.len()
+            )
+        }),
+        status: status_of(&journal),
+    }))
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+#[command]
+pub fn load_journal_status(project_path: String) -> Result<JournalStatus, String> {
+    Ok(status_of(&read_journal(Path::new(&project_path))?))
+}
+
+/// 撤销最近一组改动；没有可撤销的内容时返回 None
+#[command]
+pub fn undo(project_path: String) -> Result<Option<JournalApplyResult>, String> {
+    undo_or_redo(&project_path, true)
+}
+
+/// 重做最近一次撤销的一组改动；没有可重做的内容时返回 None
+#[command]
+pub fn redo(project_path: String) -> Result<Option<JournalApplyResult>, String> {
+    undo_or_redo(&project_path, false)
+}
+
+/// 清空本项目的撤销/重做日志
+#[command]
+pub fn clear_journal(project_path: String) -> Result<JournalStatus, String> {
+    let root = Path::new(&project_path);
+    let mut journal = read_journal(root)?;
+    journal.undo.clear();
+    journal.redo.clear();
+    write_journal(root, &mut journal)?;
+    Ok(status_of(&journal))
+}
+
+#[command]
+pub fn set_max_journal_entries(project_path: String, max_entries: usize) -> Result<JournalStatus, String> {
+    if max_entries == 0 {
+        return Err("max_entries must be at least 1".to_string());
+    }
+    let root = Path::new(&project_path);
+    let mut journal = read_journal(root)?;
+    journal.max_entries = max_entries;
+    write_journal(root, &mut journal)?;
+    Ok(status_of(&journal))
+}
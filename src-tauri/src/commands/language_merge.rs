@@ -0,0 +1,576 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+use crate::commands::bucketing::{bucket_path, load_bucketing};
+use crate::commands::language_ops::{
+    apply_workspace_change, files_with_extension, is_within, relative_path, retag_word,
+    rewrite_bucket, ReferenceFileChange, Rewrites,
+};
+use crate::commands::lexicon::{parse_word_line, read_all_words};
+use crate::commands::oplog::create_snapshot;
+use crate::commands::project_lock::check_write_access;
+use crate::commands::schema::{newer_schema_error, parse_versioned, SchemaKind};
+use crate::commands::search_index::invalidate_index;
+use crate::commands::workspace::load_workspace;
+use crate::models::{
+    CorpusIndexEntry, CorpusText, ExtraFields, GrammarConfig, SCAConfig, WordEntry,
+    CURRENT_SCHEMA_VERSION,
+};
+
+// ── 合并姊妹语言 ─────────────────────────────────────────
+//
+// 把 source 并入 target（两者必须有同一个父语言），source 随后从工作区移除：
+//   词典     按 etymology.parent_entry_id 对齐；两边相对父语言中的祖词都改动过时保留 target 版本并列入冲突
+//   语法     词性、维度、规则、词缀槽、变位类、手册章节按 ID 合并，同 ID 内容不同时保留 target 版本
+//   音变     source 的规则集接在 target 之后（保持各自的 order 先后）
+//   语料     source 的语料文本复制到 target，词条链接随词条 ID 改写
+//   其他语言 source 的子语言改挂到 target 下，各语言中指向 source 的词源链接改指 target
+// 音系沿用 target。.conlang 最后写入。
+// source 的目录原样留在磁盘上：它的 .oplog、.history、音系，以及冲突中被舍弃的版本
+// 都只存在于那里，target 的快照无法还原它们；确认不再需要后由用户自行删除。
+
+/// 两边都改动过的同源词条
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LexiconMergeConflict {
+    pub parent_entry_id: String,
+    /// 合并后保留的版本
+    pub target: WordEntry,
+    /// 被舍弃的 source 版本
+    pub source: WordEntry,
+}
+
+/// 同 ID 但内容不同的语法项，合并后保留 target 版本
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GrammarMergeConflict {
+    pub kind: String, // "pos" | "dimension" | "inflection_rule" | "derivation_rule" | "slot" | "class" | "chapter" | "override"
+    pub id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LanguageMergeReport {
+    pub dry_run: bool,
+    pub target_id: String,
+    pub source_id: String,
+    /// 按 parent_entry_id 对齐为同一词条的数量
+    pub unified: usize,
+    /// 其中采用 source 版本内容的数量
+    pub taken_from_source: usize,
+    /// 只存在于 source、作为新词条并入的数量
+    pub added: usize,
+    /// 并入时因 entry_id 与 target 重复而改名的数量
+    pub renamed: usize,
+    pub conflicts: Vec<LexiconMergeConflict>,
+    pub grammar_added: usize,
+    pub grammar_conflicts: Vec<GrammarMergeConflict>,
+    pub rule_sets_added: usize,
+    pub corpus_moved: usize,
+    /// 改挂到 target 下的 source 子语言
+    pub children_reparented: Vec<String>,
+    /// source 的目录（相对项目目录），合并后仍保留在磁盘上
+    pub source_path: String,
+    pub files: Vec<ReferenceFileChange>,
+    /// 执行前为 target 创建的快照，预览时为空
+    #[serde(default)]
+    pub log_id: Option<String>,
+}
+
+/// 比较词条内容时忽略身份、词源、同步状态与时间戳
+fn comparable(word: &WordEntry) -> serde_json::Value {
+    let mut value = serde_json::to_value(word).unwrap_or_default();
+    if let Some(obj) = value.as_object_mut() {
+        for key in ["schema_version", "entry_id", "language_id", "etymology", "evolution"] {
+            obj.remove(key);
+        }
+        if let Some(metadata) = obj.get_mut("metadata").and_then(|m| m.as_object_mut()) {
+            metadata.remove("created_at");
+            metadata.remove("updated_at");
+        }
+    }
+    value
+}
+
+/// 在 taken 中找一个不冲突的 ID：原 ID 可用时沿用，否则依次尝试 `{id}_{suffix}`、`{id}_{suffix}2`…
+pub fn unique_id(id: &str, suffix: &str, taken: &mut HashSet<String>) -> String {
+    let mut candidate = id.to_string();
+    let mut n = 1;
+    while taken.contains(&candidate) {
+        candidate = if n == 1 {
+            format!("{}_{}", id, suffix)
+        } else {
+            format!("{}_{}{}", id, suffix, n)
+        };
+        n += 1;
+    }
+    taken.insert(candidate.clone());
+    candidate
+}
+
+/// 把 source 中 target 没有的项追加到 target；同 ID 内容不同的项记为冲突
+fn merge_by_id<T: Serialize + Clone>(
+    kind: &str,
+    target: &mut Vec<T>,
+    source: &[T],
+    id: fn(&T) -> &str,
+    added: &mut usize,
+    conflicts: &mut Vec<GrammarMergeConflict>,
+) {
+    for item in source {
+        match target.iter().find(|t| id(t) == id(item)) {
+            None => {
+                target.push(item.clone());
+                *added += 1;
+            }
+            Some(existing) => {
+                if serde_json::to_value(existing).ok() != serde_json::to_value(item).ok() {
+                    conflicts.push(GrammarMergeConflict {
+                        kind: kind.to_string(),
+                        id: id(item).to_string(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn merge_grammar(
+    target: &mut GrammarConfig,
+    source: &GrammarConfig,
+    id_map: &HashMap<String, String>,
+    added: &mut usize,
+    conflicts: &mut Vec<GrammarMergeConflict>,
+) {
+    merge_by_id("pos", &mut target.parts_of_speech, &source.parts_of_speech, |p| &p.pos_id, added, conflicts);
+    merge_by_id("dimension", &mut target.inflection_dimensions, &source.inflection_dimensions, |d| &d.dim_id, added, conflicts);
+    merge_by_id("inflection_rule", &mut target.inflection_rules, &source.inflection_rules, |r| &r.rule_id, added, conflicts);
+    merge_by_id("derivation_rule", &mut target.derivation_rules, &source.derivation_rules, |r| &r.rule_id, added, conflicts);
+    merge_by_id("slot", &mut target.affix_slots, &source.affix_slots, |s| &s.slot_id, added, conflicts);
+    merge_by_id("class", &mut target.conjugation_classes, &source.conjugation_classes, |c| &c.class_id, added, conflicts);
+    merge_by_id("chapter", &mut target.grammar_manual, &source.grammar_manual, |c| &c.chapter_id, added, conflicts);
+
+    // 不规则形式以 (词条, 维度取值) 为键；source 的词条 ID 先映射到合并后的 ID
+    for item in &source.irregular_overrides {
+        let mut item = item.clone();
+        if let Some(id) = id_map.get(&item.entry_id) {
+            item.entry_id = id.clone();
+        }
+        match target
+            .irregular_overrides
+            .iter()
+            .find(|o| o.entry_id == item.entry_id && o.dimension_values == item.dimension_values)
+        {
+            None => {
+                target.irregular_overrides.push(item);
+                *added += 1;
+            }
+            Some(existing) if existing.surface_form != item.surface_form => {
+                conflicts.push(GrammarMergeConflict {
+                    kind: "override".to_string(),
+                    id: item.entry_id,
+                });
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+/// 读取并解析配置文件；文件不存在时返回 None
+fn read_config<T: serde::de::DeserializeOwned>(kind: SchemaKind, path: &Path) -> Result<Option<(Vec<u8>, T)>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let original = fs::read(path).map_err(|e| e.to_string())?;
+    let config = parse_versioned(kind, &String::from_utf8_lossy(&original))?;
+    Ok(Some((original, config)))
+}
+
+fn read_original(path: &Path) -> Result<Option<Vec<u8>>, String> {
+    if path.exists() {
+        fs::read(path).map(Some).map_err(|e| e.to_string())
+    } else {
+        Ok(None)
+    }
+}
+
+/// 把 source 并入 target。dry_run 时只返回报告
+#[command]
+pub fn merge_languages(
+    project_path: String,
+    conlang_file_path: String,
+    target_id: String,
+    source_id: String,
+    dry_run: Option<bool>,
+) -> Result<LanguageMergeReport, String> {
+    let project = Path::new(&project_path);
+    let ws_path = Path::new(&conlang_file_path);
+    let mut ws = load_workspace(conlang_file_path.clone())?;
+
+    if target_id == source_id {
+        return Err("Cannot merge a language into itself".to_string());
+    }
+    let find = |id: &str| {
+        ws.languages
+            .iter()
+            .find(|l| l.language_id == id)
+            .cloned()
+            .ok_or_else(|| format!("Language '{}' not found in workspace", id))
+    };
+    let target = find(&target_id)?;
+    let source = find(&source_id)?;
+    if target.parent_id != source.parent_id {
+        return Err("Only sibling languages (with the same parent) can be merged".to_string());
+    }
+    let source_path = source.path.trim_matches('/').replace('\\', "/");
+    if let Some(nested) = ws.languages.iter().find(|l| {
+        l.language_id != source_id && is_within(&l.path.trim_matches('/').replace('\\', "/"), &source_path)
+    }) {
+        return Err(format!(
+            "Language '{}' is stored inside '{}'; move it out before merging",
+            nested.language_id, source.path
+        ));
+    }
+
+    let target_dir = project.join(&target.path);
+    let source_dir = project.join(&source.path);
+    let target_lexicon = target_dir.join("lexicon");
+    let mut rewrites = Rewrites::default();
+
+    // ── 词典 ──
+    let target_words = read_all_words(&target_lexicon)?;
+    let source_words = read_all_words(&source_dir.join("lexicon"))?;
+    let ancestors: HashMap<String, WordEntry> = match ws
+        .languages
+        .iter()
+        .find(|l| Some(&l.language_id) == target.parent_id.as_ref())
+    {
+        Some(parent) => read_all_words(&project.join(&parent.path).join("lexicon"))?
+            .into_iter()
+            .map(|w| (w.entry_id.clone(), w))
+            .collect(),
+        None => HashMap::new(),
+    };
+    let mut target_by_parent: HashMap<&str, &WordEntry> = HashMap::new();
+    for word in &target_words {
+        if let Some(parent_entry_id) = word.etymology.parent_entry_id.as_deref() {
+            target_by_parent.entry(parent_entry_id).or_insert(word);
+        }
+    }
+
+    let mut taken: HashSet<String> = target_words.iter().map(|w| w.entry_id.clone()).collect();
+    // source entry_id → 合并后的 entry_id
+    let mut id_map: HashMap<String, String> = HashMap::new();
+    // 采用 source 内容的 target 词条
+    let mut replaced: HashMap<String, WordEntry> = HashMap::new();
+    // 采用 source 版本且拼写变了的词条：从原分桶移除，按新拼写重新分桶
+    let mut moved: HashSet<String> = HashSet::new();
+    let mut appended: Vec<WordEntry> = Vec::new();
+    let mut conflicts = Vec::new();
+    let (mut unified, mut renamed) = (0, 0);
+    for word in &source_words {
+        let counterpart = word
+            .etymology
+            .parent_entry_id
+            .as_deref()
+            .and_then(|id| target_by_parent.remove(id));
+        let mut merged = word.clone();
+        retag_word(&mut merged, &source_id, &target_id);
+        let Some(counterpart) = counterpart else {
+            merged.entry_id = unique_id(&word.entry_id, &source_id, &mut taken);
+            if merged.entry_id != word.entry_id {
+                renamed += 1;
+            }
+            id_map.insert(word.entry_id.clone(), merged.entry_id.clone());
+            appended.push(merged);
+            continue;
+        };
+
+        unified += 1;
+        id_map.insert(word.entry_id.clone(), counterpart.entry_id.clone());
+        if comparable(word) == comparable(counterpart) {
+            continue;
+        }
+        // 与父语言中的祖词比较判断哪一边改动过；找不到祖词时视为两边都改动过
+        let ancestor = word.etymology.parent_entry_id.as_ref().and_then(|id| ancestors.get(id));
+        let changed = |w: &WordEntry| ancestor.is_none_or(|a| comparable(w) != comparable(a));
+        if changed(word) && changed(counterpart) {
+            conflicts.push(LexiconMergeConflict {
+                parent_entry_id: word.etymology.parent_entry_id.clone().unwrap_or_default(),
+                target: counterpart.clone(),
+                source: word.clone(),
+            });
+        } else if changed(word) {
+            merged.entry_id = counterpart.entry_id.clone();
+            merged.etymology = counterpart.etymology.clone();
+            merged.evolution = counterpart.evolution.clone();
+            merged.metadata.created_at = counterpart.metadata.created_at.clone();
+            if merged.con_word_romanized != counterpart.con_word_romanized {
+                moved.insert(counterpart.entry_id.clone());
+            }
+            replaced.insert(counterpart.entry_id.clone(), merged);
+        }
+    }
+    let taken_from_source = replaced.len();
+    let added = appended.len();
+
+    // 原有分桶只替换内容有变化的词条，其余行原样保留；新词条按 target 的分桶策略追加
+    let bucketing = load_bucketing(&target_lexicon)?;
+    let mut appends: BTreeMap<PathBuf, Vec<String>> = BTreeMap::new();
+    let mut relocated: Vec<&WordEntry> = replaced.iter().filter(|(id, _)| moved.contains(*id)).map(|(_, w)| w).collect();
+    relocated.sort_by(|a, b| a.entry_id.cmp(&b.entry_id));
+    for word in appended.iter().chain(relocated) {
+        if word.schema_version > CURRENT_SCHEMA_VERSION {
+            return Err(newer_schema_error(&word.entry_id, word.schema_version));
+        }
+        let mut word = word.clone();
+        word.schema_version = CURRENT_SCHEMA_VERSION;
+        let line = serde_json::to_string(&word).map_err(|e| e.to_string())?;
+        appends
+            .entry(bucket_path(&target_lexicon, &bucketing, &word.con_word_romanized))
+            .or_default()
+            .push(line);
+    }
+    let mut buckets = files_with_extension(&target_lexicon, "ndjson")?;
+    buckets.extend(appends.keys().filter(|p| !p.exists()).cloned());
+    for path in buckets {
+        let original = read_original(&path)?;
+        let content = original
+            .as_ref()
+            .map(|o| String::from_utf8_lossy(o).to_string())
+            .unwrap_or_default();
+        // 换了分桶的词条先从原分桶中删掉，下面再追加到新分桶
+        let mut removed = 0;
+        let content = if moved.is_empty() {
+            content
+        } else {
+            let kept: Vec<&str> = content
+                .lines()
+                .filter(|line| {
+                    let leaving = parse_word_line(line.trim()).is_ok_and(|w| moved.contains(&w.entry_id));
+                    removed += leaving as usize;
+                    !leaving
+                })
+                .collect();
+            if removed == 0 {
+                content
+            } else if kept.iter().all(|l| l.trim().is_empty()) {
+                String::new()
+            } else {
+                kept.join("\n") + "\n"
+            }
+        };
+        let edited = rewrite_bucket(&path, &content, |word| match replaced.get(&word.entry_id) {
+            Some(merged) => {
+                *word = merged.clone();
+                1
+            }
+            None => 0,
+        })?;
+        let (mut updated, mut references) = match edited {
+            Some((updated, words, _)) => (updated, words + removed),
+            None => (content, removed),
+        };
+        if let Some(lines) = appends.get(&path) {
+            if !updated.is_empty() && !updated.ends_with('\n') {
+                updated.push('\n');
+            }
+            updated.push_str(&(lines.join("\n") + "\n"));
+            references += lines.len();
+        }
+        if references > 0 {
+            rewrites.words_updated += references;
+            rewrites.add(project, &path, "lexicon", references, original, updated);
+        }
+    }
+
+    // ── 语法 ──
+    let mut grammar_added = 0;
+    let mut grammar_conflicts = Vec::new();
+    let source_grammar: Option<(Vec<u8>, GrammarConfig)> = read_config(SchemaKind::Grammar, &source_dir.join("grammar.json"))?;
+    if let Some((_, source_grammar)) = &source_grammar {
+        let path = target_dir.join("grammar.json");
+        let (original, mut grammar) = match read_config::<GrammarConfig>(SchemaKind::Grammar, &path)? {
+            Some((original, grammar)) => (Some(original), grammar),
+            None => (None, GrammarConfig { language_id: target_id.clone(), ..GrammarConfig::default() }),
+        };
+        merge_grammar(&mut grammar, source_grammar, &id_map, &mut grammar_added, &mut grammar_conflicts);
+        if grammar_added > 0 {
+            if grammar.schema_version > CURRENT_SCHEMA_VERSION {
+                return Err(newer_schema_error("grammar.json", grammar.schema_version));
+            }
+            grammar.schema_version = CURRENT_SCHEMA_VERSION;
+            let content = serde_json::to_string_pretty(&grammar).map_err(|e| e.to_string())?;
+            rewrites.add(project, &path, "grammar", grammar_added, original, content);
+        }
+    }
+
+    // ── 音变规则 ──
+    let mut rule_sets_added = 0;
+    if let Some((_, source_sca)) = read_config::<SCAConfig>(SchemaKind::Sca, &source_dir.join("sca_rules.json"))? {
+        let path = target_dir.join("sca_rules.json");
+        let (original, mut sca) = match read_config::<SCAConfig>(SchemaKind::Sca, &path)? {
+            Some((original, sca)) => (Some(original), sca),
+            None => (None, SCAConfig { language_id: target_id.clone(), ..SCAConfig::default() }),
+        };
+        let offset = sca.rule_sets.iter().map(|r| r.order + 1).max().unwrap_or(0);
+        let mut taken: HashSet<String> = sca.rule_sets.iter().map(|r| r.ruleset_id.clone()).collect();
+        let mut incoming = source_sca.rule_sets;
+        incoming.sort_by_key(|r| r.order);
+        for mut rule_set in incoming {
+            rule_set.order += offset;
+            rule_set.ruleset_id = unique_id(&rule_set.ruleset_id, &source_id, &mut taken);
+            sca.rule_sets.push(rule_set);
+            rule_sets_added += 1;
+        }
+        if rule_sets_added > 0 {
+            if sca.schema_version > CURRENT_SCHEMA_VERSION {
+                return Err(newer_schema_error("sca_rules.json", sca.schema_version));
+            }
+            sca.schema_version = CURRENT_SCHEMA_VERSION;
+            let content = serde_json::to_string_pretty(&sca).map_err(|e| e.to_string())?;
+            rewrites.add(project, &path, "sca", rule_sets_added, original, content);
+        }
+    }
+
+    // ── 语料 ──
+    let target_corpus = target_dir.join("corpus");
+    let index_path = target_corpus.join("corpus_index.json");
+    let index_original = read_original(&index_path)?;
+    let mut index: Vec<CorpusIndexEntry> = index_original
+        .as_ref()
+        .and_then(|o| serde_json::from_slice(o).ok())
+        .unwrap_or_default();
+    let mut taken: HashSet<String> = files_with_extension(&target_corpus, "json")?
+        .iter()
+        .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+        .chain(index.iter().map(|e| e.corpus_id.clone()))
+        .collect();
+    let mut corpus_moved = 0;
+    for path in files_with_extension(&source_dir.join("corpus"), "json")? {
+        if path.file_name().is_some_and(|n| n == "corpus_index.json") {
+            continue;
+        }
+        let Some((_, mut text)) = read_config::<CorpusText>(SchemaKind::Corpus, &path)? else {
+            continue;
+        };
+        if text.schema_version > CURRENT_SCHEMA_VERSION {
+            return Err(newer_schema_error(&text.corpus_id, text.schema_version));
+        }
+        text.schema_version = CURRENT_SCHEMA_VERSION;
+        text.language_id = target_id.clone();
+        text.corpus_id = unique_id(&text.corpus_id, &source_id, &mut taken);
+        let mut links = 0;
+        for token in text.glossed_lines.iter_mut().flat_map(|l| l.tokens.iter_mut()) {
+            if let Some(id) = id_map.get(&token.linked_entry_id) {
+                if *id != token.linked_entry_id {
+                    token.linked_entry_id = id.clone();
+                    links += 1;
+                }
+            }
+        }
+        index.push(CorpusIndexEntry {
+            corpus_id: text.corpus_id.clone(),
+            title: text.title.clone(),
+            description: text.description.clone(),
+            metadata: text.metadata.clone(),
+            extra: ExtraFields::new(),
+        });
+        let dest = target_corpus.join(format!("{}.json", text.corpus_id));
+        let content = serde_json::to_string_pretty(&text).map_err(|e| e.to_string())?;
+        rewrites.add(project, &dest, "corpus", 1 + links, None, content);
+        corpus_moved += 1;
+    }
+    if corpus_moved > 0 {
+        index.sort_by(|a, b| a.corpus_id.cmp(&b.corpus_id));
+        let content = serde_json::to_string_pretty(&index).map_err(|e| e.to_string())?;
+        rewrites.add(project, &index_path, "corpus", corpus_moved, index_original, content);
+    }
+
+    // ── 其他语言中指向 source 的词源链接 ──
+    for lang in ws.languages.iter().filter(|l| l.language_id != target_id && l.language_id != source_id) {
+        for path in files_with_extension(&project.join(&lang.path).join("lexicon"), "ndjson")? {
+            let original = fs::read(&path).map_err(|e| e.to_string())?;
+            let content = String::from_utf8_lossy(&original).to_string();
+            let edited = rewrite_bucket(&path, &content, |word| {
+                let from_source = word.etymology.source_language_id.as_deref() == Some(source_id.as_str());
+                let mut count = retag_word(word, &source_id, &target_id);
+                if from_source {
+                    if let Some(id) = word.etymology.parent_entry_id.as_ref().and_then(|id| id_map.get(id)) {
+                        if word.etymology.parent_entry_id.as_ref() != Some(id) {
+                            word.etymology.parent_entry_id = Some(id.clone());
+                            count += 1;
+                        }
+                    }
+                }
+                count
+            })?;
+            if let Some((updated, words, references)) = edited {
+                rewrites.words_updated += words;
+                rewrites.add(project, &path, "lexicon", references, Some(original), updated);
+            }
+        }
+    }
+
+    // ── 工作区 ──
+    let mut children_reparented = Vec::new();
+    for entry in &mut ws.languages {
+        if entry.parent_id.as_deref() == Some(source_id.as_str()) {
+            entry.parent_id = Some(target_id.clone());
+            children_reparented.push(entry.language_id.clone());
+        }
+    }
+    ws.languages.retain(|l| l.language_id != source_id);
+    let mut files = rewrites.changes.clone();
+    files.push(ReferenceFileChange {
+        path: relative_path(project, ws_path),
+        kind: "workspace".to_string(),
+        references: 1 + children_reparented.len(),
+    });
+
+    let mut report = LanguageMergeReport {
+        dry_run: dry_run.unwrap_or(false),
+        target_id: target_id.clone(),
+        source_id: source_id.clone(),
+        unified,
+        taken_from_source,
+        added,
+        renamed,
+        conflicts,
+        grammar_added,
+        grammar_conflicts,
+        rule_sets_added,
+        corpus_moved,
+        children_reparented,
+        source_path: source.path.clone(),
+        files,
+        log_id: None,
+    };
+    if report.dry_run {
+        return Ok(report);
+    }
+
+    check_write_access(project)?;
+    let log = create_snapshot(
+        project_path.clone(),
+        target.path.clone(),
+        "merge".to_string(),
+        source_id.clone(),
+        target_id.clone(),
+        format!("Merge {} into {}", source_id, target_id),
+        Some(vec![
+            "lexicon".to_string(),
+            "grammar.json".to_string(),
+            "sca_rules.json".to_string(),
+            "corpus".to_string(),
+        ]),
+    )?;
+    report.log_id = log.entries.last().map(|e| e.log_id.clone());
+    apply_workspace_change(project, ws_path, &ws, &rewrites, &[])?;
+
+    // 分桶被直接改写，反查索引下次查询时重建
+    if let Err(e) = invalidate_index(&target_lexicon) {
+        eprintln!("清除反查索引失败: {}", e);
+    }
+    Ok(report)
+}
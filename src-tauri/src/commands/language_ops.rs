@@ -0,0 +1,833 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tauri::command;
+
+use crate::commands::lexicon::{atomic_write, parse_word_line, read_all_words};
+use crate::commands::oplog::{create_snapshot, oplog_file, read_log};
+use crate::commands::project_lock::check_write_access;
+use crate::commands::schema::{newer_schema_error, parse_versioned, SchemaKind};
+use crate::commands::snapshot_store::write_bytes;
+use crate::commands::workspace::load_workspace;
+use crate::models::{
+    CorpusText, GrammarConfig, PhonologyConfig, SCAConfig, WordEntry, WorkspaceConfig,
+    CURRENT_SCHEMA_VERSION,
+};
+
+// ── 语言级操作 ───────────────────────────────────────────
+//
+// 改动一门语言的身份（ID、目录）时需要同时改写整个工作区中的引用：
+//   .conlang                  languages[].language_id / parent_id / path
+//   语言目录中的配置          phonology.json、grammar.json、sca_rules.json、corpus/*.json 的 language_id
+//   所有语言的词典分桶        language_id、etymology.source_language_id、evolution.deprecated_since_language_id
+//   所有语言的 .oplog         OperationLogEntry.source_language_id / target_language_id
+//
+// 先在内存中算出全部改写（dry_run 时只返回报告），执行时逐个写入，任一步失败都把已写入的文件恢复原样；
+// .conlang 最后写入，之前失败时工作区仍指向原来的语言。
+
+/// 一个将被改写的文件
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReferenceFileChange {
+    /// 相对项目目录的路径（移动之前的位置）
+    pub path: String,
+    pub kind: String, // "workspace" | "phonology" | "grammar" | "sca" | "corpus" | "lexicon" | "oplog"
+    pub references: usize,
+}
+
+/// 目录随之移动的语言：被改名的语言本身，以及嵌套在其目录中的子语言
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MovedLanguage {
+    pub language_id: String,
+    pub old_path: String,
+    pub new_path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LanguageRenameReport {
+    pub dry_run: bool,
+    pub old_id: String,
+    pub new_id: String,
+    pub moved: Vec<MovedLanguage>,
+    pub files: Vec<ReferenceFileChange>,
+    /// 改写的词条数（所有语言合计）
+    pub words_updated: usize,
+    /// 改写的引用总数
+    pub references: usize,
+}
+
+/// 新父语言中与继承词条匹配的词条
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ParentLinkCandidate {
+    pub entry_id: String,
+    pub con_word_romanized: String,
+    pub matched_by: String, // "romanization" | "gloss"
+}
+
+/// 一个继承词条（etymology.parent_entry_id 指向原父语言）在更换父语言后的处理
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ParentLinkResolution {
+    pub entry_id: String,
+    pub con_word_romanized: String,
+    pub parent_entry_id: String,
+    /// "resolved"：新父语言中存在同一词条；"relinked"：改挂到 candidate；
+    /// "borrowed"：标记为从原父语言借入；"unresolved"：保持原样
+    pub status: String,
+    #[serde(default)]
+    pub candidate: Option<ParentLinkCandidate>,
+    /// 匹配到多个候选时的候选数（此时不自动改挂）
+    #[serde(default)]
+    pub ambiguous: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReparentReport {
+    pub dry_run: bool,
+    pub language_id: String,
+    pub old_parent_id: Option<String>,
+    pub new_parent_id: Option<String>,
+    pub links: Vec<ParentLinkResolution>,
+    pub resolved: usize,
+    pub relinked: usize,
+    pub borrowed: usize,
+    pub unresolved: usize,
+    /// 执行前创建的快照，预览或词典无改动时为空
+    #[serde(default)]
+    pub log_id: Option<String>,
+}
+
+// ── 改写计划 ─────────────────────────────────────────────
+
+struct FileRewrite {
+    path: PathBuf,
+    /// 改写前的内容，失败时据此恢复；新建的文件为 None，恢复时删除
+    original: Option<Vec<u8>>,
+    content: String,
+}
+
+/// 一组待写入的文件改动
+#[derive(Default)]
+pub struct Rewrites {
+    files: Vec<FileRewrite>,
+    pub changes: Vec<ReferenceFileChange>,
+    pub words_updated: usize,
+}
+
+impl Rewrites {
+    pub fn add(
+        &mut self,
+        project: &Path,
+        path: &Path,
+        kind: &str,
+        references: usize,
+        original: Option<Vec<u8>>,
+        content: String,
+    ) {
+        self.changes.push(ReferenceFileChange {
+            path: relative_path(project, path),
+            kind: kind.to_string(),
+            references,
+        });
+        self.files.push(FileRewrite {
+            path: path.to_path_buf(),
+            original,
+            content,
+        });
+    }
+
+    pub fn references(&self) -> usize {
+        self.changes.iter().map(|c| c.references).sum()
+    }
+
+    /// 依次写入；失败时恢复已写入的文件并返回错误
+    pub fn commit(&self) -> Result<(), String> {
+        for (i, file) in self.files.iter().enumerate() {
+            if let Err(e) = atomic_write(&file.path, &file.content) {
+                self.restore(i);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// 把前 count 个已写入的文件恢复为改写前的内容
+    fn restore(&self, count: usize) {
+        for file in self.files.iter().take(count) {
+            let result = match &file.original {
+                Some(original) => write_bytes(&file.path, original),
+                None => fs::remove_file(&file.path).map_err(|e| e.to_string()),
+            };
+            if let Err(e) = result {
+                eprintln!("恢复 {} 失败: {}", file.path.display(), e);
+            }
+        }
+    }
+
+    /// commit 成功之后的步骤失败时，恢复全部文件
+    pub fn rollback(&self) {
+        self.restore(self.files.len());
+    }
+}
+
+pub fn relative_path(project: &Path, path: &Path) -> String {
+    path.strip_prefix(project)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// 语言目录的相对路径：统一为 / 分隔，不允许绝对路径、`..` 以及点开头的目录（那是内部数据目录）
+pub fn normalize_language_path(path: &str) -> Result<String, String> {
+    let normalized = path.trim().replace('\\', "/").trim_matches('/').to_string();
+    if normalized.is_empty() {
+        return Err("Language path cannot be empty".to_string());
+    }
+    for component in Path::new(&normalized).components() {
+        match component {
+            Component::Normal(name) if !name.to_string_lossy().starts_with('.') => {}
+            _ => return Err(format!("Invalid language path: '{}'", path)),
+        }
+    }
+    Ok(normalized)
+}
+
+/// path 是否等于 dir 或位于 dir 之下（均为相对项目目录、/ 分隔的路径）
+pub fn is_within(path: &str, dir: &str) -> bool {
+    path == dir || path.starts_with(&format!("{}/", dir))
+}
+
+fn config_ids_phonology(c: &mut PhonologyConfig) -> (&mut String, &mut u32) {
+    (&mut c.language_id, &mut c.schema_version)
+}
+
+fn config_ids_grammar(c: &mut GrammarConfig) -> (&mut String, &mut u32) {
+    (&mut c.language_id, &mut c.schema_version)
+}
+
+fn config_ids_sca(c: &mut SCAConfig) -> (&mut String, &mut u32) {
+    (&mut c.language_id, &mut c.schema_version)
+}
+
+fn config_ids_corpus(c: &mut CorpusText) -> (&mut String, &mut u32) {
+    (&mut c.language_id, &mut c.schema_version)
+}
+
+/// 改写配置文件的 language_id；不引用 old_id 时返回 None
+fn retag_config<T: DeserializeOwned + Serialize>(
+    kind: SchemaKind,
+    path: &Path,
+    content: &str,
+    old_id: &str,
+    new_id: &str,
+    ids: fn(&mut T) -> (&mut String, &mut u32),
+) -> Result<Option<String>, String> {
+    let mut config: T = parse_versioned(kind, content)?;
+    let (language_id, version) = ids(&mut config);
+    if *language_id != old_id {
+        return Ok(None);
+    }
+    if *version > CURRENT_SCHEMA_VERSION {
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        return Err(newer_schema_error(&name, *version));
+    }
+    *language_id = new_id.to_string();
+    *version = CURRENT_SCHEMA_VERSION;
+    serde_json::to_string_pretty(&config).map(Some).map_err(|e| e.to_string())
+}
+
+/// 词条中对 old_id 的引用数，并改写为 new_id
+pub fn retag_word(word: &mut WordEntry, old_id: &str, new_id: &str) -> usize {
+    let mut count = 0;
+    for field in [
+        Some(&mut word.language_id),
+        word.etymology.source_language_id.as_mut(),
+        word.evolution.deprecated_since_language_id.as_mut(),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if *field == old_id {
+            *field = new_id.to_string();
+            count += 1;
+        }
+    }
+    count
+}
+
+/// 用 edit 改写分桶中的词条（返回改动数，0 表示未改动），其余行（包括无法解析的行）原样保留，不改变行序。
+/// 返回 (新内容, 改动的词条数, 改动总数)；没有任何改动时返回 None
+pub fn rewrite_bucket(
+    path: &Path,
+    content: &str,
+    mut edit: impl FnMut(&mut WordEntry) -> usize,
+) -> Result<Option<(String, usize, usize)>, String> {
+    let mut lines = Vec::new();
+    let (mut words, mut references) = (0, 0);
+    for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let Ok(mut word) = parse_word_line(line) else {
+            lines.push(line.to_string());
+            continue;
+        };
+        let count = edit(&mut word);
+        if count == 0 {
+            lines.push(line.to_string());
+            continue;
+        }
+        if word.schema_version > CURRENT_SCHEMA_VERSION {
+            let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            return Err(newer_schema_error(&name, word.schema_version));
+        }
+        word.schema_version = CURRENT_SCHEMA_VERSION;
+        lines.push(serde_json::to_string(&word).map_err(|e| e.to_string())?);
+        words += 1;
+        references += count;
+    }
+    if references == 0 {
+        return Ok(None);
+    }
+    Ok(Some((lines.join("\n") + "\n", words, references)))
+}
+
+/// 目录中直接包含的指定扩展名的文件（不进入子目录），按路径排序
+pub fn files_with_extension(dir: &Path, extension: &str) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    if !dir.is_dir() {
+        return Ok(files);
+    }
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.is_file() && path.extension().is_some_and(|e| e == extension) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// 在整个工作区中把语言 ID old_id 改写为 new_id（不含 .conlang 本身）
+pub fn plan_language_id_rewrites(
+    project: &Path,
+    ws: &WorkspaceConfig,
+    old_id: &str,
+    new_id: &str,
+) -> Result<Rewrites, String> {
+    let mut rewrites = Rewrites::default();
+
+    if let Some(lang) = ws.languages.iter().find(|l| l.language_id == old_id) {
+        let lang_dir = project.join(&lang.path);
+        let configs: [(&str, PathBuf); 3] = [
+            ("phonology", lang_dir.join("phonology.json")),
+            ("grammar", lang_dir.join("grammar.json")),
+            ("sca", lang_dir.join("sca_rules.json")),
+        ];
+        for (kind, path) in configs {
+            if !path.exists() {
+                continue;
+            }
+            let original = fs::read(&path).map_err(|e| e.to_string())?;
+            let content = String::from_utf8_lossy(&original).to_string();
+            let updated = match kind {
+                "phonology" => retag_config(SchemaKind::Phonology, &path, &content, old_id, new_id, config_ids_phonology)?,
+                "grammar" => retag_config(SchemaKind::Grammar, &path, &content, old_id, new_id, config_ids_grammar)?,
+                _ => retag_config(SchemaKind::Sca, &path, &content, old_id, new_id, config_ids_sca)?,
+            };
+            if let Some(updated) = updated {
+                rewrites.add(project, &path, kind, 1, Some(original), updated);
+            }
+        }
+        for path in files_with_extension(&lang_dir.join("corpus"), "json")? {
+            if path.file_name().is_some_and(|n| n == "corpus_index.json") {
+                continue;
+            }
+            let original = fs::read(&path).map_err(|e| e.to_string())?;
+            let content = String::from_utf8_lossy(&original).to_string();
+            if let Some(updated) = retag_config(SchemaKind::Corpus, &path, &content, old_id, new_id, config_ids_corpus)? {
+                rewrites.add(project, &path, "corpus", 1, Some(original), updated);
+            }
+        }
+    }
+
+    for lang in &ws.languages {
+        let lang_dir = project.join(&lang.path);
+        for path in files_with_extension(&lang_dir.join("lexicon"), "ndjson")? {
+            let original = fs::read(&path).map_err(|e| e.to_string())?;
+            let content = String::from_utf8_lossy(&original).to_string();
+            let retagged = rewrite_bucket(&path, &content, |word| retag_word(word, old_id, new_id))?;
+            if let Some((updated, words, references)) = retagged {
+                rewrites.words_updated += words;
+                rewrites.add(project, &path, "lexicon", references, Some(original), updated);
+            }
+        }
+
+        let log_path = oplog_file(&lang_dir);
+        if log_path.exists() {
+            let mut log = read_log(&log_path)?;
+            let mut references = 0;
+            for entry in &mut log.entries {
+                for field in [&mut entry.source_language_id, &mut entry.target_language_id] {
+                    if *field == old_id {
+                        *field = new_id.to_string();
+                        references += 1;
+                    }
+                }
+            }
+            if references > 0 {
+                let original = fs::read(&log_path).map_err(|e| e.to_string())?;
+                let updated = serde_json::to_string_pretty(&log).map_err(|e| e.to_string())?;
+                rewrites.add(project, &log_path, "oplog", references, Some(original), updated);
+            }
+        }
+    }
+
+    Ok(rewrites)
+}
+
+/// 执行改写：先写入各文件，再移动目录（moves 为相对项目目录的 旧路径 → 新路径），最后写入 .conlang。
+/// 任一步失败都撤回之前的步骤
+pub fn apply_workspace_change(
+    project: &Path,
+    ws_path: &Path,
+    ws: &WorkspaceConfig,
+    rewrites: &Rewrites,
+    moves: &[(String, String)],
+) -> Result<(), String> {
+    rewrites.commit()?;
+
+    let mut moved: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let undo_moves = |moved: &[(PathBuf, PathBuf)]| {
+        for (from, to) in moved.iter().rev() {
+            if let Err(e) = fs::rename(to, from) {
+                eprintln!("撤回目录移动 {} 失败: {}", to.display(), e);
+            }
+        }
+    };
+    for (from, to) in moves {
+        let (from, to) = (project.join(from), project.join(to));
+        if !from.exists() {
+            continue;
+        }
+        let result = to
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::rename(&from, &to))
+            .map_err(|e| format!("Failed to move {}: {}", from.display(), e));
+        if let Err(e) = result {
+            undo_moves(&moved);
+            rewrites.rollback();
+            return Err(e);
+        }
+        moved.push((from, to));
+    }
+
+    let content = serde_json::to_string_pretty(ws).map_err(|e| e.to_string())?;
+    if let Err(e) = atomic_write(ws_path, &content) {
+        undo_moves(&moved);
+        rewrites.rollback();
+        return Err(e);
+    }
+    Ok(())
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+/// 修改语言的 ID 和/或目录，并改写整个工作区中对它的引用。dry_run 时只返回将要改动的内容
+#[command]
+pub fn rename_language(
+    project_path: String,
+    conlang_file_path: String,
+    language_id: String,
+    new_id: Option<String>,
+    new_path: Option<String>,
+    dry_run: Option<bool>,
+) -> Result<LanguageRenameReport, String> {
+    let project = Path::new(&project_path);
+    let ws_path = Path::new(&conlang_file_path);
+    let mut ws = load_workspace(conlang_file_path.clone())?;
+
+    let lang = ws
+        .languages
+        .iter()
+        .find(|l| l.language_id == language_id)
+        .cloned()
+        .ok_or_else(|| format!("Language '{}' not found in workspace", language_id))?;
+
+    let new_id = new_id
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .unwrap_or_else(|| language_id.clone());
+    if new_id != language_id && ws.languages.iter().any(|l| l.language_id == new_id) {
+        return Err(format!("Language ID '{}' is already in use", new_id));
+    }
+
+    let old_path = normalize_language_path(&lang.path)?;
+    let new_path = match new_path {
+        Some(path) if !path.trim().is_empty() => normalize_language_path(&path)?,
+        _ => old_path.clone(),
+    };
+    if new_path != old_path {
+        if is_within(&new_path, &old_path) {
+            return Err("A language cannot be moved into its own directory".to_string());
+        }
+        if ws.languages.iter().any(|l| l.path.trim_matches('/') == new_path) {
+            return Err(format!("Path '{}' is already used by another language", new_path));
+        }
+        if project.join(&new_path).exists() {
+            return Err(format!("Directory '{}' already exists", new_path));
+        }
+    }
+    if new_id == language_id && new_path == old_path {
+        return Err("Nothing to change".to_string());
+    }
+
+    let mut rewrites = if new_id != language_id {
+        plan_language_id_rewrites(project, &ws, &language_id, &new_id)?
+    } else {
+        Rewrites::default()
+    };
+
+    // .conlang：ID、子语言的 parent_id、本语言及嵌套在其目录中的语言的 path
+    let mut moved = Vec::new();
+    let mut ws_references = 0;
+    for entry in &mut ws.languages {
+        if entry.language_id == language_id {
+            entry.language_id = new_id.clone();
+            ws_references += 1;
+        }
+        if entry.parent_id.as_deref() == Some(language_id.as_str()) {
+            entry.parent_id = Some(new_id.clone());
+            ws_references += 1;
+        }
+        let path = entry.path.trim_matches('/').replace('\\', "/");
+        if new_path != old_path && is_within(&path, &old_path) {
+            let relocated = format!("{}{}", new_path, &path[old_path.len()..]);
+            moved.push(MovedLanguage {
+                language_id: entry.language_id.clone(),
+                old_path: entry.path.clone(),
+                new_path: relocated.clone(),
+            });
+            entry.path = relocated;
+            ws_references += 1;
+        }
+    }
+    rewrites.changes.push(ReferenceFileChange {
+        path: relative_path(project, ws_path),
+        kind: "workspace".to_string(),
+        references: ws_references,
+    });
+
+    let dry_run = dry_run.unwrap_or(false);
+    let report = LanguageRenameReport {
+        dry_run,
+        old_id: language_id,
+        new_id,
+        moved,
+        files: rewrites.changes.clone(),
+        words_updated: rewrites.words_updated,
+        references: rewrites.references(),
+    };
+    if dry_run {
+        return Ok(report);
+    }
+
+    check_write_access(project)?;
+    let moves: Vec<(String, String)> = if new_path != old_path {
+        vec![(old_path, new_path)]
+    } else {
+        Vec::new()
+    };
+    apply_workspace_change(project, ws_path, &ws, &rewrites, &moves)?;
+    Ok(report)
+}
+
+// ── 更换父语言 ───────────────────────────────────────────
+
+fn match_key(text: &str) -> String {
+    text.trim().to_lowercase()
+}
+
+/// 新父语言词典按拼写与释义建立的查找表
+struct ParentIndex<'a> {
+    by_romanization: HashMap<String, Vec<&'a WordEntry>>,
+    by_gloss: HashMap<String, Vec<&'a WordEntry>>,
+}
+
+impl<'a> ParentIndex<'a> {
+    fn new(words: &'a [WordEntry]) -> Self {
+        let mut index = Self {
+            by_romanization: HashMap::new(),
+            by_gloss: HashMap::new(),
+        };
+        for word in words {
+            index
+                .by_romanization
+                .entry(match_key(&word.con_word_romanized))
+                .or_default()
+                .push(word);
+            let glosses: HashSet<String> = word.senses.iter().map(|s| match_key(&s.gloss)).collect();
+            for gloss in glosses.into_iter().filter(|g| !g.is_empty()) {
+                index.by_gloss.entry(gloss).or_default().push(word);
+            }
+        }
+        index
+    }
+
+    /// 依次按拼写、释义查找尚未被占用的候选；返回唯一候选，或多个候选时的候选数
+    fn find(&self, forms: &[&str], glosses: &[&str], claimed: &HashSet<String>) -> (Option<ParentLinkCandidate>, usize) {
+        for (table, keys, matched_by) in [
+            (&self.by_romanization, forms, "romanization"),
+            (&self.by_gloss, glosses, "gloss"),
+        ] {
+            let mut hits: Vec<&WordEntry> = Vec::new();
+            for key in keys.iter().map(|k| match_key(k)).filter(|k| !k.is_empty()) {
+                for word in table.get(&key).into_iter().flatten() {
+                    if !claimed.contains(&word.entry_id) && !hits.iter().any(|h| h.entry_id == word.entry_id) {
+                        hits.push(word);
+                    }
+                }
+            }
+            match hits.as_slice() {
+                [] => continue,
+                [word] => {
+                    let candidate = ParentLinkCandidate {
+                        entry_id: word.entry_id.clone(),
+                        con_word_romanized: word.con_word_romanized.clone(),
+                        matched_by: matched_by.to_string(),
+                    };
+                    return (Some(candidate), 0);
+                }
+                _ => return (None, hits.len()),
+            }
+        }
+        (None, 0)
+    }
+}
+
+/// 从原父语言继承而来、需要随父语言变化重新核对的词条；借词与来自其他语言的链接不受影响
+fn is_inherited_from(word: &WordEntry, parent_id: Option<&str>) -> bool {
+    let Some(parent_id) = parent_id else {
+        return false;
+    };
+    word.etymology.parent_entry_id.is_some()
+        && word.etymology.origin_type != "borrowed"
+        && word.etymology.source_language_id.as_deref().unwrap_or(parent_id) == parent_id
+}
+
+/// 把语言挂到另一个父语言之下（new_parent_id 为空时成为根语言），并核对继承词条的 parent_entry_id。
+/// 新父语言中找不到的链接：relink 时按拼写或释义改挂到唯一匹配的词条，borrow_unmatched 时标记为从原父语言借入，
+/// 否则保持原样。dry_run 时只返回报告
+#[command]
+pub fn reparent_language(
+    project_path: String,
+    conlang_file_path: String,
+    language_id: String,
+    new_parent_id: Option<String>,
+    relink: Option<bool>,
+    borrow_unmatched: Option<bool>,
+    dry_run: Option<bool>,
+) -> Result<ReparentReport, String> {
+    let project = Path::new(&project_path);
+    let ws_path = Path::new(&conlang_file_path);
+    let mut ws = load_workspace(conlang_file_path.clone())?;
+
+    let lang = ws
+        .languages
+        .iter()
+        .find(|l| l.language_id == language_id)
+        .cloned()
+        .ok_or_else(|| format!("Language '{}' not found in workspace", language_id))?;
+    let old_parent_id = lang.parent_id.clone();
+    let new_parent_id = new_parent_id
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty());
+    if new_parent_id == old_parent_id {
+        return Err("Nothing to change".to_string());
+    }
+
+    // 新父语言不能是自身或自己的后代
+    let new_parent = match &new_parent_id {
+        Some(id) => {
+            let parent = ws
+                .languages
+                .iter()
+                .find(|l| &l.language_id == id)
+                .cloned()
+                .ok_or_else(|| format!("Language '{}' not found in workspace", id))?;
+            let mut visited = HashSet::new();
+            let mut current = Some(id.clone());
+            while let Some(ancestor) = current {
+                if ancestor == language_id {
+                    return Err(format!(
+                        "Cannot move '{}' under '{}': it would create a cycle in the family tree",
+                        language_id, id
+                    ));
+                }
+                if !visited.insert(ancestor.clone()) {
+                    break;
+                }
+                current = ws
+                    .languages
+                    .iter()
+                    .find(|l| l.language_id == ancestor)
+                    .and_then(|l| l.parent_id.clone());
+            }
+            Some(parent)
+        }
+        None => None,
+    };
+
+    let lexicon_of = |path: &str| project.join(path).join("lexicon");
+    let parent_words = match &new_parent {
+        Some(parent) => read_all_words(&lexicon_of(&parent.path))?,
+        None => Vec::new(),
+    };
+    let old_parent_words: HashMap<String, WordEntry> = match ws
+        .languages
+        .iter()
+        .find(|l| Some(&l.language_id) == old_parent_id.as_ref())
+    {
+        Some(parent) => read_all_words(&lexicon_of(&parent.path))?
+            .into_iter()
+            .map(|w| (w.entry_id.clone(), w))
+            .collect(),
+        None => HashMap::new(),
+    };
+    let child_words = read_all_words(&lexicon_of(&lang.path))?;
+
+    let inherited: Vec<&WordEntry> = child_words
+        .iter()
+        .filter(|w| is_inherited_from(w, old_parent_id.as_deref()))
+        .collect();
+    let parent_ids: HashSet<&str> = parent_words.iter().map(|w| w.entry_id.as_str()).collect();
+    // 已被子语言词条链接的父语言词条不再作为改挂候选，避免两个词条继承自同一个词
+    let mut claimed: HashSet<String> = inherited
+        .iter()
+        .filter_map(|w| w.etymology.parent_entry_id.clone())
+        .filter(|id| parent_ids.contains(id.as_str()))
+        .collect();
+
+    let relink = relink.unwrap_or(false);
+    let borrow_unmatched = borrow_unmatched.unwrap_or(false);
+    let index = ParentIndex::new(&parent_words);
+    let mut links = Vec::new();
+    for word in inherited {
+        let parent_entry_id = word.etymology.parent_entry_id.clone().unwrap_or_default();
+        let mut link = ParentLinkResolution {
+            entry_id: word.entry_id.clone(),
+            con_word_romanized: word.con_word_romanized.clone(),
+            parent_entry_id: parent_entry_id.clone(),
+            status: "unresolved".to_string(),
+            candidate: None,
+            ambiguous: 0,
+        };
+        if parent_ids.contains(parent_entry_id.as_str()) {
+            link.status = "resolved".to_string();
+            links.push(link);
+            continue;
+        }
+
+        // 除了子语言自身的拼写与释义，也用原父语言中祖词的拼写与释义匹配
+        let ancestor = old_parent_words.get(&parent_entry_id);
+        let forms: Vec<&str> = std::iter::once(word.con_word_romanized.as_str())
+            .chain(ancestor.map(|a| a.con_word_romanized.as_str()))
+            .collect();
+        let glosses: Vec<&str> = word
+            .senses
+            .iter()
+            .chain(ancestor.into_iter().flat_map(|a| a.senses.iter()))
+            .map(|s| s.gloss.as_str())
+            .collect();
+        let (candidate, ambiguous) = index.find(&forms, &glosses, &claimed);
+        match &candidate {
+            Some(c) if relink => {
+                claimed.insert(c.entry_id.clone());
+                link.status = "relinked".to_string();
+            }
+            _ if borrow_unmatched => link.status = "borrowed".to_string(),
+            _ => {}
+        }
+        link.candidate = candidate;
+        link.ambiguous = ambiguous;
+        links.push(link);
+    }
+
+    let count = |status: &str| links.iter().filter(|l| l.status == status).count();
+    let (resolved, relinked, borrowed, unresolved) =
+        (count("resolved"), count("relinked"), count("borrowed"), count("unresolved"));
+    let mut report = ReparentReport {
+        dry_run: dry_run.unwrap_or(false),
+        language_id: language_id.clone(),
+        old_parent_id: old_parent_id.clone(),
+        new_parent_id: new_parent_id.clone(),
+        links,
+        resolved,
+        relinked,
+        borrowed,
+        unresolved,
+        log_id: None,
+    };
+    if report.dry_run {
+        return Ok(report);
+    }
+
+    // 只改写状态有变化的继承词条；同步哈希针对的是原父语言中的词，一并清空，下次拉取时按内容重新比较
+    let plan: HashMap<&str, &ParentLinkResolution> = report
+        .links
+        .iter()
+        .filter(|l| l.status != "unresolved")
+        .map(|l| (l.entry_id.as_str(), l))
+        .collect();
+    let edit = |word: &mut WordEntry| {
+        let Some(link) = plan.get(word.entry_id.as_str()) else {
+            return 0;
+        };
+        if link.status == "borrowed" {
+            word.etymology.origin_type = "borrowed".to_string();
+            word.etymology.source_language_id = old_parent_id.clone();
+        } else {
+            if link.status == "relinked" {
+                word.etymology.parent_entry_id = link.candidate.as_ref().map(|c| c.entry_id.clone());
+            }
+            word.etymology.source_language_id = new_parent_id.clone();
+        }
+        word.evolution.parent_snapshot_hash = None;
+        word.evolution.last_synced_word_hash = None;
+        1
+    };
+    let mut rewrites = Rewrites::default();
+    for path in files_with_extension(&lexicon_of(&lang.path), "ndjson")? {
+        let original = fs::read(&path).map_err(|e| e.to_string())?;
+        let content = String::from_utf8_lossy(&original).to_string();
+        if let Some((updated, words, references)) = rewrite_bucket(&path, &content, &edit)? {
+            rewrites.words_updated += words;
+            rewrites.add(project, &path, "lexicon", references, Some(original), updated);
+        }
+    }
+
+    for entry in &mut ws.languages {
+        if entry.language_id == language_id {
+            entry.parent_id = new_parent_id.clone();
+        }
+    }
+
+    check_write_access(project)?;
+    if !rewrites.changes.is_empty() {
+        let log = create_snapshot(
+            project_path.clone(),
+            lang.path.clone(),
+            "reparent".to_string(),
+            old_parent_id.clone().unwrap_or_default(),
+            language_id.clone(),
+            format!(
+                "Reparent {}: {} → {}",
+                language_id,
+                old_parent_id.as_deref().unwrap_or("(root)"),
+                new_parent_id.as_deref().unwrap_or("(root)")
+            ),
+            Some(vec!["lexicon".to_string()]),
+        )?;
+        report.log_id = log.entries.last().map(|e| e.log_id.clone());
+    }
+    apply_workspace_change(project, ws_path, &ws, &rewrites, &[])?;
+    Ok(report)
+}
@@ -1,4 +1,19 @@
-use crate::models::WordEntry;
+use crate::commands::bucketing::{bucket_path, load_bucketing};
+use crate::commands::history::record_revisions;
+use crate::commands::journal::{begin, capture_before};
+use crate::commands::phonology::load_phonology;
+use crate::commands::project_lock::check_write_access;
+use crate::commands::pull_sync::sync_digest;
+use crate::commands::sca_engine::IpaGenerator;
+use crate::commands::schema::{migrate_value, newer_schema_error, SchemaKind};
+use crate::commands::search_index::{index_remove_words, index_upsert_words};
+use crate::commands::timestamp::stamp_timestamps;
+use crate::commands::watcher::{conflict_error, note_own_removal, note_own_write, word_hash};
+use crate::models::{
+    BucketingConfig, WordEntry, WordLifecycle, WordQuery, WordQueryResult, CURRENT_SCHEMA_VERSION,
+};
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tauri::command;
@@ -14,29 +29,166 @@ fn is_valid_word_entry(word: &WordEntry) -> bool {
 
 /// 安全写入：先写临时文件，再原子性 rename 替换，防止写入中途崩溃导致数据丢失
 pub fn atomic_write(path: &Path, content: &str) -> Result<(), String> {
+    check_write_access(path)?;
+    capture_before(path);
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|e| e.to_string())?;
     }
     let tmp_path = path.with_extension("tmp");
     fs::write(&tmp_path, content).map_err(|e| e.to_string())?;
+    // 先登记再替换，避免监听线程在登记之前就处理到这次写入
+    note_own_write(path, content);
     fs::rename(&tmp_path, path).map_err(|e| e.to_string())?; // 同一文件系统上为原子操作
     Ok(())
 }
 
-fn bucket_path(lexicon_dir: &Path, word: &str) -> PathBuf {
-    let first_char = word
-        .chars()
-        .next()
-        .map(|c| c.to_lowercase().next().unwrap_or('_'))
-        .unwrap_or('_');
-    if first_char.is_ascii_alphabetic() {
-        lexicon_dir.join(format!("lexicon_{}.ndjson", first_char))
-    } else {
-        lexicon_dir.join("lexicon_others.ndjson")
+/// 分桶中无法解析为 WordEntry 的行。改写分桶前会先移入隔离文件，而不是随改写被丢弃
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+pub struct QuarantineRecord {
+    pub bucket: String,
+    pub line: String,
+    pub error: String,
+}
+
+#[derive(Default)]
+pub struct ParsedBucket {
+    pub words: Vec<WordEntry>,
+    pub malformed: Vec<QuarantineRecord>,
+}
+
+/// 解析分桶中的一行，旧 schema 的词条在反序列化前先迁移到当前版本
+pub fn parse_word_line(line: &str) -> Result<WordEntry, String> {
+    let mut value: serde_json::Value = serde_json::from_str(line).map_err(|e| e.to_string())?;
+    migrate_value(SchemaKind::Lexicon, &mut value);
+    serde_json::from_value(value).map_err(|e| e.to_string())
+}
+
+/// 解析分桶文件，分别返回可解析的词条与无法解析的原始行；文件不存在时返回空结果
+pub fn parse_bucket(path: &Path) -> Result<ParsedBucket, String> {
+    let mut parsed = ParsedBucket::default();
+    if !path.exists() {
+        return Ok(parsed);
+    }
+    let bucket_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match parse_word_line(line) {
+            Ok(word) => parsed.words.push(word),
+            Err(e) => parsed.malformed.push(QuarantineRecord {
+                bucket: bucket_name.clone(),
+                line: line.to_string(),
+                error: e.to_string(),
+            }),
+        }
+    }
+    Ok(parsed)
+}
+
+/// 读取单个分桶文件中的全部词条（无法解析的行会被跳过并打印日志）
+pub fn read_bucket(path: &Path) -> Result<Vec<WordEntry>, String> {
+    let parsed = parse_bucket(path)?;
+    for record in &parsed.malformed {
+        eprintln!("解析词条失败，跳过该行 ({}): {}", record.bucket, record.error);
+    }
+    Ok(parsed.words)
+}
+
+fn quarantine_file(lexicon_dir: &Path) -> PathBuf {
+    lexicon_dir.join(".quarantine").join("quarantine.ndjson")
+}
+
+fn load_quarantine(lexicon_dir: &Path) -> Result<Vec<QuarantineRecord>, String> {
+    let path = quarantine_file(lexicon_dir);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    Ok(content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| serde_json::from_str::<QuarantineRecord>(l).ok())
+        .collect())
+}
+
+/// 将无法解析的行追加到隔离文件（同一分桶的同一行只记录一次）
+pub fn quarantine_lines(lexicon_dir: &Path, records: &[QuarantineRecord]) -> Result<(), String> {
+    if records.is_empty() {
+        return Ok(());
+    }
+    let mut existing = load_quarantine(lexicon_dir)?;
+    for record in records {
+        if !existing
+            .iter()
+            .any(|r| r.bucket == record.bucket && r.line == record.line)
+        {
+            existing.push(record.clone());
+        }
+    }
+    let lines: Result<Vec<String>, _> = existing.iter().map(serde_json::to_string).collect();
+    let content = lines.map_err(|e| e.to_string())?.join("\n") + "\n";
+    atomic_write(&quarantine_file(lexicon_dir), &content)
+}
+
+/// 分桶里有更新版本写入的词条时整桶拒绝改写，避免丢失本版本不认识的结构
+pub fn ensure_bucket_writable(bucket: &Path, parsed: &ParsedBucket) -> Result<(), String> {
+    match parsed.words.iter().map(|w| w.schema_version).max() {
+        Some(newer) if newer > CURRENT_SCHEMA_VERSION => {
+            let name = bucket
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            Err(newer_schema_error(&name, newer))
+        }
+        _ => Ok(()),
     }
 }
 
-use std::collections::HashMap;
+/// 整体改写一个分桶：先隔离无法解析的行，再按 con_word_romanized 排序写回（保证 Git diff 干净）。
+/// 分桶为空时删除文件。
+pub fn write_bucket(lexicon_dir: &Path, bucket: &Path, mut parsed: ParsedBucket) -> Result<(), String> {
+    check_write_access(bucket)?;
+    ensure_bucket_writable(bucket, &parsed)?;
+    quarantine_lines(lexicon_dir, &parsed.malformed)?;
+
+    if parsed.words.is_empty() {
+        if bucket.exists() {
+            capture_before(bucket);
+            note_own_removal(bucket);
+            fs::remove_file(bucket).map_err(|e| e.to_string())?;
+        }
+        return Ok(());
+    }
+
+    parsed
+        .words
+        .sort_by(|a, b| a.con_word_romanized.cmp(&b.con_word_romanized));
+    let lines: Result<Vec<String>, _> = parsed.words.iter().map(serde_json::to_string).collect();
+    let content = lines.map_err(|e| e.to_string())?.join("\n") + "\n";
+    atomic_write(bucket, &content)
+}
+
+/// 读取 lexicon 目录下所有分桶中的有效词条
+pub fn read_all_words(lexicon_dir: &Path) -> Result<Vec<WordEntry>, String> {
+    let mut all_words: Vec<WordEntry> = Vec::new();
+    if !lexicon_dir.exists() {
+        return Ok(all_words);
+    }
+
+    for entry in fs::read_dir(lexicon_dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().is_some_and(|e| e == "ndjson") {
+            all_words.extend(read_bucket(&path)?.into_iter().filter(is_valid_word_entry));
+        }
+    }
+    Ok(all_words)
+}
 
 #[command]
 pub fn load_all_words(
@@ -46,105 +198,567 @@ pub fn load_all_words(
     let lexicon_dir = Path::new(&project_path)
         .join(&language_path)
         .join("lexicon");
-    let mut all_words: Vec<WordEntry> = Vec::new();
+    read_all_words(&lexicon_dir)
+}
 
-    if !lexicon_dir.exists() {
-        return Ok(all_words);
+fn word_matches_query(word: &WordEntry, query: &WordQuery, text: &str, regex: Option<&Regex>) -> bool {
+    if let Some(ids) = &query.entry_ids {
+        if !ids.contains(&word.entry_id) {
+            return false;
+        }
+    }
+    if !text.is_empty() {
+        let hit = (query.text_matches_romanized && word.con_word_romanized.to_lowercase().contains(text))
+            || word.senses.iter().any(|s| {
+                s.gloss.to_lowercase().contains(text)
+                    || s.definitions.iter().any(|d| d.to_lowercase().contains(text))
+            });
+        if !hit {
+            return false;
+        }
     }
+    if let Some(pos_id) = &query.pos_id {
+        if !word.senses.iter().any(|s| &s.pos_id == pos_id) {
+            return false;
+        }
+    }
+    if !query.tags.iter().all(|t| word.metadata.tags.contains(t)) {
+        return false;
+    }
+    if let Some(origin_type) = &query.origin_type {
+        if &word.etymology.origin_type != origin_type {
+            return false;
+        }
+    }
+    if let Some(deprecated) = query.deprecated {
+        if word.evolution.is_deprecated != deprecated {
+            return false;
+        }
+    }
+    if let Some(re) = regex {
+        if !re.is_match(&word.con_word_romanized) {
+            return false;
+        }
+    }
+    true
+}
 
-    for entry in fs::read_dir(&lexicon_dir).map_err(|e| e.to_string())? {
-        let path = entry.map_err(|e| e.to_string())?.path();
-        if path.extension().map_or(false, |e| e == "ndjson") {
-            let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
-            for line in content.lines() {
-                let line = line.trim();
-                if !line.is_empty() {
-                    match serde_json::from_str::<WordEntry>(line) {
-                        Ok(word) => {
-                            if is_valid_word_entry(&word) {
-                                all_words.push(word)
-                            }
-                        }
-                        Err(e) => eprintln!("解析词条失败，跳过该行: {}", e),
-                    }
-                }
-            }
+fn first_gloss(word: &WordEntry) -> &str {
+    word.senses.first().map(|s| s.gloss.as_str()).unwrap_or("")
+}
+
+/// 对照父语言判定词条状态用到的数据：父语言词条与双方的 IPA 生成器
+struct ParentContext {
+    words: HashMap<String, WordEntry>,
+    child_ipa: Option<IpaGenerator>,
+    parent_ipa: Option<IpaGenerator>,
+}
+
+impl ParentContext {
+    fn load(project_path: &str, language_path: &str, parent_path: &str) -> Result<Self, String> {
+        let lexicon_dir = Path::new(project_path).join(parent_path).join("lexicon");
+        let words = read_all_words(&lexicon_dir)?
+            .into_iter()
+            .map(|w| (w.entry_id.clone(), w))
+            .collect();
+        let child = load_phonology(project_path.to_string(), language_path.to_string())?;
+        let parent = load_phonology(project_path.to_string(), parent_path.to_string())?;
+        Ok(Self {
+            words,
+            child_ipa: IpaGenerator::new(&child),
+            parent_ipa: IpaGenerator::new(&parent),
+        })
+    }
+
+    /// 父词条：parent_entry_id 在父语言中存在，且来源语言未指向别处
+    fn parent_of(&self, word: &WordEntry) -> Option<&WordEntry> {
+        let parent = self.words.get(word.etymology.parent_entry_id.as_deref()?)?;
+        match word.etymology.source_language_id.as_deref() {
+            Some(source) if !source.is_empty() && source != parent.language_id => None,
+            _ => Some(parent),
         }
     }
-    Ok(all_words)
+
+    /// 与父词比较同步摘要（词形、手动 IPA、义项、语义演变说明）；摘要相同时再比较
+    /// 两种语言各自生成的音位形式，音系不同也算改动
+    fn lifecycle(&self, word: &WordEntry) -> WordLifecycle {
+        let is_deprecated = word.evolution.is_deprecated;
+        let Some(parent) = self.parent_of(word) else {
+            return WordLifecycle { is_new: true, is_deprecated, is_changed: false };
+        };
+        let is_changed = !is_deprecated
+            && (sync_digest(word) != sync_digest(parent)
+                || match (&self.child_ipa, &self.parent_ipa) {
+                    (Some(c), Some(p)) => {
+                        c.generate(&word.con_word_romanized) != p.generate(&parent.con_word_romanized)
+                    }
+                    _ => false,
+                });
+        WordLifecycle { is_new: false, is_deprecated, is_changed }
+    }
 }
 
+/// 在后端完成过滤、排序和分页，前端只拿到当前页的词条
 #[command]
-pub fn save_word(
+pub fn query_words(
     project_path: String,
     language_path: String,
-    word: WordEntry,
-    old_romanized: Option<String>,
-) -> Result<(), String> {
-    if !is_valid_word_entry(&word) {
-        return Ok(());
-    }
-
+    query: WordQuery,
+) -> Result<WordQueryResult, String> {
     let lexicon_dir = Path::new(&project_path)
         .join(&language_path)
         .join("lexicon");
-    let bucket = bucket_path(&lexicon_dir, &word.con_word_romanized);
-
-    // Cross-bucket deduplication logic
-    if let Some(old_spell) = old_romanized {
-        let old_bucket = bucket_path(&lexicon_dir, &old_spell);
-        if old_bucket != bucket && old_bucket.exists() {
-            let content = fs::read_to_string(&old_bucket).unwrap_or_default();
-            let mut old_words: Vec<WordEntry> = content
-                .lines()
-                .filter(|l| !l.trim().is_empty())
-                .filter_map(|l| serde_json::from_str::<WordEntry>(l).ok())
-                .collect();
-
-            let initial_len = old_words.len();
-            old_words.retain(|w| w.entry_id != word.entry_id);
-
-            if old_words.len() < initial_len {
-                if old_words.is_empty() {
-                    let _ = fs::remove_file(&old_bucket);
-                } else {
-                    let lines: Result<Vec<String>, _> =
-                        old_words.iter().map(|w| serde_json::to_string(w)).collect();
-                    if let Ok(lines_str) = lines {
-                        let _ = atomic_write(&old_bucket, &(lines_str.join("\n") + "\n"));
+
+    let regex = match query.romanized_regex.as_deref().map(str::trim) {
+        Some(pattern) if !pattern.is_empty() => {
+            Some(Regex::new(pattern).map_err(|e| format!("Invalid romanization regex: {}", e))?)
+        }
+        _ => None,
+    };
+    let text = query.text.as_deref().unwrap_or("").trim().to_lowercase();
+    let parent = match query.parent_language_path.as_deref() {
+        Some(parent_path) if !parent_path.is_empty() => {
+            Some(ParentContext::load(&project_path, &language_path, parent_path)?)
+        }
+        _ => None,
+    };
+    if parent.is_none() && matches!(query.lifecycle.as_deref(), Some("added" | "changed")) {
+        return Err("lifecycle filter requires a parent language".to_string());
+    }
+
+    let all_words = read_all_words(&lexicon_dir)?;
+    let all_tags: Vec<String> = all_words
+        .iter()
+        .flat_map(|w| w.metadata.tags.iter().cloned())
+        .collect::<BTreeSet<String>>()
+        .into_iter()
+        .collect();
+
+    let mut words: Vec<WordEntry> = Vec::new();
+    for word in all_words {
+        if !word_matches_query(&word, &query, &text, regex.as_ref()) {
+            continue;
+        }
+        let keep = match query.lifecycle.as_deref() {
+            None | Some("") => true,
+            Some("deprecated") => word.evolution.is_deprecated,
+            Some(kind @ ("added" | "changed")) => parent.as_ref().is_some_and(|p| {
+                let flags = p.lifecycle(&word);
+                if kind == "added" { flags.is_new } else { flags.is_changed }
+            }),
+            Some(other) => return Err(format!("Unknown lifecycle filter '{}'", other)),
+        };
+        if keep {
+            words.push(word);
+        }
+    }
+
+    match query.sort_by.as_str() {
+        "gloss" => words.sort_by(|a, b| first_gloss(a).cmp(first_gloss(b))),
+        "ipa" => words.sort_by(|a, b| a.phonetic_ipa.cmp(&b.phonetic_ipa)),
+        "created_at" => words.sort_by(|a, b| a.metadata.created_at.cmp(&b.metadata.created_at)),
+        "updated_at" => words.sort_by(|a, b| a.metadata.updated_at.cmp(&b.metadata.updated_at)),
+        _ => words.sort_by(|a, b| a.con_word_romanized.cmp(&b.con_word_romanized)),
+    }
+    if query.descending {
+        words.reverse();
+    }
+
+    let total = words.len();
+    let limit = query.limit.unwrap_or(total);
+    let words: Vec<WordEntry> = words.into_iter().skip(query.offset).take(limit).collect();
+    let hashes = words.iter().map(|w| (w.entry_id.clone(), word_hash(w))).collect();
+    let lifecycle = parent
+        .as_ref()
+        .map(|p| words.iter().map(|w| (w.entry_id.clone(), p.lifecycle(w))).collect())
+        .unwrap_or_default();
+
+    Ok(WordQueryResult {
+        total,
+        words,
+        hashes,
+        lifecycle,
+        all_tags,
+    })
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct WordSaveRequest {
+    pub word: WordEntry,
+    /// 拼写修改前的 romanized，用于从旧分桶中移除该词条
+    #[serde(default)]
+    pub old_romanized: Option<String>,
+    /// 加载时该词条的哈希（load_word_hashes），磁盘上的词条已被外部修改时拒绝保存；新词条传空字符串
+    #[serde(default)]
+    pub expected_hash: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct WordDeleteRequest {
+    pub entry_id: String,
+    pub con_word_romanized: String,
+    #[serde(default)]
+    pub expected_hash: Option<String>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct BatchEntryResult {
+    pub entry_id: String,
+    pub status: String, // "saved" | "deleted" | "skipped" | "not_found" | "error"
+    #[serde(default)]
+    pub error: Option<String>,
+    /// 保存成功后词条的新哈希
+    #[serde(default)]
+    pub content_hash: Option<String>,
+}
+
+impl BatchEntryResult {
+    fn new(entry_id: &str, status: &str) -> Self {
+        Self {
+            entry_id: entry_id.to_string(),
+            status: status.to_string(),
+            error: None,
+            content_hash: None,
+        }
+    }
+
+    fn failed(entry_id: &str, error: String) -> Self {
+        Self {
+            entry_id: entry_id.to_string(),
+            status: "error".to_string(),
+            error: Some(error),
+            content_hash: None,
+        }
+    }
+}
+
+/// 单个分桶上待执行的改动
+#[derive(Default)]
+struct BucketPlan {
+    upserts: Vec<WordEntry>,
+    removals: HashSet<String>,
+    /// entry_id → 调用方期望该分桶中现有词条的哈希
+    expected: HashMap<String, String>,
+}
+
+/// 写入前检查所有计划中的期望哈希，返回冲突的 entry_id 并把它们从计划中剔除。
+/// 必须在任何分桶写入之前完成，否则跨桶移动的词条可能只写了一半
+fn take_conflicts(plans: &mut BTreeMap<PathBuf, BucketPlan>) -> Result<HashSet<String>, String> {
+    let mut conflicts = HashSet::new();
+    for (bucket, plan) in plans.iter() {
+        if plan.expected.is_empty() {
+            continue;
+        }
+        let parsed = parse_bucket(bucket)?;
+        for (entry_id, expected) in &plan.expected {
+            let current = parsed
+                .words
+                .iter()
+                .find(|w| &w.entry_id == entry_id)
+                .map(word_hash)
+                .unwrap_or_default();
+            if &current != expected {
+                conflicts.insert(entry_id.clone());
+            }
+        }
+    }
+    if !conflicts.is_empty() {
+        for plan in plans.values_mut() {
+            plan.upserts.retain(|w| !conflicts.contains(&w.entry_id));
+            plan.removals.retain(|id| !conflicts.contains(id));
+        }
+    }
+    Ok(conflicts)
+}
+
+fn entry_conflict(entry_id: &str) -> BatchEntryResult {
+    BatchEntryResult::failed(entry_id, conflict_error(&format!("entry '{}'", entry_id)))
+}
+
+/// 读取分桶、应用删除与更新/插入后写回一次。返回实际被删除的词条
+fn apply_bucket_plan(lexicon_dir: &Path, bucket: &Path, plan: BucketPlan) -> Result<Vec<WordEntry>, String> {
+    let mut parsed = parse_bucket(bucket)?;
+    ensure_bucket_writable(bucket, &parsed)?;
+    let (removed, kept): (Vec<WordEntry>, Vec<WordEntry>) = parsed
+        .words
+        .into_iter()
+        .partition(|w| plan.removals.contains(&w.entry_id));
+    parsed.words = kept;
+    if removed.is_empty() && plan.upserts.is_empty() {
+        return Ok(removed);
+    }
+    for word in plan.upserts {
+        match parsed.words.iter().position(|w| w.entry_id == word.entry_id) {
+            Some(i) => parsed.words[i] = word,
+            None => parsed.words.push(word),
+        }
+    }
+    write_bucket(lexicon_dir, bucket, parsed)?;
+    Ok(removed)
+}
+
+fn word_times(word: &mut WordEntry) -> (&mut String, &mut String) {
+    (&mut word.metadata.created_at, &mut word.metadata.updated_at)
+}
+
+/// 读取批次中词条在磁盘上的现有版本：位于目标分桶，或拼写变化前的旧分桶
+fn existing_words(
+    lexicon_dir: &Path,
+    config: &BucketingConfig,
+    requests: &[WordSaveRequest],
+) -> HashMap<String, WordEntry> {
+    let ids: HashSet<&str> = requests.iter().map(|r| r.word.entry_id.as_str()).collect();
+    let mut buckets: BTreeSet<PathBuf> = BTreeSet::new();
+    for req in requests {
+        buckets.insert(bucket_path(lexicon_dir, config, &req.word.con_word_romanized));
+        if let Some(old_spell) = &req.old_romanized {
+            buckets.insert(bucket_path(lexicon_dir, config, old_spell));
+        }
+    }
+    let mut existing = HashMap::new();
+    for bucket in buckets.iter().filter(|b| b.exists()) {
+        // 读取失败的分桶在写入阶段会报告错误
+        let Ok(parsed) = parse_bucket(bucket) else {
+            continue;
+        };
+        for word in parsed.words {
+            if ids.contains(word.entry_id.as_str()) {
+                existing.insert(word.entry_id.clone(), word);
+            }
+        }
+    }
+    existing
+}
+
+/// 批量保存：按目标分桶分组，拼写变化导致跨桶移动时同时从旧分桶移除，每个受影响的分桶只改写一次
+pub fn save_words_in_dir(lexicon_dir: &Path, mut requests: Vec<WordSaveRequest>) -> Vec<BatchEntryResult> {
+    let config = match load_bucketing(lexicon_dir) {
+        Ok(c) => c,
+        Err(e) => {
+            return requests
+                .iter()
+                .map(|r| BatchEntryResult::failed(&r.word.entry_id, e.clone()))
+                .collect()
+        }
+    };
+
+    // 来自更新 schema 的词条不写入；其余词条统一标记为当前版本
+    let mut newer: HashMap<usize, String> = HashMap::new();
+    for (i, req) in requests.iter_mut().enumerate() {
+        if req.word.schema_version > CURRENT_SCHEMA_VERSION {
+            newer.insert(i, newer_schema_error(&req.word.entry_id, req.word.schema_version));
+        } else {
+            req.word.schema_version = CURRENT_SCHEMA_VERSION;
+        }
+    }
+
+    // created_at / updated_at 由后端维护：对照磁盘上的现有词条盖时间戳
+    let existing = existing_words(lexicon_dir, &config, &requests);
+    for req in requests.iter_mut() {
+        let old = existing.get(&req.word.entry_id);
+        stamp_timestamps(&mut req.word, old, word_times);
+    }
+
+    // 同一批次中同一 entry_id 出现多次时，以最后一次为准
+    let mut last_index: HashMap<String, usize> = HashMap::new();
+    for (i, req) in requests.iter().enumerate() {
+        last_index.insert(req.word.entry_id.clone(), i);
+    }
+
+    let mut plans: BTreeMap<PathBuf, BucketPlan> = BTreeMap::new();
+    let mut touched: Vec<Option<Vec<PathBuf>>> = Vec::with_capacity(requests.len());
+    for (i, req) in requests.iter().enumerate() {
+        let word = &req.word;
+        if newer.contains_key(&i) || last_index.get(&word.entry_id) != Some(&i) || !is_valid_word_entry(word) {
+            touched.push(None);
+            continue;
+        }
+        let bucket = bucket_path(lexicon_dir, &config, &word.con_word_romanized);
+        let mut buckets = vec![bucket.clone()];
+        if let Some(old_spell) = &req.old_romanized {
+            let old_bucket = bucket_path(lexicon_dir, &config, old_spell);
+            if old_bucket != bucket {
+                plans
+                    .entry(old_bucket.clone())
+                    .or_default()
+                    .removals
+                    .insert(word.entry_id.clone());
+                buckets.push(old_bucket);
+            }
+        }
+        if let Some(expected) = &req.expected_hash {
+            // 现有词条位于旧分桶（跨桶移动时）或目标分桶中
+            let current_bucket = buckets.last().cloned().unwrap_or_else(|| bucket.clone());
+            plans
+                .entry(current_bucket)
+                .or_default()
+                .expected
+                .insert(word.entry_id.clone(), expected.clone());
+        }
+        plans.entry(bucket).or_default().upserts.push(word.clone());
+        touched.push(Some(buckets));
+    }
+
+    let conflicts = match take_conflicts(&mut plans) {
+        Ok(c) => c,
+        Err(e) => {
+            return requests
+                .iter()
+                .map(|r| BatchEntryResult::failed(&r.word.entry_id, e.clone()))
+                .collect()
+        }
+    };
+
+    let mut failures: HashMap<PathBuf, String> = HashMap::new();
+    for (bucket, plan) in plans {
+        if let Err(e) = apply_bucket_plan(lexicon_dir, &bucket, plan) {
+            failures.insert(bucket, e);
+        }
+    }
+
+    let mut saved: Vec<WordEntry> = Vec::new();
+    let mut previous: Vec<WordEntry> = Vec::new();
+    let results = requests
+        .into_iter()
+        .zip(touched)
+        .enumerate()
+        .map(|(i, (req, buckets))| {
+            if let Some(e) = newer.remove(&i) {
+                return BatchEntryResult::failed(&req.word.entry_id, e);
+            }
+            let Some(buckets) = buckets else {
+                return BatchEntryResult::new(&req.word.entry_id, "skipped");
+            };
+            if conflicts.contains(&req.word.entry_id) {
+                return entry_conflict(&req.word.entry_id);
+            }
+            match buckets.iter().find_map(|b| failures.get(b)) {
+                Some(e) => BatchEntryResult::failed(&req.word.entry_id, e.clone()),
+                None => {
+                    let mut result = BatchEntryResult::new(&req.word.entry_id, "saved");
+                    result.content_hash = Some(word_hash(&req.word));
+                    if let Some(old) = existing.get(&req.word.entry_id) {
+                        if word_hash(old) != word_hash(&req.word) {
+                            previous.push(old.clone());
+                        }
                     }
+                    saved.push(req.word);
+                    result
                 }
             }
-        }
+        })
+        .collect();
+
+    // 索引更新失败不影响词条本身的保存，下次重建即可恢复
+    if let Err(e) = index_upsert_words(lexicon_dir, &saved) {
+        eprintln!("更新反查索引失败: {}", e);
     }
+    // 修订历史同理：记录失败不回滚已完成的保存
+    if let Err(e) = record_revisions(lexicon_dir, &previous, "save") {
+        eprintln!("记录修订历史失败: {}", e);
+    }
+    results
+}
 
-    // 读取现有词条
-    let mut words: Vec<WordEntry> = if bucket.exists() {
-        let content = fs::read_to_string(&bucket).map_err(|e| e.to_string())?;
-        content
-            .lines()
-            .filter(|l| !l.trim().is_empty())
-            .filter_map(|l| serde_json::from_str::<WordEntry>(l).ok())
-            .collect()
-    } else {
-        Vec::new()
+/// 批量删除：按分桶分组，每个受影响的分桶只改写一次
+pub fn delete_words_in_dir(lexicon_dir: &Path, requests: Vec<WordDeleteRequest>) -> Vec<BatchEntryResult> {
+    let config = match load_bucketing(lexicon_dir) {
+        Ok(c) => c,
+        Err(e) => {
+            return requests
+                .iter()
+                .map(|r| BatchEntryResult::failed(&r.entry_id, e.clone()))
+                .collect()
+        }
     };
 
-    // 更新或插入
-    let pos = words.iter().position(|w| w.entry_id == word.entry_id);
-    match pos {
-        Some(i) => words[i] = word,
-        None => words.push(word),
+    let mut plans: BTreeMap<PathBuf, BucketPlan> = BTreeMap::new();
+    for req in &requests {
+        let plan = plans
+            .entry(bucket_path(lexicon_dir, &config, &req.con_word_romanized))
+            .or_default();
+        plan.removals.insert(req.entry_id.clone());
+        if let Some(expected) = &req.expected_hash {
+            plan.expected.insert(req.entry_id.clone(), expected.clone());
+        }
     }
+    let conflicts = match take_conflicts(&mut plans) {
+        Ok(c) => c,
+        Err(e) => {
+            return requests
+                .iter()
+                .map(|r| BatchEntryResult::failed(&r.entry_id, e.clone()))
+                .collect()
+        }
+    };
 
-    // 强制按 con_word_romanized 字母序排序（保证 Git diff 干净）
-    words.sort_by(|a, b| a.con_word_romanized.cmp(&b.con_word_romanized));
+    let mut removed: HashMap<String, WordEntry> = HashMap::new();
+    let mut failures: HashMap<PathBuf, String> = HashMap::new();
+    for (bucket, plan) in plans {
+        match apply_bucket_plan(lexicon_dir, &bucket, plan) {
+            Ok(words) => removed.extend(words.into_iter().map(|w| (w.entry_id.clone(), w))),
+            Err(e) => {
+                failures.insert(bucket, e);
+            }
+        }
+    }
 
-    // 序列化为 NDJSON
-    let lines: Result<Vec<String>, _> = words.iter().map(|w| serde_json::to_string(w)).collect();
-    let content = lines.map_err(|e| e.to_string())?.join("\n") + "\n";
+    let results = requests
+        .iter()
+        .map(|req| {
+            let bucket = bucket_path(lexicon_dir, &config, &req.con_word_romanized);
+            if conflicts.contains(&req.entry_id) {
+                entry_conflict(&req.entry_id)
+            } else if let Some(e) = failures.get(&bucket) {
+                BatchEntryResult::failed(&req.entry_id, e.clone())
+            } else if removed.contains_key(&req.entry_id) {
+                BatchEntryResult::new(&req.entry_id, "deleted")
+            } else {
+                BatchEntryResult::new(&req.entry_id, "not_found")
+            }
+        })
+        .collect();
 
-    atomic_write(&bucket, &content)
+    let removed_ids: Vec<String> = removed.keys().cloned().collect();
+    if let Err(e) = index_remove_words(lexicon_dir, &removed_ids) {
+        eprintln!("更新反查索引失败: {}", e);
+    }
+    // 被删除的词条进入修订历史，之后仍可恢复
+    let deleted: Vec<WordEntry> = removed.into_values().collect();
+    if let Err(e) = record_revisions(lexicon_dir, &deleted, "delete") {
+        eprintln!("记录修订历史失败: {}", e);
+    }
+    results
+}
+
+fn first_error(results: Vec<BatchEntryResult>) -> Result<(), String> {
+    match results.into_iter().find_map(|r| r.error) {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+#[command]
+pub fn save_word(
+    project_path: String,
+    language_path: String,
+    word: WordEntry,
+    old_romanized: Option<String>,
+    expected_hash: Option<String>,
+    transaction_id: Option<String>,
+) -> Result<(), String> {
+    let _journal = begin(Path::new(&project_path), "save_word", transaction_id);
+    let lexicon_dir = Path::new(&project_path)
+        .join(&language_path)
+        .join("lexicon");
+    first_error(save_words_in_dir(
+        &lexicon_dir,
+        vec![WordSaveRequest {
+            word,
+            old_romanized,
+            expected_hash,
+        }],
+    ))
 }
 
 #[command]
@@ -153,27 +767,63 @@ pub fn delete_word(
     language_path: String,
     entry_id: String,
     con_word_romanized: String,
+    expected_hash: Option<String>,
+    transaction_id: Option<String>,
 ) -> Result<(), String> {
+    let _journal = begin(Path::new(&project_path), "delete_word", transaction_id);
     let lexicon_dir = Path::new(&project_path)
         .join(&language_path)
         .join("lexicon");
-    let bucket = bucket_path(&lexicon_dir, &con_word_romanized);
-    if !bucket.exists() {
-        return Ok(());
-    }
+    first_error(delete_words_in_dir(
+        &lexicon_dir,
+        vec![WordDeleteRequest {
+            entry_id,
+            con_word_romanized,
+            expected_hash,
+        }],
+    ))
+}
 
-    let content = fs::read_to_string(&bucket).map_err(|e| e.to_string())?;
-    let words: Vec<WordEntry> = content
-        .lines()
-        .filter(|l| !l.trim().is_empty())
-        .filter_map(|l| serde_json::from_str::<WordEntry>(l).ok())
-        .filter(|w| w.entry_id != entry_id)
-        .collect();
+/// 批量保存词条（导入、SCA 批量应用、派生生成等），返回逐条结果
+#[command]
+pub fn save_words(
+    project_path: String,
+    language_path: String,
+    words: Vec<WordSaveRequest>,
+    transaction_id: Option<String>,
+) -> Result<Vec<BatchEntryResult>, String> {
+    let _journal = begin(Path::new(&project_path), "save_words", transaction_id);
+    let lexicon_dir = Path::new(&project_path)
+        .join(&language_path)
+        .join("lexicon");
+    Ok(save_words_in_dir(&lexicon_dir, words))
+}
 
-    let lines: Result<Vec<String>, _> = words.iter().map(|w| serde_json::to_string(w)).collect();
-    let new_content = lines.map_err(|e| e.to_string())?.join("\n") + "\n";
+/// 批量删除词条，返回逐条结果
+#[command]
+pub fn delete_words(
+    project_path: String,
+    language_path: String,
+    entries: Vec<WordDeleteRequest>,
+    transaction_id: Option<String>,
+) -> Result<Vec<BatchEntryResult>, String> {
+    let _journal = begin(Path::new(&project_path), "delete_words", transaction_id);
+    let lexicon_dir = Path::new(&project_path)
+        .join(&language_path)
+        .join("lexicon");
+    Ok(delete_words_in_dir(&lexicon_dir, entries))
+}
 
-    atomic_write(&bucket, &new_content)
+/// 列出因无法解析而被移入隔离区的分桶行
+#[command]
+pub fn load_quarantine_report(
+    project_path: String,
+    language_path: String,
+) -> Result<Vec<QuarantineRecord>, String> {
+    let lexicon_dir = Path::new(&project_path)
+        .join(&language_path)
+        .join("lexicon");
+    load_quarantine(&lexicon_dir)
 }
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -1,4 +1,5 @@
 pub mod lexicon;
+pub mod bucketing;
 pub mod phonology;
 pub mod grammar;
 pub mod workspace;
@@ -8,3 +9,21 @@ pub mod corpus;
 pub mod export_import;
 pub mod registry;
 pub mod oplog;
+pub mod search_index;
+pub mod integrity;
+pub mod schema;
+pub mod watcher;
+pub mod project_lock;
+pub mod timestamp;
+pub mod history;
+pub mod snapshot_store;
+pub mod journal;
+pub mod git;
+pub mod grammar_refs;
+pub mod language_ops;
+pub mod language_merge;
+pub mod phoneme_features;
+pub mod sca_engine;
+pub mod pull_sync;
+pub mod borrowing;
+pub mod cognates;
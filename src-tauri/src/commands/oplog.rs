@@ -1,10 +1,45 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tauri::command;
 
-use crate::commands::fork::copy_dir_recursive;
-use crate::commands::lexicon::atomic_write;
-use crate::models::{OperationLog, OperationLogEntry};
+use crate::commands::history::{diff_words, FieldChange};
+use crate::commands::journal::{begin, capture_before};
+use crate::commands::lexicon::{
+    atomic_write, delete_words_in_dir, read_all_words, save_words_in_dir, BatchEntryResult,
+    WordDeleteRequest, WordSaveRequest,
+};
+use crate::commands::project_lock::check_write_access;
+use crate::commands::search_index::invalidate_index;
+use crate::commands::snapshot_store::{
+    collect_garbage, load_manifest, materialize_component, migrate_legacy_snapshots,
+    remove_snapshot, snapshot_words, store_snapshot,
+};
+use crate::commands::timestamp::{normalize_timestamp, now_rfc3339};
+use crate::commands::watcher::{note_own_bytes, note_own_removal};
+use crate::commands::workspace::load_workspace;
+use crate::models::{ExtraFields, OperationLog, OperationLogEntry, WordEntry};
+
+/// One entry that differs between a snapshot and the live lexicon.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SnapshotEntryDiff {
+    pub entry_id: String,
+    pub con_word_romanized: String,
+    /// "added" = created after the snapshot, "removed" = deleted since, "modified" = changed since
+    pub status: String,
+    /// Field-level changes from the snapshot version to the live version
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SnapshotDiff {
+    pub log_id: String,
+    pub added: usize,
+    pub removed: usize,
+    pub modified: usize,
+    pub entries: Vec<SnapshotEntryDiff>,
+}
 
 // ── Helpers ──────────────────────────────────────────────
 
@@ -12,17 +47,165 @@ fn oplog_dir(language_dir: &Path) -> PathBuf {
     language_dir.join(".oplog")
 }
 
-fn oplog_file(language_dir: &Path) -> PathBuf {
+pub fn oplog_file(language_dir: &Path) -> PathBuf {
     oplog_dir(language_dir).join("oplog.json")
 }
 
-fn now_iso() -> String {
-    let d = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap_or_default();
-    let secs = d.as_secs();
-    // Simple ISO-ish timestamp: seconds since epoch (frontend will format)
-    format!("{}", secs)
+/// 读取操作日志（不存在时为默认值）。旧日志的 timestamp 是 Unix 秒，统一为 RFC 3339，下次保存时写回
+pub fn read_log(file: &Path) -> Result<OperationLog, String> {
+    if !file.exists() {
+        return Ok(OperationLog::default());
+    }
+    let content = fs::read_to_string(file).map_err(|e| e.to_string())?;
+    let mut log: OperationLog = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    for entry in &mut log.entries {
+        entry.timestamp = normalize_timestamp(&entry.timestamp);
+    }
+    Ok(log)
+}
+
+/// 子语言目录可能嵌套在父语言目录中，快照不应把它们一并包含
+fn is_language_dir(path: &Path) -> bool {
+    path.is_dir() && (path.join("phonology.json").exists() || path.join("lexicon").is_dir())
+}
+
+/// 以 . 开头的条目（.oplog、.history、lexicon/.index、lexicon/.quarantine 等）是内部数据，
+/// 不随快照保存，回滚时也原样保留
+pub fn is_internal_name(name: &str) -> bool {
+    name.starts_with('.')
+}
+
+/// 默认快照范围：语言目录中除内部目录与嵌套子语言目录以外的所有顶层条目
+fn default_components(lang_dir: &Path) -> Result<Vec<String>, String> {
+    let mut components = Vec::new();
+    for entry in fs::read_dir(lang_dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if is_internal_name(&name) || is_language_dir(&path) {
+            continue;
+        }
+        components.push(name);
+    }
+    Ok(components)
+}
+
+fn validate_component(name: &str) -> Result<(), String> {
+    if name.is_empty()
+        || is_internal_name(name)
+        || name.contains('/')
+        || name.contains('\\')
+    {
+        return Err(format!("Invalid snapshot component: '{}'", name));
+    }
+    Ok(())
+}
+
+fn remove_component(path: &Path) -> Result<(), String> {
+    if path.is_dir() {
+        fs::remove_dir_all(path).map_err(|e| e.to_string())
+    } else if path.exists() {
+        fs::remove_file(path).map_err(|e| e.to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// 删除语言目录中的文件或目录；逐个文件登记撤销日志和文件监听，回滚可以撤销，也不会被当成外部修改
+fn remove_tracked(path: &Path) -> Result<(), String> {
+    if path.is_dir() {
+        for entry in fs::read_dir(path).map_err(|e| e.to_string())? {
+            remove_tracked(&entry.map_err(|e| e.to_string())?.path())?;
+        }
+        fs::remove_dir(path).map_err(|e| e.to_string())
+    } else if path.exists() {
+        capture_before(path);
+        note_own_removal(path);
+        fs::remove_file(path).map_err(|e| e.to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// 把暂存区中的文件或目录移入语言目录，登记方式同 remove_tracked
+fn move_tracked(staged: &Path, live: &Path) -> Result<(), String> {
+    if staged.is_dir() {
+        fs::create_dir_all(live).map_err(|e| e.to_string())?;
+        for entry in fs::read_dir(staged).map_err(|e| e.to_string())? {
+            let entry = entry.map_err(|e| e.to_string())?;
+            move_tracked(&entry.path(), &live.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        let bytes = fs::read(staged).map_err(|e| e.to_string())?;
+        capture_before(live);
+        note_own_bytes(live, &bytes);
+        fs::rename(staged, live).map_err(|e| e.to_string())
+    }
+}
+
+/// 用暂存区中还原出的组件替换语言目录中的现有组件；组件目录下的内部目录保留不动
+fn replace_component(staged: &Path, live: &Path) -> Result<(), String> {
+    if live.is_dir() && !staged.is_file() {
+        for entry in fs::read_dir(live).map_err(|e| e.to_string())? {
+            let entry = entry.map_err(|e| e.to_string())?;
+            if !is_internal_name(&entry.file_name().to_string_lossy()) {
+                remove_tracked(&entry.path())?;
+            }
+        }
+    } else {
+        remove_tracked(live)?;
+    }
+    if staged.is_dir() {
+        fs::create_dir_all(live).map_err(|e| e.to_string())?;
+        for entry in fs::read_dir(staged).map_err(|e| e.to_string())? {
+            let entry = entry.map_err(|e| e.to_string())?;
+            let name = entry.file_name();
+            // 旧快照可能带有内部目录，不用它们覆盖现有的
+            if !is_internal_name(&name.to_string_lossy()) {
+                move_tracked(&entry.path(), &live.join(&name))?;
+            }
+        }
+    } else if staged.exists() {
+        move_tracked(staged, live)?;
+    }
+    Ok(())
+}
+
+/// 超出 max_snapshots 时删除最旧的快照，并清理不再被引用的 blob
+fn enforce_limit(oplog_base: &Path, log: &mut OperationLog) -> Result<(), String> {
+    while log.entries.len() > log.max_snapshots as usize {
+        let removed = log.entries.remove(0);
+        remove_snapshot(oplog_base, &removed.snapshot_dir);
+    }
+    collect_garbage(oplog_base, &log.entries)?;
+    Ok(())
+}
+
+/// Read the lexicon entries of a logged snapshot. Snapshots still in the legacy
+/// full-copy layout are read in place, so this never writes to disk.
+fn lexicon_snapshot_words(oplog_base: &Path, log: &OperationLog, log_id: &str) -> Result<Vec<WordEntry>, String> {
+    let entry = log
+        .entries
+        .iter()
+        .find(|e| e.log_id == log_id)
+        .ok_or_else(|| format!("Snapshot {} not found in log", log_id))?;
+    if !entry.components.iter().any(|c| c == "lexicon") {
+        return Err(format!("Snapshot {} does not include the lexicon", log_id));
+    }
+    match load_manifest(oplog_base, &entry.snapshot_dir) {
+        Ok(manifest) => snapshot_words(oplog_base, &manifest),
+        Err(e) => {
+            let legacy = oplog_base.join(&entry.snapshot_dir);
+            if legacy.is_dir() {
+                read_all_words(&legacy.join("lexicon"))
+            } else {
+                Err(e)
+            }
+        }
+    }
 }
 
 fn generate_log_id() -> String {
@@ -48,16 +231,34 @@ pub fn load_operation_log(
     language_path: String,
 ) -> Result<OperationLog, String> {
     let lang_dir = Path::new(&project_path).join(&language_path);
-    let file = oplog_file(&lang_dir);
-    if !file.exists() {
-        return Ok(OperationLog::default());
+    read_log(&oplog_file(&lang_dir))
+}
+
+/// Convert legacy full-copy snapshots of every language in the project to manifests + blobs.
+/// Called once when the project is opened with the write lock; returns the number converted.
+#[command]
+pub fn migrate_operation_logs(
+    project_path: String,
+    conlang_file_path: String,
+) -> Result<usize, String> {
+    let ws_config = load_workspace(conlang_file_path)?;
+    let mut migrated = 0;
+    for language in &ws_config.languages {
+        let lang_dir = Path::new(&project_path).join(&language.path);
+        let oplog_base = oplog_dir(&lang_dir);
+        if !oplog_base.exists() {
+            continue;
+        }
+        check_write_access(&oplog_base)?;
+        let log = read_log(&oplog_file(&lang_dir))?;
+        migrated += migrate_legacy_snapshots(&oplog_base, &log.entries)?;
     }
-    let content = fs::read_to_string(&file).map_err(|e| e.to_string())?;
-    let log: OperationLog = serde_json::from_str(&content).map_err(|e| e.to_string())?;
-    Ok(log)
+    Ok(migrated)
 }
 
-/// Create a snapshot of the language's lexicon directory before a destructive operation.
+/// Create a snapshot of the language directory before a destructive operation.
+/// `components` selects top-level entries of the language dir to include
+/// (default: everything except internal dot-directories and nested child languages).
 /// Returns the updated OperationLog.
 #[command]
 pub fn create_snapshot(
@@ -67,52 +268,50 @@ pub fn create_snapshot(
     source_language_id: String,
     target_language_id: String,
     description: String,
+    components: Option<Vec<String>>,
 ) -> Result<OperationLog, String> {
     let lang_dir = Path::new(&project_path).join(&language_path);
+    check_write_access(&lang_dir)?;
+    let mut components = match components {
+        Some(list) => list,
+        None => default_components(&lang_dir)?,
+    };
+    for name in &components {
+        validate_component(name)?;
+    }
+    components.sort();
+    components.dedup();
     let oplog_base = oplog_dir(&lang_dir);
     fs::create_dir_all(&oplog_base).map_err(|e| e.to_string())?;
 
     // Load existing log
     let file = oplog_file(&lang_dir);
-    let mut log: OperationLog = if file.exists() {
-        let content = fs::read_to_string(&file).map_err(|e| e.to_string())?;
-        serde_json::from_str(&content).map_err(|e| e.to_string())?
-    } else {
-        OperationLog::default()
-    };
+    let mut log = read_log(&file)?;
+    migrate_legacy_snapshots(&oplog_base, &log.entries)?;
 
     let log_id = generate_log_id();
     let snapshot_dir_name = format!("snap_{}", &log_id);
-    let snapshot_path = oplog_base.join(&snapshot_dir_name);
-    fs::create_dir_all(&snapshot_path).map_err(|e| e.to_string())?;
 
-    // Snapshot the lexicon directory
-    let lexicon_dir = lang_dir.join("lexicon");
-    if lexicon_dir.exists() {
-        let snap_lexicon = snapshot_path.join("lexicon");
-        copy_dir_recursive(&lexicon_dir, &snap_lexicon)?;
-    }
+    // Snapshot the selected components into the blob store; ones that don't exist yet
+    // are still recorded, so rolling back removes them again
+    store_snapshot(&oplog_base, &lang_dir, &snapshot_dir_name, &components)?;
 
     // Add entry
     let entry = OperationLogEntry {
         log_id: log_id.clone(),
         operation_type,
-        timestamp: now_iso(),
+        timestamp: now_rfc3339(),
         source_language_id,
         target_language_id,
         description,
         snapshot_dir: snapshot_dir_name,
+        components,
+        extra: ExtraFields::new(),
     };
     log.entries.push(entry);
 
     // Enforce max_snapshots: remove oldest entries if over limit
-    while log.entries.len() > log.max_snapshots as usize {
-        let removed = log.entries.remove(0);
-        let old_snap = oplog_base.join(&removed.snapshot_dir);
-        if old_snap.exists() {
-            let _ = fs::remove_dir_all(&old_snap);
-        }
-    }
+    enforce_limit(&oplog_base, &mut log)?;
 
     // Save log
     let content = serde_json::to_string_pretty(&log).map_err(|e| e.to_string())?;
@@ -121,7 +320,7 @@ pub fn create_snapshot(
     Ok(log)
 }
 
-/// Rollback to a specific snapshot, restoring the lexicon directory.
+/// Rollback to a specific snapshot, restoring exactly the components it covers.
 /// Returns the updated OperationLog with entries after the rolled-back one removed.
 #[command]
 pub fn rollback_to_snapshot(
@@ -130,6 +329,7 @@ pub fn rollback_to_snapshot(
     log_id: String,
 ) -> Result<OperationLog, String> {
     let lang_dir = Path::new(&project_path).join(&language_path);
+    check_write_access(&lang_dir)?;
     let oplog_base = oplog_dir(&lang_dir);
     let file = oplog_file(&lang_dir);
 
@@ -137,8 +337,8 @@ pub fn rollback_to_snapshot(
         return Err("No operation log found".to_string());
     }
 
-    let content = fs::read_to_string(&file).map_err(|e| e.to_string())?;
-    let mut log: OperationLog = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    let mut log = read_log(&file)?;
+    migrate_legacy_snapshots(&oplog_base, &log.entries)?;
 
     // Find the entry index
     let idx = log
@@ -148,33 +348,49 @@ pub fn rollback_to_snapshot(
         .ok_or_else(|| format!("Snapshot {} not found in log", log_id))?;
 
     let entry = &log.entries[idx];
-    let snapshot_path = oplog_base.join(&entry.snapshot_dir);
-    if !snapshot_path.exists() {
-        return Err(format!("Snapshot directory {} does not exist", entry.snapshot_dir));
+    let manifest = load_manifest(&oplog_base, &entry.snapshot_dir)?;
+
+    // 旧版本的默认快照包含 .history 等内部目录，回滚时跳过它们
+    let components: Vec<&String> = entry
+        .components
+        .iter()
+        .filter(|name| !is_internal_name(name))
+        .collect();
+
+    let _journal = begin(Path::new(&project_path), "rollback_to_snapshot", None);
+
+    // Stage every component first so a missing or corrupted blob leaves the language dir untouched
+    let staging = oplog_base.join(".restore");
+    remove_component(&staging)?;
+    fs::create_dir_all(&staging).map_err(|e| e.to_string())?;
+    for name in &components {
+        validate_component(name)?;
+        materialize_component(&oplog_base, &manifest, name, &staging)?;
+    }
+    for name in &components {
+        replace_component(&staging.join(name), &lang_dir.join(name))?;
     }
+    let _ = fs::remove_dir_all(&staging);
 
-    // Restore lexicon from snapshot
+    // Snapshot had an empty lexicon — make sure the dir still exists
     let lexicon_dir = lang_dir.join("lexicon");
-    if lexicon_dir.exists() {
-        fs::remove_dir_all(&lexicon_dir).map_err(|e| e.to_string())?;
-    }
-    let snap_lexicon = snapshot_path.join("lexicon");
-    if snap_lexicon.exists() {
-        copy_dir_recursive(&snap_lexicon, &lexicon_dir)?;
-    } else {
-        // Snapshot had no lexicon — ensure the dir is clean
-        fs::create_dir_all(&lexicon_dir).map_err(|e| e.to_string())?;
+    if entry.components.iter().any(|c| c == "lexicon") {
+        if !lexicon_dir.exists() {
+            fs::create_dir_all(&lexicon_dir).map_err(|e| e.to_string())?;
+        }
+        // 反查索引不在快照中，分桶回滚后需要重建
+        if let Err(e) = invalidate_index(&lexicon_dir) {
+            eprintln!("清除反查索引失败: {}", e);
+        }
     }
 
     // Remove this entry and all entries after it (they are invalidated),
-    // and clean up their snapshot directories
+    // and clean up their manifests and unreferenced blobs
     let removed_entries: Vec<OperationLogEntry> = log.entries.drain(idx..).collect();
     for removed in &removed_entries {
-        let snap = oplog_base.join(&removed.snapshot_dir);
-        if snap.exists() {
-            let _ = fs::remove_dir_all(&snap);
-        }
+        remove_snapshot(&oplog_base, &removed.snapshot_dir);
     }
+    collect_garbage(&oplog_base, &log.entries)?;
 
     // Save updated log
     let updated_content = serde_json::to_string_pretty(&log).map_err(|e| e.to_string())?;
@@ -191,30 +407,138 @@ pub fn set_max_snapshots(
     max_snapshots: u32,
 ) -> Result<OperationLog, String> {
     let lang_dir = Path::new(&project_path).join(&language_path);
+    check_write_access(&lang_dir)?;
     let oplog_base = oplog_dir(&lang_dir);
     fs::create_dir_all(&oplog_base).map_err(|e| e.to_string())?;
 
     let file = oplog_file(&lang_dir);
-    let mut log: OperationLog = if file.exists() {
-        let content = fs::read_to_string(&file).map_err(|e| e.to_string())?;
-        serde_json::from_str(&content).map_err(|e| e.to_string())?
-    } else {
-        OperationLog::default()
-    };
+    let mut log = read_log(&file)?;
+    migrate_legacy_snapshots(&oplog_base, &log.entries)?;
 
     log.max_snapshots = max_snapshots;
 
-    // Enforce new limit
-    while log.entries.len() > log.max_snapshots as usize {
-        let removed = log.entries.remove(0);
-        let old_snap = oplog_base.join(&removed.snapshot_dir);
-        if old_snap.exists() {
-            let _ = fs::remove_dir_all(&old_snap);
-        }
-    }
+    // Enforce new limit and garbage-collect blobs no remaining snapshot refers to
+    enforce_limit(&oplog_base, &mut log)?;
 
     let content = serde_json::to_string_pretty(&log).map_err(|e| e.to_string())?;
     atomic_write(&file, &content)?;
 
     Ok(log)
 }
+
+/// Compare a snapshot's lexicon with the live lexicon without changing anything.
+#[command]
+pub fn diff_snapshot(
+    project_path: String,
+    language_path: String,
+    log_id: String,
+) -> Result<SnapshotDiff, String> {
+    let lang_dir = Path::new(&project_path).join(&language_path);
+    let oplog_base = oplog_dir(&lang_dir);
+    let log = read_log(&oplog_file(&lang_dir))?;
+
+    let snapshot: BTreeMap<String, WordEntry> = lexicon_snapshot_words(&oplog_base, &log, &log_id)?
+        .into_iter()
+        .map(|w| (w.entry_id.clone(), w))
+        .collect();
+    let live: BTreeMap<String, WordEntry> = read_all_words(&lang_dir.join("lexicon"))?
+        .into_iter()
+        .map(|w| (w.entry_id.clone(), w))
+        .collect();
+
+    let mut diff = SnapshotDiff {
+        log_id,
+        added: 0,
+        removed: 0,
+        modified: 0,
+        entries: Vec::new(),
+    };
+    let mut ids: Vec<&String> = snapshot.keys().chain(live.keys()).collect();
+    ids.sort();
+    ids.dedup();
+    for id in ids {
+        let (old, new) = (snapshot.get(id), live.get(id));
+        let changes = diff_words(old, new);
+        if changes.is_empty() {
+            continue;
+        }
+        let status = match (old, new) {
+            (None, _) => {
+                diff.added += 1;
+                "added"
+            }
+            (_, None) => {
+                diff.removed += 1;
+                "removed"
+            }
+            _ => {
+                diff.modified += 1;
+                "modified"
+            }
+        };
+        diff.entries.push(SnapshotEntryDiff {
+            entry_id: id.clone(),
+            con_word_romanized: new.or(old).map(|w| w.con_word_romanized.clone()).unwrap_or_default(),
+            status: status.to_string(),
+            changes,
+        });
+    }
+    Ok(diff)
+}
+
+/// Bring selected entries back to their snapshot state without touching anything else:
+/// entries present in the snapshot are saved back, entries created after it are deleted.
+/// The operation log and newer snapshots are kept; replaced versions go to the revision history.
+#[command]
+pub fn restore_snapshot_entries(
+    project_path: String,
+    language_path: String,
+    log_id: String,
+    entry_ids: Vec<String>,
+) -> Result<Vec<BatchEntryResult>, String> {
+    let _journal = begin(Path::new(&project_path), "restore_snapshot_entries", None);
+    let lang_dir = Path::new(&project_path).join(&language_path);
+    check_write_access(&lang_dir)?;
+    let oplog_base = oplog_dir(&lang_dir);
+    let lexicon_dir = lang_dir.join("lexicon");
+    let log = read_log(&oplog_file(&lang_dir))?;
+    migrate_legacy_snapshots(&oplog_base, &log.entries)?;
+
+    let mut snapshot: HashMap<String, WordEntry> = lexicon_snapshot_words(&oplog_base, &log, &log_id)?
+        .into_iter()
+        .map(|w| (w.entry_id.clone(), w))
+        .collect();
+    let live: HashMap<String, WordEntry> = read_all_words(&lexicon_dir)?
+        .into_iter()
+        .map(|w| (w.entry_id.clone(), w))
+        .collect();
+
+    let mut saves = Vec::new();
+    let mut deletes = Vec::new();
+    let mut missing = Vec::new();
+    for id in &entry_ids {
+        match (snapshot.remove(id), live.get(id)) {
+            (Some(word), current) => saves.push(WordSaveRequest {
+                word,
+                old_romanized: current.map(|w| w.con_word_romanized.clone()),
+                expected_hash: None,
+            }),
+            (None, Some(current)) => deletes.push(WordDeleteRequest {
+                entry_id: id.clone(),
+                con_word_romanized: current.con_word_romanized.clone(),
+                expected_hash: None,
+            }),
+            (None, None) => missing.push(id.clone()),
+        }
+    }
+
+    let mut results = save_words_in_dir(&lexicon_dir, saves);
+    results.extend(delete_words_in_dir(&lexicon_dir, deletes));
+    results.extend(missing.iter().map(|id| BatchEntryResult {
+        entry_id: id.clone(),
+        status: "not_found".to_string(),
+        error: None,
+        content_hash: None,
+    }));
+    Ok(results)
+}
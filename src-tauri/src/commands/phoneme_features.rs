@@ -0,0 +1,161 @@
+// ── 音素区分特征表 ───────────────────────────────────────
+//
+// 与前端 src/data/ipa_features.ts 保持一致，供后端执行特征模式音变时使用。
+// 顺序也要一致：按特征找最接近的音素时并列取先出现者，两端结果才对得上。
+
+/// 音素 → 区分特征
+pub const PHONEME_FEATURES: &[(&str, &[&str])] = &[
+    // ── 塞音 ──
+    ("p", &["consonant", "stop", "bilabial", "voiceless"]),
+    ("b", &["consonant", "stop", "bilabial", "voiced"]),
+    ("t", &["consonant", "stop", "alveolar", "voiceless"]),
+    ("d", &["consonant", "stop", "alveolar", "voiced"]),
+    ("ʈ", &["consonant", "stop", "retroflex", "voiceless"]),
+    ("ɖ", &["consonant", "stop", "retroflex", "voiced"]),
+    ("c", &["consonant", "stop", "palatal", "voiceless"]),
+    ("ɟ", &["consonant", "stop", "palatal", "voiced"]),
+    ("k", &["consonant", "stop", "velar", "voiceless"]),
+    ("ɡ", &["consonant", "stop", "velar", "voiced"]),
+    ("g", &["consonant", "stop", "velar", "voiced"]),
+    ("q", &["consonant", "stop", "uvular", "voiceless"]),
+    ("ɢ", &["consonant", "stop", "uvular", "voiced"]),
+    ("ʔ", &["consonant", "stop", "glottal", "voiceless"]),
+
+    // ── 鼻音 ──
+    ("m", &["consonant", "nasal", "bilabial", "voiced"]),
+    ("ɱ", &["consonant", "nasal", "labiodental", "voiced"]),
+    ("n", &["consonant", "nasal", "alveolar", "voiced"]),
+    ("ɳ", &["consonant", "nasal", "retroflex", "voiced"]),
+    ("ɲ", &["consonant", "nasal", "palatal", "voiced"]),
+    ("ŋ", &["consonant", "nasal", "velar", "voiced"]),
+    ("ɴ", &["consonant", "nasal", "uvular", "voiced"]),
+
+    // ── 颤音 ──
+    ("r", &["consonant", "trill", "alveolar", "voiced"]),
+    ("ʀ", &["consonant", "trill", "uvular", "voiced"]),
+    ("ʙ", &["consonant", "trill", "bilabial", "voiced"]),
+
+    // ── 闪音 ──
+    ("ɾ", &["consonant", "tap", "alveolar", "voiced"]),
+    ("ɽ", &["consonant", "tap", "retroflex", "voiced"]),
+
+    // ── 擦音 ──
+    ("ɸ", &["consonant", "fricative", "bilabial", "voiceless"]),
+    ("β", &["consonant", "fricative", "bilabial", "voiced"]),
+    ("f", &["consonant", "fricative", "labiodental", "voiceless"]),
+    ("v", &["consonant", "fricative", "labiodental", "voiced"]),
+    ("θ", &["consonant", "fricative", "dental", "voiceless"]),
+    ("ð", &["consonant", "fricative", "dental", "voiced"]),
+    ("s", &["consonant", "fricative", "alveolar", "voiceless"]),
+    ("z", &["consonant", "fricative", "alveolar", "voiced"]),
+    ("ʃ", &["consonant", "fricative", "postalveolar", "voiceless"]),
+    ("ʒ", &["consonant", "fricative", "postalveolar", "voiced"]),
+    ("ʂ", &["consonant", "fricative", "retroflex", "voiceless"]),
+    ("ʐ", &["consonant", "fricative", "retroflex", "voiced"]),
+    ("ɕ", &["consonant", "fricative", "alveolopalatal", "voiceless"]),
+    ("ʑ", &["consonant", "fricative", "alveolopalatal", "voiced"]),
+    ("ç", &["consonant", "fricative", "palatal", "voiceless"]),
+    ("ʝ", &["consonant", "fricative", "palatal", "voiced"]),
+    ("x", &["consonant", "fricative", "velar", "voiceless"]),
+    ("ɣ", &["consonant", "fricative", "velar", "voiced"]),
+    ("χ", &["consonant", "fricative", "uvular", "voiceless"]),
+    ("ʁ", &["consonant", "fricative", "uvular", "voiced"]),
+    ("ħ", &["consonant", "fricative", "pharyngeal", "voiceless"]),
+    ("ʕ", &["consonant", "fricative", "pharyngeal", "voiced"]),
+    ("h", &["consonant", "fricative", "glottal", "voiceless"]),
+    ("ɦ", &["consonant", "fricative", "glottal", "voiced"]),
+
+    // ── 近音 ──
+    ("ʋ", &["consonant", "approximant", "labiodental", "voiced"]),
+    ("ɹ", &["consonant", "approximant", "alveolar", "voiced"]),
+    ("ɻ", &["consonant", "approximant", "retroflex", "voiced"]),
+    ("j", &["consonant", "approximant", "palatal", "voiced"]),
+    ("ɰ", &["consonant", "approximant", "velar", "voiced"]),
+    ("w", &["consonant", "approximant", "labiovelar", "voiced"]),
+
+    // ── 边音 ──
+    ("l", &["consonant", "lateral", "alveolar", "voiced"]),
+    ("ɭ", &["consonant", "lateral", "retroflex", "voiced"]),
+    ("ʎ", &["consonant", "lateral", "palatal", "voiced"]),
+    ("ʟ", &["consonant", "lateral", "velar", "voiced"]),
+
+    // ── 边擦音 ──
+    ("ɬ", &["consonant", "lateral_fricative", "alveolar", "voiceless"]),
+    ("ɮ", &["consonant", "lateral_fricative", "alveolar", "voiced"]),
+
+    // ── 塞擦音 ──
+    ("ts", &["consonant", "affricate", "alveolar", "voiceless"]),
+    ("dz", &["consonant", "affricate", "alveolar", "voiced"]),
+    ("tʃ", &["consonant", "affricate", "postalveolar", "voiceless"]),
+    ("dʒ", &["consonant", "affricate", "postalveolar", "voiced"]),
+    ("tɕ", &["consonant", "affricate", "alveolopalatal", "voiceless"]),
+    ("dʑ", &["consonant", "affricate", "alveolopalatal", "voiced"]),
+
+    // ── 元音 ──
+    ("i", &["vowel", "close", "front", "unrounded"]),
+    ("y", &["vowel", "close", "front", "rounded"]),
+    ("ɨ", &["vowel", "close", "central", "unrounded"]),
+    ("ʉ", &["vowel", "close", "central", "rounded"]),
+    ("ɯ", &["vowel", "close", "back", "unrounded"]),
+    ("u", &["vowel", "close", "back", "rounded"]),
+    ("ɪ", &["vowel", "near_close", "front", "unrounded"]),
+    ("ʏ", &["vowel", "near_close", "front", "rounded"]),
+    ("ʊ", &["vowel", "near_close", "back", "rounded"]),
+    ("e", &["vowel", "close_mid", "front", "unrounded"]),
+    ("ø", &["vowel", "close_mid", "front", "rounded"]),
+    ("ɘ", &["vowel", "close_mid", "central", "unrounded"]),
+    ("ɵ", &["vowel", "close_mid", "central", "rounded"]),
+    ("ɤ", &["vowel", "close_mid", "back", "unrounded"]),
+    ("o", &["vowel", "close_mid", "back", "rounded"]),
+    ("ə", &["vowel", "mid", "central", "unrounded"]),
+    ("ɛ", &["vowel", "open_mid", "front", "unrounded"]),
+    ("œ", &["vowel", "open_mid", "front", "rounded"]),
+    ("ɜ", &["vowel", "open_mid", "central", "unrounded"]),
+    ("ɞ", &["vowel", "open_mid", "central", "rounded"]),
+    ("ʌ", &["vowel", "open_mid", "back", "unrounded"]),
+    ("ɔ", &["vowel", "open_mid", "back", "rounded"]),
+    ("æ", &["vowel", "near_open", "front", "unrounded"]),
+    ("ɐ", &["vowel", "near_open", "central", "unrounded"]),
+    ("a", &["vowel", "open", "front", "unrounded"]),
+    ("ɶ", &["vowel", "open", "front", "rounded"]),
+    ("ä", &["vowel", "open", "central", "unrounded"]),
+    ("ɑ", &["vowel", "open", "back", "unrounded"]),
+    ("ɒ", &["vowel", "open", "back", "rounded"]),
+];
+
+/// 查询音素的特征；未收录的音素返回 None
+pub fn features_of(phoneme: &str) -> Option<&'static [&'static str]> {
+    PHONEME_FEATURES
+        .iter()
+        .find(|(p, _)| *p == phoneme)
+        .map(|(_, features)| *features)
+}
+
+/// 两组特征的 Jaccard 相似度
+pub fn similarity(a: &[&str], b: &[&str]) -> f64 {
+    let intersection = a.iter().filter(|f| b.contains(f)).count();
+    let union = a.len() + b.len() - intersection;
+    if union == 0 {
+        0.0
+    } else {
+        intersection as f64 / union as f64
+    }
+}
+
+/// 在候选音素中找与给定特征最接近的一个；并列时取先出现者
+pub fn nearest_phoneme<'a>(
+    features: &[&str],
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let mut best: Option<(&str, f64)> = None;
+    for candidate in candidates {
+        let Some(candidate_features) = features_of(candidate) else {
+            continue;
+        };
+        let score = similarity(features, candidate_features);
+        if best.is_none_or(|(_, s)| score > s) {
+            best = Some((candidate, score));
+        }
+    }
+    best.map(|(p, _)| p)
+}
@@ -1,18 +1,23 @@
 use std::fs;
 use std::path::Path;
 use tauri::command;
-use crate::models::{PhonologyConfig, PhonemeInventory, Phonotactics, VowelHarmony, ToneSystem};
+use crate::models::{CURRENT_SCHEMA_VERSION, ExtraFields, PhonologyConfig, PhonemeInventory, Phonotactics, VowelHarmony, ToneSystem};
+use crate::commands::journal::begin;
 use crate::commands::lexicon::atomic_write;
+use crate::commands::watcher::{check_expected_hash, content_hash};
+use crate::commands::schema::{ensure_writable, parse_versioned, SchemaKind};
 
 #[command]
 pub fn load_phonology(project_path: String, language_path: String) -> Result<PhonologyConfig, String> {
     let path = Path::new(&project_path).join(&language_path).join("phonology.json");
     if !path.exists() {
         return Ok(PhonologyConfig {
+            schema_version: CURRENT_SCHEMA_VERSION,
             language_id: "lang_proto".to_string(),
             phoneme_inventory: PhonemeInventory {
                 consonants: Vec::new(),
                 vowels: Vec::new(),
+                extra: ExtraFields::new(),
             },
             romanization_maps: Vec::new(),
             phonotactics: Phonotactics {
@@ -21,19 +26,33 @@ pub fn load_phonology(project_path: String, language_path: String) -> Result<Pho
                 blacklist_patterns: Vec::new(),
                 vowel_harmony: VowelHarmony::default(),
                 tone_system: ToneSystem::default(),
+                extra: ExtraFields::new(),
             },
             allophony_rules: Vec::new(),
+            extra: ExtraFields::new(),
         });
     }
     let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
-    serde_json::from_str(&content).map_err(|e| e.to_string())
+    parse_versioned(SchemaKind::Phonology, &content)
 }
 
 #[command]
-pub fn save_phonology(project_path: String, language_path: String, config: PhonologyConfig) -> Result<(), String> {
+pub fn save_phonology(
+    project_path: String,
+    language_path: String,
+    config: PhonologyConfig,
+    expected_hash: Option<String>,
+    transaction_id: Option<String>,
+) -> Result<String, String> {
+    let _journal = begin(Path::new(&project_path), "save_phonology", transaction_id);
     let dir = Path::new(&project_path).join(&language_path);
     fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
     let path = dir.join("phonology.json");
+    check_expected_hash(&path, expected_hash.as_deref())?;
+    ensure_writable(&path, config.schema_version)?;
+    let mut config = config;
+    config.schema_version = CURRENT_SCHEMA_VERSION;
     let content = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
-    atomic_write(&path, &content)
+    atomic_write(&path, &content)?;
+    Ok(content_hash(content.as_bytes()))
 }
@@ -0,0 +1,416 @@
+use crate::models::ExtraFields;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{command, AppHandle, Emitter};
+
+// ── 跨进程项目锁 ─────────────────────────────────────────
+//
+// 打开项目时在 .conlang 文件旁创建 `<name>.conlang.lock`，记录持有者的 PID、主机名和心跳时间。
+// 持有者每 HEARTBEAT_INTERVAL 更新一次心跳；心跳超过 STALE_AFTER 未更新，
+// 或持有者与本机相同且进程已不存在时，视为残留锁，可以直接接管。
+// 本机或持有者的主机名无法确定时（gethostname 失败、旧锁文件中的 "unknown-host"）不做判断，
+// 锁一律视为有效，只能由用户显式接管（force）。
+// 拿不到锁的实例以只读方式打开项目：所有写入（atomic_write 及删除类命令）都返回 "project locked by …" 错误。
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+const STALE_AFTER_SECS: u64 = 120;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectLockInfo {
+    /// 持有者实例的唯一 ID（同一进程内不变）
+    pub owner_id: String,
+    pub pid: u32,
+    /// 持有者的主机名，无法确定时为空
+    pub hostname: String,
+    /// Unix 秒
+    pub acquired_at: u64,
+    pub heartbeat_at: u64,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectLockStatus {
+    pub conlang_file_path: String,
+    pub mode: String, // "owned" | "read_only" | "unlocked"
+    /// 当前锁文件中的持有者
+    pub holder: Option<ProjectLockInfo>,
+    /// 本次获取时接管了残留锁
+    #[serde(default)]
+    pub replaced_stale: bool,
+}
+
+struct OpenProject {
+    conlang_file: PathBuf,
+    lock_file: PathBuf,
+    /// 项目目录：原始路径与规范化路径，写入检查时两者任一为前缀即视为属于该项目
+    dirs: Vec<PathBuf>,
+    /// None = 本实例持有锁；Some = 只读，记录锁的持有者
+    locked_by: Option<ProjectLockInfo>,
+}
+
+fn open_projects() -> &'static Mutex<Vec<OpenProject>> {
+    static OPEN_PROJECTS: OnceLock<Mutex<Vec<OpenProject>>> = OnceLock::new();
+    OPEN_PROJECTS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+fn owner_id() -> &'static str {
+    static OWNER_ID: OnceLock<String> = OnceLock::new();
+    OWNER_ID.get_or_init(|| {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .subsec_nanos();
+        format!("{}-{:08x}", std::process::id(), nanos)
+    })
+}
+
+/// 旧版本在取不到主机名时写入锁文件的占位值
+const UNKNOWN_HOST: &str = "unknown-host";
+
+/// 系统调用 gethostname 得到的本机主机名；失败或为空时返回 None
+pub fn hostname() -> Option<String> {
+    ::hostname::get()
+        .ok()
+        .map(|name| name.to_string_lossy().trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// 只有 Linux 能廉价地判断任意 PID 是否存活；其他平台返回 None，仅依赖心跳超时
+fn process_alive(pid: u32) -> Option<bool> {
+    if cfg!(target_os = "linux") {
+        Some(Path::new(&format!("/proc/{}", pid)).exists())
+    } else {
+        None
+    }
+}
+
+fn lock_path(conlang_file: &Path) -> PathBuf {
+    PathBuf::from(format!("{}.lock", conlang_file.to_string_lossy()))
+}
+
+fn lock_age_secs(path: &Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.elapsed().ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(u64::MAX)
+}
+
+fn read_lock(path: &Path) -> Option<ProjectLockInfo> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn is_stale(info: &ProjectLockInfo) -> bool {
+    let Some(local) = hostname() else {
+        return false;
+    };
+    if info.hostname.is_empty() || info.hostname == UNKNOWN_HOST {
+        return false;
+    }
+    if now_secs().saturating_sub(info.heartbeat_at) > STALE_AFTER_SECS {
+        return true;
+    }
+    info.hostname == local && process_alive(info.pid) == Some(false)
+}
+
+fn new_lock_info() -> ProjectLockInfo {
+    let now = now_secs();
+    ProjectLockInfo {
+        owner_id: owner_id().to_string(),
+        pid: std::process::id(),
+        hostname: hostname().unwrap_or_default(),
+        acquired_at: now,
+        heartbeat_at: now,
+        extra: ExtraFields::new(),
+    }
+}
+
+/// create_new 保证多个进程同时创建时只有一个成功
+fn try_create_lock(path: &Path) -> Result<Option<ProjectLockInfo>, String> {
+    let info = new_lock_info();
+    let content = serde_json::to_string_pretty(&info).map_err(|e| e.to_string())?;
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(mut file) => {
+            file.write_all(content.as_bytes()).map_err(|e| e.to_string())?;
+            Ok(Some(info))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(None),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// 锁文件不走 atomic_write（后者会反过来检查锁），用同样的 tmp + rename 方式更新心跳
+fn rewrite_lock(path: &Path, info: &ProjectLockInfo) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(info).map_err(|e| e.to_string())?;
+    let tmp = PathBuf::from(format!("{}.tmp", path.to_string_lossy()));
+    fs::write(&tmp, content).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
+
+pub fn locked_error(info: &ProjectLockInfo) -> String {
+    format!(
+        "Project locked by {} (pid {}, last heartbeat {}s ago); it is open read-only in this window",
+        info.hostname,
+        info.pid,
+        now_secs().saturating_sub(info.heartbeat_at)
+    )
+}
+
+fn status_of(project: &OpenProject) -> ProjectLockStatus {
+    ProjectLockStatus {
+        conlang_file_path: project.conlang_file.to_string_lossy().to_string(),
+        mode: if project.locked_by.is_some() { "read_only" } else { "owned" }.to_string(),
+        holder: project.locked_by.clone().or_else(|| read_lock(&project.lock_file)),
+        replaced_stale: false,
+    }
+}
+
+fn release(project: &OpenProject) {
+    if project.locked_by.is_none()
+        && read_lock(&project.lock_file).is_some_and(|i| i.owner_id == owner_id())
+    {
+        let _ = fs::remove_file(&project.lock_file);
+    }
+}
+
+/// 锁模式（持有 / 只读）发生变化时发送给前端的事件，负载为 ProjectLockStatus
+pub const LOCK_CHANGED_EVENT: &str = "project-lock-changed";
+
+fn app_handle() -> &'static OnceLock<AppHandle> {
+    static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+    &APP_HANDLE
+}
+
+/// 应用启动时调用，之后心跳线程和写入检查才能通知前端锁模式的变化
+pub fn set_app_handle(app: AppHandle) {
+    let _ = app_handle().set(app);
+}
+
+fn notify_mode_change(status: ProjectLockStatus) {
+    if let Some(app) = app_handle().get() {
+        if let Err(e) = app.emit(LOCK_CHANGED_EVENT, status) {
+            eprintln!("发送项目锁变化事件失败: {}", e);
+        }
+    }
+}
+
+/// 后台心跳线程，整个进程只启动一次。
+/// 持有锁的项目更新心跳；只读项目重新尝试获取锁（持有者退出或心跳超时后即可接管）
+fn start_heartbeat() {
+    static STARTED: OnceLock<()> = OnceLock::new();
+    STARTED.get_or_init(|| {
+        std::thread::spawn(|| loop {
+            std::thread::sleep(HEARTBEAT_INTERVAL);
+            let Ok(mut projects) = open_projects().lock() else {
+                continue;
+            };
+            let mut changed = Vec::new();
+            for project in projects.iter_mut() {
+                let was_read_only = project.locked_by.is_some();
+                if was_read_only {
+                    match try_acquire(&project.lock_file, false) {
+                        Ok((locked_by, _)) => project.locked_by = locked_by,
+                        Err(e) => eprintln!("重新获取项目锁失败: {}", e),
+                    }
+                } else {
+                    match read_lock(&project.lock_file) {
+                        Some(mut info) if info.owner_id == owner_id() => {
+                            info.heartbeat_at = now_secs();
+                            if let Err(e) = rewrite_lock(&project.lock_file, &info) {
+                                eprintln!("更新项目锁心跳失败: {}", e);
+                            }
+                        }
+                        // 锁被其他实例接管（例如本机休眠导致心跳超时）：降级为只读
+                        Some(other) => project.locked_by = Some(other),
+                        None => {
+                            let _ = try_create_lock(&project.lock_file);
+                        }
+                    }
+                }
+                if project.locked_by.is_some() != was_read_only {
+                    changed.push(status_of(project));
+                }
+            }
+            drop(projects);
+            for status in changed {
+                notify_mode_change(status);
+            }
+        });
+    });
+}
+
+/// 尝试取得锁文件，返回 (锁的持有者, 是否接管了残留锁)；持有者为 None 表示本实例取得了锁
+fn try_acquire(lock_file: &Path, force: bool) -> Result<(Option<ProjectLockInfo>, bool), String> {
+    let mut replaced_stale = false;
+    let mut outcome: Option<Option<ProjectLockInfo>> = None; // Some(None) = 持有锁，Some(Some(x)) = 被 x 持有
+    for _ in 0..3 {
+        if try_create_lock(lock_file)?.is_some() {
+            outcome = Some(None);
+            break;
+        }
+        match read_lock(lock_file) {
+            Some(mut info) if info.owner_id == owner_id() => {
+                info.heartbeat_at = now_secs();
+                rewrite_lock(lock_file, &info)?;
+                outcome = Some(None);
+                break;
+            }
+            Some(info) if !force && !is_stale(&info) => {
+                outcome = Some(Some(info));
+                break;
+            }
+            // 内容无法解析且很新：可能是其他实例刚创建、尚未写完，下一轮再读
+            None if !force && lock_age_secs(lock_file) <= STALE_AFTER_SECS => {
+                std::thread::sleep(Duration::from_millis(50));
+            }
+            // 残留锁、被强制接管，或早已损坏的锁文件：删除后重试
+            _ => {
+                let _ = fs::remove_file(lock_file);
+                replaced_stale = true;
+            }
+        }
+    }
+    let locked_by = match outcome {
+        Some(holder) => holder,
+        None => Some(read_lock(lock_file).ok_or_else(|| {
+            format!("Could not acquire project lock {}", lock_file.display())
+        })?),
+    };
+    Ok((locked_by, replaced_stale))
+}
+
+/// 获取项目锁。同一进程只持有一个项目的锁，打开新项目时释放之前的锁。
+/// 锁被其他存活实例持有时以只读模式打开；`force` 为 true 时无条件接管
+pub fn acquire_lock(conlang_file_path: &str, force: bool) -> Result<ProjectLockStatus, String> {
+    let conlang_file = PathBuf::from(conlang_file_path);
+    let lock_file = lock_path(&conlang_file);
+    let project_dir = conlang_file
+        .parent()
+        .ok_or_else(|| "Cannot determine project directory".to_string())?
+        .to_path_buf();
+    let mut dirs = vec![project_dir.clone()];
+    if let Ok(canonical) = fs::canonicalize(&project_dir) {
+        if canonical != project_dir {
+            dirs.push(canonical);
+        }
+    }
+
+    let mut projects = open_projects().lock().map_err(|e| e.to_string())?;
+    for other in projects.iter().filter(|p| p.conlang_file != conlang_file) {
+        release(other);
+    }
+    projects.retain(|p| p.conlang_file == conlang_file);
+
+    let (locked_by, replaced_stale) = try_acquire(&lock_file, force)?;
+
+    projects.retain(|p| p.conlang_file != conlang_file);
+    projects.push(OpenProject {
+        conlang_file,
+        lock_file,
+        dirs,
+        locked_by,
+    });
+    start_heartbeat();
+    let project = projects.last().ok_or_else(|| "Lock state lost".to_string())?;
+    let mut status = status_of(project);
+    status.replaced_stale = replaced_stale;
+    Ok(status)
+}
+
+/// 写入前检查：路径属于某个以只读方式打开的项目时返回 "project locked by …" 错误。
+/// 持有锁时顺便确认锁文件仍属于本实例，被接管则降级为只读。
+/// 不属于任何已打开项目的路径（应用数据目录、新建项目等）不受限制
+pub fn check_write_access(path: &Path) -> Result<(), String> {
+    let mut projects = open_projects().lock().map_err(|e| e.to_string())?;
+    let Some(project) = projects
+        .iter_mut()
+        .find(|p| p.dirs.iter().any(|d| path.starts_with(d)))
+    else {
+        return Ok(());
+    };
+    if path == project.lock_file {
+        return Ok(());
+    }
+    if project.locked_by.is_none() {
+        match read_lock(&project.lock_file) {
+            Some(info) if info.owner_id != owner_id() => project.locked_by = Some(info),
+            Some(_) => {}
+            None => {
+                // 锁文件被外部删除：重新创建；创建失败说明已被其他实例抢先
+                if try_create_lock(&project.lock_file)?.is_none() {
+                    project.locked_by = read_lock(&project.lock_file);
+                }
+            }
+        }
+        // 刚刚降级为只读：通知前端切换到只读模式
+        if let Some(info) = project.locked_by.clone() {
+            let status = status_of(project);
+            drop(projects);
+            notify_mode_change(status);
+            return Err(locked_error(&info));
+        }
+    }
+    match &project.locked_by {
+        Some(info) => Err(locked_error(info)),
+        None => Ok(()),
+    }
+}
+
+/// 应用退出时调用，释放本实例持有的所有锁
+pub fn release_all() {
+    if let Ok(mut projects) = open_projects().lock() {
+        for project in projects.iter() {
+            release(project);
+        }
+        projects.clear();
+    }
+}
+
+/// 打开项目时获取锁（注册表流程中已自动调用，此命令用于启动时自动打开和“强制接管”）
+#[command]
+pub fn acquire_project_lock(conlang_file_path: String, force: Option<bool>) -> Result<ProjectLockStatus, String> {
+    acquire_lock(&conlang_file_path, force.unwrap_or(false))
+}
+
+/// 关闭项目时释放锁
+#[command]
+pub fn release_project_lock(conlang_file_path: String) -> Result<(), String> {
+    let conlang_file = PathBuf::from(&conlang_file_path);
+    let mut projects = open_projects().lock().map_err(|e| e.to_string())?;
+    for project in projects.iter().filter(|p| p.conlang_file == conlang_file) {
+        release(project);
+    }
+    projects.retain(|p| p.conlang_file != conlang_file);
+    Ok(())
+}
+
+/// 查询项目当前的锁状态；项目未通过本实例打开时报告锁文件中的持有者
+#[command]
+pub fn get_project_lock_status(conlang_file_path: String) -> Result<ProjectLockStatus, String> {
+    let conlang_file = PathBuf::from(&conlang_file_path);
+    let projects = open_projects().lock().map_err(|e| e.to_string())?;
+    if let Some(project) = projects.iter().find(|p| p.conlang_file == conlang_file) {
+        return Ok(status_of(project));
+    }
+    let holder = read_lock(&lock_path(&conlang_file)).filter(|i| !is_stale(i));
+    Ok(ProjectLockStatus {
+        conlang_file_path,
+        mode: if holder.is_some() { "read_only" } else { "unlocked" }.to_string(),
+        holder,
+        replaced_stale: false,
+    })
+}
@@ -0,0 +1,340 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use tauri::command;
+
+use crate::commands::fork::load_language_entry_ids;
+use crate::commands::language_merge::unique_id;
+use crate::commands::lexicon::{read_all_words, save_words, WordSaveRequest};
+use crate::commands::oplog::create_snapshot;
+use crate::commands::phonology::load_phonology;
+use crate::commands::sca::load_sca;
+use crate::commands::sca_engine::{build_macros, fired_rules, IpaGenerator, SoundChanger};
+use crate::commands::watcher::content_hash;
+use crate::commands::workspace::load_workspace;
+use crate::models::WordEntry;
+
+// ── 从父语言拉取同步 ─────────────────────────────────────
+//
+// 对父语言的每个词条执行子语言的音变得到演变形式，再与子语言中的继承词条做三方比较：
+//   基准   上次同步时记录的 evolution.parent_snapshot_hash（父词）与 last_synced_word_hash（子词）
+//   new             子语言中没有对应词条，加入演变形式
+//   unchanged       两边自上次同步都没变
+//   parent_changed  只有父词变了，用新的演变形式覆盖子词
+//   child_changed   只有子词变了，保留子词
+//   both_changed    两边都变了，保留子词并作为冲突返回
+// 父语言中已删除的词条在子语言中标记为弃用。
+// 从未同步过（或仍是旧版前端 djb2 哈希）的词条没有基准：子词与父词原样相同视为只有父词变了，
+// 与演变形式相同视为未变，其余按冲突处理。
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PullSyncEntry {
+    /// 子语言中的词条 ID；新词条为即将写入的 ID
+    pub entry_id: String,
+    pub parent_entry_id: String,
+    pub status: String, // "new" | "parent_changed" | "child_changed" | "both_changed" | "deprecated"
+    pub parent_form: String,
+    /// 子语言中现有的词形，新词条为空
+    #[serde(default)]
+    pub child_form: Option<String>,
+    /// 父词经音变后的词形，弃用词条为空
+    #[serde(default)]
+    pub evolved_form: Option<String>,
+}
+
+/// 父词与子词自上次同步后都改动过
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PullSyncConflict {
+    pub parent: WordEntry,
+    /// 保留的子语言版本
+    pub child: WordEntry,
+    /// 父词按当前音变规则演变出的版本
+    pub evolved: WordEntry,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PullSyncReport {
+    pub dry_run: bool,
+    pub language_id: String,
+    pub parent_id: String,
+    pub added: usize,
+    pub unchanged: usize,
+    pub updated: usize,
+    pub child_changed: usize,
+    pub deprecated: usize,
+    /// 除 unchanged 以外的全部词条
+    pub entries: Vec<PullSyncEntry>,
+    pub conflicts: Vec<PullSyncConflict>,
+    /// 执行前创建的快照，预览或无改动时为空
+    #[serde(default)]
+    pub log_id: Option<String>,
+}
+
+/// 同步比较的内容摘要（SHA-256）：词形、手动 IPA、义项与语义演变说明，
+/// 不含 ID、词源、同步状态与时间戳
+pub fn sync_digest(word: &WordEntry) -> String {
+    let payload = serde_json::json!({
+        "con_word_romanized": word.con_word_romanized,
+        "phonetic_ipa": if word.phonetic_override { word.phonetic_ipa.as_str() } else { "" },
+        "phonetic_override": word.phonetic_override,
+        "senses": word.senses,
+        "semantic_shift_note": word.etymology.semantic_shift_note,
+    });
+    content_hash(payload.to_string().as_bytes())
+}
+
+/// 旧版前端写入的是 8 位十六进制 djb2 哈希，不能作为三方比较的基准
+fn is_sync_digest(hash: Option<&str>) -> bool {
+    hash.is_some_and(|h| h.len() == 64 && h.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+/// 子词是否继承自该父语言：有 parent_entry_id、不是借词，且来源语言未指向别处
+fn inherited_from(word: &WordEntry, parent_id: &str) -> Option<String> {
+    let link = word.etymology.parent_entry_id.clone()?;
+    if word.etymology.origin_type == "borrowed" {
+        return None;
+    }
+    match word.etymology.source_language_id.as_deref() {
+        Some(source) if source != parent_id => None,
+        _ => Some(link),
+    }
+}
+
+/// 父词按子语言音变演变出的词条，并记下本次同步的基准
+fn evolve(
+    parent: &WordEntry,
+    child_id: &str,
+    parent_id: &str,
+    changer: &SoundChanger,
+    ipa: Option<&IpaGenerator>,
+) -> WordEntry {
+    let (result, steps) = changer.apply(&parent.con_word_romanized);
+    let mut word = parent.clone();
+    word.language_id = child_id.to_string();
+    word.con_word_romanized = result.trim().to_string();
+    word.phonetic_override = false;
+    word.phonetic_ipa = ipa.map(|g| g.generate(&word.con_word_romanized)).unwrap_or_default();
+    word.etymology.origin_type = "evolved".to_string();
+    word.etymology.parent_entry_id = Some(parent.entry_id.clone());
+    word.etymology.source_language_id = Some(parent_id.to_string());
+    word.etymology.applied_sound_changes = fired_rules(&steps);
+    word.evolution.is_deprecated = false;
+    word.evolution.deprecated_since_language_id = None;
+    word.evolution.parent_snapshot_hash = Some(sync_digest(parent));
+    word.evolution.last_synced_word_hash = Some(sync_digest(&word));
+    word
+}
+
+/// 只更新子词的同步基准，内容保持不变
+fn restamp(child: &WordEntry, parent: &WordEntry) -> WordEntry {
+    let mut word = child.clone();
+    word.evolution.parent_snapshot_hash = Some(sync_digest(parent));
+    word.evolution.last_synced_word_hash = Some(sync_digest(child));
+    word
+}
+
+#[command]
+pub fn pull_from_parent(
+    project_path: String,
+    conlang_file_path: String,
+    language_id: String,
+    dry_run: Option<bool>,
+) -> Result<PullSyncReport, String> {
+    let project = Path::new(&project_path);
+    let ws = load_workspace(conlang_file_path)?;
+    let language = ws
+        .languages
+        .iter()
+        .find(|l| l.language_id == language_id)
+        .ok_or_else(|| format!("Language '{}' not found in workspace", language_id))?;
+    let parent_id = language
+        .parent_id
+        .clone()
+        .ok_or_else(|| format!("Language '{}' has no parent language", language_id))?;
+    let parent = ws
+        .languages
+        .iter()
+        .find(|l| l.language_id == parent_id)
+        .ok_or_else(|| format!("Parent language '{}' not found in workspace", parent_id))?;
+
+    let phonology = load_phonology(project_path.clone(), language.path.clone())?;
+    let sca = load_sca(project_path.clone(), language.path.clone())?;
+    let (changer, _) = SoundChanger::new(&sca.rule_sets, &build_macros(&phonology));
+    let ipa = IpaGenerator::new(&phonology);
+
+    let parent_words = read_all_words(&project.join(&parent.path).join("lexicon"))?;
+    // 无效词条（缺义项等）不参与同步，但仍算作存在，不能据此把子词标记为弃用
+    let parent_ids = load_language_entry_ids(project, &parent.path)?;
+    let child_words = read_all_words(&project.join(&language.path).join("lexicon"))?;
+
+    let mut child_by_link: HashMap<String, &WordEntry> = HashMap::new();
+    for word in &child_words {
+        if let Some(link) = inherited_from(word, &parent_id) {
+            child_by_link.entry(link).or_insert(word);
+        }
+    }
+    // 旧数据可能缺少 parent_entry_id，只靠与父词相同的 entry_id 关联
+    for word in &child_words {
+        if word.etymology.parent_entry_id.is_none() && parent_ids.contains(&word.entry_id) {
+            child_by_link.entry(word.entry_id.clone()).or_insert(word);
+        }
+    }
+    let mut taken: HashSet<String> = child_words.iter().map(|w| w.entry_id.clone()).collect();
+
+    let mut report = PullSyncReport {
+        dry_run: dry_run.unwrap_or(false),
+        language_id: language_id.clone(),
+        parent_id: parent_id.clone(),
+        added: 0,
+        unchanged: 0,
+        updated: 0,
+        child_changed: 0,
+        deprecated: 0,
+        entries: Vec::new(),
+        conflicts: Vec::new(),
+        log_id: None,
+    };
+    let mut writes: Vec<WordSaveRequest> = Vec::new();
+
+    for parent_word in &parent_words {
+        let evolved = evolve(parent_word, &language_id, &parent_id, &changer, ipa.as_ref());
+        let entry = |entry_id: &str, status: &str, child: Option<&WordEntry>| PullSyncEntry {
+            entry_id: entry_id.to_string(),
+            parent_entry_id: parent_word.entry_id.clone(),
+            status: status.to_string(),
+            parent_form: parent_word.con_word_romanized.clone(),
+            child_form: child.map(|c| c.con_word_romanized.clone()),
+            evolved_form: Some(evolved.con_word_romanized.clone()),
+        };
+
+        let Some(child) = child_by_link.get(&parent_word.entry_id).copied() else {
+            let mut word = evolved.clone();
+            word.entry_id = unique_id(&parent_word.entry_id, &language_id, &mut taken);
+            report.added += 1;
+            report.entries.push(entry(&word.entry_id, "new", None));
+            writes.push(WordSaveRequest {
+                word,
+                old_romanized: None,
+                expected_hash: Some(String::new()),
+            });
+            continue;
+        };
+
+        let child_digest = sync_digest(child);
+        let evolved_digest = sync_digest(&evolved);
+        let (parent_changed, child_changed) = if is_sync_digest(child.evolution.parent_snapshot_hash.as_deref())
+            && is_sync_digest(child.evolution.last_synced_word_hash.as_deref())
+        {
+            (
+                child.evolution.parent_snapshot_hash.as_deref() != Some(sync_digest(parent_word).as_str()),
+                child.evolution.last_synced_word_hash.as_deref() != Some(child_digest.as_str()),
+            )
+        } else if child_digest == evolved_digest {
+            (false, false)
+        } else if child_digest == sync_digest(parent_word) {
+            (true, false)
+        } else {
+            (true, true)
+        };
+
+        // 两边改成了同一个结果，视同未变
+        let converged = child_digest == evolved_digest;
+        if converged || !(parent_changed || child_changed) {
+            report.unchanged += 1;
+            let stamped = restamp(child, parent_word);
+            if stamped.evolution.parent_snapshot_hash != child.evolution.parent_snapshot_hash
+                || stamped.evolution.last_synced_word_hash != child.evolution.last_synced_word_hash
+            {
+                writes.push(WordSaveRequest {
+                    word: stamped,
+                    old_romanized: None,
+                    expected_hash: None,
+                });
+            }
+        } else if parent_changed && child_changed {
+            report.entries.push(entry(&child.entry_id, "both_changed", Some(child)));
+            report.conflicts.push(PullSyncConflict {
+                parent: parent_word.clone(),
+                child: child.clone(),
+                evolved,
+            });
+        } else if parent_changed {
+            let mut word = evolved.clone();
+            word.entry_id = child.entry_id.clone();
+            word.metadata = child.metadata.clone();
+            report.updated += 1;
+            report.entries.push(entry(&child.entry_id, "parent_changed", Some(child)));
+            writes.push(WordSaveRequest {
+                word,
+                old_romanized: Some(child.con_word_romanized.clone()),
+                expected_hash: None,
+            });
+        } else {
+            report.child_changed += 1;
+            report.entries.push(entry(&child.entry_id, "child_changed", Some(child)));
+        }
+    }
+
+    // 父语言中已删除的祖词：子词保留，标记为自父语言起弃用
+    for child in &child_words {
+        let Some(link) = inherited_from(child, &parent_id) else {
+            continue;
+        };
+        if parent_ids.contains(&link) || child.evolution.is_deprecated {
+            continue;
+        }
+        let mut word = child.clone();
+        word.evolution.is_deprecated = true;
+        word.evolution.deprecated_since_language_id = Some(parent_id.clone());
+        report.deprecated += 1;
+        report.entries.push(PullSyncEntry {
+            entry_id: child.entry_id.clone(),
+            parent_entry_id: link,
+            status: "deprecated".to_string(),
+            parent_form: String::new(),
+            child_form: Some(child.con_word_romanized.clone()),
+            evolved_form: None,
+        });
+        writes.push(WordSaveRequest {
+            word,
+            old_romanized: None,
+            expected_hash: None,
+        });
+    }
+
+    if report.dry_run || writes.is_empty() {
+        return Ok(report);
+    }
+
+    let log = create_snapshot(
+        project_path.clone(),
+        language.path.clone(),
+        "pull_sync".to_string(),
+        parent_id.clone(),
+        language_id.clone(),
+        format!(
+            "Pull from {}: {} new, {} updated, {} deprecated",
+            parent_id, report.added, report.updated, report.deprecated
+        ),
+        Some(vec!["lexicon".to_string()]),
+    )?;
+    report.log_id = log.entries.last().map(|e| e.log_id.clone());
+    // 部分写入失败时把快照 ID 放进错误信息，前端据此回滚
+    let log_id = report.log_id.clone().unwrap_or_default();
+
+    let results = save_words(project_path, language.path.clone(), writes, None)
+        .map_err(|e| format!("Pull sync failed; roll back to snapshot '{}' to undo: {}", log_id, e))?;
+    let failed: Vec<String> = results
+        .iter()
+        .filter(|r| r.status == "error")
+        .map(|r| format!("{}: {}", r.entry_id, r.error.clone().unwrap_or_default()))
+        .collect();
+    if !failed.is_empty() {
+        return Err(format!(
+            "Pull sync saved only part of the lexicon; roll back to snapshot '{}' to undo: {}",
+            log_id,
+            failed.join("; ")
+        ));
+    }
+    Ok(report)
+}
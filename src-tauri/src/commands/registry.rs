@@ -1,5 +1,7 @@
 use crate::commands::lexicon::atomic_write;
-use crate::models::{FamilyEntry, GlobalRegistry, WorkspaceConfig};
+use crate::commands::project_lock::acquire_lock;
+use crate::commands::timestamp::{decode_legacy_registry_timestamp, normalize_timestamp, now_rfc3339};
+use crate::models::{ExtraFields, FamilyEntry, GlobalRegistry, WorkspaceConfig};
 use std::fs;
 use std::path::PathBuf;
 use tauri::{command, AppHandle, Manager};
@@ -19,7 +21,13 @@ pub fn load_registry(app: AppHandle) -> Result<GlobalRegistry, String> {
         return Ok(GlobalRegistry::default());
     }
     let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
-    serde_json::from_str(&content).map_err(|e| e.to_string())
+    let mut registry: GlobalRegistry = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    // 旧版写入的伪 ISO 时间按原规则反推；下次保存注册表时写回
+    for family in &mut registry.families {
+        family.last_opened = decode_legacy_registry_timestamp(&family.last_opened)
+            .unwrap_or_else(|| normalize_timestamp(&family.last_opened));
+    }
+    Ok(registry)
 }
 
 /// Save the global registry to the app data directory.
@@ -50,7 +58,7 @@ pub fn register_family(
         for family in &mut registry.families {
             if family.conlang_file_path == conlang_file_path {
                 family.name = name.clone();
-                family.last_opened = now_iso();
+                family.last_opened = now_rfc3339();
             }
         }
     } else {
@@ -58,7 +66,8 @@ pub fn register_family(
         registry.families.push(FamilyEntry {
             name,
             conlang_file_path: conlang_file_path.clone(),
-            last_opened: now_iso(),
+            last_opened: now_rfc3339(),
+            extra: ExtraFields::new(),
         });
     }
 
@@ -72,6 +81,10 @@ pub fn register_family(
     }
 
     save_registry(app, registry.clone())?;
+    // 打开项目时获取项目锁；被其他实例占用时以只读方式打开，不阻止注册
+    if let Err(e) = acquire_lock(&conlang_file_path, false) {
+        eprintln!("获取项目锁失败: {}", e);
+    }
     Ok(registry)
 }
 
@@ -114,9 +127,12 @@ pub fn set_active_family(app: AppHandle, index: usize) -> Result<GlobalRegistry,
     }
 
     registry.active_family_index = Some(index);
-    registry.families[index].last_opened = now_iso();
+    registry.families[index].last_opened = now_rfc3339();
 
     save_registry(app, registry.clone())?;
+    if let Err(e) = acquire_lock(&registry.families[index].conlang_file_path, false) {
+        eprintln!("获取项目锁失败: {}", e);
+    }
     Ok(registry)
 }
 
@@ -149,24 +165,3 @@ pub fn validate_conlang_file(conlang_file_path: String) -> Result<Vec<String>, S
 
     Ok(missing)
 }
-
-fn now_iso() -> String {
-    use std::time::{SystemTime, UNIX_EPOCH};
-    let secs = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs();
-    // Simple ISO-ish timestamp (good enough for sorting)
-    let days = secs / 86400;
-    let years = 1970 + days / 365;
-    let remaining_days = days % 365;
-    let months = remaining_days / 30 + 1;
-    let day = remaining_days % 30 + 1;
-    let hours = (secs % 86400) / 3600;
-    let minutes = (secs % 3600) / 60;
-    let seconds = secs % 60;
-    format!(
-        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
-        years, months, day, hours, minutes, seconds
-    )
-}
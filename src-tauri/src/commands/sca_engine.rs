@@ -0,0 +1,442 @@
+use crate::commands::phoneme_features::{features_of, nearest_phoneme, PHONEME_FEATURES};
+use crate::models::{FeatureExpression, FeatureReplacement, PhonologyConfig, RomanizationRule, SCARuleSet};
+use regex::Regex;
+use std::collections::HashMap;
+
+// ── 后端音变引擎 ─────────────────────────────────────────
+//
+// 与前端 src/utils/scaEngine.ts 的语义保持一致：规则集按 order 排序后逐条执行，
+// 字符模式按空白拆分 target/replacement（替换项不足时重复最后一个），
+// 前后语境支持宏（V/C 与音位配列中定义的宏）和 `#` 词界；
+// 特征模式按音素特征表切分词形并匹配，替换后取特征最接近的音素。
+// regex crate 不支持环视，语境改为在每个候选位置分别匹配前缀和后缀。
+
+/// 特征表达式里的词界标记（前端解析 `#` 后存为该值）
+pub const SCA_BOUNDARY_FEATURE: &str = "__BOUNDARY__";
+
+pub type Macros = HashMap<String, Vec<String>>;
+
+/// 由音位库与音位配列宏构造 SCA 宏：V = 元音，C = 辅音，自定义宏可覆盖二者
+pub fn build_macros(phonology: &PhonologyConfig) -> Macros {
+    let mut macros = Macros::new();
+    macros.insert("V".to_string(), phonology.phoneme_inventory.vowels.clone());
+    macros.insert("C".to_string(), phonology.phoneme_inventory.consonants.clone());
+    for (key, values) in &phonology.phonotactics.macros {
+        macros.insert(key.clone(), values.clone());
+    }
+    macros
+}
+
+/// 一条规则在某个词上生效的记录
+#[derive(Debug, Clone)]
+pub struct SoundChangeStep {
+    pub rule_id: String,
+    pub before: String,
+    pub after: String,
+    /// 实际发生的音段替换 (原音段, 新音段)；新音段为空表示脱落
+    pub segments: Vec<(String, String)>,
+}
+
+/// 生效过的规则 ID，按首次生效的顺序去重
+pub fn fired_rules(steps: &[SoundChangeStep]) -> Vec<String> {
+    let mut fired: Vec<String> = Vec::new();
+    for step in steps {
+        if !fired.contains(&step.rule_id) {
+            fired.push(step.rule_id.clone());
+        }
+    }
+    fired
+}
+
+/// 词形一侧的语境：前语境匹配候选位置之前的全部内容，后语境匹配之后的全部内容
+enum Context {
+    Any,
+    Pattern(Regex),
+}
+
+impl Context {
+    fn compile(pattern: &str, macros: &[(String, String)], before: bool) -> Result<Self, regex::Error> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            return Ok(Context::Any);
+        }
+        let expanded = expand_context(pattern, macros, before);
+        let anchored = if before {
+            format!("(?:{})$", expanded)
+        } else {
+            format!("^(?:{})", expanded)
+        };
+        Regex::new(&anchored).map(Context::Pattern)
+    }
+
+    fn matches(&self, text: &str) -> bool {
+        match self {
+            Context::Any => true,
+            Context::Pattern(re) => re.is_match(text),
+        }
+    }
+}
+
+/// 展开语境中的宏与 `#`；其余字符按正则原样保留，与前端一致
+fn expand_context(pattern: &str, macros: &[(String, String)], before: bool) -> String {
+    let mut result = String::new();
+    let mut rest = pattern;
+    'outer: while let Some(ch) = rest.chars().next() {
+        if ch == '#' {
+            result.push(if before { '^' } else { '$' });
+            rest = &rest[1..];
+            continue;
+        }
+        for (key, alternatives) in macros {
+            if rest.starts_with(key.as_str()) {
+                result.push_str(alternatives);
+                rest = &rest[key.len()..];
+                continue 'outer;
+            }
+        }
+        result.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+    result
+}
+
+/// 宏按名称长度降序排列，展开为非捕获分组；空宏不展开
+fn compile_macros(macros: &Macros) -> Vec<(String, String)> {
+    let mut compiled: Vec<(String, String)> = macros
+        .iter()
+        .filter(|(key, values)| !key.is_empty() && !values.is_empty())
+        .map(|(key, values)| {
+            let mut sorted: Vec<&String> = values.iter().collect();
+            sorted.sort_by_key(|v| std::cmp::Reverse(v.chars().count()));
+            let alternatives: Vec<String> = sorted.iter().map(|v| regex::escape(v)).collect();
+            (key.clone(), format!("(?:{})", alternatives.join("|")))
+        })
+        .collect();
+    compiled.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+    compiled
+}
+
+enum CompiledRule {
+    Character {
+        pairs: Vec<(String, String)>,
+        before: Context,
+        after: Context,
+    },
+    Feature {
+        target: FeatureExpression,
+        replacement: FeatureReplacement,
+        before: Option<FeatureExpression>,
+        after: Option<FeatureExpression>,
+    },
+}
+
+struct Rule {
+    rule_id: String,
+    exceptions: Vec<String>,
+    kind: CompiledRule,
+}
+
+/// 预编译的规则序列，可对大批词条重复执行
+pub struct SoundChanger {
+    rules: Vec<Rule>,
+    /// 特征模式切分用的音素表，按长度降序
+    phonemes: Vec<&'static str>,
+}
+
+impl SoundChanger {
+    /// 编译规则集；语境无法编译为正则的规则会被跳过并返回其 rule_id
+    pub fn new(rule_sets: &[SCARuleSet], macros: &Macros) -> (Self, Vec<String>) {
+        let macro_patterns = compile_macros(macros);
+        let mut sorted: Vec<&SCARuleSet> = rule_sets.iter().collect();
+        sorted.sort_by_key(|set| set.order);
+
+        let mut rules = Vec::new();
+        let mut skipped = Vec::new();
+        for rule in sorted.iter().flat_map(|set| set.rules.iter()) {
+            let kind = if rule.feature_mode {
+                let (Some(target), Some(replacement)) = (&rule.target_features, &rule.replacement_features)
+                else {
+                    continue;
+                };
+                CompiledRule::Feature {
+                    target: target.clone(),
+                    replacement: replacement.clone(),
+                    before: rule.context_before_features.clone(),
+                    after: rule.context_after_features.clone(),
+                }
+            } else {
+                let targets: Vec<&str> = rule.target.split_whitespace().collect();
+                if targets.is_empty() {
+                    continue;
+                }
+                let mut replacements: Vec<&str> = rule.replacement.split_whitespace().collect();
+                while replacements.len() < targets.len() {
+                    replacements.push(replacements.last().copied().unwrap_or(""));
+                }
+                let contexts = Context::compile(&rule.context_before, &macro_patterns, true).and_then(|before| {
+                    Context::compile(&rule.context_after, &macro_patterns, false).map(|after| (before, after))
+                });
+                let Ok((before, after)) = contexts else {
+                    skipped.push(rule.rule_id.clone());
+                    continue;
+                };
+                CompiledRule::Character {
+                    pairs: targets
+                        .iter()
+                        .zip(replacements.iter())
+                        .map(|(t, r)| (t.to_string(), r.to_string()))
+                        .collect(),
+                    before,
+                    after,
+                }
+            };
+            rules.push(Rule {
+                rule_id: rule.rule_id.clone(),
+                exceptions: rule.exceptions.clone(),
+                kind,
+            });
+        }
+
+        let mut phonemes: Vec<&'static str> = PHONEME_FEATURES.iter().map(|(p, _)| *p).collect();
+        phonemes.sort_by_key(|p| std::cmp::Reverse(p.chars().count()));
+        (Self { rules, phonemes }, skipped)
+    }
+
+    /// 依次执行全部规则，返回结果词形与生效的步骤
+    pub fn apply(&self, word: &str) -> (String, Vec<SoundChangeStep>) {
+        let mut current = word.to_string();
+        let mut steps = Vec::new();
+        for rule in &self.rules {
+            let (result, segments) = match &rule.kind {
+                CompiledRule::Character { pairs, before, after } => {
+                    apply_character_rule(&current, pairs, before, after, &rule.exceptions)
+                }
+                CompiledRule::Feature { target, replacement, before, after } => {
+                    if rule.exceptions.iter().any(|ex| !ex.is_empty() && current.contains(ex.as_str())) {
+                        continue;
+                    }
+                    self.apply_feature_rule(&current, target, replacement, before.as_ref(), after.as_ref())
+                }
+            };
+            if result != current {
+                steps.push(SoundChangeStep {
+                    rule_id: rule.rule_id.clone(),
+                    before: current.clone(),
+                    after: result.clone(),
+                    segments,
+                });
+                current = result;
+            }
+        }
+        (current, steps)
+    }
+
+    fn tokenize(&self, word: &str) -> Vec<String> {
+        let mut tokens = Vec::new();
+        let mut rest = word;
+        while let Some(ch) = rest.chars().next() {
+            let len = self
+                .phonemes
+                .iter()
+                .find(|p| rest.starts_with(**p))
+                .map_or(ch.len_utf8(), |p| p.len());
+            tokens.push(rest[..len].to_string());
+            rest = &rest[len..];
+        }
+        tokens
+    }
+
+    fn apply_feature_rule(
+        &self,
+        word: &str,
+        target: &FeatureExpression,
+        replacement: &FeatureReplacement,
+        before: Option<&FeatureExpression>,
+        after: Option<&FeatureExpression>,
+    ) -> (String, Vec<(String, String)>) {
+        let tokens = self.tokenize(word);
+        let mut result = tokens.clone();
+        let mut segments = Vec::new();
+        let last = tokens.len().saturating_sub(1);
+
+        for (i, token) in tokens.iter().enumerate() {
+            if !matches_features(token, target) {
+                continue;
+            }
+            // 语境按原词形判断，与前端一致
+            if let Some(expr) = before {
+                if !context_matches(expr, i == 0, || tokens.get(i.wrapping_sub(1))) {
+                    continue;
+                }
+            }
+            if let Some(expr) = after {
+                if !context_matches(expr, i == last, || tokens.get(i + 1)) {
+                    continue;
+                }
+            }
+            let new_phoneme = apply_feature_replacement(token, replacement);
+            if new_phoneme != *token {
+                segments.push((token.clone(), new_phoneme.clone()));
+                result[i] = new_phoneme;
+            }
+        }
+        (result.concat(), segments)
+    }
+}
+
+fn apply_character_rule(
+    word: &str,
+    pairs: &[(String, String)],
+    before: &Context,
+    after: &Context,
+    exceptions: &[String],
+) -> (String, Vec<(String, String)>) {
+    let mut current = word.to_string();
+    let mut segments = Vec::new();
+    for (target, replacement) in pairs {
+        if exceptions.iter().any(|ex| !ex.is_empty() && current.contains(ex.as_str())) {
+            continue;
+        }
+        let mut out = String::with_capacity(current.len());
+        let mut i = 0;
+        while i < current.len() {
+            let rest = &current[i..];
+            if rest.starts_with(target.as_str())
+                && before.matches(&current[..i])
+                && after.matches(&current[i + target.len()..])
+            {
+                out.push_str(replacement);
+                i += target.len();
+            } else {
+                let ch = rest.chars().next().unwrap_or_default();
+                out.push(ch);
+                i += ch.len_utf8();
+            }
+        }
+        if out != current {
+            segments.push((target.clone(), replacement.clone()));
+            current = out;
+        }
+    }
+    (current, segments)
+}
+
+fn is_boundary(feature: &str) -> bool {
+    feature == SCA_BOUNDARY_FEATURE || feature == "#"
+}
+
+/// 音素是否满足特征表达式；未收录的音素一律不匹配
+pub fn matches_features(phoneme: &str, expr: &FeatureExpression) -> bool {
+    let Some(features) = features_of(phoneme) else {
+        return false;
+    };
+    expr.positive.iter().all(|f| features.contains(&f.as_str()))
+        && !expr.negative.iter().any(|f| features.contains(&f.as_str()))
+}
+
+/// 特征语境：含词界标记时要求处在词首/词尾，否则检查相邻音素
+fn context_matches<'a>(
+    expr: &FeatureExpression,
+    at_edge: bool,
+    neighbour: impl FnOnce() -> Option<&'a String>,
+) -> bool {
+    if expr.positive.iter().any(|f| is_boundary(f)) {
+        return at_edge;
+    }
+    let stripped = FeatureExpression {
+        positive: expr.positive.iter().filter(|f| !is_boundary(f)).cloned().collect(),
+        negative: expr.negative.iter().filter(|f| !is_boundary(f)).cloned().collect(),
+        extra: Default::default(),
+    };
+    if stripped.positive.is_empty() && stripped.negative.is_empty() {
+        return true;
+    }
+    neighbour().is_some_and(|p| matches_features(p, &stripped))
+}
+
+/// 去掉/加上特征后，在特征表中取最接近的音素
+pub fn apply_feature_replacement(phoneme: &str, replacement: &FeatureReplacement) -> String {
+    let Some(features) = features_of(phoneme) else {
+        return phoneme.to_string();
+    };
+    let mut current: Vec<&str> = features
+        .iter()
+        .copied()
+        .filter(|f| !replacement.remove_features.iter().any(|r| r == f))
+        .collect();
+    for f in &replacement.set_features {
+        if !current.contains(&f.as_str()) {
+            current.push(f.as_str());
+        }
+    }
+    nearest_phoneme(&current, PHONEME_FEATURES.iter().map(|(p, _)| *p))
+        .map_or_else(|| phoneme.to_string(), str::to_string)
+}
+
+// ── IPA 生成 ────────────────────────────────────────────
+//
+// 对应前端 generateIPA 的音位层：按默认转写表（无默认时取第一张）最长优先
+// 贪心转写。词典里保存的 phonetic_ipa 就是这一层（不带斜线），
+// 音位变体规则只在显示时计算，这里不处理。
+
+pub struct IpaGenerator {
+    rules: Vec<RomanizationRule>,
+}
+
+impl IpaGenerator {
+    /// 没有转写表时返回 None
+    pub fn new(phonology: &PhonologyConfig) -> Option<Self> {
+        let map = phonology
+            .romanization_maps
+            .iter()
+            .find(|m| m.is_default)
+            .or_else(|| phonology.romanization_maps.first())?;
+        let mut rules: Vec<RomanizationRule> =
+            map.rules.iter().filter(|r| !r.input.is_empty()).cloned().collect();
+        rules.sort_by_key(|r| std::cmp::Reverse(r.input.chars().count()));
+        Some(Self { rules })
+    }
+
+    pub fn generate(&self, romanized: &str) -> String {
+        if romanized.trim().is_empty() {
+            return String::new();
+        }
+        let lower = romanized.to_lowercase();
+        let mut result = String::new();
+        let mut i = 0;
+        while i < lower.len() {
+            let rest = &lower[i..];
+            let matched = self.rules.iter().find(|rule| {
+                if !rest.starts_with(rule.input.as_str()) {
+                    return false;
+                }
+                let prev = lower[..i].chars().next_back();
+                let next = rest[rule.input.len()..].chars().next();
+                single_char_context(&rule.context_before, prev) && single_char_context(&rule.context_after, next)
+            });
+            match matched {
+                Some(rule) => {
+                    result.push_str(&rule.output_phoneme);
+                    i += rule.input.len();
+                }
+                None => {
+                    let ch = rest.chars().next().unwrap_or_default();
+                    result.push(ch);
+                    i += ch.len_utf8();
+                }
+            }
+        }
+        result
+    }
+}
+
+/// 转写表的语境只比较相邻的一个字符；空或 `_` 表示任意
+fn single_char_context(context: &str, neighbour: Option<char>) -> bool {
+    if context.is_empty() || context == "_" {
+        return true;
+    }
+    neighbour.is_some_and(|c| {
+        let mut buf = [0u8; 4];
+        c.encode_utf8(&mut buf) == context
+    })
+}
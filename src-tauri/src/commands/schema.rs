@@ -0,0 +1,369 @@
+use crate::commands::lexicon::atomic_write;
+use crate::commands::timestamp::normalize_timestamp;
+use crate::models::{
+    CorpusText, GrammarConfig, PhonologyConfig, SCAConfig, WordEntry, CURRENT_SCHEMA_VERSION,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use tauri::command;
+
+// ── 语言文件的 schema 版本与迁移 ─────────────────────────
+//
+// phonology.json / grammar.json / sca_rules.json / corpus/<id>.json 在顶层、
+// 词典分桶在每个词条上记录 schema_version（缺失视为 0）。
+// 读取时按 MIGRATIONS 中的顺序逐版本升级到 CURRENT_SCHEMA_VERSION；
+// 磁盘上或前端传来的版本高于当前版本时拒绝写入，防止旧版应用覆盖新版数据。
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaKind {
+    Phonology,
+    Grammar,
+    Sca,
+    Corpus,
+    Lexicon,
+}
+
+impl SchemaKind {
+    fn name(self) -> &'static str {
+        match self {
+            SchemaKind::Phonology => "phonology",
+            SchemaKind::Grammar => "grammar",
+            SchemaKind::Sca => "sca",
+            SchemaKind::Corpus => "corpus",
+            SchemaKind::Lexicon => "lexicon",
+        }
+    }
+}
+
+struct Migration {
+    kind: SchemaKind,
+    from_version: u32,
+    description: &'static str,
+    apply: fn(&mut serde_json::Map<String, Value>),
+}
+
+/// 已注册的迁移，按 (kind, from_version) 顺序执行。没有迁移的版本步只更新版本号。
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        kind: SchemaKind::Grammar,
+        from_version: 0,
+        description: "rename inflections → inflection_rules and rule pos → pos_id",
+        apply: grammar_v0_to_v1,
+    },
+    Migration {
+        kind: SchemaKind::Lexicon,
+        from_version: 0,
+        description: "rename sense pos → pos_id",
+        apply: lexicon_v0_to_v1,
+    },
+    Migration {
+        kind: SchemaKind::Lexicon,
+        from_version: 1,
+        description: "normalize metadata timestamps to RFC 3339",
+        apply: normalize_metadata_timestamps,
+    },
+    Migration {
+        kind: SchemaKind::Corpus,
+        from_version: 1,
+        description: "normalize metadata timestamps to RFC 3339",
+        apply: normalize_metadata_timestamps,
+    },
+];
+
+fn rename_key(obj: &mut serde_json::Map<String, Value>, from: &str, to: &str) {
+    if obj.contains_key(to) {
+        return;
+    }
+    if let Some(v) = obj.remove(from) {
+        obj.insert(to.to_string(), v);
+    }
+}
+
+fn grammar_v0_to_v1(obj: &mut serde_json::Map<String, Value>) {
+    rename_key(obj, "inflections", "inflection_rules");
+    if let Some(Value::Array(rules)) = obj.get_mut("inflection_rules") {
+        for rule in rules.iter_mut().filter_map(Value::as_object_mut) {
+            rename_key(rule, "pos", "pos_id");
+        }
+    }
+}
+
+fn lexicon_v0_to_v1(obj: &mut serde_json::Map<String, Value>) {
+    if let Some(Value::Array(senses)) = obj.get_mut("senses") {
+        for sense in senses.iter_mut().filter_map(Value::as_object_mut) {
+            rename_key(sense, "pos", "pos_id");
+        }
+    }
+}
+
+/// metadata.created_at / updated_at：Unix 秒、旧注册表式伪日期等统一为 RFC 3339
+fn normalize_metadata_timestamps(obj: &mut serde_json::Map<String, Value>) {
+    if let Some(Value::Object(meta)) = obj.get_mut("metadata") {
+        for key in ["created_at", "updated_at"] {
+            if let Some(Value::String(ts)) = meta.get_mut(key) {
+                *ts = normalize_timestamp(ts);
+            }
+        }
+    }
+}
+
+pub fn schema_version_of(value: &Value) -> u32 {
+    value
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .map(|v| v as u32)
+        .unwrap_or(0)
+}
+
+pub fn newer_schema_error(file: &str, version: u32) -> String {
+    format!(
+        "{} was written by a newer version of Conlang Maker (schema v{}, this build supports v{}); refusing to overwrite it",
+        file, version, CURRENT_SCHEMA_VERSION
+    )
+}
+
+/// 把 JSON 升级到当前 schema 版本，返回执行过的迁移说明。
+/// 版本高于当前版本时不做任何修改（仍可读取，但写入会被拒绝）。
+pub fn migrate_value(kind: SchemaKind, value: &mut Value) -> Vec<String> {
+    let mut applied = Vec::new();
+    let mut version = schema_version_of(value);
+    if version >= CURRENT_SCHEMA_VERSION {
+        return applied;
+    }
+    let Some(obj) = value.as_object_mut() else {
+        return applied;
+    };
+    while version < CURRENT_SCHEMA_VERSION {
+        for m in MIGRATIONS
+            .iter()
+            .filter(|m| m.kind == kind && m.from_version == version)
+        {
+            (m.apply)(obj);
+            applied.push(format!("{} v{}→v{}: {}", kind.name(), version, version + 1, m.description));
+        }
+        version += 1;
+    }
+    obj.insert("schema_version".to_string(), Value::from(CURRENT_SCHEMA_VERSION));
+    applied
+}
+
+/// 读取 JSON 字符串并在反序列化前完成迁移
+pub fn parse_versioned<T: DeserializeOwned>(kind: SchemaKind, content: &str) -> Result<T, String> {
+    let mut value: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    migrate_value(kind, &mut value);
+    serde_json::from_value(value).map_err(|e| e.to_string())
+}
+
+/// 写入前检查：传入数据与磁盘上的现有文件都不能来自更新的 schema 版本
+pub fn ensure_writable(path: &Path, incoming_version: u32) -> Result<(), String> {
+    let file = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if incoming_version > CURRENT_SCHEMA_VERSION {
+        return Err(newer_schema_error(&file, incoming_version));
+    }
+    if path.exists() {
+        let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        if let Ok(value) = serde_json::from_str::<Value>(&content) {
+            let on_disk = schema_version_of(&value);
+            if on_disk > CURRENT_SCHEMA_VERSION {
+                return Err(newer_schema_error(&file, on_disk));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FileMigration {
+    /// 相对语言目录的路径
+    pub file: String,
+    pub kind: String,
+    pub from_version: u32,
+    pub to_version: u32,
+    /// 词典分桶中被升级的词条数（其他文件为 0）
+    #[serde(default)]
+    pub entries_upgraded: usize,
+    pub applied: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SchemaMigrationReport {
+    pub dry_run: bool,
+    pub current_version: u32,
+    pub migrated: Vec<FileMigration>,
+    /// 来自更新版本、本版本不会改写的文件
+    pub newer_files: Vec<String>,
+}
+
+fn migrate_config_file<T: DeserializeOwned + Serialize>(
+    lang_dir: &Path,
+    rel: &str,
+    kind: SchemaKind,
+    dry_run: bool,
+    report: &mut SchemaMigrationReport,
+) -> Result<(), String> {
+    let path = lang_dir.join(rel);
+    if !path.exists() {
+        return Ok(());
+    }
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let mut value: Value = serde_json::from_str(&content).map_err(|e| format!("{}: {}", rel, e))?;
+    let from_version = schema_version_of(&value);
+    if from_version > CURRENT_SCHEMA_VERSION {
+        report.newer_files.push(rel.to_string());
+        return Ok(());
+    }
+    if from_version == CURRENT_SCHEMA_VERSION {
+        return Ok(());
+    }
+    let applied = migrate_value(kind, &mut value);
+    if !dry_run {
+        let typed: T = serde_json::from_value(value).map_err(|e| format!("{}: {}", rel, e))?;
+        let pretty = serde_json::to_string_pretty(&typed).map_err(|e| e.to_string())?;
+        atomic_write(&path, &pretty)?;
+    }
+    report.migrated.push(FileMigration {
+        file: rel.to_string(),
+        kind: kind.name().to_string(),
+        from_version,
+        to_version: CURRENT_SCHEMA_VERSION,
+        entries_upgraded: 0,
+        applied,
+    });
+    Ok(())
+}
+
+fn migrate_bucket_file(
+    lang_dir: &Path,
+    path: &Path,
+    dry_run: bool,
+    report: &mut SchemaMigrationReport,
+) -> Result<(), String> {
+    let rel = path
+        .strip_prefix(lang_dir)
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let mut new_lines: Vec<String> = Vec::new();
+    let mut from_version = CURRENT_SCHEMA_VERSION;
+    let mut upgraded = 0;
+    let mut applied: Vec<String> = Vec::new();
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let Ok(mut value) = serde_json::from_str::<Value>(trimmed) else {
+            // 无法解析的行保持原样，由词典读写流程负责隔离
+            new_lines.push(trimmed.to_string());
+            continue;
+        };
+        let version = schema_version_of(&value);
+        if version > CURRENT_SCHEMA_VERSION {
+            report.newer_files.push(rel);
+            return Ok(());
+        }
+        if version == CURRENT_SCHEMA_VERSION {
+            new_lines.push(trimmed.to_string());
+            continue;
+        }
+        from_version = from_version.min(version);
+        for step in migrate_value(SchemaKind::Lexicon, &mut value) {
+            if !applied.contains(&step) {
+                applied.push(step);
+            }
+        }
+        match serde_json::from_value::<WordEntry>(value) {
+            Ok(word) => {
+                upgraded += 1;
+                new_lines.push(serde_json::to_string(&word).map_err(|e| e.to_string())?);
+            }
+            Err(_) => new_lines.push(trimmed.to_string()),
+        }
+    }
+
+    if upgraded == 0 {
+        return Ok(());
+    }
+    if !dry_run {
+        atomic_write(path, &(new_lines.join("\n") + "\n"))?;
+    }
+    report.migrated.push(FileMigration {
+        file: rel,
+        kind: SchemaKind::Lexicon.name().to_string(),
+        from_version,
+        to_version: CURRENT_SCHEMA_VERSION,
+        entries_upgraded: upgraded,
+        applied,
+    });
+    Ok(())
+}
+
+/// 把一个语言目录下的所有文件升级到当前 schema 版本并写回磁盘，返回迁移报告。默认 dry-run。
+#[command]
+pub fn migrate_language_files(
+    project_path: String,
+    language_path: String,
+    dry_run: Option<bool>,
+) -> Result<SchemaMigrationReport, String> {
+    let lang_dir = Path::new(&project_path).join(&language_path);
+    let is_dry_run = dry_run.unwrap_or(true);
+    let mut report = SchemaMigrationReport {
+        dry_run: is_dry_run,
+        current_version: CURRENT_SCHEMA_VERSION,
+        migrated: Vec::new(),
+        newer_files: Vec::new(),
+    };
+
+    migrate_config_file::<PhonologyConfig>(&lang_dir, "phonology.json", SchemaKind::Phonology, is_dry_run, &mut report)?;
+    migrate_config_file::<GrammarConfig>(&lang_dir, "grammar.json", SchemaKind::Grammar, is_dry_run, &mut report)?;
+    migrate_config_file::<SCAConfig>(&lang_dir, "sca_rules.json", SchemaKind::Sca, is_dry_run, &mut report)?;
+
+    let corpus_dir = lang_dir.join("corpus");
+    if corpus_dir.exists() {
+        let mut names: Vec<String> = Vec::new();
+        for entry in fs::read_dir(&corpus_dir).map_err(|e| e.to_string())? {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if name.ends_with(".json") && name != "corpus_index.json" {
+                names.push(name);
+            }
+        }
+        names.sort();
+        for name in names {
+            migrate_config_file::<CorpusText>(
+                &lang_dir,
+                &format!("corpus/{}", name),
+                SchemaKind::Corpus,
+                is_dry_run,
+                &mut report,
+            )?;
+        }
+    }
+
+    let lexicon_dir = lang_dir.join("lexicon");
+    if lexicon_dir.exists() {
+        let mut buckets = Vec::new();
+        for entry in fs::read_dir(&lexicon_dir).map_err(|e| e.to_string())? {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            if path.extension().is_some_and(|e| e == "ndjson") {
+                buckets.push(path);
+            }
+        }
+        buckets.sort();
+        for path in buckets {
+            migrate_bucket_file(&lang_dir, &path, is_dry_run, &mut report)?;
+        }
+    }
+
+    Ok(report)
+}
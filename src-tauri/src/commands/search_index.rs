@@ -0,0 +1,626 @@
+use crate::commands::lexicon::{atomic_write, read_all_words};
+use crate::commands::project_lock::check_write_access;
+use crate::models::WordEntry;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+// ── 反查索引（自然语言 → 造语） ─────────────────────────
+//
+// 索引以倒排表形式存放在 lexicon/.index/reverse_index.json。
+// save_word / delete_word 不改写整个索引，只把变更追加到 reverse_index.delta.ndjson，
+// 读取时在基础索引上重放；增量日志超过基础文件的一半时合并回基础文件。
+// 增量日志首行记录其对应的基础索引 generation，两者不一致、日志缺失或有损坏的行时
+// 视为索引缺失，在首次查询时整体重建。
+
+const INDEX_VERSION: u32 = 1;
+
+/// 增量日志小于该大小时不合并，避免小词典频繁改写基础索引
+const DELTA_COMPACT_MIN_BYTES: u64 = 256 * 1024;
+
+/// 各字段命中的权重：gloss 最重要，其次是释义，例句最低
+const GLOSS_WEIGHT: f64 = 3.0;
+const DEFINITION_WEIGHT: f64 = 1.5;
+const EXAMPLE_WEIGHT: f64 = 0.5;
+
+const STOP_WORDS: &[&str] = &["a", "an", "the", "to", "of", "or", "and", "be", "is", "in", "on"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct IndexedEntry {
+    #[serde(default)]
+    pub con_word_romanized: String,
+    #[serde(default)]
+    pub glosses: Vec<String>,
+    /// term → 加权词频
+    #[serde(default)]
+    pub terms: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReverseIndex {
+    #[serde(default)]
+    pub version: u32,
+    /// 每次写入基础索引时更新，用于识别属于它的增量日志
+    #[serde(default)]
+    pub generation: u64,
+    #[serde(default)]
+    pub entries: BTreeMap<String, IndexedEntry>,
+    /// term → 包含该 term 的 entry_id 集合
+    #[serde(default)]
+    pub postings: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Default for ReverseIndex {
+    fn default() -> Self {
+        Self {
+            version: INDEX_VERSION,
+            generation: 0,
+            entries: BTreeMap::new(),
+            postings: BTreeMap::new(),
+        }
+    }
+}
+
+impl ReverseIndex {
+    fn remove(&mut self, entry_id: &str) {
+        let Some(old) = self.entries.remove(entry_id) else {
+            return;
+        };
+        for term in old.terms.keys() {
+            if let Some(ids) = self.postings.get_mut(term) {
+                ids.remove(entry_id);
+                if ids.is_empty() {
+                    self.postings.remove(term);
+                }
+            }
+        }
+    }
+
+    fn insert(&mut self, entry_id: &str, indexed: IndexedEntry) {
+        self.remove(entry_id);
+        for term in indexed.terms.keys() {
+            self.postings
+                .entry(term.clone())
+                .or_default()
+                .insert(entry_id.to_string());
+        }
+        self.entries.insert(entry_id.to_string(), indexed);
+    }
+
+    fn upsert(&mut self, word: &WordEntry) {
+        self.insert(&word.entry_id, index_word(word));
+    }
+
+    /// 重放一条增量记录；无法识别的记录返回 false
+    fn apply(&mut self, record: DeltaRecord) -> bool {
+        match (record.op.as_str(), record.entry) {
+            ("upsert", Some(indexed)) => self.insert(&record.entry_id, indexed),
+            ("remove", _) => self.remove(&record.entry_id),
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// 增量日志的首行
+#[derive(Debug, Serialize, Deserialize)]
+struct DeltaHeader {
+    generation: u64,
+}
+
+/// 增量日志中的一行
+#[derive(Debug, Serialize, Deserialize)]
+struct DeltaRecord {
+    op: String, // "upsert" | "remove"
+    entry_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entry: Option<IndexedEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReverseSearchHit {
+    pub entry_id: String,
+    pub con_word_romanized: String,
+    pub glosses: Vec<String>,
+    pub score: f64,
+    pub matched_terms: Vec<String>,
+}
+
+fn index_file(lexicon_dir: &Path) -> PathBuf {
+    lexicon_dir.join(".index").join("reverse_index.json")
+}
+
+fn delta_file(lexicon_dir: &Path) -> PathBuf {
+    lexicon_dir.join(".index").join("reverse_index.delta.ndjson")
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF     // 平假名 / 片假名
+        | 0x3400..=0x4DBF   // CJK 扩展 A
+        | 0x4E00..=0x9FFF   // CJK 统一表意文字
+        | 0xAC00..=0xD7AF   // 谚文音节
+        | 0xF900..=0xFAFF   // CJK 兼容表意文字
+        | 0x20000..=0x2FA1F // CJK 扩展 B 及以后
+    )
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+/// 去掉 -ed / -ing 之后补回被吞掉的词尾 e：loved → lov → love、hoping → hop → hope。
+/// 英语单词不以 v 结尾；单音节且以“辅音 + 元音 + 辅音”结尾的词干也补 e（w / x / y 结尾除外）
+fn restore_e(base: &str) -> String {
+    let chars: Vec<char> = base.chars().collect();
+    if chars.last() == Some(&'v') {
+        return format!("{}e", base);
+    }
+    let vowel_groups = chars
+        .iter()
+        .enumerate()
+        .filter(|(i, c)| is_vowel(**c) && (*i == 0 || !is_vowel(chars[i - 1])))
+        .count();
+    if let [.., a, b, c] = chars.as_slice() {
+        if vowel_groups == 1 && !is_vowel(*a) && is_vowel(*b) && !is_vowel(*c) && !matches!(c, 'w' | 'x' | 'y') {
+            return format!("{}e", base);
+        }
+    }
+    base.to_string()
+}
+
+/// 极简英文词干提取：只处理最常见的屈折后缀，保证 "rivers" / "running" / "loved" 能命中 "river" / "run" / "love"
+fn stem(word: &str) -> String {
+    let w = word;
+    let len = w.chars().count();
+    if len <= 3 {
+        return w.to_string();
+    }
+    // lies / died → lie / die，cities / cried → city / cry
+    for suffix in ["ies", "ied"] {
+        if let Some(base) = w.strip_suffix(suffix) {
+            return if len == 4 {
+                format!("{}ie", base)
+            } else {
+                format!("{}y", base)
+            };
+        }
+    }
+    if w.ends_with("sses") || w.ends_with("ss") || w.ends_with("us") {
+        return w.trim_end_matches("es").to_string();
+    }
+    for suffix in ["ing", "ed"] {
+        let Some(base) = w.strip_suffix(suffix) else {
+            continue;
+        };
+        // bring / string / need 之类的词干没有元音或过短，不是屈折形式
+        let has_vowel = base.chars().any(|c| is_vowel(c) || c == 'y');
+        if base.chars().count() < 3 || !has_vowel || base.ends_with('e') {
+            break;
+        }
+        let mut chars: Vec<char> = base.chars().collect();
+        let n = chars.len();
+        // running → runn → run；calling / added 的 ll、dd 保留（call、add）
+        if n >= 4 && chars[n - 1] == chars[n - 2] && !matches!(chars[n - 1], 'l' | 's' | 'z') {
+            chars.pop();
+            return chars.into_iter().collect();
+        }
+        return restore_e(base);
+    }
+    if let Some(base) = w.strip_suffix("ly") {
+        if base.chars().count() >= 3 {
+            return base.to_string();
+        }
+    }
+    if let Some(base) = w.strip_suffix("es") {
+        if base.ends_with("sh") || base.ends_with("ch") || base.ends_with('x') {
+            return base.to_string();
+        }
+    }
+    if let Some(base) = w.strip_suffix('s') {
+        return base.to_string();
+    }
+    w.to_string()
+}
+
+/// 分词：拉丁等字母文字按非字母数字切分并做大小写折叠与词干化；
+/// 中日韩文字逐字切分，并额外生成相邻二元组以提高多字词的排名
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut cjk_run: Vec<char> = Vec::new();
+
+    let flush_word = |word: &mut String, tokens: &mut Vec<String>| {
+        if !word.is_empty() {
+            let lower = word.to_lowercase();
+            if !STOP_WORDS.contains(&lower.as_str()) {
+                tokens.push(stem(&lower));
+            }
+            word.clear();
+        }
+    };
+    let flush_cjk = |run: &mut Vec<char>, tokens: &mut Vec<String>| {
+        for c in run.iter() {
+            tokens.push(c.to_string());
+        }
+        for pair in run.windows(2) {
+            tokens.push(pair.iter().collect());
+        }
+        run.clear();
+    };
+
+    for c in text.chars() {
+        if is_cjk(c) {
+            flush_word(&mut word, &mut tokens);
+            cjk_run.push(c);
+        } else if c.is_alphanumeric() {
+            flush_cjk(&mut cjk_run, &mut tokens);
+            word.push(c);
+        } else {
+            flush_word(&mut word, &mut tokens);
+            flush_cjk(&mut cjk_run, &mut tokens);
+        }
+    }
+    flush_word(&mut word, &mut tokens);
+    flush_cjk(&mut cjk_run, &mut tokens);
+    tokens
+}
+
+fn index_word(word: &WordEntry) -> IndexedEntry {
+    let mut terms: BTreeMap<String, f64> = BTreeMap::new();
+    let mut add = |text: &str, weight: f64| {
+        for token in tokenize(text) {
+            *terms.entry(token).or_insert(0.0) += weight;
+        }
+    };
+    for sense in &word.senses {
+        add(&sense.gloss, GLOSS_WEIGHT);
+        for d in &sense.definitions {
+            add(d, DEFINITION_WEIGHT);
+        }
+        for ex in &sense.examples {
+            add(ex, EXAMPLE_WEIGHT);
+        }
+    }
+    IndexedEntry {
+        con_word_romanized: word.con_word_romanized.clone(),
+        glosses: word.senses.iter().map(|s| s.gloss.clone()).collect(),
+        terms,
+    }
+}
+
+/// 读取基础索引并重放增量日志；缺失、过旧、generation 不一致或有损坏的行时返回 None，由调用方重建
+fn load_index(lexicon_dir: &Path) -> Result<Option<ReverseIndex>, String> {
+    let path = index_file(lexicon_dir);
+    let delta = delta_file(lexicon_dir);
+    if !path.exists() || !delta.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    let mut index = match serde_json::from_str::<ReverseIndex>(&content) {
+        Ok(index) if index.version == INDEX_VERSION => index,
+        // 格式过旧或文件损坏：视为缺失，调用方会重建
+        _ => return Ok(None),
+    };
+
+    let log = fs::read_to_string(&delta).map_err(|e| e.to_string())?;
+    let mut lines = log.lines().filter(|l| !l.trim().is_empty());
+    match lines.next().and_then(|l| serde_json::from_str::<DeltaHeader>(l).ok()) {
+        Some(header) if header.generation == index.generation => {}
+        _ => return Ok(None),
+    }
+    for line in lines {
+        // 写到一半的行（进程中途退出）同样视为损坏
+        let Ok(record) = serde_json::from_str::<DeltaRecord>(line) else {
+            return Ok(None);
+        };
+        if !index.apply(record) {
+            return Ok(None);
+        }
+    }
+    Ok(Some(index))
+}
+
+/// 写入基础索引并开始一个新的空增量日志
+fn save_index(lexicon_dir: &Path, index: &mut ReverseIndex) -> Result<(), String> {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64;
+    index.generation = now.max(index.generation + 1);
+    let content = serde_json::to_string(index).map_err(|e| e.to_string())?;
+    atomic_write(&index_file(lexicon_dir), &content)?;
+    let header = serde_json::to_string(&DeltaHeader {
+        generation: index.generation,
+    })
+    .map_err(|e| e.to_string())?;
+    atomic_write(&delta_file(lexicon_dir), &(header + "\n"))
+}
+
+/// 追加增量记录；索引尚未建立时不做任何事（首次查询时再全量构建）。
+/// 日志过大时合并回基础索引
+fn append_delta(lexicon_dir: &Path, records: &[DeltaRecord]) -> Result<(), String> {
+    let path = index_file(lexicon_dir);
+    let delta = delta_file(lexicon_dir);
+    if records.is_empty() || !path.exists() || !delta.exists() {
+        return Ok(());
+    }
+    let mut lines = String::new();
+    for record in records {
+        lines.push_str(&serde_json::to_string(record).map_err(|e| e.to_string())?);
+        lines.push('\n');
+    }
+    let mut file = OpenOptions::new()
+        .append(true)
+        .open(&delta)
+        .map_err(|e| e.to_string())?;
+    file.write_all(lines.as_bytes()).map_err(|e| e.to_string())?;
+    drop(file);
+
+    let base_len = fs::metadata(&path).map_err(|e| e.to_string())?.len();
+    let delta_len = fs::metadata(&delta).map_err(|e| e.to_string())?.len();
+    if delta_len > DELTA_COMPACT_MIN_BYTES.max(base_len / 2) {
+        match load_index(lexicon_dir)? {
+            Some(mut index) => save_index(lexicon_dir, &mut index)?,
+            None => invalidate_index(lexicon_dir)?,
+        }
+    }
+    Ok(())
+}
+
+fn build_index(lexicon_dir: &Path) -> Result<ReverseIndex, String> {
+    let mut index = ReverseIndex::default();
+    for word in read_all_words(lexicon_dir)? {
+        index.upsert(&word);
+    }
+    Ok(index)
+}
+
+/// 读取索引；不存在时从词典全量重建并落盘。
+/// 项目以只读方式打开时只在内存中重建，不写入 .index（查询本身不应因拿不到写锁而失败）
+fn load_or_build_index(lexicon_dir: &Path) -> Result<ReverseIndex, String> {
+    if let Some(index) = load_index(lexicon_dir)? {
+        return Ok(index);
+    }
+    let mut index = build_index(lexicon_dir)?;
+    if check_write_access(lexicon_dir).is_ok() {
+        save_index(lexicon_dir, &mut index)?;
+    }
+    Ok(index)
+}
+
+/// save_word 之后调用：把词条的新索引内容追加到增量日志
+pub fn index_upsert_words(lexicon_dir: &Path, words: &[WordEntry]) -> Result<(), String> {
+    let records: Vec<DeltaRecord> = words
+        .iter()
+        .map(|word| DeltaRecord {
+            op: "upsert".to_string(),
+            entry_id: word.entry_id.clone(),
+            entry: Some(index_word(word)),
+        })
+        .collect();
+    append_delta(lexicon_dir, &records)
+}
+
+/// delete_word 之后调用：在增量日志中记录移除
+pub fn index_remove_words(lexicon_dir: &Path, entry_ids: &[String]) -> Result<(), String> {
+    let records: Vec<DeltaRecord> = entry_ids
+        .iter()
+        .map(|id| DeltaRecord {
+            op: "remove".to_string(),
+            entry_id: id.clone(),
+            entry: None,
+        })
+        .collect();
+    append_delta(lexicon_dir, &records)
+}
+
+/// 绕过 save_word 批量改写分桶（或检测到外部修改）后调用：删除索引，下次查询时重建
+pub fn invalidate_index(lexicon_dir: &Path) -> Result<(), String> {
+    // 先删增量日志：之后的追加会因日志缺失而跳过
+    for path in [delta_file(lexicon_dir), index_file(lexicon_dir)] {
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// 反查词典：按 gloss / 释义 / 例句查找造语词条，结果按 TF-IDF 风格得分降序排列
+#[command]
+pub fn reverse_search(
+    project_path: String,
+    language_path: String,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<ReverseSearchHit>, String> {
+    let lexicon_dir = Path::new(&project_path)
+        .join(&language_path)
+        .join("lexicon");
+    let index = load_or_build_index(&lexicon_dir)?;
+
+    let mut query_terms = tokenize(&query);
+    query_terms.sort();
+    query_terms.dedup();
+    if query_terms.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let total_docs = index.entries.len().max(1) as f64;
+    let mut scores: HashMap<&str, (f64, Vec<String>)> = HashMap::new();
+    for term in &query_terms {
+        let Some(ids) = index.postings.get(term) else {
+            continue;
+        };
+        let idf = (total_docs / ids.len() as f64).ln() + 1.0;
+        for id in ids {
+            let Some(entry) = index.entries.get(id) else {
+                continue;
+            };
+            let tf = entry.terms.get(term).copied().unwrap_or(0.0);
+            let slot = scores.entry(id.as_str()).or_insert((0.0, Vec::new()));
+            slot.0 += tf * idf;
+            slot.1.push(term.clone());
+        }
+    }
+
+    let normalized_query = query.trim().to_lowercase();
+    let mut hits: Vec<ReverseSearchHit> = scores
+        .into_iter()
+        .filter_map(|(id, (mut score, matched_terms))| {
+            let entry = index.entries.get(id)?;
+            // gloss 与查询完全一致时给予额外加分（"river" 应排在 "river bank" 之前）
+            if entry.glosses.iter().any(|g| g.trim().to_lowercase() == normalized_query) {
+                score *= 2.0;
+            }
+            // 命中查询中更多的 term 优先
+            score *= matched_terms.len() as f64 / query_terms.len() as f64;
+            Some(ReverseSearchHit {
+                entry_id: id.to_string(),
+                con_word_romanized: entry.con_word_romanized.clone(),
+                glosses: entry.glosses.clone(),
+                score,
+                matched_terms,
+            })
+        })
+        .collect();
+
+    hits.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.con_word_romanized.cmp(&b.con_word_romanized))
+    });
+    hits.truncate(limit.unwrap_or(50));
+    Ok(hits)
+}
+
+/// 丢弃现有索引并从词典全量重建，返回已索引的词条数
+#[command]
+pub fn rebuild_reverse_index(project_path: String, language_path: String) -> Result<usize, String> {
+    let lexicon_dir = Path::new(&project_path)
+        .join(&language_path)
+        .join("lexicon");
+    let mut index = build_index(&lexicon_dir)?;
+    save_index(&lexicon_dir, &mut index)?;
+    Ok(index.entries.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_lexicon(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("conlang-index-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn word(entry_id: &str, romanized: &str, gloss: &str) -> WordEntry {
+        serde_json::from_value(serde_json::json!({
+            "entry_id": entry_id,
+            "language_id": "lang",
+            "con_word_romanized": romanized,
+            "senses": [{ "sense_id": "s1", "pos_id": "noun", "gloss": gloss }],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn load_replays_delta_on_base_index() {
+        let dir = temp_lexicon("replay");
+        let mut base = ReverseIndex::default();
+        base.upsert(&word("w1", "aqua", "water"));
+        base.upsert(&word("w2", "ignis", "fire"));
+        save_index(&dir, &mut base).unwrap();
+
+        index_upsert_words(&dir, &[word("w1", "aqua", "river"), word("w3", "terra", "earth")]).unwrap();
+        index_remove_words(&dir, &["w2".to_string()]).unwrap();
+
+        let index = load_index(&dir).unwrap().expect("index with delta");
+        assert_eq!(index.generation, base.generation);
+        assert_eq!(index.entries.keys().collect::<Vec<_>>(), ["w1", "w3"]);
+        assert_eq!(index.entries["w1"].glosses, ["river"]);
+        assert!(!index.postings.contains_key("water"));
+        assert!(!index.postings.contains_key("fire"));
+        assert!(index.postings["earth"].contains("w3"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn load_rejects_mismatched_or_torn_delta() {
+        let dir = temp_lexicon("torn");
+        let mut base = ReverseIndex::default();
+        base.upsert(&word("w1", "aqua", "water"));
+        save_index(&dir, &mut base).unwrap();
+
+        let mut log = fs::read_to_string(delta_file(&dir)).unwrap();
+        log.push_str("{\"op\":\"upsert\",\"entry_id\":\"w2\"");
+        fs::write(delta_file(&dir), &log).unwrap();
+        assert!(load_index(&dir).unwrap().is_none());
+
+        fs::write(delta_file(&dir), "{\"generation\":1}\n").unwrap();
+        assert!(load_index(&dir).unwrap().is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn invalidate_removes_index_and_stops_delta_appends() {
+        let dir = temp_lexicon("invalidate");
+        let mut base = ReverseIndex::default();
+        base.upsert(&word("w1", "aqua", "water"));
+        save_index(&dir, &mut base).unwrap();
+
+        invalidate_index(&dir).unwrap();
+        assert!(!index_file(&dir).exists());
+        assert!(!delta_file(&dir).exists());
+        assert!(load_index(&dir).unwrap().is_none());
+
+        index_upsert_words(&dir, &[word("w2", "ignis", "fire")]).unwrap();
+        assert!(!delta_file(&dir).exists());
+        // 再次调用不报错
+        invalidate_index(&dir).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn stem_restores_dropped_e() {
+        assert_eq!(stem("loved"), "love");
+        assert_eq!(stem("loving"), "love");
+        assert_eq!(stem("loves"), "love");
+        assert_eq!(stem("love"), "love");
+        assert_eq!(stem("hoped"), "hope");
+        assert_eq!(stem("hopped"), "hop");
+    }
+
+    #[test]
+    fn stem_handles_ies_and_ied() {
+        assert_eq!(stem("lies"), "lie");
+        assert_eq!(stem("died"), "die");
+        assert_eq!(stem("cities"), "city");
+        assert_eq!(stem("cried"), "cry");
+    }
+
+    #[test]
+    fn stem_keeps_common_inflections() {
+        assert_eq!(stem("rivers"), "river");
+        assert_eq!(stem("running"), "run");
+        assert_eq!(stem("called"), "call");
+        assert_eq!(stem("added"), "add");
+        assert_eq!(stem("boxes"), "box");
+        assert_eq!(stem("classes"), "class");
+    }
+
+    #[test]
+    fn stem_leaves_non_inflected_words_alone() {
+        assert_eq!(stem("bring"), "bring");
+        assert_eq!(stem("string"), "string");
+        assert_eq!(stem("need"), "need");
+        assert_eq!(stem("speed"), "speed");
+        assert_eq!(stem("open"), "open");
+        assert_eq!(stem("opened"), "open");
+    }
+}
@@ -0,0 +1,280 @@
+use crate::commands::lexicon::parse_word_line;
+use crate::commands::oplog::is_internal_name;
+use crate::commands::project_lock::check_write_access;
+use crate::commands::timestamp::now_rfc3339;
+use crate::commands::watcher::content_hash;
+use crate::models::{ExtraFields, OperationLogEntry, WordEntry};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// ── 快照的内容寻址存储 ───────────────────────────────────
+//
+// .oplog/blobs/<前两位>/<sha256>   文件内容，按哈希去重，多个快照共享未变化的分桶
+// .oplog/manifests/<snap_id>.json  快照清单：相对语言目录的路径 → blob 哈希
+//
+// 快照被裁剪或回滚删除后，由 collect_garbage 清理不再被任何清单引用的 blob。
+// 旧版本的快照是完整复制的 .oplog/snap_<id>/ 目录，打开项目（持有写锁）或修改日志时
+// 由 migrate_legacy_snapshots 转换为清单 + blob；只读操作直接读取旧目录，不做转换。
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SnapshotManifest {
+    pub snapshot_id: String,
+    pub created_at: String,
+    pub components: Vec<String>,
+    /// 相对语言目录的文件路径（以 / 分隔）→ blob 哈希
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+    /// 目录（包括空目录），恢复时按原样重建
+    #[serde(default)]
+    pub dirs: BTreeSet<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+fn blobs_dir(oplog_base: &Path) -> PathBuf {
+    oplog_base.join("blobs")
+}
+
+fn manifests_dir(oplog_base: &Path) -> PathBuf {
+    oplog_base.join("manifests")
+}
+
+fn manifest_path(oplog_base: &Path, snapshot_id: &str) -> PathBuf {
+    manifests_dir(oplog_base).join(format!("{}.json", snapshot_id))
+}
+
+fn blob_path(oplog_base: &Path, hash: &str) -> PathBuf {
+    blobs_dir(oplog_base).join(&hash[..2.min(hash.len())]).join(hash)
+}
+
+/// 与 atomic_write 相同的 tmp + rename 写法，用于任意字节内容
+pub fn write_bytes(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    check_write_access(path)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let tmp = PathBuf::from(format!("{}.tmp", path.to_string_lossy()));
+    fs::write(&tmp, bytes).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
+
+/// 写入 blob（已存在则跳过），返回其哈希
+pub fn put_blob(oplog_base: &Path, bytes: &[u8]) -> Result<String, String> {
+    let hash = content_hash(bytes);
+    let path = blob_path(oplog_base, &hash);
+    if !path.exists() {
+        write_bytes(&path, bytes)?;
+    }
+    Ok(hash)
+}
+
+/// 读取 blob 并校验哈希，损坏时返回错误而不是恢复出错误的内容
+pub fn read_blob(oplog_base: &Path, hash: &str) -> Result<Vec<u8>, String> {
+    let bytes = fs::read(blob_path(oplog_base, hash))
+        .map_err(|e| format!("Snapshot blob {} is missing: {}", hash, e))?;
+    if content_hash(&bytes) != hash {
+        return Err(format!("Snapshot blob {} is corrupted", hash));
+    }
+    Ok(bytes)
+}
+
+/// 把 root/rel（文件或目录）加入清单，跳过其中的内部目录（如 lexicon/.index）
+fn add_tree(oplog_base: &Path, root: &Path, rel: &str, manifest: &mut SnapshotManifest) -> Result<(), String> {
+    let path = root.join(rel);
+    if path.is_dir() {
+        manifest.dirs.insert(rel.to_string());
+        let mut children: Vec<String> = Vec::new();
+        for entry in fs::read_dir(&path).map_err(|e| e.to_string())? {
+            let entry = entry.map_err(|e| e.to_string())?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !is_internal_name(&name) {
+                children.push(name);
+            }
+        }
+        children.sort();
+        for child in children {
+            add_tree(oplog_base, root, &format!("{}/{}", rel, child), manifest)?;
+        }
+    } else if path.is_file() {
+        let bytes = fs::read(&path).map_err(|e| e.to_string())?;
+        manifest.files.insert(rel.to_string(), put_blob(oplog_base, &bytes)?);
+    }
+    Ok(())
+}
+
+fn write_manifest(oplog_base: &Path, manifest: &SnapshotManifest) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(manifest).map_err(|e| e.to_string())?;
+    write_bytes(&manifest_path(oplog_base, &manifest.snapshot_id), content.as_bytes())
+}
+
+/// 为语言目录中选定的组件创建快照：写入 blob 与清单。不存在的组件只记录在 components 中
+pub fn store_snapshot(
+    oplog_base: &Path,
+    lang_dir: &Path,
+    snapshot_id: &str,
+    components: &[String],
+) -> Result<SnapshotManifest, String> {
+    let mut manifest = SnapshotManifest {
+        snapshot_id: snapshot_id.to_string(),
+        created_at: now_rfc3339(),
+        components: components.to_vec(),
+        ..Default::default()
+    };
+    for name in components {
+        add_tree(oplog_base, lang_dir, name, &mut manifest)?;
+    }
+    write_manifest(oplog_base, &manifest)?;
+    Ok(manifest)
+}
+
+pub fn load_manifest(oplog_base: &Path, snapshot_id: &str) -> Result<SnapshotManifest, String> {
+    let path = manifest_path(oplog_base, snapshot_id);
+    let content = fs::read_to_string(&path)
+        .map_err(|_| format!("Snapshot {} does not exist", snapshot_id))?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+fn in_component(path: &str, component: &str) -> bool {
+    path == component || path.starts_with(&format!("{}/", component))
+}
+
+/// 把清单中某个组件的内容还原到 dst_root 下（保持相对路径）。快照中不存在该组件时什么也不做
+pub fn materialize_component(
+    oplog_base: &Path,
+    manifest: &SnapshotManifest,
+    component: &str,
+    dst_root: &Path,
+) -> Result<(), String> {
+    for dir in manifest.dirs.iter().filter(|d| in_component(d, component)) {
+        fs::create_dir_all(dst_root.join(dir)).map_err(|e| e.to_string())?;
+    }
+    for (rel, hash) in manifest.files.iter().filter(|(p, _)| in_component(p, component)) {
+        write_bytes(&dst_root.join(rel), &read_blob(oplog_base, hash)?)?;
+    }
+    Ok(())
+}
+
+/// 快照中词典分桶里的全部词条（不含 .index 等内部目录）。无法解析的行跳过
+pub fn snapshot_words(oplog_base: &Path, manifest: &SnapshotManifest) -> Result<Vec<WordEntry>, String> {
+    let mut words = Vec::new();
+    for (rel, hash) in &manifest.files {
+        let Some(name) = rel.strip_prefix("lexicon/") else {
+            continue;
+        };
+        if name.contains('/') || !name.ends_with(".ndjson") {
+            continue;
+        }
+        let bytes = read_blob(oplog_base, hash)?;
+        let content = String::from_utf8_lossy(&bytes);
+        for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            if let Ok(word) = parse_word_line(line) {
+                words.push(word);
+            }
+        }
+    }
+    Ok(words)
+}
+
+/// 删除快照的清单（以及可能残留的旧格式目录）；blob 由 collect_garbage 统一清理
+pub fn remove_snapshot(oplog_base: &Path, snapshot_id: &str) {
+    let _ = fs::remove_file(manifest_path(oplog_base, snapshot_id));
+    let legacy = oplog_base.join(snapshot_id);
+    if legacy.is_dir() {
+        let _ = fs::remove_dir_all(legacy);
+    }
+}
+
+/// 清理不属于 live_entries 的清单、旧格式目录，以及不再被任何清单引用的 blob。返回删除的 blob 数
+pub fn collect_garbage(oplog_base: &Path, live_entries: &[OperationLogEntry]) -> Result<usize, String> {
+    let live: HashSet<&str> = live_entries.iter().map(|e| e.snapshot_dir.as_str()).collect();
+
+    let mut referenced: HashSet<String> = HashSet::new();
+    let manifests = manifests_dir(oplog_base);
+    if manifests.exists() {
+        for entry in fs::read_dir(&manifests).map_err(|e| e.to_string())? {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            let id = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if !live.contains(id.as_str()) {
+                check_write_access(&path)?;
+                let _ = fs::remove_file(&path);
+                continue;
+            }
+            let manifest = load_manifest(oplog_base, &id)?;
+            referenced.extend(manifest.files.into_values());
+        }
+    }
+
+    // 未被日志引用的旧格式快照目录
+    for entry in fs::read_dir(oplog_base).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if path.is_dir() && name.starts_with("snap_") && !live.contains(name.as_str()) {
+            check_write_access(&path)?;
+            let _ = fs::remove_dir_all(&path);
+        }
+    }
+
+    prune_blobs(oplog_base, &referenced)
+}
+
+/// 删除 base/blobs 下不在 referenced 中的 blob，返回删除数量
+pub fn prune_blobs(base: &Path, referenced: &HashSet<String>) -> Result<usize, String> {
+    let mut removed = 0;
+    let blobs = blobs_dir(base);
+    if !blobs.exists() {
+        return Ok(removed);
+    }
+    for shard in fs::read_dir(&blobs).map_err(|e| e.to_string())? {
+        let shard = shard.map_err(|e| e.to_string())?.path();
+        if !shard.is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(&shard).map_err(|e| e.to_string())? {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if !referenced.contains(&name) {
+                check_write_access(&path)?;
+                fs::remove_file(&path).map_err(|e| e.to_string())?;
+                removed += 1;
+            }
+        }
+        let _ = fs::remove_dir(&shard); // 仅在分片目录为空时成功
+    }
+    Ok(removed)
+}
+
+/// 把旧格式的 .oplog/snap_<id>/ 完整副本转换为清单 + blob，并删除原目录。返回转换的快照数
+pub fn migrate_legacy_snapshots(oplog_base: &Path, entries: &[OperationLogEntry]) -> Result<usize, String> {
+    let mut migrated = 0;
+    for entry in entries {
+        let legacy = oplog_base.join(&entry.snapshot_dir);
+        if !legacy.is_dir() || manifest_path(oplog_base, &entry.snapshot_dir).exists() {
+            continue;
+        }
+        check_write_access(&legacy)?;
+        let mut manifest = SnapshotManifest {
+            snapshot_id: entry.snapshot_dir.clone(),
+            created_at: entry.timestamp.clone(),
+            components: entry.components.clone(),
+            ..Default::default()
+        };
+        for name in &entry.components {
+            add_tree(oplog_base, &legacy, name, &mut manifest)?;
+        }
+        write_manifest(oplog_base, &manifest)?;
+        fs::remove_dir_all(&legacy).map_err(|e| e.to_string())?;
+        migrated += 1;
+    }
+    Ok(migrated)
+}
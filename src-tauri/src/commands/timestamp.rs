@@ -0,0 +1,199 @@
+use regex::Regex;
+use serde::Serialize;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// ── 统一时间戳 ───────────────────────────────────────────
+//
+// 所有由后端写入的时间戳都是 UTC 的 RFC 3339 字符串，精确到毫秒，
+// 与前端 `Date.toISOString()` 的格式一致（例如 2026-10-17T08:30:00.000Z），按字符串排序即按时间排序。
+// 历史数据中混有以下格式，读取或迁移时由 normalize_timestamp 统一：
+//   - 旧 oplog 的 Unix 秒字符串（"1760000000"）以及毫秒数
+//   - 旧注册表 now_iso 按 365 天/年、30 天/月推算的伪 ISO 时间（无小数秒，可能出现 13 月、2 月 30 日）
+//   - 带时区偏移、空格分隔或只有日期的 ISO 8601 写法
+
+/// 当前时间的 RFC 3339 字符串
+pub fn now_rfc3339() -> String {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default();
+    format_unix_millis(millis)
+}
+
+/// Unix 毫秒 → RFC 3339（UTC）
+pub fn format_unix_millis(millis: i64) -> String {
+    let days = millis.div_euclid(86_400_000);
+    let ms_of_day = millis.rem_euclid(86_400_000);
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        ms_of_day / 3_600_000,
+        ms_of_day / 60_000 % 60,
+        ms_of_day / 1000 % 60,
+        ms_of_day % 1000
+    )
+}
+
+/// 公历日期 → 1970-01-01 起的天数（Howard Hinnant 的 days_from_civil 算法）
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// 1970-01-01 起的天数 → 公历日期
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+fn iso_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"^(\d{4})-(\d{2})-(\d{2})(?:[Tt ](\d{2}):(\d{2})(?::(\d{2})(?:[.,](\d+))?)?\s*(Z|z|[+-]\d{2}(?::?\d{2})?)?)?$",
+        )
+        .expect("valid timestamp pattern")
+    })
+}
+
+/// 解析任意受支持格式的时间戳，返回 Unix 毫秒；无法识别（包括日期不存在）时返回 None
+pub fn parse_timestamp(value: &str) -> Option<i64> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if value.bytes().all(|b| b.is_ascii_digit()) {
+        let n: i64 = value.parse().ok()?;
+        // 10^11 秒约在公元 5138 年，更大的数只可能是毫秒
+        return Some(if n >= 100_000_000_000 { n } else { n * 1000 });
+    }
+
+    let caps = iso_pattern().captures(value)?;
+    let num = |i: usize| caps.get(i).and_then(|m| m.as_str().parse::<i64>().ok());
+    let year = num(1)?;
+    let month = num(2)? as u32;
+    let day = num(3)? as u32;
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    let hour = num(4).unwrap_or(0);
+    let minute = num(5).unwrap_or(0);
+    let second = num(6).unwrap_or(0);
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    let millis = caps
+        .get(7)
+        .map(|m| format!("{:0<3}", &m.as_str()[..m.as_str().len().min(3)]))
+        .and_then(|s| s.parse::<i64>().ok())
+        .unwrap_or(0);
+    // 没有时区标记的时间按 UTC 处理
+    let offset_minutes = match caps.get(8).map(|m| m.as_str()) {
+        None | Some("Z") | Some("z") => 0,
+        Some(tz) => {
+            let sign = if tz.starts_with('-') { -1 } else { 1 };
+            let digits: String = tz[1..].chars().filter(char::is_ascii_digit).collect();
+            let hours: i64 = digits.get(..2)?.parse().ok()?;
+            let minutes: i64 = digits.get(2..).filter(|m| !m.is_empty()).map_or(Some(0), |m| m.parse().ok())?;
+            sign * (hours * 60 + minutes)
+        }
+    };
+
+    let days = days_from_civil(year, month, day);
+    let local = ((days * 24 + hour) * 60 + minute) * 60_000 + second * 1000 + millis;
+    Some(local - offset_minutes * 60_000)
+}
+
+/// 统一为 RFC 3339；空字符串保持为空，无法识别的值原样返回
+pub fn normalize_timestamp(value: &str) -> String {
+    match parse_timestamp(value) {
+        Some(millis) => format_unix_millis(millis),
+        None => value.to_string(),
+    }
+}
+
+/// 旧版注册表 now_iso 写入的 last_opened：按 365 天/年、30 天/月把 Unix 秒拆成“日期”，
+/// 格式固定为 YYYY-MM-DDTHH:MM:SSZ（无小数秒）。按同样的规则反推回真实时间。
+/// 不符合该格式（例如新版写入的带毫秒时间）时返回 None
+pub fn decode_legacy_registry_timestamp(value: &str) -> Option<String> {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| {
+        Regex::new(r"^(\d{4})-(\d{2})-(\d{2})T(\d{2}):(\d{2}):(\d{2})Z$").expect("valid legacy pattern")
+    });
+    let caps = pattern.captures(value.trim())?;
+    let num = |i: usize| caps.get(i).and_then(|m| m.as_str().parse::<i64>().ok());
+    let (year, month, day) = (num(1)?, num(2)?, num(3)?);
+    if year < 1970 || !(1..=13).contains(&month) || !(1..=30).contains(&day) {
+        return None;
+    }
+    let days = (year - 1970) * 365 + (month - 1) * 30 + (day - 1);
+    let secs = days * 86_400 + num(4)? * 3600 + num(5)? * 60 + num(6)?;
+    Some(format_unix_millis(secs * 1000))
+}
+
+/// 写入前由后端盖时间戳。`fields` 取出记录上的 (created_at, updated_at)。
+/// - 首次写入（existing 为 None）：两者都是当前时间
+/// - 更新：created_at 沿用已有记录（为空时补当前时间）；内容有变化时 updated_at 为当前时间，
+///   只是原样保存时沿用已有记录的 updated_at
+///
+/// 前端传来的时间戳一律忽略
+pub fn stamp_timestamps<T: Serialize + Clone>(
+    record: &mut T,
+    existing: Option<&T>,
+    fields: fn(&mut T) -> (&mut String, &mut String),
+) {
+    let now = now_rfc3339();
+    let Some(existing) = existing else {
+        let (created, updated) = fields(record);
+        *created = now.clone();
+        *updated = now;
+        return;
+    };
+
+    let mut old = existing.clone();
+    let (old_created, old_updated) = {
+        let (created, updated) = fields(&mut old);
+        *created = normalize_timestamp(created);
+        *updated = normalize_timestamp(updated);
+        (created.clone(), updated.clone())
+    };
+    // 比较内容时忽略时间戳本身
+    let mut probe = record.clone();
+    {
+        let (created, updated) = fields(&mut probe);
+        *created = old_created.clone();
+        *updated = old_updated.clone();
+    }
+    let unchanged = serde_json::to_value(&probe).ok() == serde_json::to_value(&old).ok();
+
+    let (created, updated) = fields(record);
+    *created = if old_created.is_empty() { now.clone() } else { old_created };
+    *updated = if unchanged && !old_updated.is_empty() { old_updated } else { now };
+}
@@ -0,0 +1,297 @@
+use crate::commands::lexicon::read_all_words;
+use crate::commands::search_index::invalidate_index;
+use crate::models::{WordEntry, WorkspaceConfig};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::{command, AppHandle, Emitter, State};
+
+// ── 外部修改检测 ─────────────────────────────────────────
+//
+// 1. 监听当前项目目录，文件内容真正发生变化时向前端发送 "project-file-changed" 事件；
+// 2. 保存命令可携带加载时的内容哈希（expected_hash），磁盘内容已被外部修改时返回冲突错误而不是覆盖。
+//
+// KNOWN_STATE 记录文件最近一次已知的内容哈希（本应用写入的或已通知过前端的），
+// 监听回调据此过滤掉本应用自己的写入以及同一次修改触发的重复事件。
+// 条目匹配到对应的变化事件后只再保留 DEDUP_WINDOW 用于吸收重复事件；
+// 一直没有等到事件的条目（例如写入的文件不在监听目录内）在 PENDING_TTL 后丢弃。
+
+pub const FILE_CHANGED_EVENT: &str = "project-file-changed";
+
+const DEDUP_WINDOW: Duration = Duration::from_secs(2);
+const PENDING_TTL: Duration = Duration::from_secs(60);
+
+struct KnownState {
+    hash: Option<String>,
+    noted_at: Instant,
+    /// 第一次匹配到变化事件的时间；None 表示仍在等待事件
+    matched_at: Option<Instant>,
+}
+
+fn known_state() -> &'static Mutex<HashMap<PathBuf, KnownState>> {
+    static KNOWN_STATE: OnceLock<Mutex<HashMap<PathBuf, KnownState>>> = OnceLock::new();
+    KNOWN_STATE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn prune(known: &mut HashMap<PathBuf, KnownState>, now: Instant) {
+    known.retain(|_, state| match state.matched_at {
+        Some(matched_at) => now.duration_since(matched_at) < DEDUP_WINDOW,
+        None => now.duration_since(state.noted_at) < PENDING_TTL,
+    });
+}
+
+/// 统一路径形式：监听器给出的是规范化后的绝对路径（macOS 上 /var → /private/var）
+fn state_key(path: &Path) -> PathBuf {
+    match (path.parent().and_then(|p| fs::canonicalize(p).ok()), path.file_name()) {
+        (Some(parent), Some(name)) => parent.join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+fn remember(path: &Path, hash: Option<String>) {
+    let key = state_key(path);
+    if let Ok(mut known) = known_state().lock() {
+        let now = Instant::now();
+        prune(&mut known, now);
+        known.insert(
+            key,
+            KnownState {
+                hash,
+                noted_at: now,
+                matched_at: None,
+            },
+        );
+    }
+}
+
+/// atomic_write 在替换文件之前调用，使监听器忽略本应用自己的写入
+pub fn note_own_write(path: &Path, content: &str) {
+    note_own_bytes(path, content.as_bytes());
+}
+
+/// 同 note_own_write，用于按原始字节写入或移入的文件
+pub fn note_own_bytes(path: &Path, bytes: &[u8]) {
+    remember(path, Some(content_hash(bytes)));
+}
+
+/// 本应用删除文件之前调用
+pub fn note_own_removal(path: &Path) {
+    remember(path, None);
+}
+
+/// SHA-256 十六进制摘要
+pub fn content_hash(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// 文件当前内容的哈希；文件不存在时为 None
+pub fn file_hash(path: &Path) -> Result<Option<String>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let bytes = fs::read(path).map_err(|e| e.to_string())?;
+    Ok(Some(content_hash(&bytes)))
+}
+
+/// 词条的哈希：对其规范序列化（即分桶中的一行）取摘要
+pub fn word_hash(word: &WordEntry) -> String {
+    content_hash(serde_json::to_string(word).unwrap_or_default().as_bytes())
+}
+
+pub fn conflict_error(what: &str) -> String {
+    format!(
+        "Conflict: {} was changed on disk after it was loaded; reload it before saving",
+        what
+    )
+}
+
+/// 保存前检查：expected 为 None 时不检查；文件不存在对应空字符串
+pub fn check_expected_hash(path: &Path, expected: Option<&str>) -> Result<(), String> {
+    let Some(expected) = expected else {
+        return Ok(());
+    };
+    let current = file_hash(path)?.unwrap_or_default();
+    if current != expected {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        return Err(conflict_error(&name));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectFileChange {
+    /// 工作区文件（.conlang）本身变化时为 None
+    pub language_id: Option<String>,
+    pub language_path: Option<String>,
+    pub kind: String, // "lexicon_bucket" | "grammar" | "phonology" | "sca" | "corpus" | "workspace"
+    /// 相对项目目录的路径
+    pub file: String,
+    pub change: String, // "created" | "modified" | "removed"
+    /// 变化后的内容哈希，删除时为 None
+    pub content_hash: Option<String>,
+}
+
+/// 由 lib.rs 通过 `.manage()` 注册；同一时间只监听一个项目，替换或丢弃 watcher 即停止监听
+#[derive(Default)]
+pub struct ProjectWatcher(Mutex<Option<RecommendedWatcher>>);
+
+/// 判断语言目录内的文件属于哪一类；内部目录（.oplog、.index 等）与临时文件返回 None
+fn classify(rest: &Path) -> Option<&'static str> {
+    let parts: Vec<String> = rest
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    if parts.iter().any(|p| p.starts_with('.')) {
+        return None;
+    }
+    match parts.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["phonology.json"] => Some("phonology"),
+        ["grammar.json"] => Some("grammar"),
+        ["sca_rules.json"] => Some("sca"),
+        ["lexicon", name] if name.starts_with("lexicon_") && name.ends_with(".ndjson") => {
+            Some("lexicon_bucket")
+        }
+        ["corpus", name] if name.ends_with(".json") => Some("corpus"),
+        _ => None,
+    }
+}
+
+fn describe_change(project_dir: &Path, conlang_file: &Path, path: &Path, created: bool) -> Option<ProjectFileChange> {
+    let path = state_key(path);
+    let mut change = ProjectFileChange {
+        language_id: None,
+        language_path: None,
+        kind: "workspace".to_string(),
+        file: path
+            .strip_prefix(project_dir)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        change: String::new(),
+        content_hash: None,
+    };
+
+    if path != conlang_file {
+        let rel = path.strip_prefix(project_dir).ok()?;
+        let content = fs::read_to_string(conlang_file).ok()?;
+        let ws: WorkspaceConfig = serde_json::from_str(&content).ok()?;
+        // 语言目录可能嵌套，取最长匹配
+        let language = ws
+            .languages
+            .iter()
+            .filter(|l| rel.starts_with(&l.path))
+            .max_by_key(|l| l.path.len())?;
+        let rest = rel.strip_prefix(&language.path).ok()?;
+        change.kind = classify(rest)?.to_string();
+        change.language_id = Some(language.language_id.clone());
+        change.language_path = Some(language.path.clone());
+    }
+
+    // 读取当前内容与比较、更新记录都在锁内完成，避免与 note_own_write 交错
+    let mut known = known_state().lock().ok()?;
+    let now = Instant::now();
+    prune(&mut known, now);
+    let current = file_hash(&path).ok()?;
+    if let Some(state) = known.get_mut(&path) {
+        if state.hash == current {
+            // 本应用自己的写入，或同一次修改的重复事件
+            state.matched_at.get_or_insert(now);
+            return None;
+        }
+    }
+    let previous = known.get(&path).map(|state| state.hash.clone());
+    change.change = match (&previous, &current) {
+        (_, None) => "removed",
+        (Some(Some(_)), Some(_)) => "modified",
+        (Some(None), Some(_)) => "created",
+        // 之前没有记录：以事件类型为准
+        (None, Some(_)) if created => "created",
+        (None, Some(_)) => "modified",
+    }
+    .to_string();
+    change.content_hash = current.clone();
+    known.insert(
+        path,
+        KnownState {
+            hash: current,
+            noted_at: now,
+            matched_at: Some(now),
+        },
+    );
+    Some(change)
+}
+
+/// 开始监听项目目录（替换之前的监听）。文件变化时发送 "project-file-changed" 事件
+#[command]
+pub fn watch_project(
+    app: AppHandle,
+    watcher: State<'_, ProjectWatcher>,
+    project_path: String,
+    conlang_file_path: String,
+) -> Result<(), String> {
+    let project_dir = fs::canonicalize(&project_path).map_err(|e| e.to_string())?;
+    let conlang_file = state_key(Path::new(&conlang_file_path));
+
+    let dir = project_dir.clone();
+    let mut inner = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let Ok(event) = res else {
+            return;
+        };
+        if matches!(event.kind, EventKind::Access(_) | EventKind::Any | EventKind::Other) {
+            return;
+        }
+        let created = matches!(event.kind, EventKind::Create(_));
+        for path in &event.paths {
+            if let Some(change) = describe_change(&dir, &conlang_file, path, created) {
+                // 外部改写的分桶没有经过 save_word，反查索引已过期
+                if let (Some(language_path), "lexicon_bucket") = (&change.language_path, change.kind.as_str()) {
+                    if let Err(e) = invalidate_index(&dir.join(language_path).join("lexicon")) {
+                        eprintln!("清除反查索引失败: {}", e);
+                    }
+                }
+                if let Err(e) = app.emit(FILE_CHANGED_EVENT, change) {
+                    eprintln!("发送文件变化事件失败: {}", e);
+                }
+            }
+        }
+    })
+    .map_err(|e| e.to_string())?;
+    inner
+        .watch(&project_dir, RecursiveMode::Recursive)
+        .map_err(|e| e.to_string())?;
+
+    let mut guard = watcher.0.lock().map_err(|e| e.to_string())?;
+    *guard = Some(inner);
+    Ok(())
+}
+
+/// 停止监听（关闭项目时调用）
+#[command]
+pub fn unwatch_project(watcher: State<'_, ProjectWatcher>) -> Result<(), String> {
+    watcher.0.lock().map_err(|e| e.to_string())?.take();
+    Ok(())
+}
+
+/// 读取文件当前内容的哈希（不存在时为空字符串），前端加载配置后保存它，保存时作为 expected_hash 传回
+#[command]
+pub fn load_file_hash(project_path: String, language_path: String, file: String) -> Result<String, String> {
+    let path = Path::new(&project_path).join(&language_path).join(&file);
+    Ok(file_hash(&path)?.unwrap_or_default())
+}
+
+/// 所有词条的哈希：entry_id → hash，用于 save_words 的 expected_hash
+#[command]
+pub fn load_word_hashes(project_path: String, language_path: String) -> Result<BTreeMap<String, String>, String> {
+    let lexicon_dir = Path::new(&project_path).join(&language_path).join("lexicon");
+    Ok(read_all_words(&lexicon_dir)?
+        .iter()
+        .map(|w| (w.entry_id.clone(), word_hash(w)))
+        .collect())
+}
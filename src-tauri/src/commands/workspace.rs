@@ -1,5 +1,8 @@
+use crate::commands::journal::begin;
 use crate::commands::lexicon::atomic_write;
-use crate::models::{CreateProjectResult, GrammarConfig, SCAConfig, WorkspaceConfig};
+use crate::models::{
+    CreateProjectResult, ExtraFields, GrammarConfig, SCAConfig, WorkspaceConfig, CURRENT_SCHEMA_VERSION,
+};
 use std::fs;
 use std::path::Path;
 use tauri::command;
@@ -17,8 +20,14 @@ pub fn load_workspace(conlang_file_path: String) -> Result<WorkspaceConfig, Stri
 
 /// Save workspace config to a .conlang file (absolute path).
 #[command]
-pub fn save_workspace(conlang_file_path: String, config: WorkspaceConfig) -> Result<(), String> {
+pub fn save_workspace(
+    conlang_file_path: String,
+    config: WorkspaceConfig,
+    transaction_id: Option<String>,
+) -> Result<(), String> {
     let path = Path::new(&conlang_file_path);
+    let project_root = path.parent().unwrap_or(Path::new(""));
+    let _journal = begin(project_root, "save_workspace", transaction_id);
     let content = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
     atomic_write(path, &content)
 }
@@ -63,7 +72,9 @@ pub fn create_new_project(
             name: lang_name.clone(),
             path: lang_dir_name.clone(),
             parent_id: None,
+            extra: ExtraFields::new(),
         }],
+        extra: ExtraFields::new(),
     };
 
     // Create language data directory scaffold
@@ -73,6 +84,7 @@ pub fn create_new_project(
     fs::create_dir_all(lang_dir.join("corpus")).map_err(|e| e.to_string())?;
 
     let phonology_json = serde_json::json!({
+        "schema_version": CURRENT_SCHEMA_VERSION,
         "language_id": lang_id,
         "phoneme_inventory": {
             "consonants": [],
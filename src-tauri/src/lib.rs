@@ -1,4 +1,5 @@
 pub mod commands;
+pub mod merge;
 pub mod models;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -9,11 +10,28 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_process::init())
+        .manage(commands::watcher::ProjectWatcher::default())
+        .setup(|app| {
+            commands::project_lock::set_app_handle(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             commands::lexicon::load_all_words,
+            commands::lexicon::query_words,
             commands::lexicon::save_word,
             commands::lexicon::delete_word,
+            commands::lexicon::save_words,
+            commands::lexicon::delete_words,
+            commands::lexicon::load_quarantine_report,
+            commands::history::list_word_revisions,
+            commands::history::diff_word_revisions,
+            commands::history::restore_word_revision,
+            commands::history::set_max_revisions,
             commands::lexicon::count_words_all_languages,
+            commands::bucketing::load_bucketing_config,
+            commands::bucketing::migrate_lexicon_bucketing,
+            commands::search_index::reverse_search,
+            commands::search_index::rebuild_reverse_index,
             commands::phonology::load_phonology,
             commands::phonology::save_phonology,
             commands::grammar::load_grammar,
@@ -25,6 +43,15 @@ pub fn run() {
             commands::fork::fork_language,
             commands::fork::delete_language,
             commands::fork::migrate_inherited_lexicon_links,
+            commands::integrity::check_lexicon,
+            commands::schema::migrate_language_files,
+            commands::watcher::watch_project,
+            commands::watcher::unwatch_project,
+            commands::watcher::load_file_hash,
+            commands::watcher::load_word_hashes,
+            commands::project_lock::acquire_project_lock,
+            commands::project_lock::release_project_lock,
+            commands::project_lock::get_project_lock_status,
             commands::sca::load_sca,
             commands::sca::save_sca,
             commands::corpus::load_corpus_index,
@@ -42,11 +69,41 @@ pub fn run() {
             commands::registry::set_active_family,
             commands::registry::validate_conlang_file,
             commands::oplog::load_operation_log,
+            commands::oplog::migrate_operation_logs,
             commands::oplog::create_snapshot,
             commands::oplog::rollback_to_snapshot,
             commands::oplog::set_max_snapshots,
+            commands::oplog::diff_snapshot,
+            commands::oplog::restore_snapshot_entries,
+            commands::journal::load_journal_status,
+            commands::journal::undo,
+            commands::journal::redo,
+            commands::journal::clear_journal,
+            commands::journal::set_max_journal_entries,
+            commands::git::git_status,
+            commands::git::git_init,
+            commands::git::git_checkpoint,
+            commands::git::git_log,
+            commands::git::git_revert_language,
+            commands::git::list_merge_conflicts,
+            commands::git::resolve_merge_conflict,
+            commands::grammar_refs::preview_grammar_ref_change,
+            commands::grammar_refs::apply_grammar_ref_change,
+            commands::language_ops::rename_language,
+            commands::language_ops::reparent_language,
+            commands::language_merge::merge_languages,
+            commands::pull_sync::pull_from_parent,
+            commands::borrowing::borrow_words,
+            commands::cognates::build_cognate_sets,
+            commands::cognates::export_cognate_sets,
             commands::workspace::show_in_folder
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_app, event| {
+            // 退出时释放本实例持有的项目锁
+            if let tauri::RunEvent::Exit = event {
+                commands::project_lock::release_all();
+            }
+        });
 }
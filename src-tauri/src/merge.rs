@@ -0,0 +1,684 @@
+use crate::commands::lexicon::parse_word_line;
+use crate::models::{GrammarConfig, PhonologyConfig, SCAConfig, WordEntry};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+// ── Git 合并驱动与 textconv ──────────────────────────────
+//
+// 由配套的 conlang-merge 可执行文件调用（见 src/bin/conlang-merge.rs），git_init 会在仓库中注册：
+//   merge.conlang.driver  = conlang-merge merge %O %A %B %P
+//   diff.conlang.textconv = conlang-merge textconv
+//
+// - 词典分桶（*.ndjson）：按 entry_id 做三方合并；双方都改了同一词条时再逐字段合并
+// - JSON 配置（grammar.json、sca_rules.json 等）：逐字段三方合并，对象数组按 rule_id / pos_id / dim_id 等 ID 对齐
+//
+// 无法自动解决的字段保留本地（ours）的值，并在所在对象上写入 `_merge_conflicts` 记录双方的值，
+// 文件仍是合法的 JSON/NDJSON，应用可以照常打开；git 看到非零退出码会把文件标记为冲突。
+// 应用通过 list_merge_conflicts 列出这些记录，resolve_merge_conflict 选定一方后写回字段并删除记录（见 commands/git.rs）。
+
+/// 冲突记录所在的字段；WordEntry / GrammarConfig / SCAConfig 的未知字段会原样保留
+pub const CONFLICTS_KEY: &str = "_merge_conflicts";
+
+/// 对象数组用来对齐元素的 ID 字段，按优先级排列
+const ID_KEYS: &[&str] = &[
+    "entry_id",
+    "sense_id",
+    "rule_id",
+    "ruleset_id",
+    "dim_id",
+    "val_id",
+    "pos_id",
+    "class_id",
+    "slot_id",
+    "paradigm_id",
+    "chapter_id",
+    "corpus_id",
+    "line_id",
+    "token_id",
+    "tone_id",
+    "map_id",
+    "language_id",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MergeConflict {
+    /// 相对冲突记录所在对象的字段路径，例如 "senses[s1].gloss"；整个条目为 "entry"
+    pub field: String,
+    pub base: Option<Value>,
+    pub ours: Option<Value>,
+    pub theirs: Option<Value>,
+}
+
+#[derive(Debug, Default)]
+pub struct MergeOutcome {
+    pub content: String,
+    pub conflicts: usize,
+}
+
+fn join_path(prefix: &str, field: &str) -> String {
+    if prefix.is_empty() {
+        field.to_string()
+    } else {
+        format!("{}.{}", prefix, field)
+    }
+}
+
+/// 数组中所有元素都是带有唯一字符串 key 的对象时，返回可用于对齐的 key
+fn id_key(arrays: &[&Vec<Value>]) -> Option<&'static str> {
+    let elements: Vec<&Value> = arrays.iter().flat_map(|a| a.iter()).collect();
+    if elements.is_empty() {
+        return None;
+    }
+    ID_KEYS.iter().copied().find(|key| {
+        arrays.iter().all(|array| {
+            let mut seen = BTreeSet::new();
+            array
+                .iter()
+                .all(|v| v.get(*key).and_then(Value::as_str).is_some_and(|id| seen.insert(id)))
+        })
+    })
+}
+
+fn keyed<'a>(array: Option<&'a Vec<Value>>, key: &str) -> BTreeMap<&'a str, &'a Value> {
+    array
+        .into_iter()
+        .flatten()
+        .filter_map(|v| Some((v.get(key)?.as_str()?, v)))
+        .collect()
+}
+
+/// 三方合并一个值。None 表示该字段/元素不存在（或被删除）。冲突时取 ours 并记录
+pub fn merge_value(
+    base: Option<&Value>,
+    ours: Option<&Value>,
+    theirs: Option<&Value>,
+    path: &str,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Option<Value> {
+    if ours == theirs {
+        return ours.cloned();
+    }
+    if base == ours {
+        return theirs.cloned();
+    }
+    if base == theirs {
+        return ours.cloned();
+    }
+
+    match (ours, theirs) {
+        (Some(Value::Object(o)), Some(Value::Object(t))) => {
+            let empty = Map::new();
+            let b = base.and_then(Value::as_object).unwrap_or(&empty);
+            let mut merged = Map::new();
+            // 字段顺序：ours 的字段在前，theirs 新增的字段在后
+            let keys: Vec<&String> = o.keys().chain(t.keys().filter(|k| !o.contains_key(*k))).collect();
+            for key in keys {
+                if key == CONFLICTS_KEY {
+                    continue;
+                }
+                if let Some(v) = merge_value(b.get(key), o.get(key), t.get(key), &join_path(path, key), conflicts) {
+                    merged.insert(key.clone(), v);
+                }
+            }
+            Some(Value::Object(merged))
+        }
+        (Some(Value::Array(o)), Some(Value::Array(t))) => {
+            let b = base.and_then(Value::as_array);
+            let mut arrays = vec![o, t];
+            arrays.extend(b);
+            let Some(key) = id_key(&arrays) else {
+                return conflict(base, ours, theirs, path, conflicts);
+            };
+            let (bm, om, tm) = (keyed(b, key), keyed(Some(o), key), keyed(Some(t), key));
+            // 元素顺序：按 ours 的顺序，theirs 新增的元素追加在后
+            let mut ids: Vec<&str> = o.iter().filter_map(|v| v.get(key)?.as_str()).collect();
+            ids.extend(t.iter().filter_map(|v| v.get(key)?.as_str()).filter(|id| !om.contains_key(id)));
+            let merged = ids
+                .into_iter()
+                .filter_map(|id| {
+                    let element_path = format!("{}[{}]", path, id);
+                    merge_value(bm.get(id).copied(), om.get(id).copied(), tm.get(id).copied(), &element_path, conflicts)
+                })
+                .collect();
+            Some(Value::Array(merged))
+        }
+        _ => conflict(base, ours, theirs, path, conflicts),
+    }
+}
+
+fn conflict(
+    base: Option<&Value>,
+    ours: Option<&Value>,
+    theirs: Option<&Value>,
+    path: &str,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Option<Value> {
+    conflicts.push(MergeConflict {
+        field: if path.is_empty() { "entry".to_string() } else { path.to_string() },
+        base: base.cloned(),
+        ours: ours.cloned(),
+        theirs: theirs.cloned(),
+    });
+    // 一方删除、另一方修改时保留修改后的版本，避免静默丢失内容
+    ours.or(theirs).cloned()
+}
+
+/// 把冲突记录写到对象的 `_merge_conflicts` 字段
+fn attach_conflicts(value: &mut Value, conflicts: Vec<MergeConflict>) {
+    if conflicts.is_empty() {
+        return;
+    }
+    if let Value::Object(map) = value {
+        let records = serde_json::to_value(conflicts).unwrap_or_default();
+        map.insert(CONFLICTS_KEY.to_string(), records);
+    }
+}
+
+// ── 词典分桶 ─────────────────────────────────────────────
+
+/// 分桶内容：能解析的词条按 entry_id（规范化后的 JSON），解析失败的行原样保留。
+/// 缺少 entry_id 的词条按规范化后的整行内容对齐，不会彼此合并成一条
+fn parse_bucket(content: &str) -> (BTreeMap<String, Value>, Vec<String>) {
+    let mut words = BTreeMap::new();
+    let mut malformed = Vec::new();
+    for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        match parse_word_line(line).and_then(|w| serde_json::to_value(&w).map_err(|e| e.to_string())) {
+            Ok(value) => {
+                let id = match value.get("entry_id").and_then(Value::as_str) {
+                    Some(id) if !id.is_empty() => id.to_string(),
+                    // 以 NUL 开头，不会与真实的 entry_id 冲突
+                    _ => format!("\0{}", value),
+                };
+                words.insert(id, value);
+            }
+            Err(_) => malformed.push(line.to_string()),
+        }
+    }
+    (words, malformed)
+}
+
+/// 按 entry_id 三方合并分桶。结果与 write_bucket 的格式一致：按拼写排序、每行一个词条
+pub fn merge_ndjson(base: &str, ours: &str, theirs: &str) -> Result<MergeOutcome, String> {
+    let (b, _) = parse_bucket(base);
+    let (o, o_bad) = parse_bucket(ours);
+    let (t, t_bad) = parse_bucket(theirs);
+
+    let ids: BTreeSet<&String> = b.keys().chain(o.keys()).chain(t.keys()).collect();
+    let mut outcome = MergeOutcome::default();
+    let mut words: Vec<WordEntry> = Vec::new();
+    for id in ids {
+        let mut conflicts = Vec::new();
+        let Some(mut merged) = merge_value(b.get(id), o.get(id), t.get(id), "", &mut conflicts) else {
+            continue;
+        };
+        outcome.conflicts += conflicts.len();
+        attach_conflicts(&mut merged, conflicts);
+        let word: WordEntry = serde_json::from_value(merged).map_err(|e| format!("{}: {}", id.trim_start_matches('\0'), e))?;
+        words.push(word);
+    }
+    words.sort_by(|a, b| a.con_word_romanized.cmp(&b.con_word_romanized));
+
+    let mut lines = Vec::new();
+    for word in &words {
+        lines.push(serde_json::to_string(word).map_err(|e| e.to_string())?);
+    }
+    // 无法解析的行不做合并，双方的都保留，交给应用的隔离区处理
+    lines.extend(o_bad.iter().cloned());
+    lines.extend(t_bad.into_iter().filter(|l| !o_bad.contains(l)));
+    outcome.content = if lines.is_empty() { String::new() } else { lines.join("\n") + "\n" };
+    Ok(outcome)
+}
+
+// ── JSON 配置 ────────────────────────────────────────────
+
+/// 已知的配置文件按模型结构体重新序列化，使字段顺序与应用保存时一致（冲突记录作为未知字段排在最后）
+pub fn to_pretty_json(path_hint: &str, value: Value) -> Result<String, String> {
+    fn via<T: serde::de::DeserializeOwned + Serialize>(value: &Value) -> Option<String> {
+        let parsed: T = serde_json::from_value(value.clone()).ok()?;
+        serde_json::to_string_pretty(&parsed).ok()
+    }
+    let name = Path::new(path_hint).file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    let typed = match name {
+        "grammar.json" => via::<GrammarConfig>(&value),
+        "sca_rules.json" => via::<SCAConfig>(&value),
+        "phonology.json" => via::<PhonologyConfig>(&value),
+        _ => None,
+    };
+    match typed {
+        Some(content) => Ok(content),
+        None => serde_json::to_string_pretty(&value).map_err(|e| e.to_string()),
+    }
+}
+
+/// 三方合并 JSON 文档（grammar.json、sca_rules.json 等），冲突记录写在根对象上
+pub fn merge_json(path_hint: &str, base: &str, ours: &str, theirs: &str) -> Result<MergeOutcome, String> {
+    let parse = |s: &str| -> Result<Option<Value>, String> {
+        if s.trim().is_empty() {
+            Ok(None)
+        } else {
+            serde_json::from_str(s).map(Some).map_err(|e| e.to_string())
+        }
+    };
+    let (b, o, t) = (parse(base)?, parse(ours)?, parse(theirs)?);
+    let mut conflicts = Vec::new();
+    let Some(mut merged) = merge_value(b.as_ref(), o.as_ref(), t.as_ref(), "", &mut conflicts) else {
+        return Ok(MergeOutcome::default());
+    };
+    let count = conflicts.len();
+    attach_conflicts(&mut merged, conflicts);
+    Ok(MergeOutcome {
+        content: to_pretty_json(path_hint, merged)?,
+        conflicts: count,
+    })
+}
+
+/// 按文件名选择合并方式
+pub fn merge_file(path_hint: &str, base: &str, ours: &str, theirs: &str) -> Result<MergeOutcome, String> {
+    if path_hint.ends_with(".ndjson") {
+        merge_ndjson(base, ours, theirs)
+    } else {
+        merge_json(path_hint, base, ours, theirs)
+    }
+}
+
+// ── 解决冲突 ─────────────────────────────────────────────
+
+/// 对象上记录的冲突；没有记录或格式不对时为空
+pub fn conflicts_of(value: &Value) -> Vec<MergeConflict> {
+    value
+        .get(CONFLICTS_KEY)
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+#[derive(Debug, PartialEq)]
+enum PathSegment {
+    Field(String),
+    Element(String),
+}
+
+/// 解析冲突记录的字段路径："rule_sets[rs1].rules[r1].target" → 字段 / 数组元素 ID 序列
+fn parse_path(path: &str) -> Result<Vec<PathSegment>, String> {
+    let mut segments = Vec::new();
+    let mut rest = path;
+    while !rest.is_empty() {
+        if let Some(inner) = rest.strip_prefix('[') {
+            let end = inner.find(']').ok_or_else(|| format!("Invalid conflict path '{}'", path))?;
+            segments.push(PathSegment::Element(inner[..end].to_string()));
+            rest = &inner[end + 1..];
+        } else {
+            let end = rest.find(['.', '[']).unwrap_or(rest.len());
+            if end > 0 {
+                segments.push(PathSegment::Field(rest[..end].to_string()));
+            }
+            rest = &rest[end..];
+        }
+        rest = rest.strip_prefix('.').unwrap_or(rest);
+    }
+    Ok(segments)
+}
+
+/// 把 value 写到路径上（None 表示删除该字段或元素）
+fn set_path(node: &mut Value, segments: &[PathSegment], value: Option<Value>, path: &str) -> Result<(), String> {
+    let missing = || format!("Conflict field '{}' no longer exists", path);
+    // 数组中 ID 为 id 的元素下标，ID 字段与合并时的对齐方式一致
+    let element_index = |array: &Vec<Value>, id: &str| {
+        let key = id_key(&[array])?;
+        array.iter().position(|v| v.get(key).and_then(Value::as_str) == Some(id))
+    };
+    let Some((last, parents)) = segments.split_last() else {
+        return Err(missing());
+    };
+    let mut node = node;
+    for segment in parents {
+        node = match segment {
+            PathSegment::Field(name) => node.get_mut(name.as_str()),
+            PathSegment::Element(id) => node
+                .as_array_mut()
+                .and_then(|array| element_index(array, id).map(move |i| &mut array[i])),
+        }
+        .ok_or_else(missing)?;
+    }
+    match last {
+        PathSegment::Field(name) => {
+            let map = node.as_object_mut().ok_or_else(missing)?;
+            match value {
+                Some(v) => {
+                    map.insert(name.clone(), v);
+                }
+                None => {
+                    map.remove(name);
+                }
+            }
+        }
+        PathSegment::Element(id) => {
+            let array = node.as_array_mut().ok_or_else(missing)?;
+            match (element_index(array, id), value) {
+                (Some(i), Some(v)) => array[i] = v,
+                (Some(i), None) => {
+                    array.remove(i);
+                }
+                (None, Some(v)) => array.push(v),
+                (None, None) => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 用冲突记录中的一方（"ours" | "theirs" | "base"）解决 target 上字段为 field 的冲突：
+/// 把该方的值写回字段并删除这条记录。返回 false 表示选中的一方删除了整个对象（field 为 "entry"）
+pub fn resolve_conflict(target: &mut Value, field: &str, choice: &str) -> Result<bool, String> {
+    let mut conflicts = conflicts_of(target);
+    let index = conflicts
+        .iter()
+        .position(|c| c.field == field)
+        .ok_or_else(|| format!("No merge conflict recorded for '{}'", field))?;
+    let record = conflicts.remove(index);
+    let chosen = match choice {
+        "ours" => record.ours,
+        "theirs" => record.theirs,
+        "base" => record.base,
+        other => return Err(format!("Unknown conflict resolution '{}'", other)),
+    };
+
+    if field == "entry" {
+        let Some(mut whole) = chosen else {
+            return Ok(false);
+        };
+        if let Value::Object(map) = &mut whole {
+            map.remove(CONFLICTS_KEY);
+        }
+        *target = whole;
+    } else {
+        set_path(target, &parse_path(field)?, chosen, field)?;
+    }
+    if let Value::Object(map) = target {
+        map.remove(CONFLICTS_KEY);
+    }
+    attach_conflicts(target, conflicts);
+    Ok(true)
+}
+
+// ── textconv ─────────────────────────────────────────────
+
+/// 供 git diff 使用的可读文本：分桶中每个词条展开为缩进的 JSON，前面加上 "## 拼写 [entry_id]" 标题；
+/// JSON 文件按缩进格式输出。无法解析的内容原样输出
+pub fn textconv(path_hint: &str, content: &str) -> String {
+    if path_hint.ends_with(".ndjson") {
+        let mut out = String::new();
+        for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            match parse_word_line(line) {
+                Ok(word) => {
+                    out.push_str(&format!("## {} [{}]\n", word.con_word_romanized, word.entry_id));
+                    out.push_str(&serde_json::to_string_pretty(&word).unwrap_or_else(|_| line.to_string()));
+                    out.push_str("\n\n");
+                }
+                Err(_) => {
+                    out.push_str(line);
+                    out.push('\n');
+                }
+            }
+        }
+        return out;
+    }
+    match serde_json::from_str::<Value>(content).and_then(|v| serde_json::to_string_pretty(&v)) {
+        Ok(pretty) => pretty + "\n",
+        Err(_) => content.to_string(),
+    }
+}
+
+// ── 命令行入口 ───────────────────────────────────────────
+
+const USAGE: &str = "usage:\n  conlang-merge merge <base> <ours> <theirs> [<path>]\n  conlang-merge textconv <file>";
+
+/// conlang-merge 的入口，返回进程退出码：0 = 成功，1 = 有冲突（结果已写入 ours），2 = 出错
+pub fn run_cli(args: &[String]) -> i32 {
+    let result = match args.first().map(String::as_str) {
+        Some("merge") if args.len() >= 4 => {
+            let (base, ours, theirs) = (&args[1], &args[2], &args[3]);
+            // %P 是文件在仓库中的路径；ours 是 git 生成的临时文件，名字不带原扩展名
+            let hint = args.get(4).unwrap_or(ours);
+            run_merge(hint, Path::new(base), Path::new(ours), Path::new(theirs))
+        }
+        Some("textconv") if args.len() >= 2 => fs::read_to_string(&args[1])
+            .map(|content| {
+                print!("{}", textconv(&args[1], &content));
+                0
+            })
+            .map_err(|e| e.to_string()),
+        _ => Err(USAGE.to_string()),
+    };
+    result.unwrap_or_else(|e| {
+        eprintln!("conlang-merge: {}", e);
+        2
+    })
+}
+
+fn run_merge(hint: &str, base: &Path, ours: &Path, theirs: &Path) -> Result<i32, String> {
+    let read = |p: &Path| fs::read_to_string(p).map_err(|e| format!("{}: {}", p.display(), e));
+    let outcome = merge_file(hint, &read(base)?, &read(ours)?, &read(theirs)?)?;
+    fs::write(ours, &outcome.content).map_err(|e| e.to_string())?;
+    if outcome.conflicts > 0 {
+        eprintln!("conlang-merge: {} conflicting field(s) in {}", outcome.conflicts, hint);
+        Ok(1)
+    } else {
+        Ok(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn word(entry_id: &str, romanized: &str, gloss: &str) -> Value {
+        json!({
+            "entry_id": entry_id,
+            "language_id": "lang",
+            "con_word_romanized": romanized,
+            "senses": [{ "sense_id": "s1", "pos_id": "n", "gloss": gloss }],
+        })
+    }
+
+    fn bucket(words: &[Value]) -> String {
+        words.iter().map(|w| w.to_string() + "\n").collect()
+    }
+
+    /// 合并结果中的词条，按 entry_id 索引（缺少 entry_id 的按拼写）
+    fn merged_words(content: &str) -> BTreeMap<String, Value> {
+        content
+            .lines()
+            .map(|l| serde_json::from_str::<Value>(l).unwrap())
+            .map(|v| {
+                let id = v["entry_id"].as_str().filter(|id| !id.is_empty());
+                let key = id.unwrap_or(v["con_word_romanized"].as_str().unwrap()).to_string();
+                (key, v)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ndjson_merges_edits_to_different_fields() {
+        let base = bucket(&[word("w1", "aqua", "water")]);
+        let ours = word("w1", "aqua", "fresh water");
+        let mut theirs = word("w1", "aqua", "water");
+        theirs["phonetic_ipa"] = json!("akwa");
+        let outcome = merge_ndjson(&base, &bucket(&[ours]), &bucket(&[theirs])).unwrap();
+        assert_eq!(outcome.conflicts, 0);
+        let words = merged_words(&outcome.content);
+        assert_eq!(words["w1"]["senses"][0]["gloss"], "fresh water");
+        assert_eq!(words["w1"]["phonetic_ipa"], "akwa");
+        assert!(words["w1"].get(CONFLICTS_KEY).is_none());
+    }
+
+    #[test]
+    fn ndjson_records_field_conflict_and_keeps_ours() {
+        let base = bucket(&[word("w1", "aqua", "water")]);
+        let ours = bucket(&[word("w1", "aqua", "river")]);
+        let theirs = bucket(&[word("w1", "aqua", "lake")]);
+        let outcome = merge_ndjson(&base, &ours, &theirs).unwrap();
+        assert_eq!(outcome.conflicts, 1);
+        let words = merged_words(&outcome.content);
+        assert_eq!(words["w1"]["senses"][0]["gloss"], "river");
+        assert_eq!(
+            conflicts_of(&words["w1"]),
+            vec![MergeConflict {
+                field: "senses[s1].gloss".to_string(),
+                base: Some(json!("water")),
+                ours: Some(json!("river")),
+                theirs: Some(json!("lake")),
+            }]
+        );
+    }
+
+    #[test]
+    fn delete_vs_modify_keeps_the_modified_entry() {
+        let base = bucket(&[word("w1", "aqua", "water"), word("w2", "ignis", "fire")]);
+        let ours = bucket(&[word("w2", "ignis", "fire")]);
+        let theirs = bucket(&[word("w1", "aqua", "river"), word("w2", "ignis", "fire")]);
+        let outcome = merge_ndjson(&base, &ours, &theirs).unwrap();
+        assert_eq!(outcome.conflicts, 1);
+        let words = merged_words(&outcome.content);
+        assert_eq!(words["w1"]["senses"][0]["gloss"], "river");
+        let conflicts = conflicts_of(&words["w1"]);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].field, "entry");
+        assert_eq!(conflicts[0].ours, None);
+        assert!(conflicts[0].theirs.is_some());
+
+        // 一方删除、另一方未改动时直接删除，不算冲突
+        let theirs = bucket(&[word("w1", "aqua", "water"), word("w2", "ignis", "fire")]);
+        let outcome = merge_ndjson(&base, &ours, &theirs).unwrap();
+        assert_eq!(outcome.conflicts, 0);
+        assert!(!merged_words(&outcome.content).contains_key("w1"));
+    }
+
+    #[test]
+    fn id_keyed_arrays_merge_element_by_element() {
+        let base = json!({ "parts_of_speech": [{ "pos_id": "n", "name": "Noun" }] }).to_string();
+        let ours = json!({ "parts_of_speech": [
+            { "pos_id": "n", "name": "Noun" },
+            { "pos_id": "v", "name": "Verb" },
+        ] })
+        .to_string();
+        let theirs = json!({ "parts_of_speech": [
+            { "pos_id": "n", "name": "Substantive" },
+            { "pos_id": "adj", "name": "Adjective" },
+        ] })
+        .to_string();
+        let outcome = merge_json("test.json", &base, &ours, &theirs).unwrap();
+        assert_eq!(outcome.conflicts, 0);
+        let merged: Value = serde_json::from_str(&outcome.content).unwrap();
+        assert_eq!(
+            merged["parts_of_speech"],
+            json!([
+                { "pos_id": "n", "name": "Substantive" },
+                { "pos_id": "v", "name": "Verb" },
+                { "pos_id": "adj", "name": "Adjective" },
+            ])
+        );
+    }
+
+    #[test]
+    fn arrays_without_ids_conflict_as_a_whole() {
+        let base = json!({ "vowels": ["a", "i"] }).to_string();
+        let ours = json!({ "vowels": ["a", "i", "u"] }).to_string();
+        let theirs = json!({ "vowels": ["a", "e", "i"] }).to_string();
+        let outcome = merge_json("test.json", &base, &ours, &theirs).unwrap();
+        assert_eq!(outcome.conflicts, 1);
+        let merged: Value = serde_json::from_str(&outcome.content).unwrap();
+        assert_eq!(merged["vowels"], json!(["a", "i", "u"]));
+        assert_eq!(conflicts_of(&merged)[0].field, "vowels");
+    }
+
+    #[test]
+    fn id_less_words_are_kept_apart() {
+        let base = bucket(&[word("w1", "aqua", "water")]);
+        let ours = bucket(&[word("w1", "aqua", "water"), word("", "ignis", "fire")]);
+        let theirs = bucket(&[word("w1", "aqua", "water"), word("", "terra", "earth")]);
+        let outcome = merge_ndjson(&base, &ours, &theirs).unwrap();
+        assert_eq!(outcome.conflicts, 0);
+        let words = merged_words(&outcome.content);
+        assert_eq!(words.keys().collect::<Vec<_>>(), ["ignis", "terra", "w1"]);
+
+        // 双方加入相同的无 ID 词条时只保留一条
+        let outcome = merge_ndjson(&base, &ours, &ours).unwrap();
+        assert_eq!(outcome.content.lines().count(), 2);
+    }
+
+    #[test]
+    fn parse_path_splits_fields_and_elements() {
+        use PathSegment::{Element, Field};
+        assert_eq!(
+            parse_path("rule_sets[rs1].rules[r1].target").unwrap(),
+            vec![
+                Field("rule_sets".to_string()),
+                Element("rs1".to_string()),
+                Field("rules".to_string()),
+                Element("r1".to_string()),
+                Field("target".to_string()),
+            ]
+        );
+        assert_eq!(parse_path("vowels").unwrap(), vec![Field("vowels".to_string())]);
+        assert!(parse_path("senses[s1.gloss").is_err());
+    }
+
+    #[test]
+    fn set_path_writes_and_removes_fields_and_elements() {
+        let mut value = json!({ "senses": [
+            { "sense_id": "s1", "gloss": "water" },
+            { "sense_id": "s2", "gloss": "river" },
+        ] });
+        set_path(&mut value, &parse_path("senses[s1].gloss").unwrap(), Some(json!("lake")), "").unwrap();
+        set_path(&mut value, &parse_path("senses[s2]").unwrap(), None, "").unwrap();
+        set_path(&mut value, &parse_path("senses[s3]").unwrap(), Some(json!({ "sense_id": "s3", "gloss": "sea" })), "")
+            .unwrap();
+        assert_eq!(
+            value,
+            json!({ "senses": [
+                { "sense_id": "s1", "gloss": "lake" },
+                { "sense_id": "s3", "gloss": "sea" },
+            ] })
+        );
+        assert!(set_path(&mut value, &parse_path("senses[s9].gloss").unwrap(), Some(json!("x")), "").is_err());
+    }
+
+    #[test]
+    fn resolve_conflict_round_trips_a_merge() {
+        let base = bucket(&[word("w1", "aqua", "water")]);
+        let ours = bucket(&[word("w1", "aqua", "river")]);
+        let theirs = bucket(&[word("w1", "aqua", "lake")]);
+        let outcome = merge_ndjson(&base, &ours, &theirs).unwrap();
+        let mut merged = merged_words(&outcome.content).remove("w1").unwrap();
+
+        assert!(resolve_conflict(&mut merged, "senses[s1].gloss", "middle").is_err());
+        assert!(resolve_conflict(&mut merged, "senses[s1].pos_id", "theirs").is_err());
+        assert!(resolve_conflict(&mut merged, "senses[s1].gloss", "theirs").unwrap());
+        assert_eq!(merged["senses"][0]["gloss"], "lake");
+        assert!(merged.get(CONFLICTS_KEY).is_none());
+        // 解决后的词条与直接采用 theirs 时写出的内容一致
+        let resolved: WordEntry = serde_json::from_value(merged).unwrap();
+        let expected = merge_ndjson(&theirs, &theirs, &theirs).unwrap().content;
+        assert_eq!(serde_json::to_string(&resolved).unwrap() + "\n", expected);
+    }
+
+    #[test]
+    fn resolve_entry_conflict_can_remove_the_entry() {
+        let base = bucket(&[word("w1", "aqua", "water")]);
+        let theirs = bucket(&[word("w1", "aqua", "river")]);
+        let outcome = merge_ndjson(&base, "", &theirs).unwrap();
+        let merged = merged_words(&outcome.content).remove("w1").unwrap();
+
+        let mut keep = merged.clone();
+        assert!(resolve_conflict(&mut keep, "entry", "theirs").unwrap());
+        assert_eq!(keep["senses"][0]["gloss"], "river");
+        assert!(keep.get(CONFLICTS_KEY).is_none());
+
+        let mut remove = merged;
+        assert!(!resolve_conflict(&mut remove, "entry", "ours").unwrap());
+    }
+}
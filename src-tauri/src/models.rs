@@ -1,5 +1,16 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+/// 当前结构体未识别的 JSON 字段。所有落盘模型都通过 `#[serde(flatten)]` 收集它们，
+/// 以便手工编辑或新版本写入的字段在读-改-写之后原样保留。
+pub type ExtraFields = serde_json::Map<String, serde_json::Value>;
+
+/// 落盘文件的当前 schema 版本。缺少 schema_version 的旧文件视为 v0，读取时由 commands::schema 逐步迁移
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+fn current_schema_version() -> u32 {
+    CURRENT_SCHEMA_VERSION
+}
 
 // ── 音系相关 ──────────────────────────────────────────────
 
@@ -7,6 +18,8 @@ use std::collections::HashMap;
 pub struct PhonemeInventory {
     pub consonants: Vec<String>,
     pub vowels: Vec<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -17,6 +30,8 @@ pub struct RomanizationRule {
     pub context_before: String,
     #[serde(default)]
     pub context_after: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -25,6 +40,8 @@ pub struct RomanizationMap {
     pub name: String,
     pub is_default: bool,
     pub rules: Vec<RomanizationRule>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -34,6 +51,8 @@ pub struct VowelHarmony {
     pub group_a: Vec<String>,
     #[serde(default)]
     pub group_b: Vec<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 impl Default for VowelHarmony {
@@ -42,6 +61,7 @@ impl Default for VowelHarmony {
             enabled: false,
             group_a: Vec::new(),
             group_b: Vec::new(),
+            extra: ExtraFields::new(),
         }
     }
 }
@@ -52,6 +72,8 @@ pub struct ToneDefinition {
     pub name: String,
     #[serde(default)]
     pub marker: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -59,6 +81,8 @@ pub struct ToneSystem {
     pub enabled: bool,
     #[serde(default)]
     pub tones: Vec<ToneDefinition>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 impl Default for ToneSystem {
@@ -66,6 +90,7 @@ impl Default for ToneSystem {
         Self {
             enabled: false,
             tones: Vec::new(),
+            extra: ExtraFields::new(),
         }
     }
 }
@@ -80,6 +105,8 @@ pub struct Phonotactics {
     pub vowel_harmony: VowelHarmony,
     #[serde(default)]
     pub tone_system: ToneSystem,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -94,10 +121,14 @@ pub struct AllophonyRule {
     pub context_after: String,
     #[serde(default)]
     pub priority: u32,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PhonologyConfig {
+    #[serde(default)]
+    pub schema_version: u32,
     pub language_id: String,
     pub phoneme_inventory: PhonemeInventory,
     #[serde(default)]
@@ -105,6 +136,8 @@ pub struct PhonologyConfig {
     pub phonotactics: Phonotactics,
     #[serde(default)]
     pub allophony_rules: Vec<AllophonyRule>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 // ── 词典相关 ──────────────────────────────────────────────
@@ -123,6 +156,8 @@ pub struct Sense {
     pub examples: Vec<String>,
     #[serde(default)]
     pub grammatical_function: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -137,6 +172,8 @@ pub struct Etymology {
     pub applied_sound_changes: Vec<String>,
     #[serde(default)]
     pub semantic_shift_note: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 fn default_origin_type() -> String {
@@ -151,6 +188,7 @@ impl Default for Etymology {
             source_language_id: None,
             applied_sound_changes: Vec::new(),
             semantic_shift_note: String::new(),
+            extra: ExtraFields::new(),
         }
     }
 }
@@ -163,6 +201,8 @@ pub struct EntryMetadata {
     pub created_at: String,
     #[serde(default)]
     pub updated_at: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 impl Default for EntryMetadata {
@@ -171,6 +211,7 @@ impl Default for EntryMetadata {
             tags: Vec::new(),
             created_at: String::new(),
             updated_at: String::new(),
+            extra: ExtraFields::new(),
         }
     }
 }
@@ -185,6 +226,8 @@ pub struct WordEvolution {
     pub parent_snapshot_hash: Option<String>,
     #[serde(default)]
     pub last_synced_word_hash: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 impl Default for WordEvolution {
@@ -194,12 +237,15 @@ impl Default for WordEvolution {
             deprecated_since_language_id: None,
             parent_snapshot_hash: None,
             last_synced_word_hash: None,
+            extra: ExtraFields::new(),
         }
     }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct WordEntry {
+    #[serde(default)]
+    pub schema_version: u32,
     pub entry_id: String,
     pub language_id: String,
     pub con_word_romanized: String,
@@ -215,6 +261,122 @@ pub struct WordEntry {
     pub metadata: EntryMetadata,
     #[serde(default)]
     pub evolution: WordEvolution,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+// ── 词典分桶 ──────────────────────────────────────────────
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BucketingConfig {
+    #[serde(default = "default_bucketing_strategy")]
+    pub strategy: String, // "ascii" | "grapheme" | "hash" | "size"
+    /// grapheme / hash 策略的折叠方式："nfc" 保留附加符号，"nfd" 去掉附加符号（é → e）
+    #[serde(default = "default_bucketing_normalization")]
+    pub normalization: String,
+    #[serde(default = "default_shard_count")]
+    pub shard_count: u32,
+    #[serde(default = "default_max_bucket_size")]
+    pub max_bucket_size: usize,
+    /// size 策略的切分点（有序），由迁移命令生成；保存词条时不会重新计算
+    #[serde(default)]
+    pub split_points: Vec<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+fn default_bucketing_strategy() -> String {
+    "ascii".to_string()
+}
+fn default_bucketing_normalization() -> String {
+    "nfd".to_string()
+}
+fn default_shard_count() -> u32 {
+    32
+}
+fn default_max_bucket_size() -> usize {
+    2000
+}
+
+impl Default for BucketingConfig {
+    fn default() -> Self {
+        Self {
+            strategy: default_bucketing_strategy(),
+            normalization: default_bucketing_normalization(),
+            shard_count: default_shard_count(),
+            max_bucket_size: default_max_bucket_size(),
+            split_points: Vec::new(),
+            extra: ExtraFields::new(),
+        }
+    }
+}
+
+// ── 词典查询 ──────────────────────────────────────────────
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WordQuery {
+    /// 在 gloss 与 definitions 中做不区分大小写的子串匹配
+    #[serde(default)]
+    pub text: Option<String>,
+    /// 为 true 时 text 也匹配拼写（词典侧栏的搜索框）
+    #[serde(default)]
+    pub text_matches_romanized: bool,
+    /// 只返回这些词条，用于按 ID 查找单个或少量词条
+    #[serde(default)]
+    pub entry_ids: Option<Vec<String>>,
+    #[serde(default)]
+    pub pos_id: Option<String>,
+    /// 词条必须同时包含所有列出的标签
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub origin_type: Option<String>,
+    #[serde(default)]
+    pub deprecated: Option<bool>,
+    #[serde(default)]
+    pub romanized_regex: Option<String>,
+    /// 父语言目录；给出时结果附带每个词条相对父语言的生命周期标记
+    #[serde(default)]
+    pub parent_language_path: Option<String>,
+    /// 按生命周期过滤："added" | "changed" | "deprecated"，需要 parent_language_path（deprecated 除外）
+    #[serde(default)]
+    pub lifecycle: Option<String>,
+    #[serde(default = "default_sort_by")]
+    pub sort_by: String, // "romanized" | "gloss" | "ipa" | "created_at" | "updated_at"
+    #[serde(default)]
+    pub descending: bool,
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+fn default_sort_by() -> String {
+    "romanized".to_string()
+}
+
+/// 词条相对父语言的状态：父语言中没有对应词条、已废弃、或与父词不同
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct WordLifecycle {
+    pub is_new: bool,
+    pub is_deprecated: bool,
+    pub is_changed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WordQueryResult {
+    /// 分页前的匹配总数
+    pub total: usize,
+    pub words: Vec<WordEntry>,
+    /// 本页词条的内容哈希：entry_id → hash，用于 save_words 的 expected_hash
+    #[serde(default)]
+    pub hashes: BTreeMap<String, String>,
+    /// 本页词条的生命周期标记，只在查询给出 parent_language_path 时填写
+    #[serde(default)]
+    pub lifecycle: BTreeMap<String, WordLifecycle>,
+    /// 整个词典中出现过的标签（已排序去重），供筛选与补全使用
+    #[serde(default)]
+    pub all_tags: Vec<String>,
 }
 
 // ── 语法相关 ──────────────────────────────────────────────
@@ -227,6 +389,8 @@ pub struct SyntaxConfig {
     pub modifier_position: String,
     #[serde(default = "default_adposition_type")]
     pub adposition_type: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 fn default_word_order() -> String {
@@ -245,6 +409,7 @@ impl Default for SyntaxConfig {
             word_order: default_word_order(),
             modifier_position: default_modifier_position(),
             adposition_type: default_adposition_type(),
+            extra: ExtraFields::new(),
         }
     }
 }
@@ -261,6 +426,8 @@ pub struct PartOfSpeech {
     pub requires_definition: bool,
     #[serde(default)]
     pub requires_pronunciation: bool,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 // ── 屈折维度 (Phase 2) ──────────────────────────────────
@@ -271,6 +438,8 @@ pub struct DimensionValue {
     pub name: String,
     #[serde(default)]
     pub gloss: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -281,6 +450,8 @@ pub struct InflectionDimension {
     pub applies_to_pos: Vec<String>,
     #[serde(default)]
     pub values: Vec<DimensionValue>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 // ── 屈折规则 (Phase 2 完整形态) ─────────────────────────
@@ -297,6 +468,8 @@ pub struct ConditionClause {
     pub then_affix: String,
     #[serde(default)]
     pub else_affix: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -305,6 +478,8 @@ pub struct InfixConfig {
     pub position_regex: String,
     #[serde(default)]
     pub morpheme: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -313,12 +488,16 @@ pub struct CircumfixConfig {
     pub prefix_part: String,
     #[serde(default)]
     pub suffix_part: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ReduplicationConfig {
     #[serde(default = "default_reduplication_mode")]
     pub mode: String, // "full" | "partial_onset" | "partial_coda"
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 fn default_reduplication_mode() -> String {
@@ -331,6 +510,8 @@ pub struct AblautConfig {
     pub target_vowel: String,
     #[serde(default)]
     pub replacement_vowel: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -369,6 +550,8 @@ pub struct InflectionRule {
     pub fused_dimensions: Option<Vec<HashMap<String, String>>>,
     #[serde(default)]
     pub sca_mutable: bool,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 fn default_match_regex() -> String {
@@ -398,6 +581,8 @@ pub struct DerivationRule {
     pub semantic_note: String,
     #[serde(default)]
     pub sca_mutable: bool,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 // ── 语法手册 (Phase 2) ──────────────────────────────────
@@ -410,6 +595,8 @@ pub struct EmbeddedParadigm {
     pub dimension_ids: Vec<String>,
     #[serde(default)]
     pub test_word: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -423,6 +610,8 @@ pub struct GrammarChapter {
     pub order: u32,
     #[serde(default)]
     pub embedded_paradigms: Vec<EmbeddedParadigm>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 // ── 语言类型学 (Typology) ──────────────────────────────
@@ -439,6 +628,8 @@ pub struct TypologyConfig {
     pub head_marking: String,
     #[serde(default = "default_auto_estimated")]
     pub auto_estimated: bool,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 fn default_morphological_type() -> String {
@@ -465,6 +656,7 @@ impl Default for TypologyConfig {
             fusion_index: default_fusion_index(),
             head_marking: default_head_marking(),
             auto_estimated: default_auto_estimated(),
+            extra: ExtraFields::new(),
         }
     }
 }
@@ -480,6 +672,8 @@ pub struct AffixSlot {
     pub is_obligatory: bool,
     #[serde(default)]
     pub label: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -493,6 +687,8 @@ pub struct ConjugationClass {
     pub stem_pattern: String,
     #[serde(default)]
     pub rule_ids: Vec<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -502,12 +698,16 @@ pub struct IrregularOverride {
     pub dimension_values: HashMap<String, String>,
     #[serde(default)]
     pub surface_form: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 // ── GrammarConfig ───────────────────────────────────────
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GrammarConfig {
+    #[serde(default)]
+    pub schema_version: u32,
     #[serde(default = "default_language_id")]
     pub language_id: String,
     #[serde(default)]
@@ -531,6 +731,8 @@ pub struct GrammarConfig {
     pub conjugation_classes: Vec<ConjugationClass>,
     #[serde(default)]
     pub irregular_overrides: Vec<IrregularOverride>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 fn default_language_id() -> String {
@@ -540,6 +742,7 @@ fn default_language_id() -> String {
 impl Default for GrammarConfig {
     fn default() -> Self {
         GrammarConfig {
+            schema_version: current_schema_version(),
             language_id: default_language_id(),
             syntax: SyntaxConfig::default(),
             parts_of_speech: Vec::new(),
@@ -551,6 +754,7 @@ impl Default for GrammarConfig {
             affix_slots: Vec::new(),
             conjugation_classes: Vec::new(),
             irregular_overrides: Vec::new(),
+            extra: ExtraFields::new(),
         }
     }
 }
@@ -564,6 +768,8 @@ pub struct LanguageEntry {
     pub path: String,
     #[serde(default)]
     pub parent_id: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -572,6 +778,8 @@ pub struct WorkspaceConfig {
     pub workspace_version: String,
     #[serde(default)]
     pub languages: Vec<LanguageEntry>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 fn default_workspace_version() -> String {
@@ -587,7 +795,9 @@ impl Default for WorkspaceConfig {
                 name: "Proto Language".to_string(),
                 path: "proto_language".to_string(),
                 parent_id: None,
+                extra: ExtraFields::new(),
             }],
+            extra: ExtraFields::new(),
         }
     }
 }
@@ -600,6 +810,8 @@ pub struct FeatureExpression {
     pub positive: Vec<String>,
     #[serde(default)]
     pub negative: Vec<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -608,6 +820,8 @@ pub struct FeatureReplacement {
     pub set_features: Vec<String>,
     #[serde(default)]
     pub remove_features: Vec<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -636,6 +850,8 @@ pub struct SCARule {
     pub context_before_features: Option<FeatureExpression>,
     #[serde(default)]
     pub context_after_features: Option<FeatureExpression>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -647,21 +863,32 @@ pub struct SCARuleSet {
     pub order: u32,
     #[serde(default)]
     pub rules: Vec<SCARule>,
+    /// 派生语言时已作用于继承词条的时间（RFC 3339）；批量应用到词典时跳过，避免同一音变执行两次
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lexicon_applied_at: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SCAConfig {
+    #[serde(default)]
+    pub schema_version: u32,
     #[serde(default)]
     pub language_id: String,
     #[serde(default)]
     pub rule_sets: Vec<SCARuleSet>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 impl Default for SCAConfig {
     fn default() -> Self {
         Self {
+            schema_version: current_schema_version(),
             language_id: default_language_id(),
             rule_sets: Vec::new(),
+            extra: ExtraFields::new(),
         }
     }
 }
@@ -681,6 +908,8 @@ pub struct GlossToken {
     pub linked_entry_id: String,
     #[serde(default)]
     pub ipa: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -692,6 +921,8 @@ pub struct GlossedLine {
     pub tokens: Vec<GlossToken>,
     #[serde(default)]
     pub translation: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -704,6 +935,8 @@ pub struct AutoGlossReport {
     pub pending: u32,
     #[serde(default)]
     pub unresolved: u32,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -716,10 +949,14 @@ pub struct CorpusTextMeta {
     pub updated_at: String,
     #[serde(default)]
     pub auto_gloss_report: Option<AutoGlossReport>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct CorpusText {
+    #[serde(default)]
+    pub schema_version: u32,
     pub corpus_id: String,
     #[serde(default)]
     pub language_id: String,
@@ -735,6 +972,8 @@ pub struct CorpusText {
     pub free_translation: String,
     #[serde(default)]
     pub metadata: CorpusTextMeta,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -746,6 +985,8 @@ pub struct CorpusIndexEntry {
     pub description: String,
     #[serde(default)]
     pub metadata: CorpusTextMeta,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 // ── Global Registry (app data dir) ──────────────────────
@@ -756,6 +997,8 @@ pub struct FamilyEntry {
     pub conlang_file_path: String,
     #[serde(default)]
     pub last_opened: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 fn default_registry_version() -> String {
@@ -770,6 +1013,8 @@ pub struct GlobalRegistry {
     pub families: Vec<FamilyEntry>,
     #[serde(default)]
     pub active_family_index: Option<usize>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 impl Default for GlobalRegistry {
@@ -778,6 +1023,7 @@ impl Default for GlobalRegistry {
             registry_version: default_registry_version(),
             families: Vec::new(),
             active_family_index: None,
+            extra: ExtraFields::new(),
         }
     }
 }
@@ -793,6 +1039,16 @@ pub struct OperationLogEntry {
     pub target_language_id: String,
     pub description: String,
     pub snapshot_dir: String,
+    /// 快照覆盖的语言目录顶层条目（"lexicon"、"grammar.json"、"corpus" 等），回滚时只恢复这些
+    #[serde(default = "legacy_snapshot_components")]
+    pub components: Vec<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// 早期版本的快照只包含词典
+fn legacy_snapshot_components() -> Vec<String> {
+    vec!["lexicon".to_string()]
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -801,6 +1057,8 @@ pub struct OperationLog {
     pub max_snapshots: u32,
     #[serde(default)]
     pub entries: Vec<OperationLogEntry>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 fn default_max_snapshots() -> u32 {
@@ -812,6 +1070,7 @@ impl Default for OperationLog {
         Self {
             max_snapshots: default_max_snapshots(),
             entries: Vec::new(),
+            extra: ExtraFields::new(),
         }
     }
 }
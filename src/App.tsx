@@ -32,6 +32,9 @@ import { useSCAStore } from "./store/scaStore";
 import { useCorpusStore } from "./store/corpusStore";
 import { useRegistryStore } from "./store/registryStore";
 import { check } from "@tauri-apps/plugin-updater";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { ProjectFileChange, ProjectLockStatus } from "./types";
 import "./App.css";
 
 /** Routes that don't require a loaded workspace */
@@ -75,6 +78,7 @@ function App() {
 
   // 3. Reload data when active language changes
   const activeLanguageId = useWorkspaceStore((s) => s.activeLanguageId);
+  const dataVersion = useWorkspaceStore((s) => s.dataVersion);
   useEffect(() => {
     if (!conlangFilePath) return;
     const reload = async () => {
@@ -85,9 +89,78 @@ function App() {
       await corpusLoadIndex(projectPath, activeLanguagePath);
     };
     reload();
-  }, [projectPath, activeLanguagePath, activeLanguageId]);
+  }, [projectPath, activeLanguagePath, activeLanguageId, dataVersion]);
 
-  // 4. Silent updater check on startup; installs in background if a signed update is available.
+  // 4. Watch the project directory; reload the active language's data when its files change on disk
+  useEffect(() => {
+    if (!conlangFilePath || !projectPath) return;
+    invoke("watch_project", { projectPath, conlangFilePath }).catch((err) =>
+      console.warn(`监听项目目录失败：${err}`),
+    );
+    const unlisten = listen<ProjectFileChange>("project-file-changed", (event) => {
+      const change = event.payload;
+      if (change.language_path !== activeLanguagePath) return;
+      switch (change.kind) {
+        case "lexicon_bucket":
+          loadFromBackend(projectPath, activeLanguagePath);
+          break;
+        case "phonology":
+          phonoLoadConfig(projectPath, activeLanguagePath);
+          break;
+        case "grammar":
+          grammarLoadConfig(projectPath, activeLanguagePath);
+          break;
+        case "sca":
+          scaLoadConfig(projectPath, activeLanguagePath);
+          break;
+        case "corpus":
+          corpusLoadIndex(projectPath, activeLanguagePath);
+          break;
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [projectPath, conlangFilePath, activeLanguagePath]);
+
+  // 5. Follow lock mode changes from the heartbeat: a read-only window becomes writable once the
+  // other instance releases the lock, and an owned project drops to read-only when it is taken over
+  useEffect(() => {
+    if (!conlangFilePath) return;
+    const unlisten = listen<ProjectLockStatus>("project-lock-changed", (event) => {
+      const status = event.payload;
+      if (status.conlang_file_path !== conlangFilePath) return;
+      useWorkspaceStore.setState({ lockStatus: status });
+      if (status.mode === "owned") {
+        useWorkspaceStore.getState().migrateOperationLogs();
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [conlangFilePath]);
+
+  // 6. Project-wide undo/redo shortcuts; text fields keep their native undo
+  useEffect(() => {
+    if (!conlangFilePath) return;
+    const onKeyDown = (e: KeyboardEvent) => {
+      if (!(e.ctrlKey || e.metaKey) || e.altKey) return;
+      const target = e.target as HTMLElement | null;
+      if (target && (target.isContentEditable || ["INPUT", "TEXTAREA", "SELECT"].includes(target.tagName))) return;
+      const key = e.key.toLowerCase();
+      if (key === "z" && !e.shiftKey) {
+        e.preventDefault();
+        useWorkspaceStore.getState().undo();
+      } else if ((key === "z" && e.shiftKey) || key === "y") {
+        e.preventDefault();
+        useWorkspaceStore.getState().redo();
+      }
+    };
+    window.addEventListener("keydown", onKeyDown);
+    return () => window.removeEventListener("keydown", onKeyDown);
+  }, [conlangFilePath]);
+
+  // 7. Silent updater check on startup; installs in background if a signed update is available.
   useEffect(() => {
     if (!initDone) return;
     const runSilentUpdate = async () => {
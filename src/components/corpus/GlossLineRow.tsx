@@ -1,4 +1,4 @@
-import { useState, useMemo } from "react";
+import { useState, useEffect } from "react";
 import { useTranslation } from "react-i18next";
 import { Trash2 } from "lucide-react";
 import { useCorpusStore } from "../../store/corpusStore";
@@ -24,11 +24,14 @@ export function GlossLineRow({ line }: GlossLineRowProps) {
     y: 0,
   });
   const [showDeleteConfirm, setShowDeleteConfirm] = useState(false);
-  const wordsList = useLexiconStore((s) => s.wordsList);
-  const wordsMap = useMemo(
-    () => new Map(wordsList.map((w) => [w.entry_id, w])),
-    [wordsList],
-  );
+  const wordsMap = useLexiconStore((s) => s.wordsMap);
+  const fetchWords = useLexiconStore((s) => s.fetchWords);
+  // 只按 ID 取本行关联的词条
+  const linkedIds = line.tokens.map((tok) => tok.linked_entry_id).filter((id) => id);
+  const linkedKey = linkedIds.join(",");
+  useEffect(() => {
+    if (linkedIds.length > 0) fetchWords(linkedIds);
+  }, [linkedKey, fetchWords]);
 
   const updateToken = (tokenId: string, token: GlossToken) => {
     const updated: GlossedLine = {
@@ -112,9 +115,9 @@ export function GlossLineRow({ line }: GlossLineRowProps) {
               {/* Word popover on hover */}
               {hoverTokenId === token.token_id &&
                 token.linked_entry_id &&
-                wordsMap.get(token.linked_entry_id) && (
+                wordsMap[token.linked_entry_id] && (
                   <WordPopover
-                    entry={wordsMap.get(token.linked_entry_id)!}
+                    entry={wordsMap[token.linked_entry_id]}
                     position={hoverPosition}
                   />
                 )}
@@ -1,6 +1,7 @@
-import { useState } from "react";
+import { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
 import { useTranslation } from "react-i18next";
-import { GlossToken } from "../../types";
+import { GlossToken, WordEntry, WordQueryResult } from "../../types";
 import { useLexiconStore } from "../../store/lexiconStore";
 import {
   INPUT,
@@ -27,21 +28,38 @@ export function GlossTokenEdit({
   const { t } = useTranslation();
   const [draft, setDraft] = useState<GlossToken>({ ...token });
   const [showDeleteConfirm, setShowDeleteConfirm] = useState(false);
-  const wordsList = useLexiconStore((s) => s.wordsList);
+  const { projectPath, languagePath, wordsMap, fetchWords } = useLexiconStore();
+  const [suggestions, setSuggestions] = useState<WordEntry[]>([]);
 
-  // Find matching entries for linking
-  const suggestions = draft.surface_form
-    ? wordsList
-      .filter((w) =>
-        w.con_word_romanized
-          .toLowerCase()
-          .startsWith(draft.surface_form.toLowerCase()),
-      )
-      .slice(0, 5)
-    : [];
+  // Find matching entries for linking（后端按拼写前缀查询前 5 条）
+  useEffect(() => {
+    const prefix = draft.surface_form.trim();
+    if (!prefix) {
+      setSuggestions([]);
+      return;
+    }
+    let cancelled = false;
+    const escaped = prefix.replace(/[.*+?^${}()|[\]\\]/g, "\\$&");
+    invoke<WordQueryResult>("query_words", {
+      projectPath,
+      languagePath,
+      query: { romanized_regex: `(?i)^${escaped}`, limit: 5 },
+    })
+      .then((result) => {
+        if (!cancelled) setSuggestions(result.words);
+      })
+      .catch((err) => console.warn(`查询词条失败：${err}`));
+    return () => {
+      cancelled = true;
+    };
+  }, [draft.surface_form, projectPath, languagePath]);
+
+  useEffect(() => {
+    if (draft.linked_entry_id) fetchWords([draft.linked_entry_id]);
+  }, [draft.linked_entry_id, fetchWords]);
 
   const linkEntry = (entryId: string) => {
-    const entry = wordsList.find((w) => w.entry_id === entryId);
+    const entry = suggestions.find((w) => w.entry_id === entryId) ?? wordsMap[entryId];
     if (!entry) return;
     setDraft({
       ...draft,
@@ -125,8 +143,7 @@ export function GlossTokenEdit({
           <div className="text-xs text-green-600">
             {t("corpus.linkedEntry")}:{" "}
             {
-              wordsList.find((w) => w.entry_id === draft.linked_entry_id)
-                ?.con_word_romanized
+              wordsMap[draft.linked_entry_id]?.con_word_romanized
             }
           </div>
         )}
@@ -2,7 +2,7 @@ import { useEffect, useRef, useState } from "react";
 import { useTranslation } from "react-i18next";
 import { X, Plus, Wand2 } from "lucide-react";
 import { useCorpusStore } from "../../store/corpusStore";
-import { useLexiconStore } from "../../store/lexiconStore";
+import { useFullLexicon, useLexiconStore } from "../../store/lexiconStore";
 import { useGrammarStore } from "../../store/grammarStore";
 import { usePhonoStore } from "../../store/phonoStore";
 import { useSCAStore } from "../../store/scaStore";
@@ -509,7 +509,8 @@ export function GlossingEditor() {
   const corpus = useCorpusStore((s) => s.activeCorpus);
   const addGlossedLine = useCorpusStore((s) => s.addGlossedLine);
   const upsertCorpus = useCorpusStore((s) => s.upsertCorpus);
-  const wordsList = useLexiconStore((s) => s.wordsList);
+  // 自动标注要把语料中的每个词形与整个词典比对
+  const wordsList = useFullLexicon();
   const grammarConfig = useGrammarStore((s) => s.config);
   const phonoConfig = usePhonoStore((s) => s.config);
   const scaConfig = useSCAStore((s) => s.config);
@@ -1,7 +1,7 @@
 import React from "react";
 import { useTranslation } from "react-i18next";
 import { useGrammarStore } from "../../store/grammarStore";
-import { useLexiconStore } from "../../store/lexiconStore";
+import { useFullLexicon, useLexiconStore } from "../../store/lexiconStore";
 import { usePhonoStore } from "../../store/phonoStore";
 import {
   generateDerivedWords,
@@ -39,6 +39,8 @@ export const DerivationEditor: React.FC = () => {
     deleteDerivation,
     setDerivations,
   } = useGrammarStore();
+  // 派生预览要对所有符合词性的词条套用规则
+  useFullLexicon();
   const { wordsMap, upsertWord } = useLexiconStore();
   const phonoConfig = usePhonoStore((s) => s.config);
   const rules = config.derivation_rules;
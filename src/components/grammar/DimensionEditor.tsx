@@ -1,7 +1,11 @@
 import React from "react";
 import { useTranslation } from "react-i18next";
 import { useGrammarStore } from "../../store/grammarStore";
-import type { InflectionDimension, DimensionValue } from "../../types";
+import type {
+  InflectionDimension,
+  DimensionValue,
+  GrammarRefChange,
+} from "../../types";
 import { Plus, Trash2 } from "lucide-react";
 import {
   INPUT,
@@ -11,23 +15,17 @@ import {
   BTN_GHOST,
   CHECKBOX,
 } from "../../lib/ui";
-import { ConfirmModal } from "../common/ConfirmModal";
+import { ReferenceCascadeModal } from "./ReferenceCascadeModal";
 import { EmptyState } from "../common/EmptyState";
 
 export const DimensionEditor: React.FC = () => {
   const { t } = useTranslation();
-  const { config, addDimension, updateDimension, deleteDimension } =
-    useGrammarStore();
+  const { config, addDimension, updateDimension } = useGrammarStore();
   const dimensions = config.inflection_dimensions;
   const partsOfSpeech = config.parts_of_speech;
 
-  const [confirmDeleteDimId, setConfirmDeleteDimId] = React.useState<
-    string | null
-  >(null);
-  const [confirmDeleteValueId, setConfirmDeleteValueId] = React.useState<{
-    dimId: string;
-    valId: string;
-  } | null>(null);
+  const [deleteTarget, setDeleteTarget] =
+    React.useState<GrammarRefChange | null>(null);
 
   const handleAddDimension = () => {
     const dim: InflectionDimension = {
@@ -84,15 +82,6 @@ export const DimensionEditor: React.FC = () => {
     updateDimension(dimId, { ...dim, values });
   };
 
-  const handleDeleteValue = (dimId: string, valId: string) => {
-    const dim = dimensions.find((d) => d.dim_id === dimId);
-    if (!dim) return;
-    updateDimension(dimId, {
-      ...dim,
-      values: dim.values.filter((v) => v.val_id !== valId),
-    });
-  };
-
   return (
     <div className="space-y-4 bg-base-100 p-6 min-w-[900px] flex-1">
       <div className="flex justify-end items-center">
@@ -121,7 +110,13 @@ export const DimensionEditor: React.FC = () => {
               placeholder={t("grammar.dimensions.namePlaceholder")}
             />
             <button
-              onClick={() => setConfirmDeleteDimId(dim.dim_id)}
+              onClick={() =>
+                setDeleteTarget({
+                  kind: "dimension",
+                  action: "delete",
+                  id: dim.dim_id,
+                })
+              }
               className={BTN_ERROR}
               title={t("grammar.dimensions.deleteDimension")}
             >
@@ -198,9 +193,11 @@ export const DimensionEditor: React.FC = () => {
                   />
                   <button
                     onClick={() =>
-                      setConfirmDeleteValueId({
-                        dimId: dim.dim_id,
-                        valId: val.val_id,
+                      setDeleteTarget({
+                        kind: "value",
+                        action: "delete",
+                        id: val.val_id,
+                        dim_id: dim.dim_id,
                       })
                     }
                     className={BTN_ERROR}
@@ -215,37 +212,14 @@ export const DimensionEditor: React.FC = () => {
         </div>
       ))}
 
-      <ConfirmModal
-        open={confirmDeleteDimId !== null}
-        title={t("common.delete")}
-        message={t(
-          "grammar.dimensions.deleteDimensionConfirm",
-          "Are you sure you want to delete this dimension?",
-        )}
-        onConfirm={() => {
-          if (confirmDeleteDimId) deleteDimension(confirmDeleteDimId);
-          setConfirmDeleteDimId(null);
-        }}
-        onCancel={() => setConfirmDeleteDimId(null)}
-      />
-
-      <ConfirmModal
-        open={confirmDeleteValueId !== null}
-        title={t("common.delete")}
-        message={t(
-          "grammar.dimensions.deleteValueConfirm",
-          "Are you sure you want to delete this value?",
-        )}
-        onConfirm={() => {
-          if (confirmDeleteValueId) {
-            handleDeleteValue(
-              confirmDeleteValueId.dimId,
-              confirmDeleteValueId.valId,
-            );
-            setConfirmDeleteValueId(null);
-          }
-        }}
-        onCancel={() => setConfirmDeleteValueId(null)}
+      <ReferenceCascadeModal
+        change={deleteTarget}
+        title={
+          deleteTarget?.kind === "value"
+            ? t("grammar.dimensions.deleteValue")
+            : t("grammar.dimensions.deleteDimension")
+        }
+        onClose={() => setDeleteTarget(null)}
       />
     </div>
   );
@@ -6,6 +6,7 @@ import {
   InflectionRule,
   MorphologyType,
   MorphologicalTypology,
+  GrammarRefChange,
 } from "../../types";
 import { Plus, Trash2, AlertTriangle, Info, GripVertical } from "lucide-react";
 import {
@@ -18,7 +19,7 @@ import {
 } from "../../lib/ui";
 import { TypeConfigPanel } from "./TypeConfigPanel";
 import { TypologyBindingPanel } from "./TypologyBindingPanel";
-import { ConfirmModal } from "../common/ConfirmModal";
+import { ReferenceCascadeModal } from "./ReferenceCascadeModal";
 import { EmptyState } from "../common/EmptyState";
 import {
   useDragReorder,
@@ -77,14 +78,15 @@ export const InflectionSection: React.FC = () => {
     config,
     addInflection,
     updateInflection,
-    deleteInflection,
     setInflections,
   } = useGrammarStore();
   const rules = config.inflection_rules;
   const partsOfSpeech = config.parts_of_speech;
   const morphType = config.typology.morphological_type;
   const availableTypes = getAvailableTypes(morphType);
-  const [deleteTarget, setDeleteTarget] = useState<string | null>(null);
+  const [deleteTarget, setDeleteTarget] = useState<GrammarRefChange | null>(
+    null,
+  );
   const { sensors, onDragEnd } = useDragReorder(
     rules,
     setInflections,
@@ -303,7 +305,13 @@ export const InflectionSection: React.FC = () => {
 
                       <div className="col-span-1 flex justify-end">
                         <button
-                          onClick={() => setDeleteTarget(rule.rule_id)}
+                          onClick={() =>
+                            setDeleteTarget({
+                              kind: "rule",
+                              action: "delete",
+                              id: rule.rule_id,
+                            })
+                          }
                           className={BTN_ERROR}
                           title={t("grammar.deleteRule")}
                         >
@@ -365,15 +373,10 @@ export const InflectionSection: React.FC = () => {
           </div>
         </div>
       )}
-      <ConfirmModal
-        open={!!deleteTarget}
+      <ReferenceCascadeModal
+        change={deleteTarget}
         title={t("grammar.deleteRule")}
-        message={t("grammar.deleteRuleConfirm")}
-        onConfirm={() => {
-          if (deleteTarget) deleteInflection(deleteTarget);
-          setDeleteTarget(null);
-        }}
-        onCancel={() => setDeleteTarget(null)}
+        onClose={() => setDeleteTarget(null)}
       />{" "}
     </div>
   );
@@ -4,11 +4,12 @@
  * 允许用户为特定词条的特定维度值组合指定手动词形，
  * 绕过常规屈折规则（如英语 go → went）。
  */
-import React, { useState, useMemo } from "react";
+import React, { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
 import { useTranslation } from "react-i18next";
 import { useGrammarStore } from "../../store/grammarStore";
 import { useLexiconStore } from "../../store/lexiconStore";
-import { IrregularOverride } from "../../types";
+import { IrregularOverride, WordEntry, WordQueryResult } from "../../types";
 import { Plus, Trash2, Info } from "lucide-react";
 import {
   INPUT,
@@ -21,10 +22,13 @@ import {
 import { ConfirmModal } from "../common/ConfirmModal";
 import { EmptyState } from "../common/EmptyState";
 
+/** 词条下拉框最多列出的候选数 */
+const ENTRY_OPTION_LIMIT = 200;
+
 export const IrregularOverrideEditor: React.FC = () => {
   const { t } = useTranslation();
   const { config, setIrregularOverrides } = useGrammarStore();
-  const { wordsList } = useLexiconStore();
+  const { projectPath, languagePath, wordsMap, fetchWords } = useLexiconStore();
   const overrides = config.irregular_overrides;
   const dimensions = config.inflection_dimensions;
 
@@ -33,15 +37,38 @@ export const IrregularOverrideEditor: React.FC = () => {
     number | null
   >(null);
 
-  const filteredWords = useMemo(() => {
-    if (!entrySearch) return wordsList;
-    const q = entrySearch.toLowerCase();
-    return wordsList.filter(
-      (w) =>
-        w.con_word_romanized.toLowerCase().includes(q) ||
-        w.senses.some((s) => s.gloss.toLowerCase().includes(q)),
-    );
-  }, [wordsList, entrySearch]);
+  const [filteredWords, setFilteredWords] = useState<WordEntry[]>([]);
+
+  // 候选词条由后端按搜索词过滤，最多取 ENTRY_OPTION_LIMIT 条
+  useEffect(() => {
+    let cancelled = false;
+    const timer = setTimeout(() => {
+      invoke<WordQueryResult>("query_words", {
+        projectPath,
+        languagePath,
+        query: {
+          text: entrySearch.trim() || null,
+          text_matches_romanized: true,
+          limit: ENTRY_OPTION_LIMIT,
+        },
+      })
+        .then((result) => {
+          if (!cancelled) setFilteredWords(result.words);
+        })
+        .catch((err) => console.warn(`查询词条失败：${err}`));
+    }, 200);
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [entrySearch, projectPath, languagePath]);
+
+  // 已有覆盖项引用的词条按 ID 取回，用于显示与维度过滤
+  const overrideIdsKey = overrides.map((o) => o.entry_id).join(",");
+  useEffect(() => {
+    const ids = overrides.map((o) => o.entry_id).filter((id) => id);
+    if (ids.length > 0) fetchWords(ids);
+  }, [overrideIdsKey, fetchWords]);
 
   const addOverride = () => {
     const newOverride: IrregularOverride = {
@@ -79,7 +106,7 @@ export const IrregularOverrideEditor: React.FC = () => {
 
   // 获取已选词条的词性，用于过滤维度
   const getEntryPosIds = (entryId: string): string[] => {
-    const entry = wordsList.find((w) => w.entry_id === entryId);
+    const entry = wordsMap[entryId];
     if (!entry) return [];
     return entry.senses.map((s) => s.pos_id);
   };
@@ -119,7 +146,12 @@ export const IrregularOverrideEditor: React.FC = () => {
           const applicableDims = dimensions.filter((d) =>
             d.applies_to_pos.some((p) => posIds.includes(p)),
           );
-          const entry = wordsList.find((w) => w.entry_id === ov.entry_id);
+          const entry = ov.entry_id ? wordsMap[ov.entry_id] : undefined;
+          // 当前选中的词条不在搜索结果中时也要保留在下拉框里
+          const entryOptions =
+            entry && !filteredWords.some((w) => w.entry_id === entry.entry_id)
+              ? [entry, ...filteredWords]
+              : filteredWords;
 
           return (
             <div
@@ -144,7 +176,7 @@ export const IrregularOverrideEditor: React.FC = () => {
                     className={`w-56 ${SELECT}`}
                   >
                     <option value="">{t("typology.selectEntry")}</option>
-                    {filteredWords.map((w) => (
+                    {entryOptions.map((w) => (
                       <option key={w.entry_id} value={w.entry_id}>
                         {w.con_word_romanized} ({w.senses[0]?.gloss ?? ""})
                       </option>
@@ -2,7 +2,7 @@ import React from "react";
 import { useState } from "react";
 import { useTranslation } from "react-i18next";
 import { useGrammarStore } from "../../store/grammarStore";
-import { PartOfSpeech } from "../../types";
+import { PartOfSpeech, GrammarRefChange } from "../../types";
 import { Plus, Trash2, GripVertical } from "lucide-react";
 import {
   INPUT,
@@ -11,7 +11,7 @@ import {
   BTN_ERROR,
   CHECKBOX,
 } from "../../lib/ui";
-import { ConfirmModal } from "../common/ConfirmModal";
+import { ReferenceCascadeModal } from "./ReferenceCascadeModal";
 import { EmptyState } from "../common/EmptyState";
 import {
   useDragReorder,
@@ -37,11 +37,12 @@ export const POSSection: React.FC = () => {
     config,
     addPartOfSpeech,
     updatePartOfSpeech,
-    deletePartOfSpeech,
     setPartsOfSpeech,
   } = useGrammarStore();
   const partsOfSpeech = config.parts_of_speech;
-  const [deleteTarget, setDeleteTarget] = useState<string | null>(null);
+  const [deleteTarget, setDeleteTarget] = useState<GrammarRefChange | null>(
+    null,
+  );
   const { sensors, onDragEnd } = useDragReorder(
     partsOfSpeech,
     setPartsOfSpeech,
@@ -179,7 +180,13 @@ export const POSSection: React.FC = () => {
                       </div>
                       <div className="col-span-1 flex justify-end">
                         <button
-                          onClick={() => setDeleteTarget(pos.pos_id)}
+                          onClick={() =>
+                            setDeleteTarget({
+                              kind: "pos",
+                              action: "delete",
+                              id: pos.pos_id,
+                            })
+                          }
                           className={BTN_ERROR}
                           title={t("grammar.deletePos")}
                         >
@@ -195,15 +202,10 @@ export const POSSection: React.FC = () => {
         </SortableContext>
       </DndContext>
 
-      <ConfirmModal
-        open={!!deleteTarget}
+      <ReferenceCascadeModal
+        change={deleteTarget}
         title={t("grammar.deletePos")}
-        message={t("grammar.deletePosConfirm")}
-        onConfirm={() => {
-          if (deleteTarget) deletePartOfSpeech(deleteTarget);
-          setDeleteTarget(null);
-        }}
-        onCancel={() => setDeleteTarget(null)}
+        onClose={() => setDeleteTarget(null)}
       />
     </div>
   );
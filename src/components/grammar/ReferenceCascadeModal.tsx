@@ -0,0 +1,120 @@
+import { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { AlertTriangle } from "lucide-react";
+import { useGrammarStore } from "../../store/grammarStore";
+import type { GrammarRefChange, GrammarRefReport } from "../../types";
+import { BADGE, BTN_GHOST, BTN_OUTLINE_ERROR } from "../../lib/ui";
+import { ModalPortal } from "../common/ModalPortal";
+
+interface ReferenceCascadeModalProps {
+  /** 为 null 时不显示 */
+  change: GrammarRefChange | null;
+  title: string;
+  onClose: () => void;
+}
+
+const EFFECT_BADGE: Record<string, string> = {
+  rename: "badge-info",
+  unlink: "badge-warning",
+  remove: "badge-error",
+};
+
+/** 删除 / 重命名语法 ID 前列出语法与词典中的全部引用，确认后由后端一次性改写 */
+export function ReferenceCascadeModal({
+  change,
+  title,
+  onClose,
+}: ReferenceCascadeModalProps) {
+  const { t } = useTranslation();
+  const { previewRefChange, applyRefChange } = useGrammarStore();
+  const [report, setReport] = useState<GrammarRefReport | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setReport(null);
+    setError(null);
+    if (!change) return;
+    previewRefChange(change)
+      .then(setReport)
+      .catch((err) => setError(String(err)));
+  }, [change]);
+
+  if (!change) return null;
+
+  const handleConfirm = async () => {
+    setBusy(true);
+    try {
+      await applyRefChange(change);
+      onClose();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  // 目标本身是第一条引用，其余才是级联改动
+  const cascade = report?.references.slice(1) ?? [];
+
+  return (
+    <ModalPortal open={true}>
+      <div className="modal modal-open">
+        <div className="modal-box max-w-2xl">
+          <h3 className="font-bold text-lg flex items-center gap-2 text-error">
+            <AlertTriangle size={20} /> {title}
+          </h3>
+          {!report && !error && (
+            <div className="py-6 text-center">
+              <span className="loading loading-spinner" />
+            </div>
+          )}
+          {report && (
+            <div className="py-3 space-y-3 text-sm">
+              <p className="text-base-content/70">
+                {cascade.length === 0
+                  ? t("grammar.refs.noReferences")
+                  : t("grammar.refs.summary", {
+                      count: cascade.length,
+                      entries: report.lexicon_entries,
+                    })}
+              </p>
+              {cascade.length > 0 && (
+                <div className="max-h-72 overflow-y-auto bg-base-200/50 rounded-lg border border-base-300 divide-y divide-base-300">
+                  {cascade.map((ref, i) => (
+                    <div key={i} className="px-3 py-1.5 flex items-center gap-2">
+                      <span className={`${BADGE} ${EFFECT_BADGE[ref.effect]}`}>
+                        {t(`grammar.refs.effect.${ref.effect}`)}
+                      </span>
+                      <span className="font-medium truncate">{ref.label}</span>
+                      <span className="ml-auto font-mono text-xs text-base-content/50 truncate">
+                        {ref.file === "lexicon" ? t("grammar.refs.lexicon") : ""}{" "}
+                        {ref.location}
+                      </span>
+                    </div>
+                  ))}
+                </div>
+              )}
+              <p className="text-xs text-base-content/50">
+                {t("grammar.refs.snapshotNote")}
+              </p>
+            </div>
+          )}
+          {error && <p className="py-2 text-xs text-error">{error}</p>}
+          <div className="modal-action">
+            <button className={BTN_GHOST} onClick={onClose}>
+              {t("common.cancel")}
+            </button>
+            <button
+              className={BTN_OUTLINE_ERROR}
+              disabled={!report || busy}
+              onClick={handleConfirm}
+            >
+              {t("common.confirm")}
+            </button>
+          </div>
+        </div>
+      </div>
+    </ModalPortal>
+  );
+}
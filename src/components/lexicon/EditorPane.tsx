@@ -9,7 +9,14 @@ import { useSCAStore } from "../../store/scaStore";
 import { Trash2, Plus, Lock, Unlock, Tag } from "lucide-react";
 import { ConfirmModal } from "../common/ConfirmModal";
 import { TagsInput } from "../common/TagsInput";
-import { Sense, WordEntry, PhonologyConfig, OriginType, LanguageEntry } from "../../types";
+import {
+  Sense,
+  WordEntry,
+  PhonologyConfig,
+  OriginType,
+  LanguageEntry,
+  WordQueryResult,
+} from "../../types";
 import { generateIPA } from "../../utils/ipaGenerator";
 import {
   INPUT,
@@ -29,7 +36,7 @@ import {
 
 export const EditorPane: React.FC = () => {
   const { t } = useTranslation();
-  const { activeWordId, wordsMap, wordsList, upsertWord, deleteWord } = useLexiconStore();
+  const { activeWordId, wordsMap, allTags, upsertWord, deleteWord } = useLexiconStore();
   const phonoConfig = usePhonoStore((s) => s.config);
   const partsOfSpeech = useGrammarStore((s) => s.config.parts_of_speech);
   const activeLanguageId = useWorkspaceStore((s) => s.activeLanguageId);
@@ -79,75 +86,60 @@ export const EditorPane: React.FC = () => {
     return chain.reverse();
   }, [activeLanguage, workspaceLanguages]);
 
-  // Load parent word data for lifecycle labels & evolution path
-  const [parentWordsById, setParentWordsById] = useState<Record<string, WordEntry>>({});
-  const [parentPhonoConfig, setParentPhonoConfig] = useState<PhonologyConfig | null>(null);
+  // 当前词条在各祖先语言中的对应词条，用于生命周期标记与演变路径；
+  // 每种祖先语言只按 ID 查一个词条，不加载整个词典
   const [ancestorWordsByLanguage, setAncestorWordsByLanguage] = useState<
-    Record<string, Record<string, WordEntry>>
+    Record<string, WordEntry>
   >({});
+  const [parentPhonoConfig, setParentPhonoConfig] = useState<PhonologyConfig | null>(null);
+  const baseEntryId = word ? word.etymology.parent_entry_id ?? word.entry_id : null;
 
   useEffect(() => {
     let cancelled = false;
     if (!parentLanguage || !projectPath) {
-      setParentWordsById({});
       setParentPhonoConfig(null);
       return;
     }
-    (async () => {
-      try {
-        const [words, phono] = await Promise.all([
-          invoke<WordEntry[]>("load_all_words", { projectPath, languagePath: parentLanguage.path }),
-          invoke<PhonologyConfig>("load_phonology", { projectPath, languagePath: parentLanguage.path }),
-        ]);
-        if (cancelled) return;
-        const byId: Record<string, WordEntry> = {};
-        words.forEach((w) => { byId[w.entry_id] = w; });
-        setParentWordsById(byId);
-        setParentPhonoConfig(phono);
-      } catch (err) {
-        if (!cancelled) {
-          setParentWordsById({});
-          setParentPhonoConfig(null);
-        }
-        console.warn("Failed to load parent words for editor:", err);
-      }
-    })();
+    invoke<PhonologyConfig>("load_phonology", { projectPath, languagePath: parentLanguage.path })
+      .then((phono) => {
+        if (!cancelled) setParentPhonoConfig(phono);
+      })
+      .catch((err) => {
+        if (!cancelled) setParentPhonoConfig(null);
+        console.warn("Failed to load parent phonology for editor:", err);
+      });
     return () => { cancelled = true; };
   }, [parentLanguage, projectPath]);
 
   useEffect(() => {
     let cancelled = false;
 
-    if (!projectPath || !activeLanguageId || lineageLanguages.length <= 1) {
-      setAncestorWordsByLanguage({});
-      return;
-    }
-
     const ancestorLanguages = lineageLanguages.filter(
       (lang) => lang.language_id !== activeLanguageId,
     );
+    if (!projectPath || !baseEntryId || ancestorLanguages.length === 0) {
+      setAncestorWordsByLanguage({});
+      return;
+    }
 
     (async () => {
       try {
         const loaded = await Promise.all(
           ancestorLanguages.map(async (lang) => {
-            const words = await invoke<WordEntry[]>("load_all_words", {
+            const result = await invoke<WordQueryResult>("query_words", {
               projectPath,
               languagePath: lang.path,
+              query: { entry_ids: [baseEntryId], limit: 1 },
             });
-            const byId: Record<string, WordEntry> = {};
-            words.forEach((entry) => {
-              byId[entry.entry_id] = entry;
-            });
-            return { languageId: lang.language_id, wordsById: byId };
+            return { languageId: lang.language_id, entry: result.words[0] };
           }),
         );
 
         if (cancelled) return;
 
-        const nextMap: Record<string, Record<string, WordEntry>> = {};
-        loaded.forEach(({ languageId, wordsById }) => {
-          nextMap[languageId] = wordsById;
+        const nextMap: Record<string, WordEntry> = {};
+        loaded.forEach(({ languageId, entry }) => {
+          if (entry) nextMap[languageId] = entry;
         });
         setAncestorWordsByLanguage(nextMap);
       } catch (error) {
@@ -161,17 +153,17 @@ export const EditorPane: React.FC = () => {
     return () => {
       cancelled = true;
     };
-  }, [projectPath, activeLanguageId, lineageLanguages]);
+  }, [projectPath, activeLanguageId, lineageLanguages, baseEntryId]);
 
   // Parent word for the active word
-  const parentWord = word?.etymology?.parent_entry_id
-    ? parentWordsById[word.etymology.parent_entry_id] ?? null
-    : null;
+  const parentWord =
+    word?.etymology?.parent_entry_id && parentLanguage
+      ? ancestorWordsByLanguage[parentLanguage.language_id] ?? null
+      : null;
 
   const evolutionPathWords = useMemo(() => {
     if (!word || !activeLanguageId) return [] as string[];
 
-    const baseEntryId = word.etymology.parent_entry_id ?? word.entry_id;
     const wordsOnPath: string[] = [];
 
     lineageLanguages.forEach((lang) => {
@@ -183,7 +175,7 @@ export const EditorPane: React.FC = () => {
         return;
       }
 
-      const ancestorWord = ancestorWordsByLanguage[lang.language_id]?.[baseEntryId];
+      const ancestorWord = ancestorWordsByLanguage[lang.language_id];
       const ancestorRomanized = ancestorWord?.con_word_romanized?.trim();
       if (ancestorRomanized) {
         wordsOnPath.push(ancestorRomanized);
@@ -211,10 +203,11 @@ export const EditorPane: React.FC = () => {
     let isChanged = false;
     if (parentLanguage && parentWord) {
       isChanged = hasWordChangedFromParent(word, parentWord);
+      // 与 query_words 的 lifecycle 判定一致：比较双方生成的音位形式
       if (!isChanged && parentPhonoConfig) {
-        const childSurface = generateIPA(word.con_word_romanized, phonoConfig).surface;
-        const parentSurface = generateIPA(parentWord.con_word_romanized, parentPhonoConfig).surface;
-        isChanged = childSurface !== parentSurface;
+        const childPhonemic = generateIPA(word.con_word_romanized, phonoConfig).phonemic;
+        const parentPhonemic = generateIPA(parentWord.con_word_romanized, parentPhonoConfig).phonemic;
+        isChanged = childPhonemic !== parentPhonemic;
       }
     }
     return { isNew, isDeprecated, isChanged: isDeprecated ? false : isChanged };
@@ -231,15 +224,6 @@ export const EditorPane: React.FC = () => {
     return map;
   }, [scaConfig]);
 
-  // Collect all unique tags from all words for autocomplete
-  const allTags = useMemo(() => {
-    const tagSet = new Set<string>();
-    wordsList.forEach((w) => {
-      w.metadata?.tags?.forEach((tag) => tagSet.add(tag));
-    });
-    return Array.from(tagSet).sort();
-  }, [wordsList]);
-
   // Auto-generate IPA when romanized form changes and override is off
   useEffect(() => {
     if (!word || word.phonetic_override) return;
@@ -1,6 +1,6 @@
 import React, { useState, useMemo } from "react";
 import { useTranslation } from "react-i18next";
-import { useLexiconStore } from "../../store/lexiconStore";
+import { useFullLexicon, useLexiconStore } from "../../store/lexiconStore";
 import { usePhonoStore } from "../../store/phonoStore";
 import { useGrammarStore } from "../../store/grammarStore";
 import { runQualityCheck } from "../../utils/qualityCheck";
@@ -34,7 +34,8 @@ export const QualityCheck: React.FC<QualityCheckProps> = ({
   onNavigateToWord,
 }) => {
   const { t } = useTranslation();
-  const wordsMap = useLexiconStore((s) => s.wordsMap);
+  // 质检规则（如重复词条）要在整个词典上运行
+  const words = useFullLexicon();
   const setActiveWordId = useLexiconStore((s) => s.setActiveWordId);
   const phonoConfig = usePhonoStore((s) => s.config);
   const grammarConfig = useGrammarStore((s) => s.config);
@@ -43,7 +44,6 @@ export const QualityCheck: React.FC<QualityCheckProps> = ({
   const [issues, setIssues] = useState<QCIssue[] | null>(null);
   const [exceptions, setExceptions] = useState<Set<string>>(new Set()); // entry_id:rule
 
-  const words = useMemo(() => Object.values(wordsMap), [wordsMap]);
 
   const toggleRule = (key: QCRuleKey) => {
     setDisabledRules((prev) =>
@@ -1,6 +1,5 @@
 import React, { useState, useMemo } from "react";
 import { useTranslation } from "react-i18next";
-import { invoke } from "@tauri-apps/api/core";
 import { useLexiconStore } from "../../store/lexiconStore";
 import { usePhonoStore } from "../../store/phonoStore";
 import { useGrammarStore } from "../../store/grammarStore";
@@ -19,35 +18,34 @@ import {
 } from "lucide-react";
 import { exportToCSV } from "../../utils/exportCSV";
 import { ipaFuzzySearch } from "../../utils/ipaSearch";
-import { generateIPA } from "../../utils/ipaGenerator";
 import { BTN_GHOST_SQ, TOGGLE, SELECT, BADGE } from "../../lib/ui";
-import { PhonologyConfig, WordEntry } from "../../types";
+import { OriginType, WordEntry, WordQuery } from "../../types";
 import { BatchEditPanel } from "./BatchEditPanel";
 import { TagSelector } from "../common/TagSelector";
 import { ConfirmModal } from "../common/ConfirmModal";
-import {
-  getWordEvolution,
-  hasWordChangedFromParent,
-  WordLifecycleFlags,
-} from "../../utils/wordLifecycle";
 
 type SortMode = "az" | "za";
 type LifecycleFilter = "" | "added" | "deprecated" | "changed";
 
 export const Sidebar: React.FC = () => {
   const { t } = useTranslation();
-  const projectPath = useWorkspaceStore((s) => s.projectPath);
   const activeLanguageId = useWorkspaceStore((s) => s.activeLanguageId);
   const workspaceLanguages = useWorkspaceStore((s) => s.config.languages);
   const {
-    wordsList,
     wordsMap,
+    pageIds,
+    pageTotal,
+    pageLifecycle,
+    allTags,
+    loadVersion,
     searchTerm,
     setSearchTerm,
     activeWordId,
     setActiveWordId,
     upsertWord,
     deleteWord,
+    queryPage,
+    loadFullLexicon,
   } = useLexiconStore();
   const config = usePhonoStore((s) => s.config);
   const partsOfSpeech = useGrammarStore((s) => s.config.parts_of_speech);
@@ -60,6 +58,7 @@ export const Sidebar: React.FC = () => {
   const [filterTags, setFilterTags] = useState<string[]>([]);
   const [filterOrigin, setFilterOrigin] = useState("");
   const [filterLifecycle, setFilterLifecycle] = useState<LifecycleFilter>("");
+  const [ipaMatchIds, setIpaMatchIds] = useState<string[] | null>(null);
 
   // Batch edit state
   const [batchMode, setBatchMode] = useState(false);
@@ -67,10 +66,6 @@ export const Sidebar: React.FC = () => {
   const [showDeleteSelectedConfirm, setShowDeleteSelectedConfirm] =
     useState(false);
   const [showCleanBlankConfirm, setShowCleanBlankConfirm] = useState(false);
-  const [parentWordsById, setParentWordsById] = useState<Record<string, WordEntry>>({});
-  const [parentPhonoConfig, setParentPhonoConfig] =
-    useState<PhonologyConfig | null>(null);
-  const [parentWordsLoaded, setParentWordsLoaded] = useState(false);
 
   const activeLanguage = useMemo(
     () => workspaceLanguages.find((lang) => lang.language_id === activeLanguageId),
@@ -85,89 +80,12 @@ export const Sidebar: React.FC = () => {
     );
   }, [workspaceLanguages, activeLanguage]);
 
+  // 切换到根语言时清除相对父语言的过滤条件，否则后端会拒绝查询
   React.useEffect(() => {
-    let cancelled = false;
-
-    const loadParentWords = async () => {
-      if (!parentLanguage) {
-        setParentWordsById({});
-        setParentPhonoConfig(null);
-        setParentWordsLoaded(true);
-        return;
-      }
-      setParentWordsLoaded(false);
-
-      try {
-        const parentWords = await invoke<WordEntry[]>("load_all_words", {
-          projectPath,
-          languagePath: parentLanguage.path,
-        });
-        const parentPhono = await invoke<PhonologyConfig>("load_phonology", {
-          projectPath,
-          languagePath: parentLanguage.path,
-        });
-        if (cancelled) return;
-
-        const byId: Record<string, WordEntry> = {};
-        parentWords.forEach((word) => {
-          byId[word.entry_id] = word;
-        });
-
-        setParentWordsById(byId);
-        setParentPhonoConfig(parentPhono);
-        setParentWordsLoaded(true);
-      } catch (error) {
-        if (!cancelled) {
-          setParentWordsById({});
-          setParentPhonoConfig(null);
-        }
-        console.warn(`加载父语言词典失败：${error}`);
-      }
-    };
-
-    loadParentWords();
-    return () => {
-      cancelled = true;
-    };
-  }, [parentLanguage, projectPath]);
-
-  const lifecycleMap = useMemo<Record<string, WordLifecycleFlags>>(() => {
-    if (parentLanguage && !parentWordsLoaded) return {};
-    const result: Record<string, WordLifecycleFlags> = {};
-
-    wordsList.forEach((word) => {
-      const parentEntryId = word.etymology?.parent_entry_id;
-      const parentWord =
-        parentLanguage && parentEntryId ? parentWordsById[parentEntryId] : null;
-      const evolution = getWordEvolution(word);
-      const isNew = !!parentLanguage && !parentWord;
-      const isChanged =
-        !!parentLanguage && !!parentWord
-          ? (() => {
-              const lexicalChanged = hasWordChangedFromParent(word, parentWord);
-              if (lexicalChanged) return true;
-              if (!parentPhonoConfig) return false;
-              const childSurface = generateIPA(
-                word.con_word_romanized,
-                config,
-              ).surface;
-              const parentSurface = generateIPA(
-                parentWord.con_word_romanized,
-                parentPhonoConfig,
-              ).surface;
-              return childSurface !== parentSurface;
-            })()
-          : false;
-      const isDeprecated = evolution.is_deprecated;
-      result[word.entry_id] = {
-        isNew,
-        isDeprecated,
-        isChanged: isDeprecated ? false : isChanged,
-      };
-    });
-
-    return result;
-  }, [wordsList, parentLanguage, parentWordsById, parentPhonoConfig, config, parentWordsLoaded]);
+    if (!parentLanguage && (filterLifecycle === "added" || filterLifecycle === "changed")) {
+      setFilterLifecycle("");
+    }
+  }, [parentLanguage, filterLifecycle]);
 
   const inventory = useMemo(
     () => [
@@ -177,18 +95,71 @@ export const Sidebar: React.FC = () => {
     [config.phoneme_inventory],
   );
 
-  // Collect all unique tags from all words
-  const allTags = useMemo(() => {
-    const tagSet = new Set<string>();
-    wordsList.forEach((w) => {
-      w.metadata?.tags?.forEach((tag) => tagSet.add(tag));
-    });
-    return Array.from(tagSet).sort();
-  }, [wordsList]);
+  const ipaSearchActive = ipaMode && !!searchTerm.trim();
+
+  // IPA 模糊搜索按音段特征打分，后端查询没有对应条件，只能在整个词典上计算；
+  // 得到的 ID 再交给 query_words，与其他筛选条件一起分页
+  React.useEffect(() => {
+    if (!ipaSearchActive) {
+      setIpaMatchIds(null);
+      return;
+    }
+    let cancelled = false;
+    const timer = setTimeout(async () => {
+      const words = await loadFullLexicon();
+      if (cancelled) return;
+      setIpaMatchIds(
+        ipaFuzzySearch(searchTerm, words, inventory, 0.6).map((r) => r.entry.entry_id),
+      );
+    }, 200);
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [ipaSearchActive, searchTerm, inventory, loadFullLexicon, loadVersion]);
+
+  const pageQuery = useMemo<WordQuery | null>(() => {
+    if (ipaSearchActive && !ipaMatchIds) return null;
+    return {
+      text: ipaSearchActive ? null : searchTerm.trim() || null,
+      text_matches_romanized: true,
+      entry_ids: ipaSearchActive ? ipaMatchIds : null,
+      pos_id: filterPos || null,
+      origin_type: (filterOrigin || null) as OriginType | null,
+      tags: filterTags,
+      lifecycle: filterLifecycle || null,
+      parent_language_path: parentLanguage?.path ?? null,
+      sort_by: "romanized",
+      descending: sortMode === "za",
+    };
+  }, [
+    ipaSearchActive,
+    ipaMatchIds,
+    searchTerm,
+    filterPos,
+    filterOrigin,
+    filterTags,
+    filterLifecycle,
+    parentLanguage,
+    sortMode,
+  ]);
 
+  // 过滤、排序与分页都在后端完成；切换语言或数据重新加载（loadVersion）后重新查询
+  React.useEffect(() => {
+    if (!pageQuery) return;
+    const timer = setTimeout(() => queryPage(pageQuery), 200);
+    return () => clearTimeout(timer);
+  }, [pageQuery, queryPage, loadVersion]);
+
+  const displayWords = useMemo(
+    () => pageIds.map((id) => wordsMap[id]).filter((w): w is WordEntry => !!w),
+    [pageIds, wordsMap],
+  );
+
+  // 空白词条只可能是尚未保存的本地草稿（后端不保存没有释义的词条）
   const blankWordIds = useMemo(
     () =>
-      wordsList
+      Object.values(wordsMap)
         .filter((w) => {
           const rom = (w.con_word_romanized || "").trim();
           const hasMeaning = w.senses.some(
@@ -197,7 +168,7 @@ export const Sidebar: React.FC = () => {
           return rom === "" && !hasMeaning;
         })
         .map((w) => w.entry_id),
-    [wordsList],
+    [wordsMap],
   );
 
   const handleDeleteSelected = () => {
@@ -243,74 +214,6 @@ export const Sidebar: React.FC = () => {
     };
   }, []);
 
-  const filteredWords: WordEntry[] = useMemo(() => {
-    let result: WordEntry[];
-    if (!searchTerm.trim()) {
-      result = [...wordsList];
-    } else if (ipaMode) {
-      result = ipaFuzzySearch(searchTerm, wordsList, inventory, 0.6).map(
-        (r) => r.entry,
-      );
-    } else {
-      const q = searchTerm.toLowerCase();
-      result = wordsList.filter(
-        (w) =>
-          w.con_word_romanized.toLowerCase().includes(q) ||
-          w.senses.some((s) => s.gloss.toLowerCase().includes(q)),
-      );
-    }
-
-    // Apply advanced filters
-    if (filterPos) {
-      result = result.filter((w) =>
-        w.senses.some((s) => s.pos_id === filterPos),
-      );
-    }
-    if (filterOrigin) {
-      result = result.filter((w) => w.etymology?.origin_type === filterOrigin);
-    }
-    if (filterTags.length > 0) {
-      result = result.filter((w) =>
-        w.metadata?.tags?.some((tag) =>
-          filterTags.some((ft) => tag.toLowerCase().includes(ft.toLowerCase())),
-        ),
-      );
-    }
-
-    if (sortMode === "az") {
-      result.sort((a, b) =>
-        a.con_word_romanized.localeCompare(b.con_word_romanized),
-      );
-    } else if (sortMode === "za") {
-      result.sort((a, b) =>
-        b.con_word_romanized.localeCompare(a.con_word_romanized),
-      );
-    }
-    return result;
-  }, [
-    searchTerm,
-    wordsList,
-    ipaMode,
-    inventory,
-    sortMode,
-    filterPos,
-    filterOrigin,
-    filterTags,
-  ]);
-
-  const displayWords = useMemo(() => {
-    if (!filterLifecycle) return filteredWords;
-
-    return filteredWords.filter((word) => {
-      const lifecycle = lifecycleMap[word.entry_id];
-      if (!lifecycle) return false;
-
-      if (filterLifecycle === "added") return lifecycle.isNew;
-      if (filterLifecycle === "deprecated") return lifecycle.isDeprecated;
-      return lifecycle.isChanged;
-    });
-  }, [filteredWords, lifecycleMap, filterLifecycle]);
-
   const handleAddWord = () => {
     cleanupEmptyWord();
     const newId = crypto.randomUUID();
@@ -369,7 +272,7 @@ export const Sidebar: React.FC = () => {
               <CheckSquare size={18} />
             </button>
             <button
-              onClick={() => exportToCSV(wordsList)}
+              onClick={async () => exportToCSV(await loadFullLexicon())}
               className={BTN_GHOST_SQ}
               title={t("lexicon.exportCsv")}
             >
@@ -511,11 +414,14 @@ export const Sidebar: React.FC = () => {
                 }
               >
                 <option value="">{t("common.all", "All")}</option>
-                <option value="added">{t("lexicon.lifecycle.added")}</option>
+                {/* 新增 / 变化是相对父语言而言的，根语言不提供 */}
+                <option value="added" disabled={!parentLanguage}>
+                  {t("lexicon.lifecycle.added")}
+                </option>
                 <option value="deprecated">
                   {t("lexicon.lifecycle.deprecated")}
                 </option>
-                <option value="changed">
+                <option value="changed" disabled={!parentLanguage}>
                   {t("lexicon.lifecycle.changed")}
                 </option>
               </select>
@@ -554,7 +460,7 @@ export const Sidebar: React.FC = () => {
       </div>
       <div className="flex-1 overflow-y-auto relative p-2 space-y-2">
         {displayWords.map((word) => {
-          const lifecycle = lifecycleMap[word.entry_id];
+          const lifecycle = pageLifecycle[word.entry_id];
           const isSelected = selectedIds.has(word.entry_id);
           const isActive =
             activeWordId === word.entry_id &&
@@ -590,19 +496,19 @@ export const Sidebar: React.FC = () => {
               )}
               <div className="flex-1 min-w-0 relative">
                 {lifecycle &&
-                  (lifecycle.isNew || lifecycle.isDeprecated || lifecycle.isChanged) && (
+                  (lifecycle.is_new || lifecycle.is_deprecated || lifecycle.is_changed) && (
                     <div className="absolute right-0 top-0 flex items-center gap-1">
-                      {lifecycle.isNew && (
+                      {lifecycle.is_new && (
                         <span className={`${BADGE} badge-success`}>
                           {t("lexicon.lifecycle.added")}
                         </span>
                       )}
-                      {lifecycle.isDeprecated && (
+                      {lifecycle.is_deprecated && (
                         <span className={`${BADGE} badge-error`}>
                           {t("lexicon.lifecycle.deprecated")}
                         </span>
                       )}
-                      {lifecycle.isChanged && (
+                      {lifecycle.is_changed && (
                         <span className={`${BADGE} badge-warning`}>
                           {t("lexicon.lifecycle.changed")}
                         </span>
@@ -633,6 +539,14 @@ export const Sidebar: React.FC = () => {
             </div>
           );
         })}
+        {pageQuery && pageIds.length < pageTotal && (
+          <button
+            className="btn btn-ghost btn-xs w-full"
+            onClick={() => queryPage(pageQuery, true)}
+          >
+            {t("lexicon.loadMore", { count: pageTotal - pageIds.length })}
+          </button>
+        )}
       </div>
 
       {batchMode && selectedIds.size > 0 && (
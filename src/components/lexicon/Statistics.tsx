@@ -1,6 +1,6 @@
 import React, { useMemo } from "react";
 import { useTranslation } from "react-i18next";
-import { useLexiconStore } from "../../store/lexiconStore";
+import { useFullLexicon } from "../../store/lexiconStore";
 import { usePhonoStore } from "../../store/phonoStore";
 import { useGrammarStore } from "../../store/grammarStore";
 import { computeStatistics } from "../../utils/statistics";
@@ -11,11 +11,11 @@ import { PageHeader } from "../common/PageHeader";
 
 export const Statistics: React.FC = () => {
   const { t } = useTranslation();
-  const wordsMap = useLexiconStore((s) => s.wordsMap);
+  // 统计覆盖整个词典
+  const words = useFullLexicon();
   const phonoConfig = usePhonoStore((s) => s.config);
   const grammarConfig = useGrammarStore((s) => s.config);
 
-  const words = useMemo(() => Object.values(wordsMap), [wordsMap]);
   const stats = useMemo(
     () => computeStatistics(words, phonoConfig, grammarConfig),
     [words, phonoConfig, grammarConfig],
@@ -15,7 +15,7 @@ import {
 export const ProjectStats: React.FC = () => {
   const { t } = useTranslation();
 
-  const wordsCount = useLexiconStore((s) => s.wordsList.length);
+  const wordsCount = useLexiconStore((s) => s.lexiconSize);
   const consonantsCount = usePhonoStore(
     (s) => s.config.phoneme_inventory.consonants.length,
   );
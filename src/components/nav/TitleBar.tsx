@@ -7,10 +7,15 @@ import {
   Sun,
   Moon,
   Info,
+  Lock,
+  Undo2,
+  Redo2,
 } from "lucide-react";
 import { QuickEntry } from "../common/QuickEntry";
 import { LanguageToggle } from "../common/LanguageToggle";
+import { ConfirmModal } from "../common/ConfirmModal";
 import { useTheme } from "../../lib/useTheme";
+import { useWorkspaceStore } from "../../store/workspaceStore";
 
 const appWindow = getCurrentWindow();
 
@@ -38,8 +43,17 @@ export function TitleBar() {
   const { t } = useTranslation();
   const { theme, toggle: toggleTheme } = useTheme();
   const [quickEntryOpen, setQuickEntryOpen] = useState(false);
+  const [takeOverOpen, setTakeOverOpen] = useState(false);
   const location = useLocation();
   const isWelcome = location.pathname === "/welcome";
+  const lockStatus = useWorkspaceStore((s) => s.lockStatus);
+  const acquireLock = useWorkspaceStore((s) => s.acquireLock);
+  const journalStatus = useWorkspaceStore((s) => s.journalStatus);
+  const refreshJournal = useWorkspaceStore((s) => s.refreshJournal);
+  const undo = useWorkspaceStore((s) => s.undo);
+  const redo = useWorkspaceStore((s) => s.redo);
+  const readOnly = lockStatus?.mode === "read_only";
+  const holder = lockStatus?.holder;
 
   return (
     <header
@@ -81,8 +95,46 @@ export function TitleBar() {
 
       {/* ── Right: Utility Actions ── */}
       <div className="flex items-center gap-1 shrink-0">
+        {!isWelcome && readOnly && (
+          <button
+            onClick={() => setTakeOverOpen(true)}
+            className="badge badge-warning badge-sm gap-1 cursor-pointer"
+            title={
+              holder
+                ? t("titlebar.readOnlyHolder", "Locked by {{host}} (pid {{pid}}) — click to take over", {
+                    host: holder.hostname || "?",
+                    pid: holder.pid,
+                  })
+                : t("titlebar.readOnly", "Read-only")
+            }
+          >
+            <Lock className="w-3 h-3" />
+            {t("titlebar.readOnly", "Read-only")}
+          </button>
+        )}
+
         {!isWelcome && (
           <>
+            {/* 保存类命令不会通知前端，悬停时刷新一次日志状态 */}
+            <div className="flex items-center" onMouseEnter={refreshJournal}>
+              <button
+                onClick={undo}
+                className="btn btn-ghost btn-xs"
+                disabled={readOnly || journalStatus?.undo_count === 0}
+                title={t("titlebar.undo", "Undo")}
+              >
+                <Undo2 className="w-4 h-4" />
+              </button>
+              <button
+                onClick={redo}
+                className="btn btn-ghost btn-xs"
+                disabled={readOnly || journalStatus?.redo_count === 0}
+                title={t("titlebar.redo", "Redo")}
+              >
+                <Redo2 className="w-4 h-4" />
+              </button>
+            </div>
+
             <button
               onClick={() => setQuickEntryOpen(true)}
               className="btn btn-ghost btn-xs text-warning"
@@ -122,6 +174,20 @@ export function TitleBar() {
         isOpen={quickEntryOpen}
         onClose={() => setQuickEntryOpen(false)}
       />
+
+      <ConfirmModal
+        open={takeOverOpen}
+        title={t("titlebar.takeOver", "Take over project lock")}
+        message={t(
+          "titlebar.takeOverConfirm",
+          "The other window will become read-only and its unsaved changes may be lost.",
+        )}
+        onConfirm={() => {
+          acquireLock(true);
+          setTakeOverOpen(false);
+        }}
+        onCancel={() => setTakeOverOpen(false)}
+      />
     </header>
   );
 }
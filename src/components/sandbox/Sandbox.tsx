@@ -7,7 +7,7 @@
  */
 import React, { useState } from "react";
 import { useTranslation } from "react-i18next";
-import { useLexiconStore } from "../../store/lexiconStore";
+import { useFullLexicon, useLexiconStore } from "../../store/lexiconStore";
 import { usePhonoStore } from "../../store/phonoStore";
 import { useGrammarStore } from "../../store/grammarStore";
 import {
@@ -36,6 +36,8 @@ const ROLES: SyntaxRole[] = ["S", "V", "O", "Mod", "Adp", "Other"];
 
 export const Sandbox: React.FC = () => {
   const { t } = useTranslation();
+  // 沙盒按释义在整个词典中查词，需要全部词条
+  useFullLexicon();
   const { wordsMap } = useLexiconStore();
   const phonoConfig = usePhonoStore((s) => s.config);
   const grammarConfig = useGrammarStore((s) => s.config);
@@ -21,8 +21,15 @@ function generateId(): string {
 
 export function RuleSetList() {
   const { t } = useTranslation();
-  const { config, addRuleSet, deleteRuleSet, addRule, updateRule, deleteRule } =
-    useSCAStore();
+  const {
+    config,
+    addRuleSet,
+    updateRuleSet,
+    deleteRuleSet,
+    addRule,
+    updateRule,
+    deleteRule,
+  } = useSCAStore();
   const [expandedSets, setExpandedSets] = useState<Set<string>>(new Set());
   const [confirmDeleteId, setConfirmDeleteId] = useState<string | null>(null);
   const [confirmDeleteRuleId, setConfirmDeleteRuleId] = useState<{ rulesetId: string, ruleId: string } | null>(null);
@@ -143,6 +150,17 @@ export function RuleSetList() {
               <span className="font-medium text-sm flex-1">
                 {rs.name || t("sca.unnamed")}
               </span>
+              {rs.lexicon_applied_at && (
+                <button
+                  className="badge badge-sm badge-ghost"
+                  onClick={() =>
+                    updateRuleSet(rs.ruleset_id, { ...rs, lexicon_applied_at: null })
+                  }
+                  title={t("sca.forkAppliedClear")}
+                >
+                  {t("sca.forkApplied")}
+                </button>
+              )}
               <span className="text-xs text-base-content/50">#{rs.order}</span>
               <button
                 className={BTN_ERROR}
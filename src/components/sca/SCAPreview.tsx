@@ -3,7 +3,7 @@ import { useTranslation } from "react-i18next";
 import { Play, CheckCircle } from "lucide-react";
 import { useSCAStore } from "../../store/scaStore";
 import { usePhonoStore } from "../../store/phonoStore";
-import { useLexiconStore } from "../../store/lexiconStore";
+import { useFullLexicon, useLexiconStore } from "../../store/lexiconStore";
 import { useGrammarStore } from "../../store/grammarStore";
 import {
   applySoundChanges,
@@ -241,7 +241,9 @@ export function SCAPreview() {
   const { t } = useTranslation();
   const { config: scaConfig } = useSCAStore();
   const { config: phonoConfig } = usePhonoStore();
-  const { wordsList, upsertWord } = useLexiconStore();
+  // 音变预览与批量应用作用于整个词典
+  const wordsList = useFullLexicon();
+  const upsertWord = useLexiconStore((s) => s.upsertWord);
   const {
     config: grammarConfig,
     setInflections,
@@ -267,6 +269,13 @@ export function SCAPreview() {
     return applySoundChanges(testWord.trim(), scaConfig.rule_sets, macros);
   }, [testWord, scaConfig.rule_sets, macros]);
 
+  // 派生时已作用于继承词条的规则集不再应用到词典，语法规则仍使用全部规则集
+  const lexiconRuleSets = useMemo(
+    () => scaConfig.rule_sets.filter((rs) => !rs.lexicon_applied_at),
+    [scaConfig.rule_sets],
+  );
+  const skippedRuleSets = scaConfig.rule_sets.length - lexiconRuleSets.length;
+
   const batchRows = useMemo(() => {
     if (mode !== "batch") return [];
     const rows: PreviewRow[] = [];
@@ -274,7 +283,7 @@ export function SCAPreview() {
     for (const w of wordsList) {
       const { result, changelog } = applySoundChanges(
         w.con_word_romanized,
-        scaConfig.rule_sets,
+        lexiconRuleSets,
         macros,
       );
       if (changelog.length === 0) continue;
@@ -317,6 +326,7 @@ export function SCAPreview() {
     grammarConfig.inflection_rules,
     grammarConfig.derivation_rules,
     scaConfig.rule_sets,
+    lexiconRuleSets,
     macros,
   ]);
 
@@ -352,7 +362,7 @@ export function SCAPreview() {
     const wordResults = new Map(
       wordsList.map((w) => [
         w.entry_id,
-        applySoundChanges(w.con_word_romanized, scaConfig.rule_sets, macros),
+        applySoundChanges(w.con_word_romanized, lexiconRuleSets, macros),
       ]),
     );
     for (const w of wordsList) {
@@ -477,6 +487,11 @@ export function SCAPreview() {
 
       {mode === "batch" && (
         <div className="border border-base-200 rounded-lg p-4">
+          {skippedRuleSets > 0 && (
+            <p className="text-xs text-base-content/60 mb-2">
+              {t("sca.forkAppliedSkipped", { count: skippedRuleSets })}
+            </p>
+          )}
           <div className="overflow-x-auto max-h-96 overflow-y-auto">
             <table className="table table-xs">
               <thead>
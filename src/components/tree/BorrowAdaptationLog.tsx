@@ -0,0 +1,46 @@
+import { useTranslation } from "react-i18next";
+import { BADGE } from "../../lib/ui";
+import { BorrowReport } from "../../types";
+
+/** dry-run 的适配记录：每个词的来源形式 → 适配后形式、逐步的替换 / 修复，以及被映射或清空的词性 */
+export function BorrowAdaptationLog({ report }: { report: BorrowReport }) {
+  const { t } = useTranslation();
+
+  return (
+    <div className="max-h-48 overflow-y-auto space-y-1 text-sm">
+      {report.skipped.length > 0 && (
+        <p className="text-xs text-base-content/50">
+          {t("tree.borrowSkipped", { count: report.skipped.length })}
+        </p>
+      )}
+      {report.words.map((w) => (
+        <div key={w.source_entry_id} className="flex flex-wrap items-center gap-1">
+          <span className="font-mono">
+            {w.source_form} /{w.source_phonemes}/ → {w.adapted_form} /{w.adapted_phonemes}/
+          </span>
+          {w.steps.length === 0 && (
+            <span className="text-xs text-base-content/50">{t("tree.borrowUnchanged")}</span>
+          )}
+          {w.steps.map((step, i) => (
+            <span
+              key={i}
+              className={`${BADGE} ${step.kind === "blacklist" ? "badge-warning" : "badge-ghost"}`}
+              title={t(`tree.adaptStep.${step.kind}`)}
+            >
+              {step.kind === "blacklist"
+                ? step.to
+                : `${step.from || "∅"} → ${step.to || "∅"}`}
+            </span>
+          ))}
+          {w.pos_changes.map((change) => (
+            <span key={change.sense_id} className={`${BADGE} badge-info`}>
+              {change.pos_id
+                ? t("tree.borrowPosMapped", { from: change.source_pos_id, to: change.pos_id })
+                : t("tree.borrowPosCleared", { from: change.source_pos_id })}
+            </span>
+          ))}
+        </div>
+      ))}
+    </div>
+  );
+}
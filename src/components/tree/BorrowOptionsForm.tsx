@@ -0,0 +1,43 @@
+import { useTranslation } from "react-i18next";
+import { SELECT, CHECKBOX } from "../../lib/ui";
+
+export type BorrowRepair = "epenthesis" | "deletion";
+
+interface BorrowOptionsFormProps {
+  adapt: boolean;
+  repair: BorrowRepair;
+  onAdaptChange: (adapt: boolean) => void;
+  onRepairChange: (repair: BorrowRepair) => void;
+}
+
+/** 借词选项：是否按借入语言音系适配，以及音节结构不合法时的修复方式 */
+export function BorrowOptionsForm({ adapt, repair, onAdaptChange, onRepairChange }: BorrowOptionsFormProps) {
+  const { t } = useTranslation();
+
+  return (
+    <div className="flex flex-wrap gap-3 items-center text-sm">
+      <label className="flex items-center gap-2">
+        <input
+          type="checkbox"
+          className={CHECKBOX}
+          checked={adapt}
+          onChange={(e) => onAdaptChange(e.target.checked)}
+        />
+        {t("tree.borrowAdapt")}
+      </label>
+      {adapt && (
+        <label className="flex items-center gap-2">
+          {t("tree.borrowRepair")}
+          <select
+            className={`${SELECT} select-sm`}
+            value={repair}
+            onChange={(e) => onRepairChange(e.target.value as BorrowRepair)}
+          >
+            <option value="epenthesis">{t("tree.borrowRepairEpenthesis")}</option>
+            <option value="deletion">{t("tree.borrowRepairDeletion")}</option>
+          </select>
+        </label>
+      )}
+    </div>
+  );
+}
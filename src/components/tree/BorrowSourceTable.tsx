@@ -0,0 +1,60 @@
+import { useTranslation } from "react-i18next";
+import { WordEntry } from "../../types";
+
+interface BorrowSourceTableProps {
+  words: WordEntry[];
+  selectedIds: Set<string>;
+  onToggle: (entryId: string) => void;
+  onToggleAll: () => void;
+}
+
+/** 来源语言的词条列表，勾选要借入的词 */
+export function BorrowSourceTable({ words, selectedIds, onToggle, onToggleAll }: BorrowSourceTableProps) {
+  const { t } = useTranslation();
+
+  return (
+    <div className="max-h-60 overflow-y-auto">
+      <table className="table table-xs">
+        <thead>
+          <tr>
+            <th className="w-8">
+              <input
+                type="checkbox"
+                className="checkbox checkbox-xs"
+                checked={words.length > 0 && words.every((w) => selectedIds.has(w.entry_id))}
+                onChange={onToggleAll}
+              />
+            </th>
+            <th>{t("lexicon.word")}</th>
+            <th>{t("lexicon.gloss")}</th>
+          </tr>
+        </thead>
+        <tbody>
+          {words.map((w) => (
+            <tr
+              key={w.entry_id}
+              className={`cursor-pointer ${selectedIds.has(w.entry_id) ? "bg-primary/5" : ""
+                }`}
+              onClick={() => onToggle(w.entry_id)}
+            >
+              <td>
+                <input
+                  type="checkbox"
+                  className="checkbox checkbox-xs"
+                  checked={selectedIds.has(w.entry_id)}
+                  onChange={() => onToggle(w.entry_id)}
+                />
+              </td>
+              <td className="font-mono text-sm">
+                {w.con_word_romanized}
+              </td>
+              <td className="text-sm">
+                {w.senses.map((s) => s.gloss).join(", ")}
+              </td>
+            </tr>
+          ))}
+        </tbody>
+      </table>
+    </div>
+  );
+}
@@ -1,12 +1,17 @@
-import { useState, useMemo } from "react";
+import { useState, useEffect } from "react";
 import { useTranslation } from "react-i18next";
 import { BookCopy } from "lucide-react";
 import { useWorkspaceStore } from "../../store/workspaceStore";
-import { useLexiconStore } from "../../store/lexiconStore";
 import { SELECT, INPUT, BTN_PRIMARY_MD, BTN_PRIMARY } from "../../lib/ui";
-import { WordEntry } from "../../types";
+import { BorrowReport, WordEntry, WordQueryResult } from "../../types";
 import { invoke } from "@tauri-apps/api/core";
 import { ConfirmModal } from "../common/ConfirmModal";
+import { BorrowOptionsForm, BorrowRepair } from "./BorrowOptionsForm";
+import { BorrowAdaptationLog } from "./BorrowAdaptationLog";
+import { BorrowSourceTable } from "./BorrowSourceTable";
+
+/** 来源词条列表每次最多显示的条数，更多的通过搜索缩小范围 */
+const SOURCE_PAGE_SIZE = 100;
 
 export function BorrowingPanel() {
   const { t } = useTranslation();
@@ -14,64 +19,100 @@ export function BorrowingPanel() {
     config: wsConfig,
     activeLanguageId,
     projectPath,
+    borrowWords,
   } = useWorkspaceStore();
-  const { importWords } = useLexiconStore();
   const [sourceLanguageId, setSourceLanguageId] = useState("");
+  const [sourceLoaded, setSourceLoaded] = useState(false);
+  // 来源语言中与搜索词匹配的前 SOURCE_PAGE_SIZE 个词条（后端分页，不加载整个词典）
   const [sourceWords, setSourceWords] = useState<WordEntry[]>([]);
+  const [sourceTotal, setSourceTotal] = useState(0);
   const [search, setSearch] = useState("");
+  // 当前语言中已从来源语言借入的词条所指向的来源词条 ID
+  const [borrowedIds, setBorrowedIds] = useState<Set<string>>(new Set());
   const [loading, setLoading] = useState(false);
   const [selectedIds, setSelectedIds] = useState<Set<string>>(new Set());
   const [showConfirmModal, setShowConfirmModal] = useState(false);
-  const lexiconWords = useLexiconStore((s) => s.wordsList);
+  const [adapt, setAdapt] = useState(true);
+  const [repair, setRepair] = useState<BorrowRepair>("epenthesis");
+  // 后端 dry-run 的适配预览；确认后以相同选项再执行一次
+  const [preview, setPreview] = useState<BorrowReport | null>(null);
+  const [message, setMessage] = useState("");
 
   const otherLanguages = wsConfig.languages.filter(
     (l) => l.language_id !== activeLanguageId,
   );
 
-  const handleLoadSource = async () => {
-    if (!sourceLanguageId) return;
-    const sourceLang = wsConfig.languages.find(
-      (l) => l.language_id === sourceLanguageId,
-    );
-    if (!sourceLang) return;
-    setLoading(true);
-    setSelectedIds(new Set());
+  const sourceLang = wsConfig.languages.find(
+    (l) => l.language_id === sourceLanguageId,
+  );
+  const activeLang = wsConfig.languages.find(
+    (l) => l.language_id === activeLanguageId,
+  );
+
+  const loadBorrowedIds = async () => {
+    if (!activeLang) return;
     try {
-      const words = await invoke<WordEntry[]>("load_all_words", {
+      const result = await invoke<WordQueryResult>("query_words", {
         projectPath,
-        languagePath: sourceLang.path,
+        languagePath: activeLang.path,
+        query: { origin_type: "borrowed" },
       });
-      setSourceWords(words);
+      setBorrowedIds(
+        new Set(
+          result.words
+            .filter((w) => w.etymology?.source_language_id === sourceLanguageId)
+            .map((w) => w.etymology?.parent_entry_id ?? "")
+            .filter((id) => id),
+        ),
+      );
     } catch (err) {
-      console.warn(`Failed to load source words: ${err}`);
-    } finally {
-      setLoading(false);
+      console.warn(`Failed to load borrowed words: ${err}`);
     }
   };
 
-  const filteredWords = useMemo(
-    () =>
-      sourceWords.filter(
-        (w) =>
-          w.con_word_romanized.toLowerCase().includes(search.toLowerCase()) ||
-          w.senses.some((s) =>
-            s.gloss.toLowerCase().includes(search.toLowerCase()),
-          ),
-      ),
-    [sourceWords, search],
-  );
-
-  const isAlreadyBorrowed = (wordId: string) => {
-    return lexiconWords.some(
-      (lw: WordEntry) =>
-        lw.etymology?.origin_type === "borrowed" &&
-        lw.etymology?.source_language_id === sourceLanguageId &&
-        lw.etymology?.parent_entry_id === wordId
-    );
+  const handleLoadSource = async () => {
+    if (!sourceLang) return;
+    setSelectedIds(new Set());
+    setPreview(null);
+    setSourceLoaded(true);
+    await loadBorrowedIds();
   };
 
+  useEffect(() => {
+    if (!sourceLoaded || !sourceLang) return;
+    let cancelled = false;
+    const timer = setTimeout(async () => {
+      setLoading(true);
+      try {
+        const result = await invoke<WordQueryResult>("query_words", {
+          projectPath,
+          languagePath: sourceLang.path,
+          query: {
+            text: search.trim() || null,
+            text_matches_romanized: true,
+            limit: SOURCE_PAGE_SIZE,
+          },
+        });
+        if (cancelled) return;
+        setSourceWords(result.words);
+        setSourceTotal(result.total);
+      } catch (err) {
+        console.warn(`Failed to load source words: ${err}`);
+      } finally {
+        setLoading(false);
+      }
+    }, 200);
+    return () => {
+      cancelled = true;
+      clearTimeout(timer);
+    };
+  }, [sourceLoaded, sourceLang?.path, projectPath, search]);
+
+  const isAlreadyBorrowed = (wordId: string) => borrowedIds.has(wordId);
+
   const toggleSelect = (entryId: string) => {
     if (isAlreadyBorrowed(entryId)) return;
+    setPreview(null);
     setSelectedIds((prev) => {
       const next = new Set(prev);
       if (next.has(entryId)) next.delete(entryId);
@@ -81,8 +122,9 @@ export function BorrowingPanel() {
   };
 
   const toggleSelectAll = () => {
-    const visible = filteredWords.slice(0, 100).filter(w => !isAlreadyBorrowed(w.entry_id));
+    const visible = sourceWords.filter(w => !isAlreadyBorrowed(w.entry_id));
     const allSelected = visible.every((w) => selectedIds.has(w.entry_id));
+    setPreview(null);
     if (allSelected) {
       setSelectedIds((prev) => {
         const next = new Set(prev);
@@ -98,51 +140,37 @@ export function BorrowingPanel() {
     }
   };
 
-  const selectedWords = useMemo(
-    () => sourceWords.filter((w) => selectedIds.has(w.entry_id)),
-    [sourceWords, selectedIds],
-  );
-
-  const handleBorrowSelected = () => {
-    if (selectedWords.length === 0) return;
-    setShowConfirmModal(true);
-  };
-
-  const confirmBorrow = async () => {
-    // Optionally create snapshot before borrowing (oplog)
+  const runBorrow = async (dryRun: boolean) => {
+    if (selectedIds.size === 0) return;
+    setLoading(true);
+    setMessage("");
     try {
-      const activeLang = wsConfig.languages.find(
-        (l) => l.language_id === activeLanguageId,
-      );
-      if (activeLang) {
-        await invoke("create_snapshot", {
-          projectPath,
-          languagePath: activeLang.path,
-          operationType: "borrowing",
-          sourceLanguageId,
-          targetLanguageId: activeLanguageId,
-          description: `Borrowed ${selectedWords.length} words`,
-        });
+      const report = await borrowWords(sourceLanguageId, [...selectedIds], {
+        adapt,
+        repair,
+        dry_run: dryRun,
+      });
+      if (dryRun) {
+        setPreview(report);
+      } else {
+        setPreview(null);
+        setSelectedIds(new Set());
+        setMessage(t("tree.borrowDone", { count: report.words.length }));
+        await loadBorrowedIds();
       }
     } catch (err) {
-      console.warn(`Snapshot failed (non-fatal): ${err}`);
+      console.warn(`Borrowing failed: ${err}`);
+      setMessage(String(err));
+    } finally {
+      setLoading(false);
     }
+  };
 
-    const borrowed: WordEntry[] = selectedWords.map((word) => ({
-      ...word,
-      entry_id: `${word.entry_id}_borrowed_${Date.now().toString(36)}_${Math.random().toString(36).slice(2, 6)}`,
-      language_id: activeLanguageId,
-      etymology: {
-        origin_type: "borrowed",
-        parent_entry_id: word.entry_id,
-        source_language_id: sourceLanguageId,
-        applied_sound_changes: [],
-        semantic_shift_note: "",
-      },
-    }));
-    importWords(borrowed);
-    setSelectedIds(new Set());
+  const handlePreview = async () => runBorrow(true);
+
+  const confirmBorrow = async () => {
     setShowConfirmModal(false);
+    await runBorrow(false);
   };
 
   return (
@@ -156,8 +184,12 @@ export function BorrowingPanel() {
           value={sourceLanguageId}
           onChange={(e) => {
             setSourceLanguageId(e.target.value);
+            setSourceLoaded(false);
             setSourceWords([]);
+            setSourceTotal(0);
+            setBorrowedIds(new Set());
             setSelectedIds(new Set());
+            setPreview(null);
           }}
         >
           <option value="">{t("tree.selectSource")}</option>
@@ -176,7 +208,22 @@ export function BorrowingPanel() {
         </button>
       </div>
 
-      {sourceWords.length > 0 && (
+      <BorrowOptionsForm
+        adapt={adapt}
+        repair={repair}
+        onAdaptChange={(value) => {
+          setAdapt(value);
+          setPreview(null);
+        }}
+        onRepairChange={(value) => {
+          setRepair(value);
+          setPreview(null);
+        }}
+      />
+
+      {message && <p className="text-xs text-base-content/60">{message}</p>}
+
+      {sourceLoaded && (
         <>
           <input
             className={`${INPUT} w-full`}
@@ -191,67 +238,41 @@ export function BorrowingPanel() {
               <span className="text-sm">
                 {t("tree.selectedCount", { count: selectedIds.size })}
               </span>
-              <button className={BTN_PRIMARY} onClick={handleBorrowSelected}>
-                <BookCopy className="w-4 h-4" /> {t("tree.borrowSelected")}
-              </button>
+              <div className="flex gap-2">
+                <button className={BTN_PRIMARY} onClick={handlePreview} disabled={loading}>
+                  {t("tree.borrowPreview")}
+                </button>
+                <button
+                  className={BTN_PRIMARY}
+                  onClick={() => setShowConfirmModal(true)}
+                  disabled={loading}
+                >
+                  <BookCopy className="w-4 h-4" /> {t("tree.borrowSelected")}
+                </button>
+              </div>
             </div>
           )}
 
-          <div className="max-h-60 overflow-y-auto">
-            <table className="table table-xs">
-              <thead>
-                <tr>
-                  <th className="w-8">
-                    <input
-                      type="checkbox"
-                      className="checkbox checkbox-xs"
-                      checked={
-                        filteredWords.length > 0 &&
-                        filteredWords
-                          .slice(0, 100)
-                          .every((w) => selectedIds.has(w.entry_id))
-                      }
-                      onChange={toggleSelectAll}
-                    />
-                  </th>
-                  <th>{t("lexicon.word")}</th>
-                  <th>{t("lexicon.gloss")}</th>
-                </tr>
-              </thead>
-              <tbody>
-                {filteredWords.slice(0, 100).map((w) => (
-                  <tr
-                    key={w.entry_id}
-                    className={`cursor-pointer ${selectedIds.has(w.entry_id) ? "bg-primary/5" : ""
-                      }`}
-                    onClick={() => toggleSelect(w.entry_id)}
-                  >
-                    <td>
-                      <input
-                        type="checkbox"
-                        className="checkbox checkbox-xs"
-                        checked={selectedIds.has(w.entry_id)}
-                        onChange={() => toggleSelect(w.entry_id)}
-                      />
-                    </td>
-                    <td className="font-mono text-sm">
-                      {w.con_word_romanized}
-                    </td>
-                    <td className="text-sm">
-                      {w.senses.map((s) => s.gloss).join(", ")}
-                    </td>
-                  </tr>
-                ))}
-              </tbody>
-            </table>
-          </div>
+          {preview && <BorrowAdaptationLog report={preview} />}
+
+          <BorrowSourceTable
+            words={sourceWords}
+            selectedIds={selectedIds}
+            onToggle={toggleSelect}
+            onToggleAll={toggleSelectAll}
+          />
+          {sourceTotal > sourceWords.length && (
+            <span className="text-xs text-base-content/50">
+              +{sourceTotal - sourceWords.length} {t("common.more")}
+            </span>
+          )}
         </>
       )}
 
       <ConfirmModal
         open={showConfirmModal}
         title={t("tree.borrowSelected")}
-        message={t("tree.confirmBorrow", { count: selectedWords.length })}
+        message={t("tree.confirmBorrow", { count: selectedIds.size })}
         onConfirm={confirmBorrow}
         onCancel={() => setShowConfirmModal(false)}
       />
@@ -0,0 +1,163 @@
+import { useState, useEffect, useMemo } from "react";
+import { useTranslation } from "react-i18next";
+import { Download } from "lucide-react";
+import { invoke } from "@tauri-apps/api/core";
+import { save } from "@tauri-apps/plugin-dialog";
+import { useWorkspaceStore } from "../../store/workspaceStore";
+import { BADGE, BTN_GHOST, INPUT } from "../../lib/ui";
+import { CognateReflex, CognateTable } from "../../types";
+
+function ReflexCell({ reflex }: { reflex: CognateReflex }) {
+  const { t } = useTranslation();
+  return (
+    <div className="whitespace-nowrap" title={reflex.glosses.join("; ")}>
+      <span className={`font-mono ${reflex.is_deprecated ? "line-through opacity-50" : ""}`}>
+        {reflex.con_word_romanized}
+      </span>
+      {reflex.phonetic_ipa && (
+        <span className="text-xs text-base-content/50 ml-1">/{reflex.phonetic_ipa}/</span>
+      )}
+      {reflex.origin_type === "borrowed" && (
+        <span className={`${BADGE} badge-warning ml-1`}>{t("tree.cognatesLoan")}</span>
+      )}
+    </div>
+  );
+}
+
+export function CognateSets() {
+  const { t } = useTranslation();
+  const { config: wsConfig, activeLanguageId, projectPath, conlangFilePath, dataVersion } =
+    useWorkspaceStore();
+  const [table, setTable] = useState<CognateTable | null>(null);
+  const [search, setSearch] = useState("");
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const activeLang = wsConfig.languages.find((l) => l.language_id === activeLanguageId);
+
+  useEffect(() => {
+    if (!projectPath || !conlangFilePath) return;
+    setLoading(true);
+    setError(null);
+    invoke<CognateTable>("build_cognate_sets", {
+      projectPath,
+      conlangFilePath,
+      languageId: activeLanguageId,
+      entryIds: null,
+    })
+      .then(setTable)
+      .catch((err) => {
+        console.warn(`Failed to build cognate sets: ${err}`);
+        setError(String(err));
+        setTable(null);
+      })
+      .finally(() => setLoading(false));
+  }, [projectPath, conlangFilePath, activeLanguageId, dataVersion]);
+
+  const filteredSets = useMemo(() => {
+    const query = search.toLowerCase();
+    return (table?.sets ?? []).filter((set) =>
+      set.reflexes.some(
+        (r) =>
+          r.con_word_romanized.toLowerCase().includes(query) ||
+          r.glosses.some((g) => g.toLowerCase().includes(query)),
+      ),
+    );
+  }, [table, search]);
+
+  const handleExport = async (format: "csv" | "markdown") => {
+    const extension = format === "csv" ? "csv" : "md";
+    try {
+      const filePath = await save({
+        title: t(format === "csv" ? "tree.exportCsv" : "tree.exportMarkdown"),
+        defaultPath: `${activeLang?.name ?? activeLanguageId}_cognates.${extension}`,
+        filters: [{ name: format === "csv" ? "CSV" : "Markdown", extensions: [extension] }],
+      });
+      if (!filePath) return;
+      const content = await invoke<string>("export_cognate_sets", {
+        projectPath,
+        conlangFilePath,
+        languageId: activeLanguageId,
+        entryIds: null,
+        format,
+      });
+      await invoke("write_text_file", { filePath, content });
+    } catch (err) {
+      console.warn(`Cognate export failed: ${err}`);
+      setError(String(err));
+    }
+  };
+
+  return (
+    <div className="space-y-3">
+      <h3 className="font-semibold">{t("tree.cognates")}</h3>
+      <p className="text-xs text-base-content/50">{t("tree.cognatesDesc")}</p>
+
+      <div className="flex gap-2 items-center">
+        <input
+          className={`${INPUT} flex-1`}
+          placeholder={t("common.search")}
+          value={search}
+          onChange={(e) => setSearch(e.target.value)}
+        />
+        <button
+          className={BTN_GHOST}
+          onClick={() => handleExport("csv")}
+          disabled={!table || table.sets.length === 0}
+        >
+          <Download className="w-4 h-4" /> {t("tree.exportCsv")}
+        </button>
+        <button
+          className={BTN_GHOST}
+          onClick={() => handleExport("markdown")}
+          disabled={!table || table.sets.length === 0}
+        >
+          <Download className="w-4 h-4" /> {t("tree.exportMarkdown")}
+        </button>
+      </div>
+
+      {error && <p className="text-xs text-error">{error}</p>}
+      {loading && <p className="text-xs text-base-content/50">{t("common.loading")}</p>}
+
+      {table && !loading && table.sets.length === 0 && (
+        <p className="text-xs text-base-content/50">{t("tree.cognatesEmpty")}</p>
+      )}
+
+      {table && table.sets.length > 0 && (
+        <div className="max-h-96 overflow-auto">
+          <table className="table table-xs table-pin-rows">
+            <thead>
+              <tr>
+                <th>{t("lexicon.gloss")}</th>
+                {table.languages.map((l) => (
+                  <th key={l.language_id}>{l.name}</th>
+                ))}
+              </tr>
+            </thead>
+            <tbody>
+              {filteredSets.slice(0, 200).map((set) => (
+                <tr key={`${set.root_language_id}:${set.root_entry_id}`}>
+                  <td className="text-sm">{set.reflexes[0].glosses.join("; ")}</td>
+                  {table.languages.map((l) => (
+                    <td key={l.language_id}>
+                      {set.reflexes
+                        .filter((r) => r.language_id === l.language_id)
+                        .map((r) => (
+                          <ReflexCell key={r.entry_id} reflex={r} />
+                        ))}
+                    </td>
+                  ))}
+                </tr>
+              ))}
+            </tbody>
+          </table>
+          {filteredSets.length > 200 && (
+            <span className="text-xs text-base-content/50">
+              +{filteredSets.length - 200} {t("common.more")}
+            </span>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
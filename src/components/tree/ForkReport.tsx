@@ -0,0 +1,69 @@
+import { useTranslation } from "react-i18next";
+import { BADGE } from "../../lib/ui";
+import { ForkSoundChangeReport } from "../../types";
+
+/** 分化时应用音变的结果：改动的词数、被跳过的规则、音位合并和新产生的同音词 */
+export function ForkReport({ report }: { report: ForkSoundChangeReport }) {
+  const { t } = useTranslation();
+
+  return (
+    <div className="py-2 space-y-3 text-sm">
+      <p className="text-base-content/70">
+        {t("tree.forkReport.summary", {
+          changed: report.changed,
+          words: report.words,
+          ipa: report.ipa_regenerated,
+        })}
+      </p>
+      {report.skipped_rules.length > 0 && (
+        <p className="text-xs text-warning">
+          {t("tree.forkReport.skipped", {
+            rules: report.skipped_rules.join(", "),
+          })}
+        </p>
+      )}
+      {report.mergers.length > 0 && (
+        <div className="space-y-1">
+          <p className="font-medium">
+            {t("tree.forkReport.mergers")}
+          </p>
+          <div className="flex flex-wrap gap-2">
+            {report.mergers.map((m) => (
+              <span
+                key={`${m.rule_id}-${m.from}-${m.into}`}
+                className={`${BADGE} badge-ghost font-mono`}
+                title={m.rule_id}
+              >
+                {m.from} → {m.into || "∅"} ({m.words})
+              </span>
+            ))}
+          </div>
+        </div>
+      )}
+      {report.homophones.length > 0 && (
+        <div className="space-y-1">
+          <p className="font-medium">
+            {t("tree.forkReport.homophones", {
+              count: report.homophones.length,
+            })}
+          </p>
+          <div className="max-h-60 overflow-y-auto bg-base-200/50 rounded-lg border border-base-300 divide-y divide-base-300">
+            {report.homophones.map((group) => (
+              <div
+                key={group.form}
+                className="px-3 py-1.5 flex items-center gap-2"
+              >
+                <span className="font-medium">{group.form}</span>
+                <span className="ml-auto text-xs text-base-content/50 truncate">
+                  {group.members
+                    .map((m) => `${m.parent_form} “${m.gloss}”`)
+                    .join(" · ")}
+                </span>
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
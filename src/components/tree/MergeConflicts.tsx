@@ -0,0 +1,106 @@
+import { useState, useEffect } from "react";
+import { useTranslation } from "react-i18next";
+import { GitMerge } from "lucide-react";
+import { invoke } from "@tauri-apps/api/core";
+import { useWorkspaceStore } from "../../store/workspaceStore";
+import { MergeConflict, MergeConflictChoice, MergeConflictGroup } from "../../types";
+
+/** 冲突一方的值：不存在时显示为已删除，字符串原样显示，其余按 JSON 显示 */
+function formatSide(value: unknown, removed: string): string {
+    if (value === null || value === undefined) return removed;
+    if (typeof value === "string") return value;
+    return JSON.stringify(value);
+}
+
+/** conlang-merge 留下的 _merge_conflicts 记录：逐个字段选择保留哪一方 */
+export function MergeConflicts() {
+    const { t } = useTranslation();
+    const { projectPath, conlangFilePath, config, dataVersion } = useWorkspaceStore();
+    const [groups, setGroups] = useState<MergeConflictGroup[]>([]);
+    const [error, setError] = useState<string | null>(null);
+
+    const load = async () => {
+        try {
+            setGroups(await invoke<MergeConflictGroup[]>("list_merge_conflicts", { projectPath, conlangFilePath }));
+        } catch (err) {
+            console.warn("Failed to load merge conflicts:", err);
+        }
+    };
+
+    useEffect(() => {
+        load();
+    }, [projectPath, conlangFilePath, dataVersion]);
+
+    const resolve = async (group: MergeConflictGroup, conflict: MergeConflict, choice: MergeConflictChoice) => {
+        setError(null);
+        try {
+            await invoke("resolve_merge_conflict", {
+                projectPath,
+                file: group.file,
+                entryId: group.entry_id,
+                field: conflict.field,
+                choice,
+            });
+            // 词条或配置已改写：让各页面重新读取当前语言的数据
+            useWorkspaceStore.setState((s) => ({ dataVersion: s.dataVersion + 1 }));
+        } catch (err) {
+            setError(String(err));
+            await load();
+        }
+    };
+
+    if (groups.length === 0) return null;
+
+    const languageName = (id: string) => config.languages.find((l) => l.language_id === id)?.name ?? id;
+    const removed = t("tree.mergeConflicts.removed");
+
+    return (
+        <div className="space-y-2">
+            <h4 className="font-semibold text-sm flex items-center gap-2 text-warning">
+                <GitMerge className="w-4 h-4" /> {t("tree.mergeConflicts.title")}
+            </h4>
+            <p className="text-xs text-base-content/60">{t("tree.mergeConflicts.description")}</p>
+            <div className="bg-base-200/50 rounded-lg border border-warning/40 max-h-96 overflow-y-auto divide-y divide-base-300">
+                {groups.map((group) =>
+                    group.conflicts.map((conflict) => (
+                        <div key={`${group.file}|${group.entry_id ?? ""}|${conflict.field}`} className="p-3 space-y-1">
+                            <div className="text-sm">
+                                <span className="font-medium">{group.label}</span>
+                                <span className="text-xs text-base-content/50">
+                                    {" "}• {languageName(group.language_id)} •{" "}
+                                    <span className="font-mono">
+                                        {conflict.field === "entry" ? t("tree.mergeConflicts.wholeEntry") : conflict.field}
+                                    </span>
+                                </span>
+                            </div>
+                            <div className="flex flex-wrap gap-1">
+                                <button
+                                    className="btn btn-xs btn-ghost font-normal"
+                                    onClick={() => resolve(group, conflict, "ours")}
+                                    title={t("tree.mergeConflicts.keepOurs")}
+                                >
+                                    {t("tree.mergeConflicts.keepOurs")}: <span className="font-mono truncate max-w-48">{formatSide(conflict.ours, removed)}</span>
+                                </button>
+                                <button
+                                    className="btn btn-xs btn-ghost font-normal"
+                                    onClick={() => resolve(group, conflict, "theirs")}
+                                    title={t("tree.mergeConflicts.useTheirs")}
+                                >
+                                    {t("tree.mergeConflicts.useTheirs")}: <span className="font-mono truncate max-w-48">{formatSide(conflict.theirs, removed)}</span>
+                                </button>
+                                <button
+                                    className="btn btn-xs btn-ghost font-normal text-base-content/60"
+                                    onClick={() => resolve(group, conflict, "base")}
+                                    title={t("tree.mergeConflicts.useBase")}
+                                >
+                                    {t("tree.mergeConflicts.useBase")}: <span className="font-mono truncate max-w-48">{formatSide(conflict.base, removed)}</span>
+                                </button>
+                            </div>
+                        </div>
+                    )),
+                )}
+            </div>
+            {error && <p className="text-xs text-error">{error}</p>}
+        </div>
+    );
+}
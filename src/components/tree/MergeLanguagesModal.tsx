@@ -0,0 +1,184 @@
+import { useEffect, useMemo, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { Merge } from "lucide-react";
+import { useWorkspaceStore } from "../../store/workspaceStore";
+import type { LanguageEntry, LanguageMergeReport } from "../../types";
+import { BADGE, BTN_GHOST, BTN_OUTLINE_ERROR, SELECT } from "../../lib/ui";
+import { ModalPortal } from "../common/ModalPortal";
+
+interface MergeLanguagesModalProps {
+  /** 合并后保留的语言；为 null 时不显示 */
+  language: LanguageEntry | null;
+  onClose: () => void;
+}
+
+/** 把一门姊妹语言并入当前语言：预览词典、语法、音变与语料的合并结果及冲突，确认后执行 */
+export function MergeLanguagesModal({
+  language,
+  onClose,
+}: MergeLanguagesModalProps) {
+  const { t } = useTranslation();
+  const { config, mergeLanguages } = useWorkspaceStore();
+  const [sourceId, setSourceId] = useState("");
+  const [report, setReport] = useState<LanguageMergeReport | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const siblings = useMemo(
+    () =>
+      language
+        ? config.languages.filter(
+            (l) =>
+              l.language_id !== language.language_id &&
+              l.parent_id === language.parent_id,
+          )
+        : [],
+    [config.languages, language],
+  );
+
+  useEffect(() => {
+    setSourceId(siblings[0]?.language_id ?? "");
+  }, [language]);
+
+  useEffect(() => {
+    setReport(null);
+    setError(null);
+    if (!language || !sourceId) return;
+    mergeLanguages(language.language_id, sourceId, true)
+      .then(setReport)
+      .catch((err) => setError(String(err)));
+  }, [language, sourceId]);
+
+  if (!language) return null;
+
+  const handleApply = async () => {
+    setBusy(true);
+    try {
+      await mergeLanguages(language.language_id, sourceId, false);
+      onClose();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const sourceName =
+    siblings.find((l) => l.language_id === sourceId)?.name ?? sourceId;
+
+  return (
+    <ModalPortal open={true}>
+      <div className="modal modal-open">
+        <div className="modal-box max-w-2xl">
+          <h3 className="font-bold text-lg flex items-center gap-2">
+            <Merge size={20} />{" "}
+            {t("tree.merge.title", { name: language.name })}
+          </h3>
+          {siblings.length === 0 ? (
+            <p className="py-3 text-sm text-base-content/60">
+              {t("tree.merge.noSiblings")}
+            </p>
+          ) : (
+            <label className="form-control mt-3">
+              <span className="label-text mb-1">{t("tree.merge.source")}</span>
+              <select
+                className={`${SELECT} w-full`}
+                value={sourceId}
+                onChange={(e) => setSourceId(e.target.value)}
+              >
+                {siblings.map((l) => (
+                  <option key={l.language_id} value={l.language_id}>
+                    {l.name}
+                  </option>
+                ))}
+              </select>
+            </label>
+          )}
+          {report && (
+            <div className="py-3 space-y-3 text-sm">
+              <p className="text-base-content/70">
+                {t("tree.merge.summary", {
+                  unified: report.unified,
+                  fromSource: report.taken_from_source,
+                  added: report.added,
+                  renamed: report.renamed,
+                })}
+              </p>
+              <p className="text-base-content/70">
+                {t("tree.merge.otherSummary", {
+                  grammar: report.grammar_added,
+                  ruleSets: report.rule_sets_added,
+                  corpus: report.corpus_moved,
+                  children: report.children_reparented.length,
+                })}
+              </p>
+              {(report.conflicts.length > 0 ||
+                report.grammar_conflicts.length > 0) && (
+                <>
+                  <p className="text-warning">
+                    {t("tree.merge.conflicts", {
+                      count:
+                        report.conflicts.length +
+                        report.grammar_conflicts.length,
+                    })}
+                  </p>
+                  <div className="max-h-60 overflow-y-auto bg-base-200/50 rounded-lg border border-base-300 divide-y divide-base-300">
+                    {report.conflicts.map((c) => (
+                      <div
+                        key={c.parent_entry_id}
+                        className="px-3 py-1.5 flex items-center gap-2"
+                      >
+                        <span className={`${BADGE} badge-warning`}>
+                          {t("tree.merge.lexicon")}
+                        </span>
+                        <span className="font-medium">
+                          {c.target.con_word_romanized}
+                        </span>
+                        <span className="text-base-content/50 line-through">
+                          {c.source.con_word_romanized}
+                        </span>
+                        <span className="ml-auto font-mono text-xs text-base-content/50 truncate">
+                          {c.target.senses[0]?.gloss}
+                        </span>
+                      </div>
+                    ))}
+                    {report.grammar_conflicts.map((c) => (
+                      <div
+                        key={`${c.kind}-${c.id}`}
+                        className="px-3 py-1.5 flex items-center gap-2"
+                      >
+                        <span className={`${BADGE} badge-warning`}>
+                          {t("tree.merge.grammar")}
+                        </span>
+                        <span className="font-mono text-xs">{c.id}</span>
+                        <span className="ml-auto text-xs text-base-content/50">
+                          {c.kind}
+                        </span>
+                      </div>
+                    ))}
+                  </div>
+                </>
+              )}
+              <p className="text-xs text-base-content/50">
+                {t("tree.merge.note", { source: sourceName, path: report.source_path })}
+              </p>
+            </div>
+          )}
+          {error && <p className="py-2 text-xs text-error">{error}</p>}
+          <div className="modal-action">
+            <button className={BTN_GHOST} onClick={onClose}>
+              {t("common.cancel")}
+            </button>
+            <button
+              className={BTN_OUTLINE_ERROR}
+              disabled={!report || busy}
+              onClick={handleApply}
+            >
+              {t("tree.merge.apply")}
+            </button>
+          </div>
+        </div>
+      </div>
+    </ModalPortal>
+  );
+}
@@ -1,26 +1,19 @@
-import { useMemo, useState } from "react";
+import { useState } from "react";
 import { useTranslation } from "react-i18next";
 import { Download, AlertTriangle } from "lucide-react";
 import { invoke } from "@tauri-apps/api/core";
 import { useWorkspaceStore } from "../../store/workspaceStore";
-import { useLexiconStore } from "../../store/lexiconStore";
-import { usePhonoStore } from "../../store/phonoStore";
 import { useSCAStore } from "../../store/scaStore";
-import { applySoundChanges, buildMacrosFromInventory } from "../../utils/scaEngine";
-import { BTN_PRIMARY } from "../../lib/ui";
-import { WordEntry } from "../../types";
-import { getWordComparableDigest, getWordEvolution, getWordSyncDigest } from "../../utils/wordLifecycle";
+import { BADGE, BTN_PRIMARY } from "../../lib/ui";
+import { PullSyncReport } from "../../types";
 
-interface PendingSyncWord {
-  word: WordEntry;
-  mode: "add" | "update";
-}
-
-function isValidWordEntry(word: WordEntry): boolean {
-  const romanized = (word.con_word_romanized || "").trim();
-  if (!romanized || romanized === "new_word") return false;
-  return word.senses.some((sense) => (sense.gloss || "").trim().length > 0);
-}
+const STATUS_BADGE: Record<string, string> = {
+  new: "badge-success",
+  parent_changed: "badge-info",
+  child_changed: "badge-ghost",
+  both_changed: "badge-warning",
+  deprecated: "badge-error",
+};
 
 export function PullSync() {
   const { t } = useTranslation();
@@ -29,27 +22,18 @@ export function PullSync() {
     activeLanguageId,
     projectPath,
     conlangFilePath,
+    pullFromParent,
   } = useWorkspaceStore();
-  const { wordsList, importWords } = useLexiconStore();
-  const { config: phonoConfig } = usePhonoStore();
   const { config: scaConfig } = useSCAStore();
 
-  const [pendingSyncWords, setPendingSyncWords] = useState<PendingSyncWord[]>([]);
-  const [conflictCount, setConflictCount] = useState(0);
+  // 后端三方比较的预览结果；拉取时再以非 dry-run 执行一次
+  const [report, setReport] = useState<PullSyncReport | null>(null);
   const [loading, setLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
   const [migrating, setMigrating] = useState(false);
   const [previewingMigration, setPreviewingMigration] = useState(false);
   const [migrationMessage, setMigrationMessage] = useState("");
 
-  const addCount = useMemo(
-    () => pendingSyncWords.filter((item) => item.mode === "add").length,
-    [pendingSyncWords],
-  );
-  const updateCount = useMemo(
-    () => pendingSyncWords.filter((item) => item.mode === "update").length,
-    [pendingSyncWords],
-  );
-
   const activeLang = wsConfig.languages.find(
     (language) => language.language_id === activeLanguageId,
   );
@@ -57,158 +41,31 @@ export function PullSync() {
     ? wsConfig.languages.find((language) => language.language_id === activeLang.parent_id)
     : null;
 
-  const macros = useMemo(
-    () =>
-      buildMacrosFromInventory(
-        phonoConfig.phoneme_inventory.consonants,
-        phonoConfig.phoneme_inventory.vowels,
-        phonoConfig.phonotactics.macros,
-      ),
-    [phonoConfig],
-  );
+  const pending = report
+    ? report.added + report.updated + report.deprecated
+    : 0;
+  // 只改动了子语言的词条会原样保留，不逐个列出
+  const listed =
+    report?.entries.filter((entry) => entry.status !== "child_changed") ?? [];
 
-  const handleCheckForUpdates = async () => {
+  const runPull = async (dryRun: boolean) => {
     if (!parentLang) return;
     setLoading(true);
-
+    setError(null);
     try {
-      const parentWords = await invoke<WordEntry[]>("load_all_words", {
-        projectPath,
-        languagePath: parentLang.path,
-      });
-      const validParentWords = parentWords.filter(isValidWordEntry);
-
-      const childByParentId = new Map<string, WordEntry>();
-      wordsList.forEach((word) => {
-        const parentEntryId = word.etymology.parent_entry_id ?? word.entry_id;
-        childByParentId.set(parentEntryId, word);
-      });
-
-      const nextPending: PendingSyncWord[] = [];
-      let conflicts = 0;
-
-      validParentWords.forEach((parentWord) => {
-        const { result, changelog } = applySoundChanges(
-          parentWord.con_word_romanized,
-          scaConfig.rule_sets,
-          macros,
-        );
-        const appliedRuleIds = Array.from(new Set(changelog.map((step) => step.rule_id)));
-        const parentEvolution = getWordEvolution(parentWord);
-
-        const parentComparableHash = getWordComparableDigest(parentWord);
-        const parentSyncHash = getWordSyncDigest(parentWord);
-
-        const syncedCandidate: WordEntry = {
-          ...parentWord,
-          entry_id: parentWord.entry_id,
-          language_id: activeLanguageId,
-          con_word_romanized: result.trim(),
-          phonetic_ipa: "",
-          phonetic_override: false,
-          etymology: {
-            ...parentWord.etymology,
-            origin_type: "evolved",
-            parent_entry_id: parentWord.entry_id,
-            source_language_id: parentLang.language_id,
-            applied_sound_changes: appliedRuleIds,
-          },
-          evolution: {
-            ...parentEvolution,
-            parent_snapshot_hash: parentSyncHash,
-            last_synced_word_hash: null,
-          },
-        };
-
-        const syncedWordSyncHash = getWordSyncDigest(syncedCandidate);
-        const normalizedEvolution = getWordEvolution(syncedCandidate);
-        syncedCandidate.evolution = {
-          ...normalizedEvolution,
-          last_synced_word_hash: syncedWordSyncHash,
-        };
-
-        const existingChild = childByParentId.get(parentWord.entry_id);
-        if (!existingChild) {
-          nextPending.push({ word: syncedCandidate, mode: "add" });
-          return;
-        }
-
-        const existingEvolution = getWordEvolution(existingChild);
-        const currentChildHash = getWordComparableDigest(existingChild);
-        const currentChildSyncHash = getWordSyncDigest(existingChild);
-
-        // Never synced (freshly forked) — compare content directly
-        if (!existingEvolution.parent_snapshot_hash) {
-          if (syncedWordSyncHash !== currentChildSyncHash) {
-            nextPending.push({
-              word: { ...syncedCandidate, entry_id: existingChild.entry_id },
-              mode: "update",
-            });
-          }
-          return;
-        }
-
-        // Has been synced before — use hash-based comparison
-        const parentSnapshotHash = existingEvolution.parent_snapshot_hash;
-        const parentChanged =
-          parentSnapshotHash !== parentSyncHash &&
-          parentSnapshotHash !== parentComparableHash;
-
-        const lastSyncedHash = existingEvolution.last_synced_word_hash;
-        const childChangedSinceLastSync = lastSyncedHash
-          ? lastSyncedHash !== currentChildSyncHash &&
-            lastSyncedHash !== currentChildHash
-          : false;
-
-        if (parentChanged && childChangedSinceLastSync) {
-          conflicts += 1;
-          return;
-        }
-
-        if (!parentChanged && !childChangedSinceLastSync) {
-          return;
-        }
-
-        nextPending.push({
-          word: {
-            ...syncedCandidate,
-            entry_id: existingChild.entry_id,
-          },
-          mode: "update",
-        });
-      });
-
-      setPendingSyncWords(nextPending);
-      setConflictCount(conflicts);
-    } catch (error) {
-      console.warn(`Pull sync check failed: ${error}`);
-      setPendingSyncWords([]);
-      setConflictCount(0);
+      const result = await pullFromParent(activeLanguageId, dryRun);
+      setReport(dryRun ? result : null);
+    } catch (err) {
+      console.warn(`Pull sync failed: ${err}`);
+      setError(String(err));
+      setReport(null);
     } finally {
       setLoading(false);
     }
   };
 
-  const handlePullAll = async () => {
-    try {
-      if (activeLang) {
-        await invoke("create_snapshot", {
-          projectPath,
-          languagePath: activeLang.path,
-          operationType: "pull_sync",
-          sourceLanguageId: parentLang?.language_id || "",
-          targetLanguageId: activeLanguageId,
-          description: `Pulled ${pendingSyncWords.length} words via sound changes`,
-        });
-      }
-    } catch (error) {
-      console.warn("Failed to create snapshot before pull sync:", error);
-    }
-
-    importWords(pendingSyncWords.map((item) => item.word));
-    setPendingSyncWords([]);
-    setConflictCount(0);
-  };
+  const handleCheckForUpdates = async () => runPull(true);
+  const handlePullAll = async () => runPull(false);
 
   const runMigration = async (dryRun: boolean) => {
     if (!conlangFilePath) return;
@@ -307,42 +164,66 @@ export function PullSync() {
         </div>
       )}
 
-      {pendingSyncWords.length > 0 && (
+      {error && <p className="text-xs text-error">{error}</p>}
+
+      {report && (pending > 0 || report.conflicts.length > 0) && (
         <div className="space-y-2">
-          {addCount > 0 && (
+          {report.added > 0 && (
+            <p className="text-sm">
+              {t("tree.newWordsAvailable", { count: report.added })}
+            </p>
+          )}
+          {report.updated > 0 && (
             <p className="text-sm">
-              {t("tree.newWordsAvailable", { count: addCount })}
+              {t("tree.updatedWordsAvailable", { count: report.updated })}
             </p>
           )}
-          {updateCount > 0 && (
+          {report.deprecated > 0 && (
             <p className="text-sm">
-              {t("tree.updatedWordsAvailable", { count: updateCount })}
+              {t("tree.deprecatedUpstream", { count: report.deprecated })}
+            </p>
+          )}
+          {report.child_changed > 0 && (
+            <p className="text-xs text-base-content/60">
+              {t("tree.childChangedKept", { count: report.child_changed })}
             </p>
           )}
-          {conflictCount > 0 && (
+          {report.conflicts.length > 0 && (
             <p className="text-xs text-warning">
-              {t("tree.syncConflicts", { count: conflictCount })}
+              {t("tree.syncConflicts", { count: report.conflicts.length })}
             </p>
           )}
           <div className="max-h-40 overflow-y-auto space-y-1">
-            {pendingSyncWords.slice(0, 20).map((item) => (
-              <span key={item.word.entry_id} className="badge badge-sm mr-1">
-                {item.word.con_word_romanized}
-              </span>
-            ))}
-            {pendingSyncWords.length > 20 && (
+            {listed.slice(0, 20).map((entry) => (
+                <span
+                  key={entry.entry_id}
+                  className={`${BADGE} ${STATUS_BADGE[entry.status]} mr-1`}
+                  title={t(`tree.syncStatus.${entry.status}`)}
+                >
+                  {entry.status === "both_changed"
+                    ? `${entry.child_form} ≠ ${entry.evolved_form}`
+                    : (entry.evolved_form ?? entry.child_form)}
+                </span>
+              ))}
+            {listed.length > 20 && (
               <span className="text-xs text-base-content/50">
-                +{pendingSyncWords.length - 20} {t("common.more")}
+                +{listed.length - 20} {t("common.more")}
               </span>
             )}
           </div>
-          <button className={BTN_PRIMARY} onClick={handlePullAll}>
-            {t("tree.pullAndEvolve")}
-          </button>
+          {pending > 0 && (
+            <button
+              className={BTN_PRIMARY}
+              onClick={handlePullAll}
+              disabled={loading}
+            >
+              {t("tree.pullAndEvolve")}
+            </button>
+          )}
         </div>
       )}
 
-      {pendingSyncWords.length === 0 && !loading && (
+      {report && pending === 0 && report.conflicts.length === 0 && !loading && (
         <p className="text-xs text-base-content/50">{t("tree.upToDate")}</p>
       )}
     </div>
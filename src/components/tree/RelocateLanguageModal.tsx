@@ -0,0 +1,156 @@
+import { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { FolderInput } from "lucide-react";
+import { useWorkspaceStore } from "../../store/workspaceStore";
+import type { LanguageEntry, LanguageRenameReport } from "../../types";
+import { BADGE, BTN_GHOST, BTN_PRIMARY, INPUT_MONO } from "../../lib/ui";
+import { ModalPortal } from "../common/ModalPortal";
+
+interface RelocateLanguageModalProps {
+  /** 为 null 时不显示 */
+  language: LanguageEntry | null;
+  onClose: () => void;
+}
+
+/** 修改语言 ID 与目录：先预览将被改写的引用，确认后由后端一次性改写整个工作区 */
+export function RelocateLanguageModal({
+  language,
+  onClose,
+}: RelocateLanguageModalProps) {
+  const { t } = useTranslation();
+  const relocateLanguage = useWorkspaceStore((s) => s.relocateLanguage);
+  const [newId, setNewId] = useState("");
+  const [newPath, setNewPath] = useState("");
+  const [report, setReport] = useState<LanguageRenameReport | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    setNewId(language?.language_id ?? "");
+    setNewPath(language?.path ?? "");
+    setReport(null);
+    setError(null);
+  }, [language]);
+
+  if (!language) return null;
+
+  const run = async (dryRun: boolean) => {
+    setBusy(true);
+    setError(null);
+    try {
+      const result = await relocateLanguage(
+        language.language_id,
+        newId.trim(),
+        newPath.trim(),
+        dryRun,
+      );
+      if (dryRun) {
+        setReport(result);
+      } else {
+        onClose();
+      }
+    } catch (err) {
+      setReport(null);
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  // 输入变化后旧的预览作废
+  const edit = (setter: (value: string) => void) => (value: string) => {
+    setter(value);
+    setReport(null);
+    setError(null);
+  };
+
+  return (
+    <ModalPortal open={true}>
+      <div className="modal modal-open">
+        <div className="modal-box max-w-2xl">
+          <h3 className="font-bold text-lg flex items-center gap-2">
+            <FolderInput size={20} />{" "}
+            {t("tree.relocate.title", { name: language.name })}
+          </h3>
+          <div className="grid grid-cols-2 gap-3 mt-3">
+            <label className="form-control">
+              <span className="label-text mb-1">{t("tree.relocate.newId")}</span>
+              <input
+                className={`${INPUT_MONO} w-full`}
+                value={newId}
+                onChange={(e) => edit(setNewId)(e.target.value)}
+              />
+            </label>
+            <label className="form-control">
+              <span className="label-text mb-1">
+                {t("tree.relocate.newPath")}
+              </span>
+              <input
+                className={`${INPUT_MONO} w-full`}
+                value={newPath}
+                onChange={(e) => edit(setNewPath)(e.target.value)}
+              />
+            </label>
+          </div>
+          {report && (
+            <div className="py-3 space-y-3 text-sm">
+              <p className="text-base-content/70">
+                {t("tree.relocate.summary", {
+                  count: report.references,
+                  files: report.files.length,
+                  words: report.words_updated,
+                })}
+              </p>
+              {report.moved.length > 0 && (
+                <div className="space-y-1">
+                  {report.moved.map((m) => (
+                    <p key={m.language_id} className="font-mono text-xs">
+                      {m.old_path} → {m.new_path}
+                    </p>
+                  ))}
+                </div>
+              )}
+              <div className="max-h-60 overflow-y-auto bg-base-200/50 rounded-lg border border-base-300 divide-y divide-base-300">
+                {report.files.map((f) => (
+                  <div
+                    key={f.path}
+                    className="px-3 py-1.5 flex items-center gap-2"
+                  >
+                    <span className={`${BADGE} badge-ghost`}>{f.kind}</span>
+                    <span className="font-mono text-xs truncate">{f.path}</span>
+                    <span className="ml-auto text-xs text-base-content/50">
+                      {t("tree.relocate.references", { count: f.references })}
+                    </span>
+                  </div>
+                ))}
+              </div>
+            </div>
+          )}
+          {error && <p className="py-2 text-xs text-error">{error}</p>}
+          <div className="modal-action">
+            <button className={BTN_GHOST} onClick={onClose}>
+              {t("common.cancel")}
+            </button>
+            {report ? (
+              <button
+                className={BTN_PRIMARY}
+                disabled={busy}
+                onClick={() => run(false)}
+              >
+                {t("tree.relocate.apply")}
+              </button>
+            ) : (
+              <button
+                className={BTN_PRIMARY}
+                disabled={busy || !newId.trim() || !newPath.trim()}
+                onClick={() => run(true)}
+              >
+                {t("tree.relocate.preview")}
+              </button>
+            )}
+          </div>
+        </div>
+      </div>
+    </ModalPortal>
+  );
+}
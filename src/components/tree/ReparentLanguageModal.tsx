@@ -0,0 +1,200 @@
+import { useEffect, useMemo, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { Network } from "lucide-react";
+import { useWorkspaceStore } from "../../store/workspaceStore";
+import type { LanguageEntry, ReparentReport } from "../../types";
+import { BADGE, BTN_GHOST, BTN_PRIMARY, CHECKBOX, SELECT } from "../../lib/ui";
+import { ModalPortal } from "../common/ModalPortal";
+
+interface ReparentLanguageModalProps {
+  /** 为 null 时不显示 */
+  language: LanguageEntry | null;
+  onClose: () => void;
+}
+
+const STATUS_BADGE: Record<string, string> = {
+  resolved: "badge-success",
+  relinked: "badge-info",
+  borrowed: "badge-warning",
+  unresolved: "badge-ghost",
+};
+
+/** 更换父语言：预览继承词条的链接在新父语言中能否对上，再决定其余词条改挂还是标记为借词 */
+export function ReparentLanguageModal({
+  language,
+  onClose,
+}: ReparentLanguageModalProps) {
+  const { t } = useTranslation();
+  const { config, reparentLanguage } = useWorkspaceStore();
+  const [newParentId, setNewParentId] = useState("");
+  const [relink, setRelink] = useState(true);
+  const [borrowUnmatched, setBorrowUnmatched] = useState(false);
+  const [report, setReport] = useState<ReparentReport | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  // 自身及其后代不能作为新的父语言
+  const options = useMemo(() => {
+    if (!language) return [];
+    const excluded = new Set([language.language_id]);
+    let grew = true;
+    while (grew) {
+      grew = false;
+      for (const l of config.languages) {
+        if (l.parent_id && excluded.has(l.parent_id) && !excluded.has(l.language_id)) {
+          excluded.add(l.language_id);
+          grew = true;
+        }
+      }
+    }
+    return config.languages.filter((l) => !excluded.has(l.language_id));
+  }, [config.languages, language]);
+
+  useEffect(() => {
+    setNewParentId(language?.parent_id ?? "");
+    setReport(null);
+    setError(null);
+  }, [language]);
+
+  const unchanged = (language?.parent_id ?? "") === newParentId;
+
+  useEffect(() => {
+    setReport(null);
+    setError(null);
+    if (!language || unchanged) return;
+    reparentLanguage(language.language_id, newParentId || null, {
+      relink,
+      borrowUnmatched,
+      dryRun: true,
+    })
+      .then(setReport)
+      .catch((err) => setError(String(err)));
+  }, [language, newParentId, relink, borrowUnmatched]);
+
+  if (!language) return null;
+
+  const handleApply = async () => {
+    setBusy(true);
+    try {
+      await reparentLanguage(language.language_id, newParentId || null, {
+        relink,
+        borrowUnmatched,
+        dryRun: false,
+      });
+      onClose();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  // 已对上的链接无需关注，只列出其余词条
+  const pending = report?.links.filter((l) => l.status !== "resolved") ?? [];
+
+  return (
+    <ModalPortal open={true}>
+      <div className="modal modal-open">
+        <div className="modal-box max-w-2xl">
+          <h3 className="font-bold text-lg flex items-center gap-2">
+            <Network size={20} />{" "}
+            {t("tree.reparent.title", { name: language.name })}
+          </h3>
+          <label className="form-control mt-3">
+            <span className="label-text mb-1">
+              {t("tree.reparent.newParent")}
+            </span>
+            <select
+              className={`${SELECT} w-full`}
+              value={newParentId}
+              onChange={(e) => setNewParentId(e.target.value)}
+            >
+              <option value="">{t("tree.reparent.noParent")}</option>
+              {options.map((l) => (
+                <option key={l.language_id} value={l.language_id}>
+                  {l.name}
+                </option>
+              ))}
+            </select>
+          </label>
+          <div className="flex gap-6 mt-3 text-sm">
+            <label className="flex items-center gap-2 cursor-pointer">
+              <input
+                type="checkbox"
+                className={CHECKBOX}
+                checked={relink}
+                onChange={(e) => setRelink(e.target.checked)}
+              />
+              {t("tree.reparent.relink")}
+            </label>
+            <label className="flex items-center gap-2 cursor-pointer">
+              <input
+                type="checkbox"
+                className={CHECKBOX}
+                checked={borrowUnmatched}
+                onChange={(e) => setBorrowUnmatched(e.target.checked)}
+              />
+              {t("tree.reparent.borrowUnmatched")}
+            </label>
+          </div>
+          {report && (
+            <div className="py-3 space-y-3 text-sm">
+              <p className="text-base-content/70">
+                {t("tree.reparent.summary", {
+                  resolved: report.resolved,
+                  relinked: report.relinked,
+                  borrowed: report.borrowed,
+                  unresolved: report.unresolved,
+                })}
+              </p>
+              {pending.length > 0 && (
+                <div className="max-h-72 overflow-y-auto bg-base-200/50 rounded-lg border border-base-300 divide-y divide-base-300">
+                  {pending.map((link) => (
+                    <div
+                      key={link.entry_id}
+                      className="px-3 py-1.5 flex items-center gap-2"
+                    >
+                      <span className={`${BADGE} ${STATUS_BADGE[link.status]}`}>
+                        {t(`tree.reparent.status.${link.status}`)}
+                      </span>
+                      <span className="font-medium truncate">
+                        {link.con_word_romanized}
+                      </span>
+                      <span className="ml-auto text-xs text-base-content/50 truncate">
+                        {link.candidate
+                          ? t("tree.reparent.candidate", {
+                              word: link.candidate.con_word_romanized,
+                              by: t(
+                                `tree.reparent.matchedBy.${link.candidate.matched_by}`,
+                              ),
+                            })
+                          : link.ambiguous > 0
+                            ? t("tree.reparent.ambiguous", {
+                                count: link.ambiguous,
+                              })
+                            : t("tree.reparent.noMatch")}
+                      </span>
+                    </div>
+                  ))}
+                </div>
+              )}
+            </div>
+          )}
+          {error && <p className="py-2 text-xs text-error">{error}</p>}
+          <div className="modal-action">
+            <button className={BTN_GHOST} onClick={onClose}>
+              {t("common.cancel")}
+            </button>
+            <button
+              className={BTN_PRIMARY}
+              disabled={!report || busy}
+              onClick={handleApply}
+            >
+              {t("tree.reparent.apply")}
+            </button>
+          </div>
+        </div>
+      </div>
+    </ModalPortal>
+  );
+}
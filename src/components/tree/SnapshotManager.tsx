@@ -1,10 +1,11 @@
 import { useState, useEffect } from "react";
 import { useTranslation } from "react-i18next";
-import { History, RotateCcw, AlertTriangle } from "lucide-react";
+import { History, RotateCcw, AlertTriangle, GitCompare } from "lucide-react";
 import { invoke } from "@tauri-apps/api/core";
 import { useWorkspaceStore } from "../../store/workspaceStore";
 import { INPUT } from "../../lib/ui";
 import { ConfirmModal } from "../common/ConfirmModal";
+import { SnapshotDiff } from "../../types";
 
 export interface OperationLogEntry {
     log_id: string;
@@ -14,6 +15,7 @@ export interface OperationLogEntry {
     target_language_id: string;
     description: string;
     snapshot_dir: string;
+    components: string[];
 }
 
 export interface OperationLog {
@@ -29,6 +31,8 @@ export function SnapshotManager() {
     const [loading, setLoading] = useState(false);
     const [maxSnapshotsInput, setMaxSnapshotsInput] = useState("10");
     const [rollbackTarget, setRollbackTarget] = useState<OperationLogEntry | null>(null);
+    const [diff, setDiff] = useState<SnapshotDiff | null>(null);
+    const [selectedIds, setSelectedIds] = useState<Set<string>>(new Set());
 
     const loadLog = async () => {
         setLoading(true);
@@ -48,8 +52,61 @@ export function SnapshotManager() {
 
     useEffect(() => {
         loadLog();
+        setDiff(null);
     }, [projectPath, activeLanguagePath]);
 
+    const loadDiff = async (logId: string) => {
+        if (diff?.log_id === logId) {
+            setDiff(null);
+            return;
+        }
+        try {
+            const data = await invoke<SnapshotDiff>("diff_snapshot", {
+                projectPath,
+                languagePath: activeLanguagePath,
+                logId,
+            });
+            setDiff(data);
+            setSelectedIds(new Set());
+        } catch (err) {
+            console.warn("Failed to diff snapshot:", err);
+        }
+    };
+
+    const toggleSelected = (entryId: string) => {
+        setSelectedIds(prev => {
+            const next = new Set(prev);
+            if (next.has(entryId)) next.delete(entryId);
+            else next.add(entryId);
+            return next;
+        });
+    };
+
+    const restoreSelected = async () => {
+        if (!diff || selectedIds.size === 0) return;
+        try {
+            await invoke("restore_snapshot_entries", {
+                projectPath,
+                languagePath: activeLanguagePath,
+                logId: diff.log_id,
+                entryIds: Array.from(selectedIds),
+            });
+            const refreshed = await invoke<SnapshotDiff>("diff_snapshot", {
+                projectPath,
+                languagePath: activeLanguagePath,
+                logId: diff.log_id,
+            });
+            setDiff(refreshed);
+            setSelectedIds(new Set());
+            useWorkspaceStore.getState().loadWorkspace(useWorkspaceStore.getState().conlangFilePath);
+        } catch (err) {
+            console.warn("Selective restore failed:", err);
+        }
+    };
+
+    const statusLabel = (status: string) =>
+        status === "added" ? t("tree.diffAdded") : status === "removed" ? t("tree.diffRemoved") : t("tree.diffModified");
+
     const handleUpdateLimit = async () => {
         const parsed = parseInt(maxSnapshotsInput, 10);
         if (isNaN(parsed) || parsed < 1 || parsed > 100) return;
@@ -118,22 +175,83 @@ export function SnapshotManager() {
                                 <div>
                                     <div className="text-sm font-medium">{entry.description}</div>
                                     <div className="text-xs text-base-content/50 mt-0.5">
-                                        {new Date(parseInt(entry.timestamp) * 1000).toLocaleString()} • {entry.operation_type}
+                                        {new Date(entry.timestamp).toLocaleString()} • {entry.operation_type}
+                                    </div>
+                                    <div className="flex flex-wrap gap-1 mt-1">
+                                        {entry.components.map(c => (
+                                            <span key={c} className="badge badge-ghost badge-xs font-mono">{c}</span>
+                                        ))}
                                     </div>
                                 </div>
-                                <button
-                                    className="btn btn-sm btn-ghost text-error"
-                                    onClick={() => setRollbackTarget(entry)}
-                                    title={t("tree.rollbackHere")}
-                                >
-                                    <RotateCcw className="w-4 h-4" />
-                                </button>
+                                <div className="flex items-center gap-1">
+                                    {entry.components.includes("lexicon") && (
+                                        <button
+                                            className={`btn btn-sm btn-ghost ${diff?.log_id === entry.log_id ? "btn-active" : ""}`}
+                                            onClick={() => loadDiff(entry.log_id)}
+                                            title={t("tree.diffSnapshot")}
+                                        >
+                                            <GitCompare className="w-4 h-4" />
+                                        </button>
+                                    )}
+                                    <button
+                                        className="btn btn-sm btn-ghost text-error"
+                                        onClick={() => setRollbackTarget(entry)}
+                                        title={t("tree.rollbackHere")}
+                                    >
+                                        <RotateCcw className="w-4 h-4" />
+                                    </button>
+                                </div>
                             </div>
                         ))}
                     </div>
                 )}
             </div>
 
+            {diff && (
+                <div className="bg-base-200/50 rounded-lg border border-base-300">
+                    {diff.entries.length === 0 ? (
+                        <div className="p-4 text-center text-base-content/50 text-sm">
+                            {t("tree.noDifferences")}
+                        </div>
+                    ) : (
+                        <>
+                            <div className="divide-y divide-base-300 max-h-72 overflow-y-auto">
+                                {diff.entries.map(item => (
+                                    <label key={item.entry_id} className="p-2 flex items-start gap-2 cursor-pointer hover:bg-base-200">
+                                        <input
+                                            type="checkbox"
+                                            className="checkbox checkbox-xs mt-0.5"
+                                            checked={selectedIds.has(item.entry_id)}
+                                            onChange={() => toggleSelected(item.entry_id)}
+                                        />
+                                        <div className="min-w-0">
+                                            <div className="text-sm font-medium flex items-center gap-2">
+                                                {item.con_word_romanized}
+                                                <span className="badge badge-ghost badge-xs">{statusLabel(item.status)}</span>
+                                            </div>
+                                            {item.status === "modified" && (
+                                                <div className="text-xs text-base-content/50 font-mono truncate">
+                                                    {item.changes.map(c => c.field).join(", ")}
+                                                </div>
+                                            )}
+                                        </div>
+                                    </label>
+                                ))}
+                            </div>
+                            <div className="p-2 flex justify-end border-t border-base-300">
+                                <button
+                                    className="btn btn-sm btn-primary"
+                                    disabled={selectedIds.size === 0}
+                                    onClick={restoreSelected}
+                                >
+                                    {t("tree.restoreSelected", { count: selectedIds.size })}
+                                </button>
+                            </div>
+                        </>
+                    )}
+                </div>
+            )}
+
             <p className="text-xs text-base-content/50 flex items-start gap-1">
                 <AlertTriangle className="w-3.5 h-3.5 flex-shrink-0 mt-0.5" />
                 {t("tree.snapshotWarning")}
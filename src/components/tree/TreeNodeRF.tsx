@@ -8,6 +8,9 @@ import {
   Check,
   X,
   FolderOpen,
+  FolderInput,
+  Network,
+  Merge,
 } from "lucide-react";
 import { invoke } from "@tauri-apps/api/core";
 import { Handle, Position } from "@xyflow/react";
@@ -17,7 +20,17 @@ import { BTN_GHOST, BTN_ERROR, CARD, CARD_BODY, INPUT } from "../../lib/ui";
 import type { TreeFlowNode } from "../../utils/useTreeLayout";
 
 function TreeNodeInner({ data }: NodeProps<TreeFlowNode>) {
-  const { language, stats, onFork, onSwitch, onDelete, canDelete } = data;
+  const {
+    language,
+    stats,
+    onFork,
+    onSwitch,
+    onDelete,
+    onRelocate,
+    onReparent,
+    onMerge,
+    canDelete,
+  } = data;
   const { t } = useTranslation();
   const activeLanguageId = useWorkspaceStore((s) => s.activeLanguageId);
   const renameLanguage = useWorkspaceStore((s) => s.renameLanguage);
@@ -135,6 +148,27 @@ function TreeNodeInner({ data }: NodeProps<TreeFlowNode>) {
             >
               <FolderOpen className="w-3.5 h-3.5" />
             </button>
+            <button
+              className={BTN_GHOST}
+              onClick={() => onRelocate(language)}
+              title={t("tree.relocate.action")}
+            >
+              <FolderInput className="w-3.5 h-3.5" />
+            </button>
+            <button
+              className={BTN_GHOST}
+              onClick={() => onReparent(language)}
+              title={t("tree.reparent.action")}
+            >
+              <Network className="w-3.5 h-3.5" />
+            </button>
+            <button
+              className={BTN_GHOST}
+              onClick={() => onMerge(language)}
+              title={t("tree.merge.action")}
+            >
+              <Merge className="w-3.5 h-3.5" />
+            </button>
             {canDelete && (
               <button
                 className={BTN_ERROR}
@@ -0,0 +1,175 @@
+import { useState, useEffect } from "react";
+import { useTranslation } from "react-i18next";
+import { GitCommitHorizontal, RotateCcw, Save } from "lucide-react";
+import { invoke } from "@tauri-apps/api/core";
+import { useWorkspaceStore } from "../../store/workspaceStore";
+import { INPUT } from "../../lib/ui";
+import { ConfirmModal } from "../common/ConfirmModal";
+import { MergeConflicts } from "./MergeConflicts";
+import { GitCommitInfo, GitRepoStatus, GitRevertResult } from "../../types";
+
+export function VersionHistory() {
+    const { t } = useTranslation();
+    const { projectPath, conlangFilePath, activeLanguagePath } = useWorkspaceStore();
+
+    const [status, setStatus] = useState<GitRepoStatus | null>(null);
+    const [commits, setCommits] = useState<GitCommitInfo[]>([]);
+    const [onlyActiveLanguage, setOnlyActiveLanguage] = useState(true);
+    const [message, setMessage] = useState("");
+    const [revertTarget, setRevertTarget] = useState<GitCommitInfo | null>(null);
+    const [error, setError] = useState<string | null>(null);
+
+    const load = async () => {
+        try {
+            const data = await invoke<GitRepoStatus>("git_status", { projectPath });
+            setStatus(data);
+            if (!data.initialized) {
+                setCommits([]);
+                return;
+            }
+            const log = await invoke<GitCommitInfo[]>("git_log", {
+                projectPath,
+                conlangFilePath,
+                languagePath: onlyActiveLanguage ? activeLanguagePath : null,
+            });
+            setCommits(log);
+        } catch (err) {
+            console.warn("Failed to load git history:", err);
+        }
+    };
+
+    useEffect(() => {
+        load();
+    }, [projectPath, activeLanguagePath, onlyActiveLanguage]);
+
+    const run = async (action: () => Promise<unknown>) => {
+        setError(null);
+        try {
+            await action();
+        } catch (err) {
+            setError(String(err));
+        }
+        await load();
+    };
+
+    const handleInit = () => run(() => invoke("git_init", { projectPath, conlangFilePath }));
+
+    const handleCheckpoint = () =>
+        run(async () => {
+            await invoke<GitCommitInfo | null>("git_checkpoint", {
+                projectPath,
+                conlangFilePath,
+                message: message.trim() || null,
+            });
+            setMessage("");
+        });
+
+    const confirmRevert = () =>
+        run(async () => {
+            if (!revertTarget) return;
+            await invoke<GitRevertResult>("git_revert_language", {
+                projectPath,
+                conlangFilePath,
+                languagePath: activeLanguagePath,
+                commitId: revertTarget.commit_id,
+            });
+            setRevertTarget(null);
+            // 与快照回退相同：重新加载工作区，让各页面读取恢复后的文件
+            useWorkspaceStore.getState().loadWorkspace(conlangFilePath);
+        });
+
+    if (!status) {
+        return <div className="p-4 text-center"><span className="loading loading-spinner" /></div>;
+    }
+
+    return (
+        <div className="space-y-4">
+            <h3 className="font-bold text-lg flex items-center gap-2">
+                <GitCommitHorizontal className="w-5 h-5" /> {t("tree.versionHistory")}
+            </h3>
+
+            {!status.initialized ? (
+                <div className="p-6 text-center space-y-3 bg-base-200/50 rounded-lg border border-base-300">
+                    <p className="text-sm text-base-content/60">{t("tree.gitNotInitialized")}</p>
+                    <button className="btn btn-sm btn-primary" onClick={handleInit}>
+                        {t("tree.gitInit")}
+                    </button>
+                </div>
+            ) : (
+                <>
+                    <div className="flex items-center gap-2">
+                        <input
+                            className={`${INPUT} input-sm flex-1`}
+                            placeholder={t("tree.checkpointPlaceholder")}
+                            value={message}
+                            onChange={e => setMessage(e.target.value)}
+                        />
+                        <button
+                            className="btn btn-sm btn-primary"
+                            disabled={status.changed_files.length === 0}
+                            onClick={handleCheckpoint}
+                        >
+                            <Save className="w-4 h-4" /> {t("tree.checkpoint")}
+                        </button>
+                    </div>
+                    <div className="flex items-center justify-between text-xs text-base-content/50">
+                        <span>{t("tree.uncommittedChanges", { count: status.changed_files.length })}</span>
+                        <label className="flex items-center gap-1 cursor-pointer">
+                            <input
+                                type="checkbox"
+                                className="checkbox checkbox-xs"
+                                checked={onlyActiveLanguage}
+                                onChange={e => setOnlyActiveLanguage(e.target.checked)}
+                            />
+                            {t("tree.onlyActiveLanguage")}
+                        </label>
+                    </div>
+
+                    <div className="bg-base-200/50 rounded-lg border border-base-300 max-h-96 overflow-y-auto">
+                        {commits.length === 0 ? (
+                            <div className="p-8 text-center text-base-content/50 text-sm">
+                                {t("tree.noCommits")}
+                            </div>
+                        ) : (
+                            <div className="divide-y divide-base-300">
+                                {commits.map(commit => (
+                                    <div key={commit.commit_id} className="p-3 flex items-center justify-between hover:bg-base-200 transition-colors">
+                                        <div className="min-w-0">
+                                            <div className="text-sm font-medium truncate">{commit.summary}</div>
+                                            <div className="text-xs text-base-content/50 mt-0.5">
+                                                <span className="font-mono">{commit.short_id}</span> • {new Date(commit.time).toLocaleString()} • {commit.author}
+                                            </div>
+                                        </div>
+                                        {onlyActiveLanguage && (
+                                            <button
+                                                className="btn btn-sm btn-ghost text-error"
+                                                onClick={() => setRevertTarget(commit)}
+                                                title={t("tree.revertLanguage")}
+                                            >
+                                                <RotateCcw className="w-4 h-4" />
+                                            </button>
+                                        )}
+                                    </div>
+                                ))}
+                            </div>
+                        )}
+                    </div>
+
+                    <MergeConflicts />
+                </>
+            )}
+
+            {error && <p className="text-xs text-error">{error}</p>}
+
+            {revertTarget && (
+                <ConfirmModal
+                    open={true}
+                    title={t("tree.revertLanguage")}
+                    message={t("tree.revertLanguageConfirm", { commit: revertTarget.short_id, summary: revertTarget.summary })}
+                    onConfirm={confirmRevert}
+                    onCancel={() => setRevertTarget(null)}
+                />
+            )}
+        </div>
+    );
+}
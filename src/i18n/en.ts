@@ -59,6 +59,7 @@ export default {
       ipaSearch: "IPA Search",
 
       ipaSearchPlaceholder: "Type IPA to find similar words...",
+      loadMore: "Load more ({{count}} remaining)",
       playTts: "Play pronunciation",
       selectWord: "Select a word to edit",
       word: "Word",
@@ -365,7 +366,6 @@ export default {
       posTitle: "Parts of Speech",
       addPos: "Add POS",
       deletePos: "Delete Part of Speech",
-      deletePosConfirm: "Are you sure you want to delete this part of speech?",
       noPos: "No parts of speech defined. Add one to get started.",
       posName: "Name",
       posNamePlaceholder: "e.g. Noun",
@@ -376,8 +376,6 @@ export default {
       reqPron: "Req. Pron",
       addRule: "Add Rule",
       deleteRule: "Delete Rule",
-      deleteRuleConfirm:
-        "Are you sure you want to delete this inflection rule?",
       noRules:
         "No inflection rules yet. Add rules to define morphological patterns.",
       pos: "POS",
@@ -420,9 +418,19 @@ export default {
         deleteValue: "Delete Value",
         valueName: "Value Name",
         valueGloss: "Gloss (e.g. NOM, ACC)",
-        deleteDimensionConfirm:
-          "Are you sure you want to delete this dimension?",
-        deleteValueConfirm: "Are you sure you want to delete this value?",
+      },
+      refs: {
+        summary:
+          "{{count}} references will be updated, including {{entries}} dictionary entries.",
+        noReferences: "Nothing else refers to it.",
+        lexicon: "Lexicon",
+        snapshotNote:
+          "A snapshot is taken first; you can roll back from the snapshot manager.",
+        effect: {
+          rename: "Rename",
+          unlink: "Unlink",
+          remove: "Remove",
+        },
       },
       derivation: {
         title: "Derivation Rules",
@@ -580,6 +588,11 @@ export default {
       batchApply: "Apply to Lexicon",
       batchApplyAll: "Apply to Lexicon & Grammar Rules",
       applied: "Applied!",
+      forkApplied: "Applied at fork",
+      forkAppliedClear:
+        "These rules were already applied to the inherited lexicon when this language was forked, so batch apply skips them for lexicon words. Click to include them again.",
+      forkAppliedSkipped:
+        "{{count}} rule set(s) were already applied to the lexicon at fork time and are skipped for lexicon words.",
       scope: "Scope",
       label: "Target",
       field: "Field",
@@ -614,12 +627,22 @@ export default {
         "Pull new vocabulary from the parent language, then apply your sound change rules to evolve them into this language's phonology.",
       borrowing: "Borrowing",
       borrowingDesc:
-        "Borrow vocabulary from any other language in the family tree. Borrowed words are copied as-is without applying sound changes.",
+        "Borrow vocabulary from any other language in the workspace. Borrowed words are adapted to this language's phoneme inventory and syllable structure.",
       words: "words",
       switchTo: "Switch to this language",
       fork: "Fork",
       forkTitle: "Fork Language",
       forkFrom: "Fork from",
+      forkSoundChanges: "Sound changes at fork time",
+      forkNoSoundChanges: "None (copy the lexicon verbatim)",
+      forkCopyRulesFrom: "Apply the rules of {{name}}",
+      forkReport: {
+        summary:
+          "{{changed}} of {{words}} inherited words changed form; IPA regenerated for {{ipa}}.",
+        skipped: "Skipped rules with invalid context: {{rules}}",
+        mergers: "Mergers",
+        homophones: "{{count}} new homophone groups",
+      },
       newLangName: "New Language Name",
       newLangNamePlaceholder: "e.g. Daughter Language A",
       noParentLanguage: "This language has no parent (root language).",
@@ -630,6 +653,15 @@ export default {
       pullAndEvolve: "Pull & Apply Sound Changes",
       upToDate: "All up to date!",
       syncConflicts: "{{count}} conflicted entries kept child-language version",
+      deprecatedUpstream: "{{count}} words deleted upstream will be marked deprecated",
+      childChangedKept: "{{count}} inherited words edited only in this language are kept",
+      syncStatus: {
+        new: "New in parent",
+        parent_changed: "Changed in parent",
+        child_changed: "Changed in this language",
+        both_changed: "Changed on both sides",
+        deprecated: "Deleted in parent",
+      },
       previewMigration: "Preview Migration (dry-run)",
       migrateLegacy: "Migrate Legacy Entries",
       migrationDone:
@@ -653,14 +685,105 @@ export default {
       selectedCount: "{{count}} selected",
       borrowSelected: "Borrow Selected",
       confirmBorrow: "Confirm Borrowing",
+      borrowAdapt: "Adapt to this language's phonology",
+      borrowRepair: "Repair illegal syllables by",
+      borrowRepairEpenthesis: "Inserting sounds",
+      borrowRepairDeletion: "Deleting sounds",
+      borrowPreview: "Preview Adaptation",
+      borrowSkipped: "{{count}} word(s) skipped (missing or already borrowed)",
+      borrowDone: "Borrowed {{count}} word(s)",
+      borrowUnchanged: "unchanged",
+      borrowPosMapped: "Part of speech {{from}} → {{to}}",
+      borrowPosCleared: "Part of speech {{from}} is not defined in this language and was cleared",
+      adaptStep: {
+        substitution: "Substitution",
+        deletion: "Deletion",
+        epenthesis: "Epenthesis",
+        blacklist: "Forbidden pattern",
+      },
+      cognates: "Cognate Sets",
+      cognatesDesc:
+        "Every descendant and loan of this language's words across the family, traced through inherited and borrowed links.",
+      cognatesEmpty: "No cognate sets found.",
+      cognatesLoan: "loan",
+      exportCsv: "Export CSV",
+      exportMarkdown: "Export Markdown",
+      checkpoint: "Checkpoint",
+      checkpointPlaceholder: "Commit message (optional, generated from changes)",
+      diffAdded: "Added since",
+      diffModified: "Modified since",
+      diffRemoved: "Removed since",
+      diffSnapshot: "Compare with current lexicon",
+      gitInit: "Enable Version History",
+      gitNotInitialized: "This project is not under version control yet. Enabling it creates a local git repository in the project folder.",
       maxSnapshots: "Max Snapshots",
+      merge: {
+        action: "Merge Sibling Into This",
+        title: "Merge a sibling language into {{name}}",
+        source: "Language to merge in",
+        noSiblings: "This language has no sibling to merge with.",
+        summary: "{{unified}} cognate entries unified ({{fromSource}} taking the merged-in version), {{added}} entries added, {{renamed}} renamed to avoid ID clashes.",
+        otherSummary: "{{grammar}} grammar items, {{ruleSets}} sound change rule sets and {{corpus}} corpus texts added; {{children}} child language(s) will be moved under this language.",
+        conflicts: "{{count}} conflict(s) — this language's version is kept:",
+        lexicon: "Lexicon",
+        grammar: "Grammar",
+        note: "{{source}} is removed from the workspace. Its directory ({{path}}) stays on disk with its history and the discarded conflict versions; delete it yourself once it is no longer needed. A snapshot of this language is taken first.",
+        apply: "Merge",
+      },
+      mergeConflicts: {
+        title: "Merge Conflicts",
+        description: "Fields that could not be merged automatically. The local version is currently kept; choose which version to keep for each.",
+        keepOurs: "Keep local",
+        useTheirs: "Use incoming",
+        useBase: "Use common ancestor",
+        removed: "(removed)",
+        wholeEntry: "whole entry",
+      },
+      noCommits: "No commits yet",
+      noDifferences: "The lexicon matches this snapshot",
       noSnapshots: "No snapshots to rollback",
+      onlyActiveLanguage: "Only this language",
+      relocate: {
+        action: "Change ID / Directory",
+        title: "Change ID and directory of {{name}}",
+        newId: "Language ID",
+        newPath: "Directory (relative to project)",
+        preview: "Preview",
+        apply: "Apply",
+        summary: "{{count}} reference(s) in {{files}} file(s) will be rewritten, including {{words}} word(s) across all languages.",
+        references: "{{count}} reference(s)",
+      },
+      reparent: {
+        action: "Change Parent Language",
+        title: "Change parent of {{name}}",
+        newParent: "New parent language",
+        noParent: "None (root language)",
+        relink: "Re-link unmatched words by romanization or gloss",
+        borrowUnmatched: "Mark remaining words as borrowed",
+        summary: "{{resolved}} inherited link(s) resolve in the new parent, {{relinked}} re-linked, {{borrowed}} marked as borrowed, {{unresolved}} left unchanged.",
+        candidate: "→ {{word}} (by {{by}})",
+        ambiguous: "{{count}} possible matches",
+        noMatch: "No match",
+        matchedBy: { romanization: "romanization", gloss: "gloss" },
+        status: {
+          resolved: "Resolved",
+          relinked: "Re-linked",
+          borrowed: "Borrowed",
+          unresolved: "Unchanged",
+        },
+        apply: "Apply",
+      },
+      restoreSelected: "Restore Selected ({{count}})",
+      revertLanguage: "Revert language to this commit",
+      revertLanguageConfirm: "Restore this language's files to commit {{commit}} ({{summary}})? The result is recorded as a new commit and can be undone.",
       rollbackConfirm: "Are you sure you want to rollback to this snapshot?",
       rollbackHere: "Rollback Here",
       rollbackTitle: "Rollback",
       showInFolder: "Show in Folder",
       snapshotWarning: "Rolling back will override current data.",
       snapshots: "Snapshots",
+      uncommittedChanges: "{{count}} uncommitted file(s)",
+      versionHistory: "Version History",
     },
     export: {
       title: "Export & Import",
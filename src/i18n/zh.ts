@@ -58,6 +58,7 @@ export default {
       exportCsv: "导出 CSV",
       ipaSearch: "IPA 搜索",
       ipaSearchPlaceholder: "输入 IPA 查找相似词...",
+      loadMore: "加载更多（还有 {{count}} 条）",
       playTts: "播放发音",
       selectWord: "请选择一个单词进行编辑",
       word: "单词",
@@ -351,7 +352,6 @@ export default {
       posTitle: "词性管理",
       addPos: "添加词性",
       deletePos: "删除词性",
-      deletePosConfirm: "确定要删除此词性吗？",
       noPos: "暂无词性定义。请添加以开始使用。",
       posName: "名称",
       posNamePlaceholder: "例如：名词",
@@ -362,7 +362,6 @@ export default {
       reqPron: "需发音",
       addRule: "添加规则",
       deleteRule: "删除规则",
-      deleteRuleConfirm: "确定要删除此屈折规则吗？",
       noRules: "暂无屈折规则。请添加规则以定义形态变化模式。",
       pos: "词性",
       tag: "标记 (Tag)",
@@ -404,8 +403,17 @@ export default {
         deleteValue: "删除值",
         valueName: "值名称",
         valueGloss: "缩写 (如 NOM, ACC)",
-        deleteDimensionConfirm: "确定要删除此维度吗？",
-        deleteValueConfirm: "确定要删除此值吗？",
+      },
+      refs: {
+        summary: "将更新 {{count}} 处引用，其中包括 {{entries}} 个词条。",
+        noReferences: "没有其他地方引用它。",
+        lexicon: "词典",
+        snapshotNote: "执行前会自动创建快照，可在快照管理中回滚。",
+        effect: {
+          rename: "改名",
+          unlink: "解除引用",
+          remove: "一并删除",
+        },
       },
       derivation: {
         title: "派生规则",
@@ -558,6 +566,11 @@ export default {
       batchApply: "应用到词典",
       batchApplyAll: "应用到词典与语法规则",
       applied: "已应用！",
+      forkApplied: "派生时已应用",
+      forkAppliedClear:
+        "派生该语言时这些规则已作用于继承的词条，批量应用到词典时会跳过。点击后重新参与词典批量应用。",
+      forkAppliedSkipped:
+        "{{count}} 个规则集已在派生时作用于词典，批量应用时不再用于词条。",
       scope: "范围",
       label: "目标",
       field: "字段",
@@ -592,12 +605,22 @@ export default {
         "从父语言拉取新词汇，并自动应用音变规则，将其演变为当前语言的语音形式。",
       borrowing: "借词",
       borrowingDesc:
-        "从语系树中的任何其他语言借入词汇。借词将原样复制，不应用音变规则。",
+        "从工作区中的任何其他语言借入词汇。借词会按本语言的音位库和音节结构进行适配。",
       words: "个词条",
       switchTo: "切换到此语言",
       fork: "派生",
       forkTitle: "派生新语言",
       forkFrom: "派生自",
+      forkSoundChanges: "派生时执行的音变",
+      forkNoSoundChanges: "不执行（原样复制词典）",
+      forkCopyRulesFrom: "套用 {{name}} 的音变规则",
+      forkReport: {
+        summary:
+          "{{words}} 个继承词中有 {{changed}} 个词形发生变化，已为 {{ipa}} 个重新生成 IPA。",
+        skipped: "以下规则的语境无效，已跳过：{{rules}}",
+        mergers: "音位合并",
+        homophones: "新产生 {{count}} 组同音词",
+      },
       newLangName: "新语言名称",
       newLangNamePlaceholder: "例如：始妹语 A",
       noParentLanguage: "此语言没有父语言（根语言）。",
@@ -608,6 +631,15 @@ export default {
       pullAndEvolve: "拉取并应用音变",
       upToDate: "已是最新！",
       syncConflicts: "有 {{count}} 个冲突词条已保留子语言版本",
+      deprecatedUpstream: "父语言中已删除的 {{count}} 个词条将标记为弃用",
+      childChangedKept: "{{count}} 个继承词只在本语言中改动过，予以保留",
+      syncStatus: {
+        new: "父语言新增",
+        parent_changed: "父语言已改动",
+        child_changed: "本语言已改动",
+        both_changed: "两边都改动过",
+        deprecated: "父语言已删除",
+      },
       previewMigration: "预览迁移（dry-run）",
       migrateLegacy: "迁移旧词条",
       migrationDone:
@@ -631,14 +663,105 @@ export default {
       selectedCount: "已选 {{count}} 个",
       borrowSelected: "借入选中词",
       confirmBorrow: "确认借词",
+      borrowAdapt: "按本语言音系适配",
+      borrowRepair: "不合法音节的修补方式",
+      borrowRepairEpenthesis: "插入增音",
+      borrowRepairDeletion: "删除音段",
+      borrowPreview: "预览适配结果",
+      borrowSkipped: "跳过 {{count}} 个词条（不存在或已借入）",
+      borrowDone: "已借入 {{count}} 个词条",
+      borrowUnchanged: "无改动",
+      borrowPosMapped: "词性 {{from}} → {{to}}",
+      borrowPosCleared: "本语言没有词性 {{from}}，已清空",
+      adaptStep: {
+        substitution: "替换",
+        deletion: "删除",
+        epenthesis: "增音",
+        blacklist: "违反音位配列",
+      },
+      cognates: "同源词表",
+      cognatesDesc:
+        "沿继承与借用链接，列出本语言词条在整个语系中的所有后代与借词。",
+      cognatesEmpty: "没有找到同源词集。",
+      cognatesLoan: "借",
+      exportCsv: "导出 CSV",
+      exportMarkdown: "导出 Markdown",
+      checkpoint: "提交",
+      checkpointPlaceholder: "提交说明（可选，留空则根据改动生成）",
+      diffAdded: "之后新增",
+      diffModified: "之后修改",
+      diffRemoved: "之后删除",
+      diffSnapshot: "与当前词典对比",
+      gitInit: "启用版本历史",
+      gitNotInitialized: "该项目尚未启用版本控制。启用后会在项目文件夹中创建本地 git 仓库。",
       maxSnapshots: "最大快照数量",
+      merge: {
+        action: "将姊妹语言并入此语言",
+        title: "将姊妹语言并入「{{name}}」",
+        source: "要并入的语言",
+        noSiblings: "此语言没有可合并的姊妹语言。",
+        summary: "{{unified}} 个同源词条合为一条（其中 {{fromSource}} 个采用并入方的版本），新增 {{added}} 个词条，{{renamed}} 个因 ID 重复而改名。",
+        otherSummary: "新增 {{grammar}} 个语法项、{{ruleSets}} 个音变规则集、{{corpus}} 篇语料；{{children}} 个子语言将改挂到此语言下。",
+        conflicts: "{{count}} 处冲突（保留此语言的版本）：",
+        lexicon: "词典",
+        grammar: "语法",
+        note: "{{source}} 将从工作区移除，其目录（{{path}}）连同历史记录和冲突中被舍弃的版本仍保留在磁盘上，确认不再需要后可自行删除。执行前会为此语言创建快照。",
+        apply: "合并",
+      },
+      mergeConflicts: {
+        title: "合并冲突",
+        description: "这些字段无法自动合并，目前保留的是本地版本。请逐项选择要保留的版本。",
+        keepOurs: "保留本地",
+        useTheirs: "使用合入方",
+        useBase: "使用共同祖先",
+        removed: "（已删除）",
+        wholeEntry: "整个条目",
+      },
+      noCommits: "还没有提交",
+      noDifferences: "词典与此快照一致",
       noSnapshots: "没有快照",
+      onlyActiveLanguage: "仅当前语言",
+      relocate: {
+        action: "修改 ID / 目录",
+        title: "修改「{{name}}」的 ID 与目录",
+        newId: "语言 ID",
+        newPath: "目录（相对项目）",
+        preview: "预览",
+        apply: "执行",
+        summary: "将改写 {{files}} 个文件中的 {{count}} 处引用，其中包括所有语言中的 {{words}} 个词条。",
+        references: "{{count}} 处引用",
+      },
+      reparent: {
+        action: "更换父语言",
+        title: "更换「{{name}}」的父语言",
+        newParent: "新的父语言",
+        noParent: "无（作为根语言）",
+        relink: "按拼写或释义重新关联未对上的词条",
+        borrowUnmatched: "将其余词条标记为借词",
+        summary: "{{resolved}} 个继承链接在新父语言中有对应，{{relinked}} 个重新关联，{{borrowed}} 个标记为借词，{{unresolved}} 个保持不变。",
+        candidate: "→ {{word}}（按{{by}}匹配）",
+        ambiguous: "{{count}} 个可能的匹配",
+        noMatch: "无匹配",
+        matchedBy: { romanization: "拼写", gloss: "释义" },
+        status: {
+          resolved: "已对应",
+          relinked: "重新关联",
+          borrowed: "借词",
+          unresolved: "不变",
+        },
+        apply: "执行",
+      },
+      restoreSelected: "恢复选中词条（{{count}}）",
+      revertLanguage: "将语言恢复到此提交",
+      revertLanguageConfirm: "将该语言的文件恢复到提交 {{commit}}（{{summary}}）？恢复结果会作为新的提交记录，并且可以撤销。",
       rollbackConfirm: "你确定要回退到这个快照吗？",
       rollbackHere: "回退到此处",
       rollbackTitle: "回退",
       showInFolder: "在文件夹中显示",
       snapshotWarning: "回退将会覆盖当前数据。",
       snapshots: "快照",
+      uncommittedChanges: "{{count}} 个文件尚未提交",
+      versionHistory: "版本历史",
     },
     export: {
       title: "导出与导入",
@@ -10,7 +10,7 @@ import {
   Bot,
   ArrowUpDown,
 } from "lucide-react";
-import { useLexiconStore } from "../store/lexiconStore";
+import { useFullLexicon, useLexiconStore } from "../store/lexiconStore";
 import { usePhonoStore } from "../store/phonoStore";
 import { useGrammarStore } from "../store/grammarStore";
 import { useWorkspaceStore } from "../store/workspaceStore";
@@ -36,7 +36,9 @@ import { PageHeader } from "../components/common/PageHeader";
 
 export function ExportImport() {
   const { t } = useTranslation();
-  const { wordsList, importWords } = useLexiconStore();
+  // 各种导出与导入冲突检查都针对整个词典
+  const wordsList = useFullLexicon();
+  const importWords = useLexiconStore((s) => s.importWords);
   const { config: phonoConfig } = usePhonoStore();
   const { config: grammarConfig } = useGrammarStore();
   const { config: scaConfig } = useSCAStore();
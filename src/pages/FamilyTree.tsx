@@ -15,16 +15,28 @@ import {
 } from "@xyflow/react";
 import "@xyflow/react/dist/style.css";
 import { useWorkspaceStore } from "../store/workspaceStore";
-import { LanguageEntry } from "../types";
-import { BTN_PRIMARY, BTN_GHOST, BTN_OUTLINE_ERROR, INPUT } from "../lib/ui";
+import { ForkSoundChangeReport, LanguageEntry } from "../types";
+import {
+  BTN_PRIMARY,
+  BTN_GHOST,
+  BTN_OUTLINE_ERROR,
+  INPUT,
+  SELECT,
+} from "../lib/ui";
 import { TreeNode } from "../components/tree/TreeNodeRF";
 import { PullSync } from "../components/tree/PullSync";
 import { BorrowingPanel } from "../components/tree/BorrowingPanel";
+import { CognateSets } from "../components/tree/CognateSets";
 import {
   FamilySelector,
   FamilyManager,
 } from "../components/tree/FamilyManager";
 import { SnapshotManager } from "../components/tree/SnapshotManager";
+import { VersionHistory } from "../components/tree/VersionHistory";
+import { RelocateLanguageModal } from "../components/tree/RelocateLanguageModal";
+import { ReparentLanguageModal } from "../components/tree/ReparentLanguageModal";
+import { MergeLanguagesModal } from "../components/tree/MergeLanguagesModal";
+import { ForkReport } from "../components/tree/ForkReport";
 import { useTreeLayout, type TreeFlowNode } from "../utils/useTreeLayout";
 import { useTheme } from "../lib/useTheme";
 import { ModalPortal } from "../components/common/ModalPortal";
@@ -58,11 +70,25 @@ export function FamilyTree() {
   const [showForkModal, setShowForkModal] = useState(false);
   const [forkParentId, setForkParentId] = useState<string | null>(null);
   const [newLangName, setNewLangName] = useState("");
+  // 分叉时复制其音变规则并立即执行的语言；空串表示不执行
+  const [forkTemplateId, setForkTemplateId] = useState("");
+  const [forkReport, setForkReport] = useState<ForkSoundChangeReport | null>(
+    null,
+  );
   const [showPullSync, setShowPullSync] = useState(false);
   const [showBorrowing, setShowBorrowing] = useState(false);
+  const [showCognates, setShowCognates] = useState(false);
   const [deleteTarget, setDeleteTarget] = useState<LanguageEntry | null>(null);
+  const [relocateTarget, setRelocateTarget] = useState<LanguageEntry | null>(
+    null,
+  );
+  const [reparentTarget, setReparentTarget] = useState<LanguageEntry | null>(
+    null,
+  );
+  const [mergeTarget, setMergeTarget] = useState<LanguageEntry | null>(null);
   const [showCreateRoot, setShowCreateRoot] = useState(false);
   const [showSnapshots, setShowSnapshots] = useState(false);
+  const [showHistory, setShowHistory] = useState(false);
   const [newRootName, setNewRootName] = useState("");
   const { theme } = useTheme();
 
@@ -99,6 +125,7 @@ export function FamilyTree() {
   // Callbacks for tree nodes — stabilise with useCallback
   const handleNodeFork = useCallback((parentId: string) => {
     setForkParentId(parentId);
+    setForkReport(null);
     setShowForkModal(true);
   }, []);
 
@@ -113,13 +140,35 @@ export function FamilyTree() {
     setDeleteTarget(lang);
   }, []);
 
+  const handleNodeRelocate = useCallback((lang: LanguageEntry) => {
+    setRelocateTarget(lang);
+  }, []);
+
+  const handleNodeReparent = useCallback((lang: LanguageEntry) => {
+    setReparentTarget(lang);
+  }, []);
+
+  const handleNodeMerge = useCallback((lang: LanguageEntry) => {
+    setMergeTarget(lang);
+  }, []);
+
   const callbacks = useMemo(
     () => ({
       onFork: handleNodeFork,
       onSwitch: handleNodeSwitch,
       onDelete: handleNodeDelete,
+      onRelocate: handleNodeRelocate,
+      onReparent: handleNodeReparent,
+      onMerge: handleNodeMerge,
     }),
-    [handleNodeFork, handleNodeSwitch, handleNodeDelete],
+    [
+      handleNodeFork,
+      handleNodeSwitch,
+      handleNodeDelete,
+      handleNodeRelocate,
+      handleNodeReparent,
+      handleNodeMerge,
+    ],
   );
 
   // dagre layout
@@ -144,9 +193,20 @@ export function FamilyTree() {
     const newId = `lang_${Date.now().toString(36)}`;
     const newPath = `lang_${newId}`;
     try {
-      await forkLanguage(forkParentId, newLangName.trim(), newId, newPath);
-      setShowForkModal(false);
+      const report = await forkLanguage(
+        forkParentId,
+        newLangName.trim(),
+        newId,
+        newPath,
+        forkTemplateId ? { template_language_id: forkTemplateId } : undefined,
+      );
       setNewLangName("");
+      setForkTemplateId("");
+      if (report) {
+        setForkReport(report);
+      } else {
+        setShowForkModal(false);
+      }
     } catch (err) {
       console.warn(`Fork failed: ${err}`);
     }
@@ -213,12 +273,24 @@ export function FamilyTree() {
           >
             {t("tree.borrowing")}
           </button>
+          <button
+            className={BTN_GHOST}
+            onClick={() => setShowCognates(!showCognates)}
+          >
+            {t("tree.cognates")}
+          </button>
           <button
             className={BTN_GHOST}
             onClick={() => setShowSnapshots(!showSnapshots)}
           >
             {t("tree.snapshots")}
           </button>
+          <button
+            className={BTN_GHOST}
+            onClick={() => setShowHistory(!showHistory)}
+          >
+            {t("tree.versionHistory")}
+          </button>
           <div className="border-l border-base-300 mx-1" />
           <FamilyManager />
         </div>
@@ -263,11 +335,26 @@ export function FamilyTree() {
         </ReactFlow>
       </div>
 
+      <RelocateLanguageModal
+        language={relocateTarget}
+        onClose={() => setRelocateTarget(null)}
+      />
+      <ReparentLanguageModal
+        language={reparentTarget}
+        onClose={() => setReparentTarget(null)}
+      />
+      <MergeLanguagesModal
+        language={mergeTarget}
+        onClose={() => setMergeTarget(null)}
+      />
+
       <ModalPortal
         open={
           showPullSync ||
           showBorrowing ||
+          showCognates ||
           showSnapshots ||
+          showHistory ||
           showForkModal ||
           !!deleteTarget ||
           showCreateRoot
@@ -305,6 +392,21 @@ export function FamilyTree() {
               </div>
             </div>
           )}
+          {showCognates && (
+            <div className="modal modal-open">
+              <div className="modal-box max-w-4xl">
+                <CognateSets />
+                <div className="modal-action">
+                  <button
+                    className={BTN_GHOST}
+                    onClick={() => setShowCognates(false)}
+                  >
+                    {t("common.close")}
+                  </button>
+                </div>
+              </div>
+            </div>
+          )}
           {showSnapshots && (
             <div className="modal modal-open">
               <div className="modal-box max-w-lg">
@@ -321,10 +423,26 @@ export function FamilyTree() {
             </div>
           )}
 
+          {showHistory && (
+            <div className="modal modal-open">
+              <div className="modal-box max-w-lg">
+                <VersionHistory />
+                <div className="modal-action mt-2">
+                  <button
+                    className={BTN_GHOST}
+                    onClick={() => setShowHistory(false)}
+                  >
+                    {t("common.close")}
+                  </button>
+                </div>
+              </div>
+            </div>
+          )}
+
           {/* Fork Modal */}
           {showForkModal && (
             <div className="modal modal-open">
-              <div className="modal-box">
+              <div className="modal-box max-w-2xl">
                 <h3 className="font-bold text-lg">{t("tree.forkTitle")}</h3>
                 <p className="py-2 text-sm text-base-content/60">
                   {t("tree.forkFrom")}:{" "}
@@ -333,17 +451,44 @@ export function FamilyTree() {
                       ?.name
                   }
                 </p>
-                <div className="form-control">
-                  <label className="label">
-                    <span className="label-text">{t("tree.newLangName")}</span>
-                  </label>
-                  <input
-                    className={`${INPUT} w-full`}
-                    value={newLangName}
-                    onChange={(e) => setNewLangName(e.target.value)}
-                    placeholder={t("tree.newLangNamePlaceholder")}
-                  />
-                </div>
+                {forkReport ? (
+                  <ForkReport report={forkReport} />
+                ) : (
+                  <>
+                    <div className="form-control">
+                      <label className="label">
+                        <span className="label-text">
+                          {t("tree.newLangName")}
+                        </span>
+                      </label>
+                      <input
+                        className={`${INPUT} w-full`}
+                        value={newLangName}
+                        onChange={(e) => setNewLangName(e.target.value)}
+                        placeholder={t("tree.newLangNamePlaceholder")}
+                      />
+                    </div>
+                    <div className="form-control">
+                      <label className="label">
+                        <span className="label-text">
+                          {t("tree.forkSoundChanges")}
+                        </span>
+                      </label>
+                      <select
+                        className={`${SELECT} w-full`}
+                        value={forkTemplateId}
+                        onChange={(e) => setForkTemplateId(e.target.value)}
+                      >
+                        <option value="">{t("tree.forkNoSoundChanges")}</option>
+                        {config.languages.map((l) => (
+                          <option key={l.language_id} value={l.language_id}>
+                            {t("tree.forkCopyRulesFrom", { name: l.name })}
+                          </option>
+                        ))}
+                      </select>
+                    </div>
+                  </>
+                )}
                 <div className="modal-action">
                   <button
                     className={BTN_GHOST}
@@ -351,13 +496,15 @@ export function FamilyTree() {
                   >
                     {t("common.close")}
                   </button>
-                  <button
-                    className={BTN_PRIMARY}
-                    onClick={handleFork}
-                    disabled={!newLangName.trim()}
-                  >
-                    <GitFork className="w-4 h-4" /> {t("tree.fork")}
-                  </button>
+                  {!forkReport && (
+                    <button
+                      className={BTN_PRIMARY}
+                      onClick={handleFork}
+                      disabled={!newLangName.trim()}
+                    >
+                      <GitFork className="w-4 h-4" /> {t("tree.fork")}
+                    </button>
+                  )}
                 </div>
               </div>
             </div>
@@ -23,6 +23,8 @@ interface CorpusStore {
 
 /** Per-entity debounce map for corpus saves */
 const saveTimers = new Map<string, ReturnType<typeof setTimeout>>();
+/** 每篇语料最近一次加载/保存时的内容哈希，保存时作为 expected_hash 传给后端以检测外部修改 */
+const corpusHashes = new Map<string, string>();
 
 function debouncedSave(projectPath: string, languagePath: string, text: CorpusText) {
     const key = text.corpus_id;
@@ -30,7 +32,13 @@ function debouncedSave(projectPath: string, languagePath: string, text: CorpusTe
     if (existing) clearTimeout(existing);
     saveTimers.set(key, setTimeout(async () => {
         try {
-            await invoke('save_corpus_text', { projectPath, languagePath, text });
+            const hash = await invoke<string>('save_corpus_text', {
+                projectPath,
+                languagePath,
+                text,
+                expectedHash: corpusHashes.get(key) ?? null,
+            });
+            corpusHashes.set(key, hash);
         } catch (err) {
             console.warn(`语料保存失败：${err}`);
         }
@@ -58,6 +66,12 @@ export const useCorpusStore = create<CorpusStore>((set, get) => ({
         const { projectPath, languagePath } = get();
         try {
             const text = await invoke<CorpusText>('load_corpus_text', { projectPath, languagePath, corpusId });
+            const hash = await invoke<string>('load_file_hash', {
+                projectPath,
+                languagePath,
+                file: `corpus/${corpusId}.json`,
+            });
+            corpusHashes.set(corpusId, hash);
             set({ activeCorpus: text, activeCorpusId: corpusId });
         } catch (err) {
             console.warn(`加载语料失败：${err}`);
@@ -97,7 +111,13 @@ export const useCorpusStore = create<CorpusStore>((set, get) => ({
             activeCorpus: state.activeCorpusId === corpusId ? null : state.activeCorpus,
         }));
         try {
-            await invoke('delete_corpus_text', { projectPath, languagePath, corpusId });
+            await invoke('delete_corpus_text', {
+                projectPath,
+                languagePath,
+                corpusId,
+                expectedHash: corpusHashes.get(corpusId) ?? null,
+            });
+            corpusHashes.delete(corpusId);
         } catch (err) {
             console.warn(`删除语料失败：${err}`);
             // Reload index to restore consistent state on failure
@@ -12,9 +12,12 @@ import {
   AffixSlot,
   ConjugationClass,
   IrregularOverride,
+  GrammarRefChange,
+  GrammarRefReport,
 } from "../types";
 import { invoke } from "@tauri-apps/api/core";
 import { DEFAULT_LANGUAGE_ID } from "../constants";
+import { useWorkspaceStore } from "./workspaceStore";
 
 interface GrammarStore {
   config: GrammarConfig;
@@ -69,6 +72,10 @@ interface GrammarStore {
   updateChapter: (chapterId: string, chapter: GrammarChapter) => void;
   deleteChapter: (chapterId: string) => void;
   reorderChapters: (chapters: GrammarChapter[]) => void;
+
+  // 重命名 / 删除词性、维度、取值、规则，并级联改写语法与词典中的引用（后端执行，自动快照）
+  previewRefChange: (change: GrammarRefChange) => Promise<GrammarRefReport>;
+  applyRefChange: (change: GrammarRefChange) => Promise<GrammarRefReport>;
 }
 
 const DEFAULT_TYPOLOGY: TypologyConfig = {
@@ -164,6 +171,9 @@ const defaultConfig: GrammarConfig = {
 
 /** 单一配置实体，共享 timer 无风险 */
 let saveTimeout: ReturnType<typeof setTimeout>;
+/** grammar.json 最近一次加载/保存时的内容哈希，保存时作为 expected_hash 传给后端以检测外部修改 */
+let contentHash: string | null = null;
+let pendingSave: (() => Promise<void>) | null = null;
 
 const debouncedSave = (
   projectPath: string,
@@ -171,13 +181,26 @@ const debouncedSave = (
   config: GrammarConfig,
 ) => {
   clearTimeout(saveTimeout);
-  saveTimeout = setTimeout(async () => {
+  pendingSave = async () => {
+    pendingSave = null;
     try {
-      await invoke("save_grammar", { projectPath, languagePath, config });
+      contentHash = await invoke<string>("save_grammar", {
+        projectPath,
+        languagePath,
+        config,
+        expectedHash: contentHash,
+      });
     } catch (err) {
       console.warn(`语法配置保存失败：${err}`);
     }
-  }, 500);
+  };
+  saveTimeout = setTimeout(() => pendingSave?.(), 500);
+};
+
+/** 立即执行尚未落盘的语法配置保存 */
+const flushSave = async () => {
+  clearTimeout(saveTimeout);
+  await pendingSave?.();
 };
 
 /** Phase 1 → Phase 2 → Typology 数据迁移：补充缺失的新字段 */
@@ -253,6 +276,11 @@ export const useGrammarStore = create<GrammarStore>((set, get) => ({
         projectPath,
         languagePath,
       });
+      contentHash = await invoke<string>("load_file_hash", {
+        projectPath,
+        languagePath,
+        file: "grammar.json",
+      });
       const rawRules = raw.inflection_rules || raw.inflections || [];
       const config: GrammarConfig = {
         language_id: raw.language_id || DEFAULT_LANGUAGE_ID,
@@ -510,4 +538,32 @@ export const useGrammarStore = create<GrammarStore>((set, get) => ({
     set((state) => ({ config: { ...state.config, grammar_manual: chapters } }));
     get().saveConfig();
   },
+
+  // ── 引用级联 ──────────────────────────────────────────
+
+  previewRefChange: async (change) => {
+    // 预览基于磁盘上的 grammar.json，先让尚未保存的编辑落盘
+    await flushSave();
+    const { projectPath, languagePath } = get();
+    return invoke<GrammarRefReport>("preview_grammar_ref_change", {
+      projectPath,
+      languagePath,
+      change,
+    });
+  },
+
+  applyRefChange: async (change) => {
+    await flushSave();
+    const { projectPath, languagePath } = get();
+    const report = await invoke<GrammarRefReport>("apply_grammar_ref_change", {
+      projectPath,
+      languagePath,
+      change,
+      expectedHash: contentHash,
+    });
+    // 词典也可能被改写：与撤销/重做相同，通过 dataVersion 重新加载当前语言的全部数据
+    useWorkspaceStore.setState((s) => ({ dataVersion: s.dataVersion + 1 }));
+    await useWorkspaceStore.getState().refreshJournal();
+    return report;
+  },
 }));
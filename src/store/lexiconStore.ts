@@ -1,10 +1,35 @@
 import { create } from "zustand";
-import { WordEntry } from "../types";
+import { useEffect } from "react";
+import {
+  BatchEntryResult,
+  WordEntry,
+  WordLifecycle,
+  WordQuery,
+  WordQueryResult,
+  WordSaveRequest,
+} from "../types";
 import { invoke } from "@tauri-apps/api/core";
 
+/** 词典侧栏每次向后端取的条数 */
+export const LEXICON_PAGE_SIZE = 200;
+
 interface LexiconStore {
+  /** 已取到前端的词条：侧栏当前页、按 ID 查到的词条、本地草稿，以及加载过的整个词典 */
   wordsMap: Record<string, WordEntry>;
+  /** 整个词典，只有通过 useFullLexicon 请求过才会填充，否则为空 */
   wordsList: WordEntry[];
+  fullLexiconLoaded: boolean;
+  /** 侧栏当前页（query_words 的结果），按后端排序 */
+  pageIds: string[];
+  /** 当前查询分页前的匹配总数 */
+  pageTotal: number;
+  pageLifecycle: Record<string, WordLifecycle>;
+  /** 整个词典中出现过的标签，随每次分页查询刷新 */
+  allTags: string[];
+  /** 当前语言的词条总数 */
+  lexiconSize: number;
+  /** 每次重新加载语言时递增，侧栏据此重新查询 */
+  loadVersion: number;
   searchTerm: string;
   activeWordId: string | null;
   projectPath: string;
@@ -13,7 +38,14 @@ interface LexiconStore {
   setProjectPath: (path: string) => void;
   setLanguagePath: (path: string) => void;
   loadWords: (words: WordEntry[]) => void;
+  /** 切换语言或数据在外部改变后调用：清空缓存，只重新取词条总数和当前词条 */
   loadFromBackend: (projectPath: string, languagePath: string) => Promise<void>;
+  /** 查询侧栏的一页；append 为 true 时接在当前页后面（“加载更多”） */
+  queryPage: (query: WordQuery, append?: boolean) => Promise<void>;
+  /** 按 ID 取词条并放入缓存，已缓存的不再请求 */
+  fetchWords: (entryIds: string[]) => Promise<WordEntry[]>;
+  /** 加载整个词典，只供确实需要遍历全部词条的页面使用，见 useFullLexicon */
+  loadFullLexicon: () => Promise<WordEntry[]>;
   upsertWord: (word: WordEntry) => void;
   deleteWord: (entryId: string) => void;
   setSearchTerm: (term: string) => void;
@@ -26,6 +58,12 @@ interface LexiconStore {
 const saveTimers = new Map<string, ReturnType<typeof setTimeout>>();
 /** 记录每个词条在当前 debounce 周期内的首个旧拼写，避免后续 upsert 覆盖丢失 */
 const pendingOldRomanized = new Map<string, string>();
+/** 每个已取到前端的词条在加载/保存时的内容哈希，保存时作为 expected_hash 传给后端以检测外部修改 */
+const wordHashes = new Map<string, string>();
+/** 侧栏查询的序号，丢弃过期的响应 */
+let pageRequestSeq = 0;
+/** 进行中的整词典加载，避免多个组件同时触发 */
+let fullLexiconRequest: Promise<WordEntry[]> | null = null;
 
 function isValidWordEntry(word: WordEntry): boolean {
   const romanized = (word.con_word_romanized || "").trim();
@@ -35,6 +73,10 @@ function isValidWordEntry(word: WordEntry): boolean {
   return true;
 }
 
+function rememberHashes(hashes: Record<string, string>) {
+  Object.entries(hashes).forEach(([id, hash]) => wordHashes.set(id, hash));
+}
+
 function debouncedSaveWord(
   projectPath: string,
   languagePath: string,
@@ -52,12 +94,26 @@ function debouncedSaveWord(
   saveTimers.set(
     key,
     setTimeout(() => {
-      invoke("save_word", {
-        projectPath,
-        languagePath,
+      const isNew = !wordHashes.has(key);
+      const request: WordSaveRequest = {
         word,
-        oldRomanized: effectiveOldRomanized,
-      }).catch((err) => console.warn(`保存词条失败：${err}`));
+        old_romanized: effectiveOldRomanized,
+        expected_hash: wordHashes.get(key) ?? "",
+      };
+      invoke<BatchEntryResult[]>("save_words", { projectPath, languagePath, words: [request] })
+        .then((results) => {
+          results.forEach((r) => {
+            if (r.status === "error") {
+              console.warn(`保存词条失败：${r.error}`);
+            } else if (r.content_hash) {
+              wordHashes.set(r.entry_id, r.content_hash);
+              if (isNew) {
+                useLexiconStore.setState((s) => ({ lexiconSize: s.lexiconSize + 1 }));
+              }
+            }
+          });
+        })
+        .catch((err) => console.warn(`保存词条失败：${err}`));
       pendingOldRomanized.delete(key);
       saveTimers.delete(key);
     }, 500),
@@ -73,6 +129,13 @@ function rebuildList(wordsMap: Record<string, WordEntry>): WordEntry[] {
 export const useLexiconStore = create<LexiconStore>((set, get) => ({
   wordsMap: {},
   wordsList: [],
+  fullLexiconLoaded: false,
+  pageIds: [],
+  pageTotal: 0,
+  pageLifecycle: {},
+  allTags: [],
+  lexiconSize: 0,
+  loadVersion: 0,
   searchTerm: "",
   activeWordId: null,
   projectPath: ".",
@@ -86,27 +149,135 @@ export const useLexiconStore = create<LexiconStore>((set, get) => ({
     words.forEach((w) => {
       wordsMap[w.entry_id] = w;
     });
-    set({ wordsMap, wordsList: rebuildList(wordsMap) });
+    set({ wordsMap, wordsList: rebuildList(wordsMap), fullLexiconLoaded: true });
   },
 
   loadFromBackend: async (projectPath: string, languagePath: string) => {
+    const { activeWordId } = get();
+    wordHashes.clear();
+    fullLexiconRequest = null;
+    set((state) => ({
+      wordsMap: {},
+      wordsList: [],
+      fullLexiconLoaded: false,
+      pageIds: [],
+      pageTotal: 0,
+      pageLifecycle: {},
+      projectPath,
+      languagePath,
+      loadVersion: state.loadVersion + 1,
+    }));
     try {
-      const words = await invoke<WordEntry[]>("load_all_words", {
+      // limit 0 只取总数与标签，不传词条
+      const counted = await invoke<WordQueryResult>("query_words", {
         projectPath,
         languagePath,
+        query: { limit: 0 },
       });
-      const wordsMap: Record<string, WordEntry> = {};
-      words.forEach((w) => {
-        wordsMap[w.entry_id] = w;
-      });
-      set({
-        wordsMap,
-        wordsList: rebuildList(wordsMap),
+      set({ lexiconSize: counted.total, allTags: counted.all_tags });
+      if (activeWordId) await get().fetchWords([activeWordId]);
+    } catch (err) {
+      console.error("Failed to load words:", err);
+    }
+  },
+
+  queryPage: async (query, append = false) => {
+    const { projectPath, languagePath } = get();
+    const seq = ++pageRequestSeq;
+    const offset = append ? get().pageIds.length : 0;
+    try {
+      const result = await invoke<WordQueryResult>("query_words", {
         projectPath,
         languagePath,
+        query: { ...query, offset, limit: query.limit ?? LEXICON_PAGE_SIZE },
+      });
+      if (seq !== pageRequestSeq) return;
+      rememberHashes(result.hashes);
+      set((state) => {
+        const wordsMap = { ...state.wordsMap };
+        // 本地还有待保存修改的词条保留前端版本
+        result.words.forEach((w) => {
+          if (!saveTimers.has(w.entry_id)) wordsMap[w.entry_id] = w;
+        });
+        const ids = result.words.map((w) => w.entry_id);
+        return {
+          wordsMap,
+          wordsList: state.fullLexiconLoaded ? rebuildList(wordsMap) : state.wordsList,
+          pageIds: append ? [...state.pageIds, ...ids] : ids,
+          pageTotal: result.total,
+          pageLifecycle: append
+            ? { ...state.pageLifecycle, ...result.lifecycle }
+            : result.lifecycle,
+          allTags: result.all_tags,
+        };
+      });
+    } catch (err) {
+      console.warn(`查询词条失败：${err}`);
+    }
+  },
+
+  fetchWords: async (entryIds) => {
+    const { projectPath, languagePath, wordsMap } = get();
+    const missing = [...new Set(entryIds)].filter((id) => id && !wordsMap[id]);
+    if (missing.length > 0) {
+      try {
+        const result = await invoke<WordQueryResult>("query_words", {
+          projectPath,
+          languagePath,
+          query: { entry_ids: missing },
+        });
+        // 语言已切换时丢弃结果
+        if (get().languagePath !== languagePath) return [];
+        rememberHashes(result.hashes);
+        set((state) => {
+          const next = { ...state.wordsMap };
+          result.words.forEach((w) => {
+            if (!next[w.entry_id]) next[w.entry_id] = w;
+          });
+          return { wordsMap: next };
+        });
+      } catch (err) {
+        console.warn(`按 ID 查询词条失败：${err}`);
+      }
+    }
+    const current = get().wordsMap;
+    return entryIds.map((id) => current[id]).filter((w): w is WordEntry => !!w);
+  },
+
+  loadFullLexicon: async () => {
+    if (get().fullLexiconLoaded) return get().wordsList;
+    if (fullLexiconRequest) return fullLexiconRequest;
+    const { projectPath, languagePath } = get();
+    const request = (async () => {
+      // 词条与哈希一起取：之后对其中任何词条的保存都需要 expected_hash
+      const [words, hashes] = await Promise.all([
+        invoke<WordEntry[]>("load_all_words", { projectPath, languagePath }),
+        invoke<Record<string, string>>("load_word_hashes", { projectPath, languagePath }),
+      ]);
+      if (get().languagePath !== languagePath) return [];
+      rememberHashes(hashes);
+      set((state) => {
+        const wordsMap = { ...state.wordsMap };
+        words.forEach((w) => {
+          if (!saveTimers.has(w.entry_id)) wordsMap[w.entry_id] = w;
+        });
+        return {
+          wordsMap,
+          wordsList: rebuildList(wordsMap),
+          fullLexiconLoaded: true,
+          lexiconSize: words.length,
+        };
       });
+      return get().wordsList;
+    })();
+    fullLexiconRequest = request;
+    try {
+      return await request;
     } catch (err) {
       console.error("Failed to load words:", err);
+      return [];
+    } finally {
+      if (fullLexiconRequest === request) fullLexiconRequest = null;
     }
   },
 
@@ -118,7 +289,15 @@ export const useLexiconStore = create<LexiconStore>((set, get) => ({
         old_romanized = existing.con_word_romanized;
       }
       const wordsMap = { ...state.wordsMap, [word.entry_id]: word };
-      return { wordsMap, wordsList: rebuildList(wordsMap) };
+      // 新建的词条放在侧栏当前页最前面，直到下一次查询按后端排序
+      const pageIds = existing || state.pageIds.includes(word.entry_id)
+        ? state.pageIds
+        : [word.entry_id, ...state.pageIds];
+      return {
+        wordsMap,
+        pageIds,
+        wordsList: state.fullLexiconLoaded ? rebuildList(wordsMap) : state.wordsList,
+      };
     });
 
     // Allow local draft editing, but persist only valid entries.
@@ -137,18 +316,28 @@ export const useLexiconStore = create<LexiconStore>((set, get) => ({
       validWords.forEach((w) => {
         wordsMap[w.entry_id] = w;
       });
-      return { wordsMap, wordsList: rebuildList(wordsMap) };
+      return {
+        wordsMap,
+        wordsList: state.fullLexiconLoaded ? rebuildList(wordsMap) : state.wordsList,
+      };
     });
-    // 批量保存：逐词 invoke，但不走 debounce（这里数量有限且已是用户主动操作）
+    // 批量保存：单次 invoke，后端按分桶分组、每个分桶只改写一次
     const { projectPath, languagePath } = get();
-    validWords.forEach((w) => {
-      invoke("save_word", {
-        projectPath,
-        languagePath,
-        word: w,
-        oldRomanized: null,
-      }).catch((err) => console.warn(`批量保存失败 [${w.entry_id}]：${err}`));
-    });
+    const requests: WordSaveRequest[] = validWords.map((w) => ({ word: w, old_romanized: null }));
+    invoke<BatchEntryResult[]>("save_words", { projectPath, languagePath, words: requests })
+      .then((results) => {
+        let added = 0;
+        results.forEach((r) => {
+          if (r.status === "error") {
+            console.warn(`批量保存失败 [${r.entry_id}]：${r.error}`);
+          } else if (r.content_hash) {
+            if (!wordHashes.has(r.entry_id)) added += 1;
+            wordHashes.set(r.entry_id, r.content_hash);
+          }
+        });
+        set((s) => ({ lexiconSize: s.lexiconSize + added, loadVersion: s.loadVersion + 1 }));
+      })
+      .catch((err) => console.warn(`批量保存失败：${err}`));
   },
 
   deleteWord: (entryId) => {
@@ -166,22 +355,52 @@ export const useLexiconStore = create<LexiconStore>((set, get) => ({
     set((state) => {
       const wordsMap = { ...state.wordsMap };
       delete wordsMap[entryId];
-      return { wordsMap, wordsList: rebuildList(wordsMap), activeWordId: null };
+      const inPage = state.pageIds.includes(entryId);
+      return {
+        wordsMap,
+        wordsList: state.fullLexiconLoaded ? rebuildList(wordsMap) : state.wordsList,
+        pageIds: inPage ? state.pageIds.filter((id) => id !== entryId) : state.pageIds,
+        pageTotal: inPage ? Math.max(0, state.pageTotal - 1) : state.pageTotal,
+        activeWordId: null,
+      };
     });
 
+    // 从未保存过的草稿只存在于前端
+    if (!wordHashes.has(entryId)) return;
+
     const { projectPath, languagePath } = get();
     invoke("delete_word", {
       projectPath,
       languagePath,
       entryId,
       conWordRomanized: wordToDelete.con_word_romanized,
-    }).catch((err) => console.warn(`删除失败：${err}`));
+      expectedHash: wordHashes.get(entryId) ?? null,
+    })
+      .then(() => {
+        wordHashes.delete(entryId);
+        set((s) => ({ lexiconSize: Math.max(0, s.lexiconSize - 1) }));
+      })
+      .catch((err) => console.warn(`删除失败：${err}`));
   },
 
   setSearchTerm: (term) => set({ searchTerm: term }),
   setActiveWordId: (id) => set({ activeWordId: id }),
 }));
 
+/**
+ * 需要遍历整个词典的页面（音变批量应用、统计与质检、语料自动标注、沙盒造句、导出）
+ * 通过这个 hook 按需加载全部词条；词典页本身只走分页查询，不会触发整词典加载。
+ */
+export function useFullLexicon(): WordEntry[] {
+  const wordsList = useLexiconStore((s) => s.wordsList);
+  const loaded = useLexiconStore((s) => s.fullLexiconLoaded);
+  const loadFullLexicon = useLexiconStore((s) => s.loadFullLexicon);
+  useEffect(() => {
+    if (!loaded) loadFullLexicon();
+  }, [loaded, loadFullLexicon]);
+  return wordsList;
+}
+
 // 向后兼容 — addWord / updateWord 是 upsertWord 的别名（避免外部引用断裂）
 export const addWord = useLexiconStore.getState().upsertWord;
 export const updateWord = useLexiconStore.getState().upsertWord;
@@ -53,12 +53,19 @@ const defaultConfig: PhonologyConfig = {
 
 /** 单一配置实体，共享 timer 无风险 */
 let saveTimeout: ReturnType<typeof setTimeout>;
+/** phonology.json 最近一次加载/保存时的内容哈希，保存时作为 expected_hash 传给后端以检测外部修改 */
+let contentHash: string | null = null;
 
 const debouncedSave = (projectPath: string, languagePath: string, config: PhonologyConfig) => {
   clearTimeout(saveTimeout);
   saveTimeout = setTimeout(async () => {
     try {
-      await invoke('save_phonology', { projectPath, languagePath, config });
+      contentHash = await invoke<string>('save_phonology', {
+        projectPath,
+        languagePath,
+        config,
+        expectedHash: contentHash,
+      });
     } catch (err) {
       console.warn(`音系配置保存失败：${err}`);
     }
@@ -76,6 +83,7 @@ export const usePhonoStore = create<PhonoStore>((set, get) => ({
   loadConfig: async (projectPath: string, languagePath: string) => {
     try {
       const config = await invoke<PhonologyConfig>('load_phonology', { projectPath, languagePath });
+      contentHash = await invoke<string>('load_file_hash', { projectPath, languagePath, file: 'phonology.json' });
       set({ config, projectPath, languagePath });
     } catch (err) {
       console.error(`加载音系配置失败：${err}`);
@@ -29,12 +29,19 @@ const defaultConfig: SCAConfig = {
 };
 
 let saveTimeout: ReturnType<typeof setTimeout>;
+/** sca_rules.json 最近一次加载/保存时的内容哈希，保存时作为 expected_hash 传给后端以检测外部修改 */
+let contentHash: string | null = null;
 
 const debouncedSave = (projectPath: string, languagePath: string, config: SCAConfig) => {
     clearTimeout(saveTimeout);
     saveTimeout = setTimeout(async () => {
         try {
-            await invoke('save_sca', { projectPath, languagePath, config });
+            contentHash = await invoke<string>('save_sca', {
+                projectPath,
+                languagePath,
+                config,
+                expectedHash: contentHash,
+            });
         } catch (err) {
             console.warn(`SCA 配置保存失败：${err}`);
         }
@@ -49,6 +56,7 @@ export const useSCAStore = create<SCAStore>((set, get) => ({
     loadConfig: async (projectPath: string, languagePath: string) => {
         try {
             const config = await invoke<SCAConfig>('load_sca', { projectPath, languagePath });
+            contentHash = await invoke<string>('load_file_hash', { projectPath, languagePath, file: 'sca_rules.json' });
             set({ config, projectPath, languagePath });
         } catch (err) {
             console.warn(`加载 SCA 配置失败：${err}`);
@@ -1,5 +1,5 @@
 import { create } from 'zustand';
-import { WorkspaceConfig, LanguageEntry, CreateProjectResult } from '../types';
+import { WorkspaceConfig, LanguageEntry, CreateProjectResult, ProjectLockStatus, JournalApplyResult, JournalStatus, LanguageRenameReport, ReparentReport, LanguageMergeReport, ForkSoundChanges, ForkSoundChangeReport, PullSyncReport, BorrowOptions, BorrowReport } from '../types';
 import { invoke } from '@tauri-apps/api/core';
 import { open } from '@tauri-apps/plugin-dialog';
 import { DEFAULT_LANGUAGE_ID, DEFAULT_LANGUAGE_PATH, WORKSPACE_VERSION } from '../constants';
@@ -12,15 +12,47 @@ interface WorkspaceStore {
     conlangFilePath: string;
     /** Derived: directory containing the .conlang file */
     projectPath: string;
+    /** 项目锁状态；被其他实例持有时为只读 */
+    lockStatus: ProjectLockStatus | null;
+    /** 撤销/重做日志状态 */
+    journalStatus: JournalStatus | null;
+    /** 撤销/重做改写了磁盘上的文件后递增，用于触发当前语言数据的重新加载 */
+    dataVersion: number;
 
     setConlangFilePath: (path: string) => void;
     loadWorkspace: (conlangFilePath: string) => Promise<void>;
+    acquireLock: (force?: boolean) => Promise<void>;
+    migrateOperationLogs: () => Promise<void>;
+    refreshJournal: () => Promise<void>;
+    undo: () => Promise<void>;
+    redo: () => Promise<void>;
     saveWorkspace: () => void;
     setActiveLanguage: (languageId: string) => void;
-    forkLanguage: (parentId: string, newName: string, newId: string, newPath: string) => Promise<void>;
+    /** 派生子语言；给出 soundChanges 时在后端对全部继承词条执行音变并返回合并/同音词报告 */
+    forkLanguage: (
+        parentId: string,
+        newName: string,
+        newId: string,
+        newPath: string,
+        soundChanges?: ForkSoundChanges,
+    ) => Promise<ForkSoundChangeReport | null>;
     deleteLanguage: (languageId: string) => Promise<void>;
     createRootLanguage: (name: string) => Promise<CreateProjectResult | null>;
     renameLanguage: (languageId: string, newName: string) => void;
+    /** 修改语言 ID 和/或目录并改写整个工作区中的引用；dryRun 时只返回报告 */
+    relocateLanguage: (languageId: string, newId: string, newPath: string, dryRun: boolean) => Promise<LanguageRenameReport>;
+    /** 更换父语言（newParentId 为 null 时成为根语言）；relink / borrowUnmatched 决定新父语言中找不到的继承链接如何处理 */
+    reparentLanguage: (
+        languageId: string,
+        newParentId: string | null,
+        options: { relink: boolean; borrowUnmatched: boolean; dryRun: boolean },
+    ) => Promise<ReparentReport>;
+    /** 把姊妹语言 sourceId 并入 targetId，sourceId 随后从工作区删除；dryRun 时只返回报告 */
+    mergeLanguages: (targetId: string, sourceId: string, dryRun: boolean) => Promise<LanguageMergeReport>;
+    /** 从父语言拉取同步（三方比较）；dryRun 时只返回报告 */
+    pullFromParent: (languageId: string, dryRun: boolean) => Promise<PullSyncReport>;
+    /** 从任意语言借入词条到当前语言，按当前语言音系适配；options.dry_run 时只返回报告 */
+    borrowWords: (sourceLanguageId: string, entryIds: string[], options: BorrowOptions) => Promise<BorrowReport>;
     getLanguageById: (languageId: string) => LanguageEntry | undefined;
     getChildLanguages: (parentId: string) => LanguageEntry[];
     copyProject: (sourceConlangFilePath: string, destDir: string, newName: string) => Promise<string>;
@@ -46,16 +78,56 @@ function deriveProjectPath(conlangFilePath: string): string {
 }
 
 let saveTimeout: ReturnType<typeof setTimeout>;
+let pendingSave: (() => Promise<void>) | null = null;
 
 const debouncedSave = (conlangFilePath: string, config: WorkspaceConfig) => {
     clearTimeout(saveTimeout);
-    saveTimeout = setTimeout(async () => {
+    pendingSave = async () => {
+        pendingSave = null;
         try {
             await invoke('save_workspace', { conlangFilePath, config });
         } catch (err) {
             console.warn(`工作区配置保存失败：${err}`);
         }
-    }, 500);
+    };
+    saveTimeout = setTimeout(() => pendingSave?.(), 500);
+};
+
+/** 立即执行尚未落盘的工作区配置保存 */
+const flushSave = async () => {
+    clearTimeout(saveTimeout);
+    await pendingSave?.();
+};
+
+/** 执行撤销/重做，然后重新加载受影响的数据：.conlang 被恢复时重读工作区配置（保留当前语言），其余交给 dataVersion */
+const applyJournal = async (command: 'undo' | 'redo') => {
+    const { projectPath, conlangFilePath } = useWorkspaceStore.getState();
+    if (!conlangFilePath) return;
+    // 先让待写入的工作区配置落盘，使它成为日志中最新的一条，而不是在撤销之后再覆盖回来
+    await flushSave();
+    try {
+        const result = await invoke<JournalApplyResult | null>(command, { projectPath });
+        if (!result) return;
+        if (result.error) {
+            console.warn(`${command === 'undo' ? '撤销' : '重做'}未全部完成：${result.error}`);
+        }
+        useWorkspaceStore.setState((s) => ({ journalStatus: result.status, dataVersion: s.dataVersion + 1 }));
+        if (result.files.some((f) => conlangFilePath.endsWith(f))) {
+            const config = await invoke<WorkspaceConfig>('load_workspace', { conlangFilePath });
+            if (config.languages.length > 0) {
+                const { activeLanguageId } = useWorkspaceStore.getState();
+                const active = config.languages.find((l) => l.language_id === activeLanguageId) ?? config.languages[0];
+                useWorkspaceStore.setState({
+                    config,
+                    activeLanguageId: active.language_id,
+                    activeLanguagePath: active.path,
+                });
+            }
+        }
+    } catch (err) {
+        console.warn(`${command === 'undo' ? '撤销' : '重做'}失败：${err}`);
+        await useWorkspaceStore.getState().refreshJournal();
+    }
 };
 
 export const useWorkspaceStore = create<WorkspaceStore>((set, get) => ({
@@ -64,6 +136,9 @@ export const useWorkspaceStore = create<WorkspaceStore>((set, get) => ({
     activeLanguagePath: DEFAULT_LANGUAGE_PATH,
     conlangFilePath: '',
     projectPath: '.',
+    lockStatus: null,
+    journalStatus: null,
+    dataVersion: 0,
 
     setConlangFilePath: (path) => set({
         conlangFilePath: path,
@@ -71,7 +146,15 @@ export const useWorkspaceStore = create<WorkspaceStore>((set, get) => ({
     }),
 
     clearWorkspace: () => {
+        invoke('unwatch_project').catch((err) => console.warn(`停止监听项目失败：${err}`));
+        const { conlangFilePath } = get();
+        if (conlangFilePath) {
+            invoke('release_project_lock', { conlangFilePath })
+                .catch((err) => console.warn(`释放项目锁失败：${err}`));
+        }
         set({
+            lockStatus: null,
+            journalStatus: null,
             projectPath: "",
             conlangFilePath: "",
             config: defaultConfig,
@@ -94,11 +177,55 @@ export const useWorkspaceStore = create<WorkspaceStore>((set, get) => ({
                 activeLanguageId: resolvedConfig.languages[0]?.language_id || undefined,
                 activeLanguagePath: resolvedConfig.languages[0]?.path || undefined,
             });
+            await get().acquireLock();
+            await get().migrateOperationLogs();
+            await get().refreshJournal();
         } catch (err) {
             console.warn(`加载工作区配置失败：${err}`);
         }
     },
 
+    acquireLock: async (force = false) => {
+        const { conlangFilePath } = get();
+        if (!conlangFilePath) return;
+        try {
+            const lockStatus = await invoke<ProjectLockStatus>('acquire_project_lock', { conlangFilePath, force });
+            set({ lockStatus });
+        } catch (err) {
+            console.warn(`获取项目锁失败：${err}`);
+        }
+    },
+
+    migrateOperationLogs: async () => {
+        const { projectPath, conlangFilePath, lockStatus } = get();
+        // 旧格式快照的转换会改写 .oplog，只读打开时跳过，下次取得写锁时再转换
+        if (!conlangFilePath || lockStatus?.mode === 'read_only') return;
+        try {
+            await invoke<number>('migrate_operation_logs', { projectPath, conlangFilePath });
+        } catch (err) {
+            console.warn(`转换旧快照失败：${err}`);
+        }
+    },
+
+        refreshJournal: async () => {
+        const { projectPath, conlangFilePath } = get();
+        if (!conlangFilePath) return;
+        try {
+            const journalStatus = await invoke<JournalStatus>('load_journal_status', { projectPath });
+            set({ journalStatus });
+        } catch (err) {
+            console.warn(`读取撤销日志失败：${err}`);
+        }
+    },
+
+    undo: async () => {
+        await applyJournal('undo');
+    },
+
+    redo: async () => {
+        await applyJournal('redo');
+    },
+
     saveWorkspace: () => {
         const { conlangFilePath, config } = get();
         if (conlangFilePath) {
@@ -117,20 +244,22 @@ export const useWorkspaceStore = create<WorkspaceStore>((set, get) => ({
         }
     },
 
-    forkLanguage: async (parentId, newName, newId, newPath) => {
+    forkLanguage: async (parentId, newName, newId, newPath, soundChanges) => {
         const { projectPath, conlangFilePath } = get();
-        await invoke('fork_language', {
+        const report = await invoke<ForkSoundChangeReport | null>('fork_language', {
             projectPath,
             conlangFilePath,
             parentId,
             newName,
             newId,
             newPath,
+            soundChanges: soundChanges ?? null,
         });
         // Reload workspace after fork
         await get().loadWorkspace(conlangFilePath);
         // Switch to the newly forked language
         get().setActiveLanguage(newId);
+        return report;
     },
 
     deleteLanguage: async (languageId: string) => {
@@ -171,6 +300,7 @@ export const useWorkspaceStore = create<WorkspaceStore>((set, get) => ({
                 activeLanguageId: result.config.languages[0].language_id,
                 activeLanguagePath: result.config.languages[0].path,
             });
+            await get().acquireLock();
             return result;
         } catch (err) {
             console.warn(`创建根语言失败：${err}`);
@@ -192,6 +322,110 @@ export const useWorkspaceStore = create<WorkspaceStore>((set, get) => ({
         }
     },
 
+    relocateLanguage: async (languageId, newId, newPath, dryRun) => {
+        const { projectPath, conlangFilePath } = get();
+        // 待写入的名称修改必须先落盘，否则会用旧 ID / 路径覆盖改写后的 .conlang
+        if (!dryRun) await flushSave();
+        const report = await invoke<LanguageRenameReport>('rename_language', {
+            projectPath,
+            conlangFilePath,
+            languageId,
+            newId,
+            newPath,
+            dryRun,
+        });
+        if (dryRun) return report;
+
+        const config = await invoke<WorkspaceConfig>('load_workspace', { conlangFilePath });
+        const { activeLanguageId } = get();
+        const activeId = activeLanguageId === report.old_id ? report.new_id : activeLanguageId;
+        const active = config.languages.find((l) => l.language_id === activeId) ?? config.languages[0];
+        set((s) => ({
+            config,
+            activeLanguageId: active?.language_id ?? '',
+            activeLanguagePath: active?.path ?? '',
+            dataVersion: s.dataVersion + 1,
+        }));
+        return report;
+    },
+
+    reparentLanguage: async (languageId, newParentId, { relink, borrowUnmatched, dryRun }) => {
+        const { projectPath, conlangFilePath } = get();
+        if (!dryRun) await flushSave();
+        const report = await invoke<ReparentReport>('reparent_language', {
+            projectPath,
+            conlangFilePath,
+            languageId,
+            newParentId,
+            relink,
+            borrowUnmatched,
+            dryRun,
+        });
+        if (dryRun) return report;
+
+        const config = await invoke<WorkspaceConfig>('load_workspace', { conlangFilePath });
+        set((s) => ({ config, dataVersion: s.dataVersion + 1 }));
+        return report;
+    },
+
+    mergeLanguages: async (targetId, sourceId, dryRun) => {
+        const { projectPath, conlangFilePath } = get();
+        if (!dryRun) await flushSave();
+        const report = await invoke<LanguageMergeReport>('merge_languages', {
+            projectPath,
+            conlangFilePath,
+            targetId,
+            sourceId,
+            dryRun,
+        });
+        if (dryRun) return report;
+
+        const config = await invoke<WorkspaceConfig>('load_workspace', { conlangFilePath });
+        const { activeLanguageId } = get();
+        const activeId = activeLanguageId === sourceId ? targetId : activeLanguageId;
+        const active = config.languages.find((l) => l.language_id === activeId) ?? config.languages[0];
+        set((s) => ({
+            config,
+            activeLanguageId: active?.language_id ?? '',
+            activeLanguagePath: active?.path ?? '',
+            dataVersion: s.dataVersion + 1,
+        }));
+        return report;
+    },
+
+    pullFromParent: async (languageId, dryRun) => {
+        const { projectPath, conlangFilePath } = get();
+        if (!dryRun) await flushSave();
+        try {
+            return await invoke<PullSyncReport>('pull_from_parent', {
+                projectPath,
+                conlangFilePath,
+                languageId,
+                dryRun,
+            });
+        } finally {
+            // 失败时也可能已写入部分词条，一并刷新
+            if (!dryRun) set((s) => ({ dataVersion: s.dataVersion + 1 }));
+        }
+    },
+
+    borrowWords: async (sourceLanguageId, entryIds, options) => {
+        const { projectPath, conlangFilePath, activeLanguageId } = get();
+        if (!options.dry_run) await flushSave();
+        try {
+            return await invoke<BorrowReport>('borrow_words', {
+                projectPath,
+                conlangFilePath,
+                languageId: activeLanguageId,
+                sourceLanguageId,
+                entryIds,
+                options,
+            });
+        } finally {
+            if (!options.dry_run) set((s) => ({ dataVersion: s.dataVersion + 1 }));
+        }
+    },
+
     getLanguageById: (languageId: string) => {
         return get().config.languages.find((l) => l.language_id === languageId);
     },
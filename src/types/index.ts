@@ -55,6 +55,8 @@ export interface AllophonyRule {
 }
 
 export interface PhonologyConfig {
+  /** 缺省视为 0；由后端在读取时迁移、保存时写入当前版本 */
+  schema_version?: number;
   language_id: string;
   phoneme_inventory: PhonemeInventory;
   romanization_maps: RomanizationMap[];
@@ -91,6 +93,7 @@ export interface Etymology {
 
 export interface EntryMetadata {
   tags: string[];
+  /** RFC 3339（UTC），由后端在保存时维护，前端传入的值会被忽略 */
   created_at: string;
   updated_at: string;
 }
@@ -103,6 +106,8 @@ export interface WordEvolution {
 }
 
 export interface WordEntry {
+  /** 缺省视为 0；由后端在读取时迁移、保存时写入当前版本 */
+  schema_version?: number;
   entry_id: string;
   language_id: string;
   con_word_romanized: string;
@@ -114,6 +119,323 @@ export interface WordEntry {
   evolution?: WordEvolution;
 }
 
+// ── 词典查询 ──────────────────────────────────────────────
+
+export type WordSortKey = "romanized" | "gloss" | "ipa" | "created_at" | "updated_at";
+
+export interface WordQuery {
+  /** 在 gloss 与 definitions 中做不区分大小写的子串匹配 */
+  text?: string | null;
+  /** 为 true 时 text 也匹配拼写 */
+  text_matches_romanized?: boolean;
+  /** 只返回这些词条（按 ID 查找） */
+  entry_ids?: string[] | null;
+  pos_id?: string | null;
+  /** 词条必须同时包含所有列出的标签 */
+  tags?: string[];
+  origin_type?: OriginType | null;
+  deprecated?: boolean | null;
+  romanized_regex?: string | null;
+  /** 父语言目录；给出时结果附带 lifecycle 标记 */
+  parent_language_path?: string | null;
+  lifecycle?: "added" | "changed" | "deprecated" | null;
+  sort_by?: WordSortKey;
+  descending?: boolean;
+  offset?: number;
+  limit?: number | null;
+}
+
+/** 词条相对父语言的状态 */
+export interface WordLifecycle {
+  is_new: boolean;
+  is_deprecated: boolean;
+  is_changed: boolean;
+}
+
+export interface WordQueryResult {
+  /** 分页前的匹配总数 */
+  total: number;
+  words: WordEntry[];
+  /** 本页词条的内容哈希，保存时作为 expected_hash */
+  hashes: Record<string, string>;
+  /** 只在查询给出 parent_language_path 时填写 */
+  lifecycle: Record<string, WordLifecycle>;
+  /** 整个词典中出现过的标签 */
+  all_tags: string[];
+}
+
+/** 反查词典（自然语言 → 造语）命中结果 */
+export interface ReverseSearchHit {
+  entry_id: string;
+  con_word_romanized: string;
+  glosses: string[];
+  score: number;
+  matched_terms: string[];
+}
+
+/** 分桶中无法解析、已被移入 lexicon/.quarantine 的原始行 */
+export interface QuarantineRecord {
+  bucket: string;
+  line: string;
+  error: string;
+}
+
+export interface WordSaveRequest {
+  word: WordEntry;
+  /** 拼写修改前的 romanized，用于从旧分桶中移除该词条 */
+  old_romanized?: string | null;
+  /** 加载时该词条的哈希（query_words 返回的 hashes 或 load_word_hashes）；新词条传空字符串，省略则不检查 */
+  expected_hash?: string | null;
+}
+
+export interface WordDeleteRequest {
+  entry_id: string;
+  con_word_romanized: string;
+  expected_hash?: string | null;
+}
+
+export type BatchEntryStatus = "saved" | "deleted" | "skipped" | "not_found" | "error";
+
+/** save_words / delete_words 的逐条结果 */
+export interface BatchEntryResult {
+  entry_id: string;
+  status: BatchEntryStatus;
+  error: string | null;
+  /** 保存成功后词条的新哈希 */
+  content_hash: string | null;
+}
+
+// ── 外部修改检测 ──────────────────────────────────────────
+
+export type ProjectFileKind = "lexicon_bucket" | "grammar" | "phonology" | "sca" | "corpus" | "workspace";
+
+/** "project-file-changed" 事件的负载 */
+export interface ProjectFileChange {
+  language_id: string | null;
+  language_path: string | null;
+  kind: ProjectFileKind;
+  file: string;
+  change: "created" | "modified" | "removed";
+  content_hash: string | null;
+}
+
+// ── 词条修订历史 ──────────────────────────────────────────
+
+export interface HistoryConfig {
+  max_revisions: number;
+}
+
+export interface WordRevision {
+  revision_id: string;
+  entry_id: string;
+  /** 该版本被替换或删除的时间（RFC 3339） */
+  recorded_at: string;
+  reason: "save" | "delete";
+  content_hash: string;
+  word: WordEntry;
+}
+
+export interface WordHistory {
+  entry_id: string;
+  max_revisions: number;
+  /** 从旧到新 */
+  revisions: WordRevision[];
+}
+
+/** 字段级变化；field 形如 "phonetic_ipa"、"senses[s1].gloss"，整个词条增删时为 "entry" */
+export interface FieldChange {
+  field: string;
+  change: "added" | "removed" | "modified";
+  before?: unknown;
+  after?: unknown;
+}
+
+/** revision ID 为 "current" 时表示词典中的当前版本 */
+export interface WordRevisionDiff {
+  entry_id: string;
+  from_revision: string;
+  to_revision: string;
+  changes: FieldChange[];
+}
+
+// ── 快照对比 ──────────────────────────────────────────────
+
+/** added：快照之后新建；removed：快照之后删除；modified：快照之后修改 */
+export interface SnapshotEntryDiff {
+  entry_id: string;
+  con_word_romanized: string;
+  status: "added" | "removed" | "modified";
+  /** 从快照版本到当前版本的字段级变化 */
+  changes: FieldChange[];
+}
+
+export interface SnapshotDiff {
+  log_id: string;
+  added: number;
+  removed: number;
+  modified: number;
+  entries: SnapshotEntryDiff[];
+}
+
+// ── 撤销/重做日志 ──────────────────────────────────────────
+
+export interface JournalStatus {
+  max_entries: number;
+  undo_count: number;
+  redo_count: number;
+  /** 下一次撤销/重做对应的命令，例如 "save_grammar" */
+  next_undo: string | null;
+  next_redo: string | null;
+}
+
+export interface JournalApplyResult {
+  transaction_id: string;
+  commands: string[];
+  /** 被恢复的文件（相对项目目录） */
+  files: string[];
+  /** 写入中途失败时的错误：files 只包含已恢复的文件，其余改动仍留在原来的栈中 */
+  error: string | null;
+  status: JournalStatus;
+}
+
+// ── 本地 Git ─────────────────────────────────────────────
+
+export interface GitCommitInfo {
+  commit_id: string;
+  short_id: string;
+  summary: string;
+  message: string;
+  author: string;
+  /** RFC 3339 */
+  time: string;
+  /** 本次提交改动的文件（相对项目目录；按语言过滤时只含该语言的文件） */
+  files: string[];
+}
+
+export interface GitRepoStatus {
+  initialized: boolean;
+  branch: string | null;
+  head: GitCommitInfo | null;
+  /** 尚未提交的改动 */
+  changed_files: string[];
+}
+
+export interface MergeConflict {
+  /** 相对冲突记录所在对象的字段路径，例如 "senses[s1].gloss"；整个条目为 "entry" */
+  field: string;
+  base: unknown;
+  ours: unknown;
+  theirs: unknown;
+}
+
+/** 一个对象上未解决的合并冲突：词条，或语言的某个配置文件 */
+export interface MergeConflictGroup {
+  /** 相对项目目录的路径 */
+  file: string;
+  language_id: string;
+  /** 冲突在词条上时为该词条的 entry_id；配置文件为 null */
+  entry_id: string | null;
+  /** 词条拼写，配置文件为文件名 */
+  label: string;
+  conflicts: MergeConflict[];
+}
+
+export type MergeConflictChoice = "ours" | "theirs" | "base";
+
+export interface GitRevertResult {
+  restored: string[];
+  removed: string[];
+  commit: GitCommitInfo | null;
+}
+
+// ── 项目锁 ────────────────────────────────────────────────
+
+export interface ProjectLockInfo {
+  owner_id: string;
+  pid: number;
+  /** 持有者的主机名，无法确定时为空 */
+  hostname: string;
+  /** Unix 秒 */
+  acquired_at: number;
+  heartbeat_at: number;
+}
+
+export interface ProjectLockStatus {
+  conlang_file_path: string;
+  mode: "owned" | "read_only" | "unlocked";
+  holder: ProjectLockInfo | null;
+  replaced_stale: boolean;
+}
+
+// ── 词典分桶 ──────────────────────────────────────────────
+
+export type BucketingStrategy = "ascii" | "grapheme" | "hash" | "size";
+
+export interface BucketingConfig {
+  strategy: BucketingStrategy;
+  /** "nfc" 保留附加符号，"nfd" 去掉附加符号（é → e） */
+  normalization: "nfc" | "nfd";
+  shard_count: number;
+  max_bucket_size: number;
+  /** size 策略的切分点，由迁移命令生成 */
+  split_points: string[];
+}
+
+export interface BucketMigrationReport {
+  dry_run: boolean;
+  strategy: BucketingStrategy;
+  words_scanned: number;
+  words_moved: number;
+  buckets_before: Record<string, number>;
+  buckets_after: Record<string, number>;
+  unparseable_lines: number;
+}
+
+// ── 词典完整性检查 ────────────────────────────────────────
+
+export type LexiconIssueKind =
+  | "duplicate_entry_id"
+  | "wrong_bucket"
+  | "language_id_mismatch"
+  | "dangling_parent_entry"
+  | "dangling_source_language"
+  | "unknown_pos"
+  | "stale_tmp_file";
+
+export interface LexiconIssue {
+  kind: LexiconIssueKind;
+  entry_id: string | null;
+  /** 分桶文件名或 .tmp 文件相对语言目录的路径 */
+  file: string | null;
+  detail: string;
+  repairable: boolean;
+}
+
+export interface LexiconCheckReport {
+  dry_run: boolean;
+  language_id: string;
+  buckets_scanned: number;
+  words_scanned: number;
+  issues: LexiconIssue[];
+  repaired: number;
+}
+
+export interface FileMigration {
+  file: string;
+  kind: "phonology" | "grammar" | "sca" | "corpus" | "lexicon";
+  from_version: number;
+  to_version: number;
+  entries_upgraded: number;
+  applied: string[];
+}
+
+export interface SchemaMigrationReport {
+  dry_run: boolean;
+  current_version: number;
+  migrated: FileMigration[];
+  newer_files: string[];
+}
+
 // ── 语法相关 ──────────────────────────────────────────────
 
 export type WordOrder = "SVO" | "SOV" | "VSO" | "VOS" | "OVS" | "OSV";
@@ -292,6 +614,8 @@ export interface IrregularOverride {
 // ── GrammarConfig ────────────────────────────────────────
 
 export interface GrammarConfig {
+  /** 缺省视为 0；由后端在读取时迁移、保存时写入当前版本 */
+  schema_version?: number;
   language_id: string;
   syntax: SyntaxConfig;
   parts_of_speech: PartOfSpeech[];
@@ -305,6 +629,36 @@ export interface GrammarConfig {
   irregular_overrides: IrregularOverride[];
 }
 
+// ── 语法 ID 引用级联 ─────────────────────────────────────
+
+export interface GrammarRefChange {
+  kind: "pos" | "dimension" | "value" | "rule";
+  action: "rename" | "delete";
+  id: string;
+  /** kind 为 "value" 时取值所属的维度 */
+  dim_id?: string;
+  new_id?: string;
+}
+
+export interface GrammarReference {
+  file: "grammar" | "lexicon";
+  /** 例如 "inflection_rules[r1].pos_id"、"senses[s1].pos_id" */
+  location: string;
+  entry_id?: string | null;
+  label: string;
+  /** rename = 改写引用；unlink = 去掉引用；remove = 所在对象一并删除 */
+  effect: "rename" | "unlink" | "remove";
+}
+
+export interface GrammarRefReport {
+  change: GrammarRefChange;
+  references: GrammarReference[];
+  lexicon_entries: number;
+  /** 执行前创建的快照，预览时为空 */
+  log_id?: string | null;
+  grammar_hash?: string | null;
+}
+
 // ── Workspace & Language Registry (Phase 2 Sprint 6) ─────
 
 export interface LanguageEntry {
@@ -319,6 +673,242 @@ export interface WorkspaceConfig {
   languages: LanguageEntry[];
 }
 
+/** 修改语言 ID / 目录时被改写的文件 */
+export interface ReferenceFileChange {
+  /** 相对项目目录的路径（移动之前的位置） */
+  path: string;
+  kind: "workspace" | "phonology" | "grammar" | "sca" | "corpus" | "lexicon" | "oplog";
+  references: number;
+}
+
+export interface MovedLanguage {
+  language_id: string;
+  old_path: string;
+  new_path: string;
+}
+
+export interface LanguageRenameReport {
+  dry_run: boolean;
+  old_id: string;
+  new_id: string;
+  moved: MovedLanguage[];
+  files: ReferenceFileChange[];
+  words_updated: number;
+  references: number;
+}
+
+export interface ParentLinkCandidate {
+  entry_id: string;
+  con_word_romanized: string;
+  matched_by: "romanization" | "gloss";
+}
+
+/** 更换父语言后一个继承词条的处理方式 */
+export interface ParentLinkResolution {
+  entry_id: string;
+  con_word_romanized: string;
+  parent_entry_id: string;
+  status: "resolved" | "relinked" | "borrowed" | "unresolved";
+  candidate?: ParentLinkCandidate | null;
+  /** 匹配到多个候选时的候选数 */
+  ambiguous: number;
+}
+
+export interface ReparentReport {
+  dry_run: boolean;
+  language_id: string;
+  old_parent_id: string | null;
+  new_parent_id: string | null;
+  links: ParentLinkResolution[];
+  resolved: number;
+  relinked: number;
+  borrowed: number;
+  unresolved: number;
+  log_id?: string | null;
+}
+
+/** 合并姊妹语言时两边都改动过的同源词条；合并后保留 target 版本 */
+export interface LexiconMergeConflict {
+  parent_entry_id: string;
+  target: WordEntry;
+  source: WordEntry;
+}
+
+export interface GrammarMergeConflict {
+  kind:
+    | "pos"
+    | "dimension"
+    | "inflection_rule"
+    | "derivation_rule"
+    | "slot"
+    | "class"
+    | "chapter"
+    | "override";
+  id: string;
+}
+
+export interface LanguageMergeReport {
+  dry_run: boolean;
+  target_id: string;
+  source_id: string;
+  unified: number;
+  taken_from_source: number;
+  added: number;
+  renamed: number;
+  conflicts: LexiconMergeConflict[];
+  grammar_added: number;
+  grammar_conflicts: GrammarMergeConflict[];
+  rule_sets_added: number;
+  corpus_moved: number;
+  children_reparented: string[];
+  /** source 的目录（相对项目目录），合并后仍保留在磁盘上 */
+  source_path: string;
+  files: ReferenceFileChange[];
+  log_id?: string | null;
+}
+
+/** 分叉时执行的祖语音变：直接给出规则集，或从某门语言复制其音变规则 */
+export interface ForkSoundChanges {
+  rule_sets?: SCARuleSet[] | null;
+  template_language_id?: string | null;
+}
+
+/** 音变造成的音位合并；into 为空表示与零合并（脱落） */
+export interface SoundChangeMerger {
+  rule_id: string;
+  from: string;
+  into: string;
+  words: number;
+}
+
+export interface HomophoneMember {
+  entry_id: string;
+  parent_form: string;
+  gloss: string;
+}
+
+/** 父语言中词形不同、音变后变得相同的词条 */
+export interface HomophoneGroup {
+  form: string;
+  members: HomophoneMember[];
+}
+
+export interface ForkSoundChangeReport {
+  language_id: string;
+  rule_sets: number;
+  skipped_rules: string[];
+  words: number;
+  changed: number;
+  ipa_regenerated: number;
+  mergers: SoundChangeMerger[];
+  homophones: HomophoneGroup[];
+}
+
+export interface PullSyncEntry {
+  entry_id: string;
+  parent_entry_id: string;
+  status:
+    | "new"
+    | "parent_changed"
+    | "child_changed"
+    | "both_changed"
+    | "deprecated";
+  parent_form: string;
+  child_form?: string | null;
+  evolved_form?: string | null;
+}
+
+/** 父词与子词自上次同步后都改动过；同步时保留 child */
+export interface PullSyncConflict {
+  parent: WordEntry;
+  child: WordEntry;
+  evolved: WordEntry;
+}
+
+export interface PullSyncReport {
+  dry_run: boolean;
+  language_id: string;
+  parent_id: string;
+  added: number;
+  unchanged: number;
+  updated: number;
+  child_changed: number;
+  deprecated: number;
+  entries: PullSyncEntry[];
+  conflicts: PullSyncConflict[];
+  log_id?: string | null;
+}
+
+export interface AdaptationStep {
+  kind: "substitution" | "deletion" | "epenthesis" | "blacklist";
+  from: string;
+  /** 结果音素；blacklist 时为命中的模式 */
+  to: string;
+  position: number;
+}
+
+export interface BorrowedWord {
+  source_entry_id: string;
+  entry_id: string;
+  source_form: string;
+  source_phonemes: string;
+  adapted_phonemes: string;
+  adapted_form: string;
+  steps: AdaptationStep[];
+  /** pos_id 被映射或清空的义项 */
+  pos_changes: BorrowPosChange[];
+}
+
+export interface BorrowPosChange {
+  sense_id: string;
+  source_pos_id: string;
+  /** 借入语言中没有对应词性时为空 */
+  pos_id: string;
+}
+
+export interface BorrowOptions {
+  /** 按借入语言音系适配；关闭时原样复制 */
+  adapt: boolean;
+  repair?: "epenthesis" | "deletion" | null;
+  dry_run: boolean;
+}
+
+export interface BorrowReport {
+  dry_run: boolean;
+  language_id: string;
+  source_language_id: string;
+  words: BorrowedWord[];
+  skipped: string[];
+  log_id?: string | null;
+}
+
+export interface CognateReflex {
+  language_id: string;
+  entry_id: string;
+  /** 直接来源词条，根词为 null */
+  parent_entry_id?: string | null;
+  origin_type: string;
+  /** 距根词的代数 */
+  depth: number;
+  con_word_romanized: string;
+  phonetic_ipa: string;
+  glosses: string[];
+  is_deprecated: boolean;
+  deprecated_since_language_id?: string | null;
+}
+
+/** 以同一祖先词为根的同源词集，根词在最前 */
+export interface CognateSet {
+  root_language_id: string;
+  root_entry_id: string;
+  reflexes: CognateReflex[];
+}
+
+export interface CognateTable {
+  languages: { language_id: string; name: string }[];
+  sets: CognateSet[];
+}
+
 // ── Global Registry ──────────────────────────────────────
 
 export interface FamilyEntry {
@@ -345,6 +935,8 @@ export interface OperationLogEntry {
   target_language_id: string;
   description: string;
   snapshot_dir: string;
+  /** 快照覆盖的语言目录顶层条目；回滚时只恢复这些 */
+  components: string[];
 }
 
 export interface OperationLog {
@@ -399,9 +991,13 @@ export interface SCARuleSet {
   name: string;
   order: number;
   rules: SCARule[];
+  /** 派生语言时已作用于继承词条的时间；批量应用到词典时跳过该规则集 */
+  lexicon_applied_at?: string | null;
 }
 
 export interface SCAConfig {
+  /** 缺省视为 0；由后端在读取时迁移、保存时写入当前版本 */
+  schema_version?: number;
   language_id: string;
   rule_sets: SCARuleSet[];
 }
@@ -445,6 +1041,8 @@ export interface GlossedLine {
 
 /** 语料库文本条目 */
 export interface CorpusText {
+  /** 缺省视为 0；由后端在读取时迁移、保存时写入当前版本 */
+  schema_version?: number;
   corpus_id: string;
   language_id: string;
   title: string;
@@ -15,6 +15,9 @@ export type TreeNodeData = {
   onFork: (parentId: string) => void;
   onSwitch: (languageId: string) => void;
   onDelete: (lang: LanguageEntry) => void;
+  onRelocate: (lang: LanguageEntry) => void;
+  onReparent: (lang: LanguageEntry) => void;
+  onMerge: (lang: LanguageEntry) => void;
   [key: string]: unknown;
 };
 
@@ -30,6 +33,9 @@ export function useTreeLayout(
     onFork: (parentId: string) => void;
     onSwitch: (languageId: string) => void;
     onDelete: (lang: LanguageEntry) => void;
+    onRelocate: (lang: LanguageEntry) => void;
+    onReparent: (lang: LanguageEntry) => void;
+    onMerge: (lang: LanguageEntry) => void;
   },
 ): { nodes: TreeFlowNode[]; edges: Edge[] } {
   return useMemo(() => {
@@ -92,6 +98,9 @@ export function useTreeLayout(
           onFork: callbacks.onFork,
           onSwitch: callbacks.onSwitch,
           onDelete: callbacks.onDelete,
+          onRelocate: callbacks.onRelocate,
+          onReparent: callbacks.onReparent,
+          onMerge: callbacks.onMerge,
         },
       };
     });
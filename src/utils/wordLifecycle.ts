@@ -1,5 +1,8 @@
 import { WordEntry, WordEvolution } from "../types";
 
+// 同步基准（last_synced_word_hash）由后端 pull_from_parent 计算和写入，
+// 这里只保留列表与编辑器展示生命周期标记所需的辅助函数。
+
 export interface WordLifecycleFlags {
   isNew: boolean;
   isDeprecated: boolean;
@@ -38,30 +41,6 @@ function buildComparablePayload(word: WordEntry): string {
   });
 }
 
-function buildSyncPayload(word: WordEntry): string {
-  return JSON.stringify({
-    con_word_romanized: word.con_word_romanized,
-    phonetic_override: word.phonetic_override,
-    phonetic_ipa: word.phonetic_override ? word.phonetic_ipa : "",
-  });
-}
-
-function djb2Hash(input: string): string {
-  let hash = 5381;
-  for (let index = 0; index < input.length; index += 1) {
-    hash = ((hash << 5) + hash) ^ input.charCodeAt(index);
-  }
-  return (hash >>> 0).toString(16);
-}
-
-export function getWordComparableDigest(word: WordEntry): string {
-  return djb2Hash(buildComparablePayload(word));
-}
-
-export function getWordSyncDigest(word: WordEntry): string {
-  return djb2Hash(buildSyncPayload(word));
-}
-
 export function hasWordChangedFromParent(
   currentWord: WordEntry,
   parentWord: WordEntry,